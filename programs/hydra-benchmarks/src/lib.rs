@@ -14,7 +14,7 @@ pub mod hydra_benchmarks {
         let now = sysvar::clock::Clock::get().unwrap().unix_timestamp as u64;
         msg!("Now = {:?}", now);
         sol_log_compute_units();
-        let value = Decimal::from_u64(10).to_compute_scale();
+        let value = Decimal::from_u64_to_compute_scale(10);
         msg!("Decimal::new = {:?}", value);
         sol_log_compute_units();
         let ln_value = value.ln().unwrap();
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Calculation of rewards failed")]
+    CalculateRewardsFailed,
+
+    #[msg("Unstake amount exceeds the amount staked")]
+    InsufficientStakedAmount,
+
+    #[msg("Reward rate must be greater than zero")]
+    InvalidRewardRate,
+
+    #[msg("Amount exceeds the rewards in the reward vault not owed to stakers")]
+    InsufficientRewards,
+}
@@ -0,0 +1,3 @@
+pub mod rewards_claimed;
+pub mod staked;
+pub mod unstaked;
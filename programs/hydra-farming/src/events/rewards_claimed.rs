@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RewardsClaimed {
+    pub user: Pubkey,
+    pub rewards_credited: u64,
+    pub rewards_unpaid: u64,
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct Unstaked {
+    pub user: Pubkey,
+    pub lp_tokens_unstaked: u64,
+    pub total_staked: u64,
+}
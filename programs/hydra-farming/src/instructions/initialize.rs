@@ -0,0 +1,87 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::farm_state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use hydra_liquidity_pools::state::pool_state::PoolState;
+use std::mem;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// the authority of the farm, part of the farm state seeds so that nobody else can take its address
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<FarmState>(),
+        payer = payer,
+        seeds = [ FARM_STATE_SEED, lp_token_mint.key().as_ref(), reward_mint.key().as_ref(), authority.key().as_ref() ],
+        bump,
+    )]
+    pub farm_state: Box<Account<'info, FarmState>>,
+
+    /// hydra-liquidity-pools pool_state the lp_token_mint belongs to.
+    #[account(
+        constraint = pool_state.lp_token_mint == lp_token_mint.key(),
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    /// lp_token_mint: Eg xlp-hyd-usdc
+    pub lp_token_mint: Box<Account<'info, Mint>>,
+
+    /// reward_mint: Eg HYD
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lp_token_mint,
+        token::authority = farm_state,
+        seeds = [ LP_TOKEN_VAULT_SEED, farm_state.key().as_ref(), lp_token_mint.key().as_ref() ],
+        bump,
+    )]
+    pub lp_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = reward_mint,
+        token::authority = farm_state,
+        seeds = [ REWARD_VAULT_SEED, farm_state.key().as_ref(), reward_mint.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    // system accounts
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<Initialize>, reward_rate: u64) -> Result<()> {
+    if reward_rate == 0 {
+        return Err(ErrorCode::InvalidRewardRate.into());
+    }
+
+    let farm_state = &mut ctx.accounts.farm_state;
+
+    farm_state.authority = ctx.accounts.authority.key();
+    farm_state.lp_token_mint = ctx.accounts.lp_token_mint.key();
+    farm_state.lp_token_vault = ctx.accounts.lp_token_vault.key();
+    farm_state.reward_mint = ctx.accounts.reward_mint.key();
+    farm_state.reward_vault = ctx.accounts.reward_vault.key();
+    farm_state.reward_rate = reward_rate;
+    farm_state.total_staked = 0;
+    farm_state.acc_reward_per_share = 0;
+    farm_state.rewards_owed = 0;
+    farm_state.last_update_timestamp = Clock::get()?.unix_timestamp;
+    farm_state.farm_state_bump = *ctx.bumps.get("farm_state").unwrap();
+    farm_state.lp_token_vault_bump = *ctx.bumps.get("lp_token_vault").unwrap();
+    farm_state.reward_vault_bump = *ctx.bumps.get("reward_vault").unwrap();
+
+    msg!("farm_state: {:?}", farm_state);
+    Ok(())
+}
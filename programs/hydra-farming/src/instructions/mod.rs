@@ -0,0 +1,5 @@
+pub mod initialize;
+pub mod recover_rewards;
+pub mod set_reward_rate;
+pub mod stake;
+pub mod unstake;
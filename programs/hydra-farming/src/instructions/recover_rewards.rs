@@ -0,0 +1,84 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::farm_state::FarmState;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RecoverRewards<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ FARM_STATE_SEED, farm_state.lp_token_mint.as_ref(), farm_state.reward_mint.as_ref(), farm_state.authority.as_ref() ],
+        bump = farm_state.farm_state_bump,
+        has_one = authority,
+        has_one = reward_vault,
+    )]
+    pub farm_state: Box<Account<'info, FarmState>>,
+
+    #[account(
+        mut,
+        seeds = [ REWARD_VAULT_SEED, farm_state.key().as_ref(), farm_state.reward_mint.as_ref() ],
+        bump = farm_state.reward_vault_bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_reward_tokens.mint == farm_state.reward_mint,
+    )]
+    /// the token account to send the recovered rewards to
+    pub authority_reward_tokens: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RecoverRewards<'info> {
+    pub fn transfer_rewards_to_authority(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.reward_vault.to_account_info(),
+            to: self.authority_reward_tokens.to_account_info(),
+            authority: self.farm_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Recover amount reward tokens from the reward vault, rewards owed to stakers up until now can't be recovered
+pub fn handle(ctx: Context<RecoverRewards>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .farm_state
+        .update_rewards(Clock::get()?.unix_timestamp)?;
+
+    let recoverable = ctx
+        .accounts
+        .farm_state
+        .recoverable_rewards(ctx.accounts.reward_vault.amount);
+    if amount > recoverable {
+        msg!("recoverable_rewards: {}", recoverable);
+        msg!("rewards_owed: {}", ctx.accounts.farm_state.rewards_owed);
+        return Err(ErrorCode::InsufficientRewards.into());
+    }
+
+    let seeds = &[
+        FARM_STATE_SEED,
+        ctx.accounts.farm_state.lp_token_mint.as_ref(),
+        ctx.accounts.farm_state.reward_mint.as_ref(),
+        ctx.accounts.farm_state.authority.as_ref(),
+        &[ctx.accounts.farm_state.farm_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    token::transfer(
+        ctx.accounts
+            .transfer_rewards_to_authority()
+            .with_signer(&signer),
+        amount,
+    )?;
+
+    msg!("rewards_recovered: {}", amount);
+    Ok(())
+}
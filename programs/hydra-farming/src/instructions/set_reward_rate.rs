@@ -0,0 +1,31 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::farm_state::FarmState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardRate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ FARM_STATE_SEED, farm_state.lp_token_mint.as_ref(), farm_state.reward_mint.as_ref(), farm_state.authority.as_ref() ],
+        bump = farm_state.farm_state_bump,
+        has_one = authority,
+    )]
+    pub farm_state: Box<Account<'info, FarmState>>,
+}
+
+pub fn handle(ctx: Context<SetRewardRate>, reward_rate: u64) -> Result<()> {
+    if reward_rate == 0 {
+        return Err(ErrorCode::InvalidRewardRate.into());
+    }
+
+    // rewards emitted up until now accrue at the previous rate
+    let farm_state = &mut ctx.accounts.farm_state;
+    farm_state.update_rewards(Clock::get()?.unix_timestamp)?;
+    farm_state.reward_rate = reward_rate;
+
+    msg!("reward_rate: {}", reward_rate);
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::rewards_claimed::RewardsClaimed;
+use crate::events::staked::Staked;
+use crate::state::farm_state::FarmState;
+use crate::state::user_state::UserState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use std::mem;
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    /// the authority allowed to transfer lp tokens from the users wallet.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ FARM_STATE_SEED, farm_state.lp_token_mint.as_ref(), farm_state.reward_mint.as_ref(), farm_state.authority.as_ref() ],
+        bump = farm_state.farm_state_bump,
+        has_one = lp_token_vault,
+        has_one = reward_vault,
+    )]
+    pub farm_state: Box<Account<'info, FarmState>>,
+
+    #[account(
+        init_if_needed,
+        space = 8 + mem::size_of::<UserState>(),
+        payer = user,
+        seeds = [ USER_STATE_SEED, farm_state.key().as_ref(), user.key().as_ref() ],
+        bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_tokens.mint == farm_state.lp_token_mint,
+        constraint = user_lp_tokens.owner == user.key()
+    )]
+    /// the lp token account to withdraw from
+    pub user_lp_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = reward_mint.key() == farm_state.reward_mint
+    )]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    /// the token account to send rewards to
+    pub user_reward_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ LP_TOKEN_VAULT_SEED, farm_state.key().as_ref(), farm_state.lp_token_mint.as_ref() ],
+        bump = farm_state.lp_token_vault_bump,
+    )]
+    pub lp_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ REWARD_VAULT_SEED, farm_state.key().as_ref(), farm_state.reward_mint.as_ref() ],
+        bump = farm_state.reward_vault_bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> Stake<'info> {
+    pub fn transfer_user_lp_tokens_to_vault(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.user_lp_tokens.to_account_info(),
+            to: self.lp_token_vault.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn transfer_rewards_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.reward_vault.to_account_info(),
+            to: self.user_reward_tokens.to_account_info(),
+            authority: self.farm_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+pub fn handle(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // first stake into the farm for this user
+    if ctx.accounts.user_state.owner == Pubkey::default() {
+        let user_state = &mut ctx.accounts.user_state;
+        user_state.farm_state = ctx.accounts.farm_state.key();
+        user_state.owner = ctx.accounts.user.key();
+        user_state.user_state_bump = *ctx.bumps.get("user_state").unwrap();
+    }
+
+    // accrue rewards up until now before the staked amount changes
+    ctx.accounts.farm_state.update_rewards(now)?;
+    let acc_reward_per_share = ctx.accounts.farm_state.acc_reward_per_share;
+    ctx.accounts
+        .user_state
+        .accrue_rewards(acc_reward_per_share)?;
+
+    let seeds = &[
+        FARM_STATE_SEED,
+        ctx.accounts.farm_state.lp_token_mint.as_ref(),
+        ctx.accounts.farm_state.reward_mint.as_ref(),
+        ctx.accounts.farm_state.authority.as_ref(),
+        &[ctx.accounts.farm_state.farm_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    // pay out pending rewards, as much as the reward vault can cover
+    let rewards_to_credit = ctx
+        .accounts
+        .user_state
+        .rewards_payable(ctx.accounts.reward_vault.amount);
+
+    if rewards_to_credit > 0 {
+        token::transfer(
+            ctx.accounts.transfer_rewards_to_user().with_signer(&signer),
+            rewards_to_credit,
+        )?;
+        ctx.accounts.user_state.rewards_unpaid -= rewards_to_credit;
+        ctx.accounts.farm_state.pay_out_rewards(rewards_to_credit);
+
+        emit!(RewardsClaimed {
+            user: ctx.accounts.user.key(),
+            rewards_credited: rewards_to_credit,
+            rewards_unpaid: ctx.accounts.user_state.rewards_unpaid,
+        });
+    }
+
+    if amount > 0 {
+        // transfer lp tokens to vault
+        token::transfer(ctx.accounts.transfer_user_lp_tokens_to_vault(), amount)?;
+
+        ctx.accounts.user_state.amount = ctx
+            .accounts
+            .user_state
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+        ctx.accounts.farm_state.total_staked = ctx
+            .accounts
+            .farm_state
+            .total_staked
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+    }
+
+    ctx.accounts.user_state.checkpoint(acc_reward_per_share)?;
+
+    emit!(Staked {
+        user: ctx.accounts.user.key(),
+        lp_tokens_staked: amount,
+        total_staked: ctx.accounts.farm_state.total_staked,
+    });
+
+    msg!("lp_tokens_staked: {}", amount);
+    msg!("rewards_credited: {}", rewards_to_credit);
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::rewards_claimed::RewardsClaimed;
+use crate::events::unstaked::Unstaked;
+use crate::state::farm_state::FarmState;
+use crate::state::user_state::UserState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct UnStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ FARM_STATE_SEED, farm_state.lp_token_mint.as_ref(), farm_state.reward_mint.as_ref(), farm_state.authority.as_ref() ],
+        bump = farm_state.farm_state_bump,
+        has_one = lp_token_vault,
+        has_one = reward_vault,
+    )]
+    pub farm_state: Box<Account<'info, FarmState>>,
+
+    #[account(
+        mut,
+        seeds = [ USER_STATE_SEED, farm_state.key().as_ref(), user.key().as_ref() ],
+        bump = user_state.user_state_bump,
+        has_one = farm_state,
+        constraint = user_state.owner == user.key(),
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_tokens.mint == farm_state.lp_token_mint,
+        constraint = user_lp_tokens.owner == user.key()
+    )]
+    /// the lp token account to send lp tokens back to
+    pub user_lp_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = reward_mint.key() == farm_state.reward_mint
+    )]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    /// the token account to send rewards to
+    pub user_reward_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ LP_TOKEN_VAULT_SEED, farm_state.key().as_ref(), farm_state.lp_token_mint.as_ref() ],
+        bump = farm_state.lp_token_vault_bump,
+    )]
+    pub lp_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ REWARD_VAULT_SEED, farm_state.key().as_ref(), farm_state.reward_mint.as_ref() ],
+        bump = farm_state.reward_vault_bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> UnStake<'info> {
+    pub fn transfer_lp_tokens_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.lp_token_vault.to_account_info(),
+            to: self.user_lp_tokens.to_account_info(),
+            authority: self.farm_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn transfer_rewards_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.reward_vault.to_account_info(),
+            to: self.user_reward_tokens.to_account_info(),
+            authority: self.farm_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+pub fn handle(ctx: Context<UnStake>, amount: u64) -> Result<()> {
+    if amount > ctx.accounts.user_state.amount {
+        return Err(ErrorCode::InsufficientStakedAmount.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // accrue rewards up until now before the staked amount changes
+    ctx.accounts.farm_state.update_rewards(now)?;
+    let acc_reward_per_share = ctx.accounts.farm_state.acc_reward_per_share;
+    ctx.accounts
+        .user_state
+        .accrue_rewards(acc_reward_per_share)?;
+
+    let seeds = &[
+        FARM_STATE_SEED,
+        ctx.accounts.farm_state.lp_token_mint.as_ref(),
+        ctx.accounts.farm_state.reward_mint.as_ref(),
+        ctx.accounts.farm_state.authority.as_ref(),
+        &[ctx.accounts.farm_state.farm_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    // pay out pending rewards, as much as the reward vault can cover
+    let rewards_to_credit = ctx
+        .accounts
+        .user_state
+        .rewards_payable(ctx.accounts.reward_vault.amount);
+
+    if rewards_to_credit > 0 {
+        token::transfer(
+            ctx.accounts.transfer_rewards_to_user().with_signer(&signer),
+            rewards_to_credit,
+        )?;
+        ctx.accounts.user_state.rewards_unpaid -= rewards_to_credit;
+
+        emit!(RewardsClaimed {
+            user: ctx.accounts.user.key(),
+            rewards_credited: rewards_to_credit,
+            rewards_unpaid: ctx.accounts.user_state.rewards_unpaid,
+        });
+    }
+
+    if amount > 0 {
+        // transfer lp tokens back to user
+        token::transfer(
+            ctx.accounts
+                .transfer_lp_tokens_to_user()
+                .with_signer(&signer),
+            amount,
+        )?;
+
+        ctx.accounts.user_state.amount -= amount;
+        ctx.accounts.farm_state.total_staked = ctx
+            .accounts
+            .farm_state
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+    }
+
+    // rewards paid out above are no longer owed to stakers
+    ctx.accounts.farm_state.pay_out_rewards(rewards_to_credit);
+    ctx.accounts.user_state.checkpoint(acc_reward_per_share)?;
+
+    emit!(Unstaked {
+        user: ctx.accounts.user.key(),
+        lp_tokens_unstaked: amount,
+        total_staked: ctx.accounts.farm_state.total_staked,
+    });
+
+    msg!("lp_tokens_unstaked: {}", amount);
+    msg!("rewards_credited: {}", rewards_to_credit);
+
+    Ok(())
+}
@@ -1,14 +1,53 @@
+mod errors;
+mod events;
+mod instructions;
+pub mod state;
+
 use anchor_lang::prelude::*;
+use instructions::initialize::*;
+use instructions::recover_rewards::*;
+use instructions::set_reward_rate::*;
+use instructions::stake::*;
+use instructions::unstake::*;
 
 declare_id!("26Z7yPwdJjU8a15GVBW2MBhPGjERhQPC5z7xTtiQMpe1");
 
+pub mod constants {
+    pub const FARM_STATE_SEED: &[u8] = b"farm_state_seed";
+    pub const LP_TOKEN_VAULT_SEED: &[u8] = b"lp_token_vault_seed";
+    pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault_seed";
+    pub const USER_STATE_SEED: &[u8] = b"user_state_seed";
+}
+
 #[program]
 pub mod hydra_farming {
     use super::*;
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-        Ok(())
+
+    /// initialize a new farm emitting reward tokens to stakers of a liquidity pools lp token
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        reward_rate: u64, // reward tokens emitted per second across all stakers
+    ) -> Result<()> {
+        instructions::initialize::handle(ctx, reward_rate)
+    }
+
+    /// change the reward tokens emitted per second, authority only
+    pub fn set_reward_rate(ctx: Context<SetRewardRate>, reward_rate: u64) -> Result<()> {
+        instructions::set_reward_rate::handle(ctx, reward_rate)
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize {}
+    /// recover reward tokens from the reward vault that aren't owed to stakers, authority only
+    pub fn recover_rewards(ctx: Context<RecoverRewards>, amount: u64) -> Result<()> {
+        instructions::recover_rewards::handle(ctx, amount)
+    }
+
+    /// stake lp tokens into the farm, paying out any pending rewards
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        instructions::stake::handle(ctx, amount)
+    }
+
+    /// unstake lp tokens from the farm, paying out any pending rewards. An amount of 0 only harvests.
+    pub fn unstake(ctx: Context<UnStake>, amount: u64) -> Result<()> {
+        instructions::unstake::handle(ctx, amount)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use hydra_math_rs::programs::farming::hydra_farming::{
+    calculate_recoverable_rewards, calculate_reward_per_share, calculate_rewards_emitted,
+};
+
+#[account]
+#[derive(Debug, Default)]
+pub struct FarmState {
+    pub authority: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub lp_token_vault: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reward_rate: u64, // reward tokens emitted per second
+    pub total_staked: u64,
+    pub acc_reward_per_share: u128, // scaled by REWARD_PER_SHARE_PRECISION
+    pub rewards_owed: u64, // rewards accrued to stakers and not yet paid out, rewards_unpaid included
+    pub last_update_timestamp: i64,
+    pub farm_state_bump: u8,
+    pub lp_token_vault_bump: u8,
+    pub reward_vault_bump: u8,
+}
+
+impl FarmState {
+    /// Accrue rewards emitted since the last update into the global reward per share accumulator.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_update_timestamp {
+            return Ok(());
+        }
+
+        let elapsed = now
+            .checked_sub(self.last_update_timestamp)
+            .ok_or(ErrorCode::CalculateRewardsFailed)? as u64;
+
+        let rewards_emitted =
+            calculate_rewards_emitted(self.reward_rate, elapsed, self.total_staked)
+                .ok_or(ErrorCode::CalculateRewardsFailed)?;
+        self.rewards_owed = self
+            .rewards_owed
+            .checked_add(rewards_emitted)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+
+        self.acc_reward_per_share = calculate_reward_per_share(
+            self.acc_reward_per_share,
+            self.reward_rate,
+            elapsed,
+            self.total_staked,
+        )
+        .ok_or(ErrorCode::CalculateRewardsFailed)?;
+        self.last_update_timestamp = now;
+
+        Ok(())
+    }
+
+    /// Rewards paid out to a staker are no longer owed. Stakers accrue rewards rounded down from those emitted.
+    pub fn pay_out_rewards(&mut self, rewards_paid: u64) {
+        self.rewards_owed = self.rewards_owed.saturating_sub(rewards_paid);
+    }
+
+    /// Reward tokens of the reward vault the authority can recover without touching the rewards owed to stakers
+    pub fn recoverable_rewards(&self, reward_vault_amount: u64) -> u64 {
+        calculate_recoverable_rewards(reward_vault_amount, self.rewards_owed)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod farm_state;
+pub mod user_state;
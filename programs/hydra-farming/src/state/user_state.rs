@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use hydra_math_rs::programs::farming::hydra_farming::{
+    calculate_pending_rewards, calculate_reward_debt,
+};
+
+#[account]
+#[derive(Debug, Default)]
+pub struct UserState {
+    pub farm_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub reward_debt: u128,
+    pub rewards_unpaid: u64, // rewards owed but not yet paid out, e.g. while the reward vault is underfunded
+    pub user_state_bump: u8,
+}
+
+impl UserState {
+    /// Move rewards accrued since the last checkpoint into rewards_unpaid
+    pub fn accrue_rewards(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let pending =
+            calculate_pending_rewards(self.amount, acc_reward_per_share, self.reward_debt)
+                .ok_or(ErrorCode::CalculateRewardsFailed)?;
+
+        self.rewards_unpaid = self
+            .rewards_unpaid
+            .checked_add(pending)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+
+        Ok(())
+    }
+
+    /// Rewards that can be paid out now given the balance left in the reward vault
+    pub fn rewards_payable(&self, reward_vault_amount: u64) -> u64 {
+        self.rewards_unpaid.min(reward_vault_amount)
+    }
+
+    /// Checkpoint the reward debt after the staked amount has changed
+    pub fn checkpoint(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = calculate_reward_debt(self.amount, acc_reward_per_share)
+            .ok_or(ErrorCode::CalculateRewardsFailed)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use hydra_math_rs::decimal::DecimalError;
+use hydra_math_rs::programs::fees::error::FeeCalculatorError;
+use hydra_math_rs::programs::liquidity_pools::error::{CircuitBreakerError, SwapCalculatorError};
 
 #[error_code]
 pub enum ErrorCode {
@@ -25,4 +28,130 @@ pub enum ErrorCode {
 
     #[msg("Token addresses order is invalid")]
     InvalidTokenOrder,
+
+    #[msg("Invalid compensation parameter")]
+    InvalidCompensationParameter,
+
+    #[msg("Signer is not the pending authority of the pool")]
+    InvalidPendingAuthority,
+
+    #[msg("Calculation of fees failed")]
+    CalculateFeesFailed,
+
+    #[msg("Host fee account provided is invalid")]
+    InvalidHostFeeAccount,
+
+    #[msg("Amount out exceeds the liquidity of the pool")]
+    InsufficientLiquidity,
+
+    #[msg("Route pools don't chain the token swapped in to the token swapped out")]
+    InvalidRoute,
+
+    #[msg("Concentrated liquidity pool calculation failed")]
+    ClPoolCalculationFailed,
+
+    #[msg("Tick arrays don't belong to the pool or don't hold the ticks required")]
+    InvalidTickArray,
+
+    #[msg("Position ticks must be initializable ticks of the pool with the lower tick below the upper tick")]
+    InvalidTickRange,
+
+    #[msg("Token account doesn't hold the position token")]
+    InvalidPositionToken,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Math underflow")]
+    MathUnderflow,
+
+    #[msg("Division by zero")]
+    DivisionByZero,
+
+    #[msg("Calculation of swap failed")]
+    CalculateSwapFailed,
+
+    #[msg("Swaps are paused on the pool")]
+    SwapsPaused,
+
+    #[msg("Deposits are paused on the pool")]
+    DepositsPaused,
+
+    #[msg("Withdrawals are paused on the pool")]
+    WithdrawalsPaused,
+
+    #[msg("Swap moves the pool price too far from the oracle price")]
+    OraclePriceDeviationExceeded,
+
+    #[msg("Swap moves the pool price too far within the slot")]
+    SlotPriceMoveExceeded,
+
+    #[msg("Fee tier is not offered by the factory or doesn't match the swap fee")]
+    InvalidFeeTier,
+
+    #[msg("Pair registry can't record more pools")]
+    PairRegistryFull,
+
+    #[msg("Flash loan borrower program can't be the pool program")]
+    InvalidBorrowerProgram,
+
+    #[msg("Flash loan wasn't repaid with its fee")]
+    FlashLoanNotRepaid,
+
+    #[msg("Flash swap wasn't repaid with the amount in of the swap")]
+    FlashSwapNotRepaid,
+
+    #[msg("Oracle deviation circuit breaker has no oracle price to check the swap against")]
+    OraclePriceUnavailable,
+
+    #[msg("Only the program upgrade authority can initialize the pool factory")]
+    InvalidFactoryAuthority,
+}
+
+/// Log a [Decimal](hydra_math_rs::decimal::Decimal) math error and map it to a program error
+pub fn decimal_error(error: DecimalError) -> Error {
+    msg!("decimal: {}", error);
+    match error {
+        DecimalError::Overflow
+        | DecimalError::ExceedsRange
+        | DecimalError::ExceedsPrecisionRange => ErrorCode::MathOverflow.into(),
+        DecimalError::Underflow => ErrorCode::MathUnderflow.into(),
+        DecimalError::DivisionByZero => ErrorCode::DivisionByZero.into(),
+        _ => ErrorCode::CalculateSwapFailed.into(),
+    }
+}
+
+/// Log a fee calculation error and map it to a program error
+pub fn fee_calculator_error(error: FeeCalculatorError) -> Error {
+    match error {
+        FeeCalculatorError::DecimalError(error) => decimal_error(error),
+        error => {
+            msg!("fee_calculator: {}", error);
+            ErrorCode::CalculateFeesFailed.into()
+        }
+    }
+}
+
+/// Log a swap calculation error and map it to a program error
+pub fn swap_calculator_error(error: SwapCalculatorError) -> Error {
+    match error {
+        SwapCalculatorError::DecimalError(error) => decimal_error(error),
+        SwapCalculatorError::FeeCalculatorError(error) => fee_calculator_error(error),
+        SwapCalculatorError::InsufficientLiquidity => ErrorCode::InsufficientLiquidity.into(),
+        error => {
+            msg!("swap_calculator: {}", error);
+            ErrorCode::CalculateSwapFailed.into()
+        }
+    }
+}
+
+/// Log a tripped circuit breaker and map it to a program error
+pub fn circuit_breaker_error(error: CircuitBreakerError) -> Error {
+    msg!("circuit_breaker: {}", error);
+    match error {
+        CircuitBreakerError::OracleDeviationExceeded(_) => {
+            ErrorCode::OraclePriceDeviationExceeded.into()
+        }
+        CircuitBreakerError::SlotPriceMoveExceeded(_) => ErrorCode::SlotPriceMoveExceeded.into(),
+    }
 }
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FlashLoaned {
+    pub borrower_program: Pubkey,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    /// flash loan fees repaid to liquidity providers
+    pub fee_x: u64,
+    pub fee_y: u64,
+    pub x_new: u64,
+    pub y_new: u64,
+    pub slot: u64,
+}
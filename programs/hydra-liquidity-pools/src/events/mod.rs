@@ -1,3 +1,7 @@
+pub mod flash_loaned;
 pub mod liquidity_added;
 pub mod liquidity_removed;
+pub mod protocol_fees_withdrawn;
+pub mod remove_liquidity_slippage_exceeded;
 pub mod slippage_exceeded;
+pub mod swapped;
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub tokens_x_withdrawn: u64,
+    pub tokens_y_withdrawn: u64,
+    pub lp_tokens_withdrawn: u64,
+}
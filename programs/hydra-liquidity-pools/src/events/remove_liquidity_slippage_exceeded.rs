@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RemoveLiquiditySlippageExceeded {
+    pub token_x_to_credit: u64,
+    pub token_y_to_credit: u64,
+    pub token_x_min_amount: u64,
+    pub token_y_min_amount: u64,
+}
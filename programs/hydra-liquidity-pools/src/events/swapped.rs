@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SwapDirection {
+    XToY,
+    YToX,
+}
+
+#[event]
+pub struct Swapped {
+    pub direction: SwapDirection,
+    /// total amount debited from the user including the swap and owner trade fees
+    pub amount_in: u64,
+    /// amount credited to the user
+    pub amount_out: u64,
+    /// swap fee paid to liquidity providers
+    pub fee: u64,
+    /// oracle price the swap was priced with, none for a constant product swap
+    pub oracle_price: Option<u64>,
+    pub x_new: u64,
+    pub y_new: u64,
+    pub slot: u64,
+}
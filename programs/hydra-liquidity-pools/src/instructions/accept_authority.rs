@@ -0,0 +1,28 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        constraint = pool_state.pending_authority == pending_authority.key() @ ErrorCode::InvalidPendingAuthority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+/// second step of an authority transfer, the pending authority takes over the pool
+pub fn handle(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+
+    pool_state.authority = ctx.accounts.pending_authority.key();
+    pool_state.pending_authority = Pubkey::default();
+
+    msg!("authority: {}", pool_state.authority);
+    Ok(())
+}
@@ -33,8 +33,6 @@ pub struct AddFirstLiquidity<'info> {
 
     #[account(
         mut,
-        seeds = [ LP_TOKEN_MINT_SEED, pool_state.token_x_mint.as_ref(), pool_state.token_y_mint.as_ref() ],
-        bump,
         constraint = lp_token_mint.key() == pool_state.lp_token_mint,
     )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
@@ -177,6 +175,15 @@ pub fn handle(
     if ctx.accounts.lp_token_mint.supply != 0 {
         return Err(ErrorCode::PoolAlreadyFunded.into());
     }
+    ctx.accounts.pool_state.pause.check_deposits()?;
+
+    // starts the twap clock of the pool, empty vaults have no price to accumulate
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
 
     if ctx.accounts.pool_state.debug {
         msg!("token_x_to_debit: {}", token_x_to_debit);
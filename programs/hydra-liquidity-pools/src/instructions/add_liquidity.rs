@@ -1,5 +1,5 @@
 use crate::constants::*;
-use crate::errors::ErrorCode;
+use crate::errors::{decimal_error, ErrorCode};
 use crate::events::liquidity_added::LiquidityAdded;
 use crate::events::slippage_exceeded::SlippageExceeded;
 use crate::state::pool_state::PoolState;
@@ -34,8 +34,6 @@ pub struct AddLiquidity<'info> {
 
     #[account(
         mut,
-        seeds = [ LP_TOKEN_MINT_SEED, pool_state.token_x_mint.as_ref(), pool_state.token_y_mint.as_ref() ],
-        bump,
         constraint = lp_token_mint.key() == pool_state.lp_token_mint,
     )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
@@ -144,7 +142,7 @@ impl<'info> AddLiquidity<'info> {
     pub fn calculate_a_and_b_tokens_to_debit_from_expected_lp_tokens(
         &self,
         expected_lp_tokens_minted: u64,
-    ) -> (u64, u64) {
+    ) -> Result<(u64, u64)> {
         calculate_x_y(
             expected_lp_tokens_minted,
             self.lp_token_mint.decimals,
@@ -154,6 +152,7 @@ impl<'info> AddLiquidity<'info> {
             self.token_y_mint.decimals,
             self.lp_token_mint.supply,
         )
+        .map_err(decimal_error)
     }
 }
 
@@ -167,6 +166,14 @@ pub fn handle(
     if ctx.accounts.lp_token_mint.supply == 0 {
         return Err(ErrorCode::PoolNotFunded.into());
     }
+    ctx.accounts.pool_state.pause.check_deposits()?;
+
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
 
     if ctx.accounts.pool_state.debug {
         msg!("expected_lp_tokens: {}", expected_lp_tokens);
@@ -183,7 +190,7 @@ pub fn handle(
 
     let debited = ctx
         .accounts
-        .calculate_a_and_b_tokens_to_debit_from_expected_lp_tokens(expected_lp_tokens);
+        .calculate_a_and_b_tokens_to_debit_from_expected_lp_tokens(expected_lp_tokens)?;
 
     let token_x_to_debit = debited.0;
     let token_y_to_debit = debited.1;
@@ -0,0 +1,157 @@
+use crate::constants::*;
+use crate::errors::{decimal_error, ErrorCode};
+use crate::state::cl_pool_state::{cl_pool_error, ClPoolState};
+use crate::state::cl_position::ClPosition;
+use crate::state::cl_tick_array::{check_tick_arrays_hold, load_tick_arrays, save_tick_arrays};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClCollectFees<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POSITION_SEED, position.position_mint.as_ref() ],
+        bump = position.bump,
+        constraint = position.cl_pool_state == cl_pool_state.key(),
+    )]
+    pub position: Box<Account<'info, ClPosition>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint @ ErrorCode::InvalidPositionToken,
+        constraint = position_token_account.owner == user.key() @ ErrorCode::InvalidPositionToken,
+        constraint = position_token_account.amount == 1 @ ErrorCode::InvalidPositionToken,
+    )]
+    /// token account of the user holding the position NFT
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_x_mint.key() == cl_pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == cl_pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_x.mint == cl_pool_state.token_x_mint,
+        constraint = user_token_x.owner == user.key()
+    )]
+    pub user_token_x: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_y.mint == cl_pool_state.token_y_mint,
+        constraint = user_token_y.owner == user.key()
+    )]
+    pub user_token_y: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_x_vault.key() == cl_pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_y_vault.key() == cl_pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClCollectFees<'info> {
+    pub fn credit_user_from_vault(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+        user_token: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[
+            CL_POOL_STATE_SEED,
+            self.cl_pool_state.token_x_mint.as_ref(),
+            self.cl_pool_state.token_y_mint.as_ref(),
+            &[self.cl_pool_state.pool_state_bump],
+        ];
+        let signer = [&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: vault.to_account_info(),
+            to: user_token.to_account_info(),
+            authority: self.cl_pool_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer),
+            amount,
+        )
+    }
+}
+
+/// collect the uncollected fees of the position of the position token held by the user, its liquidity stays in range
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ClCollectFees<'info>>) -> Result<()> {
+    let cl_pool_state_key = ctx.accounts.cl_pool_state.key();
+    let (lower_tick, upper_tick) = (
+        ctx.accounts.position.lower_tick,
+        ctx.accounts.position.upper_tick,
+    );
+    let mut tick_arrays = load_tick_arrays(ctx.remaining_accounts, &cl_pool_state_key)?;
+    check_tick_arrays_hold(
+        &tick_arrays,
+        &[lower_tick, upper_tick],
+        ctx.accounts.cl_pool_state.tick_spacing,
+    )?;
+
+    let mut cl_pool = ctx
+        .accounts
+        .cl_pool_state
+        .to_cl_pool(tick_arrays.iter().map(|a| &**a))?;
+    let mut position_state = ctx.accounts.position.to_position_state();
+
+    let result = cl_pool
+        .collect_fees(&mut position_state, lower_tick, upper_tick)
+        .map_err(cl_pool_error)?;
+
+    if ctx.accounts.cl_pool_state.debug {
+        msg!("collect_fees: {:?}", result);
+    }
+
+    let x_sent = result
+        .x_sent
+        .to_scaled_amount(ctx.accounts.token_x_mint.decimals)
+        .map_err(decimal_error)?;
+    let y_sent = result
+        .y_sent
+        .to_scaled_amount(ctx.accounts.token_y_mint.decimals)
+        .map_err(decimal_error)?;
+
+    ctx.accounts.position.save(&position_state)?;
+    ctx.accounts
+        .cl_pool_state
+        .save(&cl_pool, tick_arrays.iter_mut().map(|a| &mut **a))?;
+    save_tick_arrays(&tick_arrays, ctx.program_id)?;
+
+    msg!("x_sent: {}, y_sent: {}", x_sent, y_sent);
+    ctx.accounts.credit_user_from_vault(
+        &ctx.accounts.token_x_vault,
+        &ctx.accounts.user_token_x,
+        x_sent,
+    )?;
+    ctx.accounts.credit_user_from_vault(
+        &ctx.accounts.token_y_vault,
+        &ctx.accounts.user_token_y,
+        y_sent,
+    )
+}
@@ -0,0 +1,186 @@
+use crate::constants::*;
+use crate::errors::{decimal_error, ErrorCode};
+use crate::state::cl_pool_state::{cl_pool_error, ClPoolState};
+use crate::state::cl_position::ClPosition;
+use crate::state::cl_tick_array::{check_tick_arrays_hold, load_tick_arrays, save_tick_arrays};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use hydra_math_rs::decimal::Decimal;
+use std::ops::Neg;
+
+#[derive(Accounts)]
+pub struct ClDeposit<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POSITION_SEED, position.position_mint.as_ref() ],
+        bump = position.bump,
+        constraint = position.cl_pool_state == cl_pool_state.key(),
+    )]
+    pub position: Box<Account<'info, ClPosition>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint @ ErrorCode::InvalidPositionToken,
+        constraint = position_token_account.owner == user.key() @ ErrorCode::InvalidPositionToken,
+        constraint = position_token_account.amount == 1 @ ErrorCode::InvalidPositionToken,
+    )]
+    /// token account of the user holding the position NFT
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_x_mint.key() == cl_pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == cl_pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_x.mint == cl_pool_state.token_x_mint,
+        constraint = user_token_x.owner == user.key()
+    )]
+    pub user_token_x: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_y.mint == cl_pool_state.token_y_mint,
+        constraint = user_token_y.owner == user.key()
+    )]
+    pub user_token_y: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_x_vault.key() == cl_pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_y_vault.key() == cl_pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClDeposit<'info> {
+    /// Debit the user when amount is positive, otherwise credit the user with the uncollected fees in excess of the deposit
+    pub fn settle(
+        &self,
+        user_token: &Account<'info, TokenAccount>,
+        vault: &Account<'info, TokenAccount>,
+        amount: Decimal,
+        decimals: u8,
+    ) -> Result<u64> {
+        let cpi_program = self.token_program.to_account_info();
+        if amount.is_negative() {
+            let seeds = &[
+                CL_POOL_STATE_SEED,
+                self.cl_pool_state.token_x_mint.as_ref(),
+                self.cl_pool_state.token_y_mint.as_ref(),
+                &[self.cl_pool_state.pool_state_bump],
+            ];
+            let signer = [&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: vault.to_account_info(),
+                to: user_token.to_account_info(),
+                authority: self.cl_pool_state.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer),
+                amount
+                    .neg()
+                    .to_scaled_amount(decimals)
+                    .map_err(decimal_error)?,
+            )?;
+            return Ok(0);
+        }
+
+        let debit_amount = amount
+            .to_scaled_amount_up(decimals)
+            .map_err(decimal_error)?;
+        let cpi_accounts = Transfer {
+            from: user_token.to_account_info(),
+            to: vault.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), debit_amount)?;
+        Ok(debit_amount)
+    }
+}
+
+/// deposit into the position of the position token held by the user, along with collecting its uncollected fees
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClDeposit<'info>>,
+    tokens_x_max_amount: u64,
+    tokens_y_max_amount: u64,
+) -> Result<()> {
+    ctx.accounts.cl_pool_state.pause.check_deposits()?;
+
+    let cl_pool_state_key = ctx.accounts.cl_pool_state.key();
+    let (lower_tick, upper_tick) = (
+        ctx.accounts.position.lower_tick,
+        ctx.accounts.position.upper_tick,
+    );
+    let tick_spacing = ctx.accounts.cl_pool_state.tick_spacing;
+    let mut tick_arrays = load_tick_arrays(ctx.remaining_accounts, &cl_pool_state_key)?;
+    check_tick_arrays_hold(&tick_arrays, &[lower_tick, upper_tick], tick_spacing)?;
+
+    let mut cl_pool = ctx
+        .accounts
+        .cl_pool_state
+        .to_cl_pool(tick_arrays.iter().map(|a| &**a))?;
+    let mut position_state = ctx.accounts.position.to_position_state();
+
+    let x_decimals = ctx.accounts.token_x_mint.decimals;
+    let y_decimals = ctx.accounts.token_y_mint.decimals;
+    let result = cl_pool
+        .deposit(
+            &mut position_state,
+            Decimal::from_scaled_amount_to_compute_scale(tokens_x_max_amount, x_decimals),
+            Decimal::from_scaled_amount_to_compute_scale(tokens_y_max_amount, y_decimals),
+            lower_tick,
+            upper_tick,
+        )
+        .map_err(cl_pool_error)?;
+
+    if ctx.accounts.cl_pool_state.debug {
+        msg!("deposit: {:?}", result);
+    }
+
+    ctx.accounts.position.save(&position_state)?;
+    ctx.accounts
+        .cl_pool_state
+        .save(&cl_pool, tick_arrays.iter_mut().map(|a| &mut **a))?;
+    save_tick_arrays(&tick_arrays, ctx.program_id)?;
+
+    let x_debited = ctx.accounts.settle(
+        &ctx.accounts.user_token_x,
+        &ctx.accounts.token_x_vault,
+        result.x_debited,
+        x_decimals,
+    )?;
+    let y_debited = ctx.accounts.settle(
+        &ctx.accounts.user_token_y,
+        &ctx.accounts.token_y_vault,
+        result.y_debited,
+        y_decimals,
+    )?;
+    if x_debited > tokens_x_max_amount || y_debited > tokens_y_max_amount {
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::cl_pool_state::{cl_pool_error, ClPoolState};
+use crate::state::pool_state::validate_compensation_parameter;
+use crate::{oracle_accounts_security_check, DEBUG_MODE};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use hydra_math_rs::programs::liquidity_pools::cl_pool::ClPool;
+use std::mem;
+
+#[derive(Accounts)]
+pub struct ClInitialize<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<ClPoolState>(),
+        payer = payer,
+        seeds = [ CL_POOL_STATE_SEED, token_x_mint.key().as_ref(), token_y_mint.key().as_ref() ],
+        bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    /// token_a_mint. Eg BTC
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    // token_b_mint: Eg USDC
+    #[account(
+        constraint = token_x_mint.key().as_ref().lt(token_y_mint.key().as_ref()) @ ErrorCode::InvalidTokenOrder
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = token_x_mint,
+        token::authority = cl_pool_state,
+        seeds = [ CL_TOKEN_VAULT_SEED, token_x_mint.key().as_ref(), cl_pool_state.key().as_ref() ],
+        bump,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = token_y_mint,
+        token::authority = cl_pool_state,
+        seeds = [ CL_TOKEN_VAULT_SEED, token_y_mint.key().as_ref(), cl_pool_state.key().as_ref() ],
+        bump,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    // system accounts
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(
+    ctx: Context<ClInitialize>,
+    initial_tick: u128,
+    tick_spacing: u128,
+    compensation_parameter: u8,
+    fee_rate_numerator: u64,
+    fee_rate_denominator: u64,
+) -> Result<()> {
+    validate_compensation_parameter(compensation_parameter)?;
+    if fee_rate_numerator > 0 && fee_rate_numerator >= fee_rate_denominator {
+        return Err(ErrorCode::InvalidFee.into());
+    }
+
+    let cl_pool_state = &mut ctx.accounts.cl_pool_state;
+    cl_pool_state.authority = ctx.accounts.authority.key();
+    cl_pool_state.token_x_mint = ctx.accounts.token_x_mint.key();
+    cl_pool_state.token_y_mint = ctx.accounts.token_y_mint.key();
+    cl_pool_state.token_x_vault = ctx.accounts.token_x_vault.key();
+    cl_pool_state.token_y_vault = ctx.accounts.token_y_vault.key();
+    cl_pool_state.pool_state_bump = *ctx.bumps.get("cl_pool_state").unwrap();
+    cl_pool_state.token_x_vault_bump = *ctx.bumps.get("token_x_vault").unwrap();
+    cl_pool_state.token_y_vault_bump = *ctx.bumps.get("token_y_vault").unwrap();
+    cl_pool_state.debug = DEBUG_MODE;
+
+    cl_pool_state.tick_spacing = tick_spacing;
+    cl_pool_state.compensation_parameter = compensation_parameter;
+    cl_pool_state.fee_rate_numerator = fee_rate_numerator;
+    cl_pool_state.fee_rate_denominator = fee_rate_denominator;
+
+    // empty pool priced at the initializable tick at or below initial_tick
+    let cl_pool = ClPool::new(
+        initial_tick,
+        tick_spacing,
+        cl_pool_state.c(),
+        cl_pool_state.fee_rate()?,
+    )
+    .map_err(cl_pool_error)?;
+    cl_pool_state.save(&cl_pool, std::iter::empty())?;
+
+    cl_pool_state.oracle = oracle_accounts_security_check(ctx.remaining_accounts)?;
+
+    if cl_pool_state.debug {
+        msg!("cl_pool_state: {:?}", cl_pool_state);
+    }
+    Ok(())
+}
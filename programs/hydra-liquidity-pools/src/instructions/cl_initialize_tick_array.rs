@@ -0,0 +1,45 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::cl_pool_state::ClPoolState;
+use crate::state::cl_tick_array::ClTickArray;
+use anchor_lang::prelude::*;
+use std::mem;
+
+#[derive(Accounts)]
+#[instruction(start_tick: u128)]
+pub struct ClInitializeTickArray<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<ClTickArray>(),
+        payer = payer,
+        seeds = [ CL_TICK_ARRAY_SEED, cl_pool_state.key().as_ref(), start_tick.to_le_bytes().as_ref() ],
+        bump,
+    )]
+    pub tick_array: Box<Account<'info, ClTickArray>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// create the tick array of the pool starting at start_tick, anyone can pay for it
+pub fn handle(ctx: Context<ClInitializeTickArray>, start_tick: u128) -> Result<()> {
+    if !ClTickArray::is_valid_start_tick(start_tick, ctx.accounts.cl_pool_state.tick_spacing) {
+        return Err(ErrorCode::InvalidTickArray.into());
+    }
+
+    let tick_array = &mut ctx.accounts.tick_array;
+    tick_array.cl_pool_state = ctx.accounts.cl_pool_state.key();
+    tick_array.start_tick = start_tick;
+
+    ctx.accounts.cl_pool_state.tick_array_count += 1;
+    Ok(())
+}
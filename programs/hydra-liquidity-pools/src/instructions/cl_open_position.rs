@@ -0,0 +1,115 @@
+use crate::constants::*;
+use crate::state::cl_pool_state::ClPoolState;
+use crate::state::cl_position::ClPosition;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{Mint, MintTo, SetAuthority, Token, TokenAccount};
+use std::mem;
+
+#[derive(Accounts)]
+pub struct ClOpenPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    /// mint of the position NFT, a new keypair
+    #[account(
+        init,
+        payer = user,
+        mint::decimals = 0,
+        mint::authority = cl_pool_state,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<ClPosition>(),
+        payer = user,
+        seeds = [ CL_POSITION_SEED, position_mint.key().as_ref() ],
+        bump,
+    )]
+    pub position: Box<Account<'info, ClPosition>>,
+
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = position_mint,
+        associated_token::authority = user,
+    )]
+    /// token account of the user receiving the position NFT
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> ClOpenPosition<'info> {
+    /// Mint the single position token to the user and remove the mint authority so no other can be minted
+    pub fn mint_position_token(&self) -> Result<()> {
+        let seeds = &[
+            CL_POOL_STATE_SEED,
+            self.cl_pool_state.token_x_mint.as_ref(),
+            self.cl_pool_state.token_y_mint.as_ref(),
+            &[self.cl_pool_state.pool_state_bump],
+        ];
+        let signer = [&seeds[..]];
+
+        let cpi_accounts = MintTo {
+            mint: self.position_mint.to_account_info(),
+            to: self.position_token_account.to_account_info(),
+            authority: self.cl_pool_state.to_account_info(),
+        };
+        token::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &signer,
+            ),
+            1,
+        )?;
+
+        let cpi_accounts = SetAuthority {
+            current_authority: self.cl_pool_state.to_account_info(),
+            account_or_mint: self.position_mint.to_account_info(),
+        };
+        token::set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )
+    }
+}
+
+/// open an empty position in the range [lower_tick, upper_tick) and mint its position NFT to the user
+pub fn handle(ctx: Context<ClOpenPosition>, lower_tick: u128, upper_tick: u128) -> Result<()> {
+    ctx.accounts
+        .cl_pool_state
+        .check_position_range(lower_tick, upper_tick)?;
+
+    let position = &mut ctx.accounts.position;
+    position.cl_pool_state = ctx.accounts.cl_pool_state.key();
+    position.position_mint = ctx.accounts.position_mint.key();
+    position.lower_tick = lower_tick;
+    position.upper_tick = upper_tick;
+    position.bump = *ctx.bumps.get("position").unwrap();
+
+    ctx.accounts.mint_position_token()?;
+
+    if ctx.accounts.cl_pool_state.debug {
+        msg!("position: {:?}", ctx.accounts.position);
+    }
+    Ok(())
+}
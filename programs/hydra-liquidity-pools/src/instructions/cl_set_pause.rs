@@ -0,0 +1,24 @@
+use crate::constants::*;
+use crate::state::circuit_breaker::PauseFlags;
+use crate::state::cl_pool_state::ClPoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClSetPause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+}
+
+pub fn handle(ctx: Context<ClSetPause>, pause: PauseFlags) -> Result<()> {
+    ctx.accounts.cl_pool_state.pause = pause;
+
+    msg!("pause: {:?}", pause);
+    Ok(())
+}
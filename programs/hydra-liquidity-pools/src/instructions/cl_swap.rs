@@ -0,0 +1,202 @@
+use crate::constants::*;
+use crate::errors::{decimal_error, ErrorCode};
+use crate::state::cl_pool_state::{cl_pool_error, ClPoolState};
+use crate::state::cl_tick_array::{load_tick_arrays, save_tick_arrays};
+use crate::utils::oracle::get_oracle_price_from_account;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use hydra_math_rs::decimal::{Decimal, Sqrt};
+use std::ops::Neg;
+
+#[derive(Accounts)]
+pub struct ClSwap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ CL_POOL_STATE_SEED, cl_pool_state.token_x_mint.as_ref(), cl_pool_state.token_y_mint.as_ref() ],
+        bump = cl_pool_state.pool_state_bump,
+    )]
+    pub cl_pool_state: Box<Account<'info, ClPoolState>>,
+
+    #[account(
+        constraint = token_x_mint.key() == cl_pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == cl_pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_from_token.owner == user.key(),
+        constraint = user_from_token.mint == cl_pool_state.token_x_mint || user_from_token.mint == cl_pool_state.token_y_mint @ ErrorCode::InvalidMintAddress,
+    )]
+    /// the token account to withdraw from
+    pub user_from_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_to_token.owner == user.key(),
+        constraint = user_to_token.mint == cl_pool_state.token_x_mint || user_to_token.mint == cl_pool_state.token_y_mint @ ErrorCode::InvalidMintAddress,
+        constraint = user_to_token.mint != user_from_token.mint @ ErrorCode::InvalidMintAddress,
+    )]
+    /// token account to send too
+    pub user_to_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_x_vault.key() == cl_pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_y_vault.key() == cl_pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClSwap<'info> {
+    /// Root of the oracle price of x in y, zero (no hmm adjustment) when the pool has no oracle price
+    pub fn get_oracle_rp(&mut self, price_account: Option<&AccountInfo>) -> Result<Decimal> {
+        let exponent = self
+            .cl_pool_state
+            .oracle
+            .as_ref()
+            .map_or(0, |o| o.price_exponent);
+        match get_oracle_price_from_account(price_account, &mut self.cl_pool_state.oracle)? {
+            Some(price) => Decimal::from_scaled_amount_to_compute_scale(price, exponent)
+                .sqrt()
+                .map_err(decimal_error),
+            None => Ok(Decimal::zero()),
+        }
+    }
+
+    pub fn transfer_user_tokens_to_vault(
+        &self,
+        to_account: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.user_from_token.to_account_info(),
+            to: to_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)
+    }
+
+    pub fn transfer_tokens_to_user(
+        &self,
+        from_account: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[
+            CL_POOL_STATE_SEED,
+            self.cl_pool_state.token_x_mint.as_ref(),
+            self.cl_pool_state.token_y_mint.as_ref(),
+            &[self.cl_pool_state.pool_state_bump],
+        ];
+        let signer = [&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: from_account.to_account_info(),
+            to: self.user_to_token.to_account_info(),
+            authority: self.cl_pool_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer),
+            amount,
+        )
+    }
+}
+
+/// Swap up to amount_in across the ticks of the pool. Remaining accounts are the price account
+/// of the pool when it has an oracle, followed by all of its tick arrays. When the liquidity of the
+/// pool runs out the swap is partially filled, and only the amount swapped is debited.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClSwap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    ctx.accounts.cl_pool_state.pause.check_swaps()?;
+
+    let (price_account, tick_array_accounts) = match ctx.accounts.cl_pool_state.oracle {
+        Some(_) => (
+            ctx.remaining_accounts.first(),
+            ctx.remaining_accounts.get(1..),
+        ),
+        None => (None, Some(ctx.remaining_accounts)),
+    };
+    let rp_oracle = ctx.accounts.get_oracle_rp(price_account)?;
+
+    // crossing ticks needs every initialized tick of the pool
+    let cl_pool_state_key = ctx.accounts.cl_pool_state.key();
+    let mut tick_arrays = load_tick_arrays(tick_array_accounts.unwrap_or(&[]), &cl_pool_state_key)?;
+    if tick_arrays.len() != ctx.accounts.cl_pool_state.tick_array_count as usize {
+        return Err(ErrorCode::InvalidTickArray.into());
+    }
+
+    let mut cl_pool = ctx
+        .accounts
+        .cl_pool_state
+        .to_cl_pool(tick_arrays.iter().map(|a| &**a))?;
+
+    let x_to_y = ctx.accounts.user_from_token.mint == ctx.accounts.cl_pool_state.token_x_mint;
+    let (decimals_in, decimals_out) = match x_to_y {
+        true => (
+            ctx.accounts.token_x_mint.decimals,
+            ctx.accounts.token_y_mint.decimals,
+        ),
+        false => (
+            ctx.accounts.token_y_mint.decimals,
+            ctx.accounts.token_x_mint.decimals,
+        ),
+    };
+    let amount = Decimal::from_scaled_amount_to_compute_scale(amount_in, decimals_in);
+    let swap_output = match x_to_y {
+        true => cl_pool.execute_swap_from_x(amount, rp_oracle),
+        false => cl_pool.execute_swap_from_y(amount, rp_oracle),
+    }
+    .map_err(cl_pool_error)?;
+
+    if ctx.accounts.cl_pool_state.debug {
+        msg!("swap: {:?}", swap_output);
+    }
+
+    // the pool keeps the rounding of both amounts
+    let transfer_in_amount = swap_output
+        .recv_amount
+        .to_scaled_amount_up(decimals_in)
+        .map_err(decimal_error)?;
+    let transfer_out_amount = swap_output
+        .send_amount
+        .neg()
+        .to_scaled_amount(decimals_out)
+        .map_err(decimal_error)?;
+    if transfer_in_amount > amount_in || transfer_out_amount < minimum_amount_out {
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+
+    ctx.accounts
+        .cl_pool_state
+        .save(&cl_pool, tick_arrays.iter_mut().map(|a| &mut **a))?;
+    save_tick_arrays(&tick_arrays, ctx.program_id)?;
+
+    msg!("transfer_in_amount: {}", transfer_in_amount);
+    msg!("transfer_out_amount: {}", transfer_out_amount);
+    let (vault_in, vault_out) = match x_to_y {
+        true => (&ctx.accounts.token_x_vault, &ctx.accounts.token_y_vault),
+        false => (&ctx.accounts.token_y_vault, &ctx.accounts.token_x_vault),
+    };
+    ctx.accounts
+        .transfer_user_tokens_to_vault(vault_in, transfer_in_amount)?;
+    ctx.accounts
+        .transfer_tokens_to_user(vault_out, transfer_out_amount)
+}
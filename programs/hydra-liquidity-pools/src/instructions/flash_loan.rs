@@ -0,0 +1,225 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::flash_loaned::FlashLoaned;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use hydra_math_rs::programs::liquidity_pools::flash_loan::{
+    calculate_flash_loan_fee, repaid_balance,
+};
+
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = token_x_mint.key() == pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_x_vault_bump,
+        constraint = token_x_vault.key() == pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_y_vault_bump,
+        constraint = token_y_vault.key() == pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_x.mint == pool_state.token_x_mint @ ErrorCode::InvalidMintAddress,
+    )]
+    /// the token account the x loan is sent to
+    pub borrower_token_x: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_y.mint == pool_state.token_y_mint @ ErrorCode::InvalidMintAddress,
+    )]
+    /// the token account the y loan is sent to
+    pub borrower_token_y: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        executable,
+        constraint = borrower_program.key() != crate::ID @ ErrorCode::InvalidBorrowerProgram,
+    )]
+    /// CHECK: any program but this one, invoked with the remaining accounts once the loan is sent.
+    /// Invoking this program would let the borrower swap against the lent out vaults.
+    pub borrower_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FlashLoan<'info> {
+    pub fn transfer_vault_tokens_to_borrower(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from,
+            to,
+            authority: self.pool_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Check the vaults hold at least their repaid balances once the borrower returns
+    pub fn post_repayment_checks(&mut self, repaid_x: u64, repaid_y: u64) -> Result<()> {
+        self.token_x_vault.reload()?;
+        self.token_y_vault.reload()?;
+
+        if self.token_x_vault.amount < repaid_x {
+            msg!("repaid_x: {:?}", repaid_x);
+            msg!("token_x_vault.amount: {:?}", self.token_x_vault.amount);
+            return Err(ErrorCode::FlashLoanNotRepaid.into());
+        }
+
+        if self.token_y_vault.amount < repaid_y {
+            msg!("repaid_y: {:?}", repaid_y);
+            msg!("token_y_vault.amount: {:?}", self.token_y_vault.amount);
+            return Err(ErrorCode::FlashLoanNotRepaid.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Invoke the borrower program with data and the remaining accounts, passing their signer and writable flags on.
+/// The pool state never signs, the borrower repays with its own authority.
+pub fn invoke_borrower<'info>(
+    borrower_program: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    data: Vec<u8>,
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: borrower_program.key(),
+        accounts: remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(borrower_program.clone());
+    invoke(&instruction, &account_infos)?;
+    Ok(())
+}
+
+/// Lend amount_x and amount_y from the vaults to the borrower token accounts and invoke the borrower program,
+/// which must repay the vaults plus a fee of the pool swap fee before returning. The fee stays in the vaults,
+/// crediting the liquidity providers.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+    amount_x: u64,
+    amount_y: u64,
+    data: Vec<u8>,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_swaps()?;
+
+    if amount_x > ctx.accounts.token_x_vault.amount || amount_y > ctx.accounts.token_y_vault.amount
+    {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+
+    // twap prices accumulate up to the reserves before the loan
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+
+    let fees = &ctx.accounts.pool_state.fees;
+    let fee_x =
+        calculate_flash_loan_fee(amount_x, fees.swap_fee_numerator, fees.swap_fee_denominator)
+            .ok_or(ErrorCode::CalculateFeesFailed)?;
+    let fee_y =
+        calculate_flash_loan_fee(amount_y, fees.swap_fee_numerator, fees.swap_fee_denominator)
+            .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+    let repaid_x = repaid_balance(ctx.accounts.token_x_vault.amount, fee_x)
+        .ok_or(ErrorCode::CalculateFeesFailed)?;
+    let repaid_y = repaid_balance(ctx.accounts.token_y_vault.amount, fee_y)
+        .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+    // signer
+    let lp_token_mint = ctx.accounts.pool_state.lp_token_mint;
+    let seeds = &[
+        POOL_STATE_SEED,
+        lp_token_mint.as_ref(),
+        &[ctx.accounts.pool_state.pool_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    if amount_x > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_vault_tokens_to_borrower(
+                    ctx.accounts.token_x_vault.to_account_info(),
+                    ctx.accounts.borrower_token_x.to_account_info(),
+                )
+                .with_signer(&signer),
+            amount_x,
+        )?;
+    }
+
+    if amount_y > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_vault_tokens_to_borrower(
+                    ctx.accounts.token_y_vault.to_account_info(),
+                    ctx.accounts.borrower_token_y.to_account_info(),
+                )
+                .with_signer(&signer),
+            amount_y,
+        )?;
+    }
+
+    invoke_borrower(&ctx.accounts.borrower_program, ctx.remaining_accounts, data)?;
+
+    ctx.accounts.post_repayment_checks(repaid_x, repaid_y)?;
+
+    msg!("flash loan fee_x: {}, fee_y: {}", fee_x, fee_y);
+    emit!(FlashLoaned {
+        borrower_program: ctx.accounts.borrower_program.key(),
+        amount_x,
+        amount_y,
+        fee_x,
+        fee_y,
+        x_new: ctx.accounts.token_x_vault.amount,
+        y_new: ctx.accounts.token_y_vault.amount,
+        slot: Clock::get()?.slot,
+    });
+
+    Ok(())
+}
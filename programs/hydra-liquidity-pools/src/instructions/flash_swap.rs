@@ -0,0 +1,259 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::swapped::{SwapDirection, Swapped};
+use crate::instructions::flash_loan::invoke_borrower;
+use crate::state::pool_state::{PoolReserves, PoolState, PricedSwap, SwapMode};
+use crate::utils::oracle::get_oracle_price_from_account;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = token_x_mint.key() == pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_x_vault_bump,
+        constraint = token_x_vault.key() == pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_y_vault_bump,
+        constraint = token_y_vault.key() == pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_x_vault_bump,
+    )]
+    pub protocol_fee_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_y_vault_bump,
+    )]
+    pub protocol_fee_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_out.mint == pool_state.token_x_mint || borrower_token_out.mint == pool_state.token_y_mint @ ErrorCode::InvalidMintAddress,
+    )]
+    /// the token account amount_out is sent to, its mint is the token swapped out
+    pub borrower_token_out: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        executable,
+        constraint = borrower_program.key() != crate::ID @ ErrorCode::InvalidBorrowerProgram,
+    )]
+    /// CHECK: any program but this one, invoked with the remaining accounts once amount_out is sent.
+    /// Invoking this program would let the borrower swap against the lent out vaults.
+    pub borrower_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FlashSwap<'info> {
+    /// The borrower receives the out token, y for a swap of x to y
+    pub fn direction(&self) -> SwapDirection {
+        if self.borrower_token_out.mint == self.pool_state.token_y_mint {
+            SwapDirection::XToY
+        } else {
+            SwapDirection::YToX
+        }
+    }
+
+    /// Price the exact out swap of amount_out against the current vault balances, see [PoolState::price_swap].
+    /// A flash swap has no host fee account.
+    pub fn price_exact_out(
+        &mut self,
+        direction: SwapDirection,
+        amount_out: u64,
+        oracle_price: Option<u64>,
+    ) -> Result<PricedSwap> {
+        let reserves = PoolReserves {
+            x: self.token_x_vault.amount,
+            x_decimals: self.token_x_mint.decimals,
+            y: self.token_y_vault.amount,
+            y_decimals: self.token_y_mint.decimals,
+        };
+        self.pool_state.price_swap(
+            &reserves,
+            direction,
+            SwapMode::ExactOut,
+            amount_out,
+            oracle_price,
+            false,
+        )
+    }
+
+    pub fn transfer_vault_tokens(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from,
+            to,
+            authority: self.pool_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Check the in vault holds at least its repaid balance once the borrower returns
+    pub fn post_repayment_checks(
+        &mut self,
+        direction: SwapDirection,
+        repaid_in: u64,
+    ) -> Result<()> {
+        self.token_x_vault.reload()?;
+        self.token_y_vault.reload()?;
+
+        let vault_in = match direction {
+            SwapDirection::XToY => &self.token_x_vault,
+            SwapDirection::YToX => &self.token_y_vault,
+        };
+        if vault_in.amount < repaid_in {
+            msg!("repaid_in: {:?}", repaid_in);
+            msg!("vault_in.amount: {:?}", vault_in.amount);
+            return Err(ErrorCode::FlashSwapNotRepaid.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Send amount_out from the vault to the borrower token account and invoke the borrower program, which must repay
+/// the amount in of an exact out swap of amount_out, owner trade fees included, in the other token before returning.
+/// Remaining accounts are the price account of the pool when it has an oracle, followed by the accounts of the
+/// borrower program.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashSwap<'info>>,
+    amount_out: u64,
+    maximum_amount_in: u64,
+    data: Vec<u8>,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_swaps()?;
+
+    // twap prices accumulate up to the reserves before the swap
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+
+    let (price_account, borrower_accounts) = match ctx.accounts.pool_state.oracle {
+        Some(_) => (
+            ctx.remaining_accounts.first(),
+            ctx.remaining_accounts.get(1..).unwrap_or_default(),
+        ),
+        None => (None, ctx.remaining_accounts),
+    };
+    let oracle_price =
+        get_oracle_price_from_account(price_account, &mut ctx.accounts.pool_state.oracle)?;
+
+    let direction = ctx.accounts.direction();
+    let priced_swap = ctx
+        .accounts
+        .price_exact_out(direction, amount_out, oracle_price)?;
+    let amount_in = priced_swap.amount_in;
+    let reserve_in = match direction {
+        SwapDirection::XToY => ctx.accounts.token_x_vault.amount,
+        SwapDirection::YToX => ctx.accounts.token_y_vault.amount,
+    };
+    if amount_in > maximum_amount_in {
+        msg!("SlippageExceeded!");
+        msg!("amount_in: {:?}", amount_in);
+        msg!("maximum_amount_in: {:?}", maximum_amount_in);
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+    let repaid_in = reserve_in
+        .checked_add(amount_in)
+        .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+    // signer
+    let lp_token_mint = ctx.accounts.pool_state.lp_token_mint;
+    let seeds = &[
+        POOL_STATE_SEED,
+        lp_token_mint.as_ref(),
+        &[ctx.accounts.pool_state.pool_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    let (vault_in, vault_out, protocol_fee_vault) = match direction {
+        SwapDirection::XToY => (
+            ctx.accounts.token_x_vault.to_account_info(),
+            ctx.accounts.token_y_vault.to_account_info(),
+            ctx.accounts.protocol_fee_x_vault.to_account_info(),
+        ),
+        SwapDirection::YToX => (
+            ctx.accounts.token_y_vault.to_account_info(),
+            ctx.accounts.token_x_vault.to_account_info(),
+            ctx.accounts.protocol_fee_y_vault.to_account_info(),
+        ),
+    };
+
+    msg!("transfer_out_amount: {:?}", amount_out);
+    token::transfer(
+        ctx.accounts
+            .transfer_vault_tokens(vault_out, ctx.accounts.borrower_token_out.to_account_info())
+            .with_signer(&signer),
+        amount_out,
+    )?;
+
+    invoke_borrower(&ctx.accounts.borrower_program, borrower_accounts, data)?;
+
+    ctx.accounts.post_repayment_checks(direction, repaid_in)?;
+
+    // the borrower repays the owner trade fee into the vault, move it on to the protocol fee vault
+    if priced_swap.protocol_fee > 0 {
+        msg!("protocol_fee: {}", priced_swap.protocol_fee);
+        token::transfer(
+            ctx.accounts
+                .transfer_vault_tokens(vault_in, protocol_fee_vault)
+                .with_signer(&signer),
+            priced_swap.protocol_fee,
+        )?;
+        ctx.accounts.token_x_vault.reload()?;
+        ctx.accounts.token_y_vault.reload()?;
+    }
+
+    emit!(Swapped {
+        direction,
+        amount_in,
+        amount_out,
+        fee: priced_swap.swap_result.fees,
+        oracle_price,
+        x_new: ctx.accounts.token_x_vault.amount,
+        y_new: ctx.accounts.token_y_vault.amount,
+        slot: Clock::get()?.slot,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,16 @@
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::state::fees::Fees;
+use crate::state::pool_factory::{PairRegistry, PoolFactory, RegisteredPool};
 use crate::state::pool_state::*;
-use crate::{pyth_accounts_security_check, DEBUG_MODE};
+use crate::{oracle_accounts_security_check, DEBUG_MODE};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use hydra_math_rs::programs::liquidity_pools::hydra_lp_tokens::LIQUIDITY_POOL_SCALE;
 use std::mem;
 
 #[derive(Accounts)]
-#[instruction(token_a_vault_bump: u8, token_b_vault_bump: u8, pool_state_bump: u8, lp_token_vault_bump: u8, lp_token_mint_bump: u8)]
+#[instruction(token_a_vault_bump: u8, token_b_vault_bump: u8, pool_state_bump: u8, lp_token_vault_bump: u8, lp_token_mint_bump: u8, compensation_parameter: u8, fees: Fees, fee_tier_bps: u16)]
 pub struct Initialize<'info> {
     pub authority: Signer<'info>,
 
@@ -40,11 +41,27 @@ pub struct Initialize<'info> {
         payer = payer,
         mint::decimals = LIQUIDITY_POOL_SCALE,
         mint::authority = pool_state,
-        seeds = [ LP_TOKEN_MINT_SEED, token_x_mint.key().as_ref(), token_y_mint.key().as_ref() ],
+        seeds = [ LP_TOKEN_MINT_SEED, token_x_mint.key().as_ref(), token_y_mint.key().as_ref(), fee_tier_bps.to_le_bytes().as_ref() ],
         bump,
     )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
 
+    #[account(
+        mut,
+        seeds = [ POOL_FACTORY_SEED ],
+        bump = factory.bump,
+    )]
+    pub factory: Box<Account<'info, PoolFactory>>,
+
+    #[account(
+        init_if_needed,
+        space = 8 + mem::size_of::<PairRegistry>(),
+        payer = payer,
+        seeds = [ PAIR_REGISTRY_SEED, token_x_mint.key().as_ref(), token_y_mint.key().as_ref() ],
+        bump,
+    )]
+    pub pair_registry: Box<Account<'info, PairRegistry>>,
+
     #[account(
         init,
         payer = payer,
@@ -75,6 +92,36 @@ pub struct Initialize<'info> {
     )]
     pub lp_token_vault: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        init,
+        payer = payer,
+        token::mint = token_x_mint,
+        token::authority = pool_state,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, token_x_mint.key().as_ref(), lp_token_mint.key().as_ref() ],
+        bump,
+    )]
+    pub protocol_fee_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = token_y_mint,
+        token::authority = pool_state,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, token_y_mint.key().as_ref(), lp_token_mint.key().as_ref() ],
+        bump,
+    )]
+    pub protocol_fee_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lp_token_mint,
+        token::authority = pool_state,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.key().as_ref(), lp_token_mint.key().as_ref() ],
+        bump,
+    )]
+    pub protocol_fee_lp_vault: Box<Account<'info, TokenAccount>>,
+
     // system accounts
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -90,7 +137,13 @@ pub fn handle(
     lp_token_mint_bump: u8,
     compensation_parameter: u8,
     fees: Fees,
+    fee_tier_bps: u16,
 ) -> Result<()> {
+    validate_compensation_parameter(compensation_parameter)?;
+    ctx.accounts
+        .factory
+        .validate_pool(fee_tier_bps, compensation_parameter, &fees)?;
+
     let pool_state = &mut ctx.accounts.pool_state;
 
     // save authority
@@ -111,16 +164,29 @@ pub fn handle(
     pool_state.token_y_vault_bump = token_y_vault_bump;
     pool_state.lp_token_vault_bump = lp_token_vault_bump;
     pool_state.lp_token_mint_bump = lp_token_mint_bump;
+    pool_state.protocol_fee_x_vault_bump = *ctx.bumps.get("protocol_fee_x_vault").unwrap();
+    pool_state.protocol_fee_y_vault_bump = *ctx.bumps.get("protocol_fee_y_vault").unwrap();
+    pool_state.protocol_fee_lp_vault_bump = *ctx.bumps.get("protocol_fee_lp_vault").unwrap();
     pool_state.debug = DEBUG_MODE;
 
-    // TODO: Review this and add some error handling once @correkt-horse refactors the math crate
     pool_state.compensation_parameter = compensation_parameter;
+    pool_state.fee_tier_bps = fee_tier_bps;
 
     // save fees
     fees.validate()?;
     pool_state.fees = fees;
 
-    pool_state.pyth = pyth_accounts_security_check(&ctx.remaining_accounts)?;
+    pool_state.oracle = oracle_accounts_security_check(&ctx.remaining_accounts)?;
+
+    // record the pool in the registry of its pair
+    let pair_registry = &mut ctx.accounts.pair_registry;
+    pair_registry.token_x_mint = pool_state.token_x_mint;
+    pair_registry.token_y_mint = pool_state.token_y_mint;
+    pair_registry.register(RegisteredPool {
+        fee_tier_bps,
+        pool_state: pool_state.key(),
+    })?;
+    ctx.accounts.factory.pool_count += 1;
 
     if pool_state.debug {
         msg!("pool_state: {:?}", pool_state);
@@ -0,0 +1,51 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::program::HydraLiquidityPools;
+use crate::state::pool_factory::PoolFactory;
+use anchor_lang::prelude::*;
+use std::mem;
+
+#[derive(Accounts)]
+pub struct InitializeFactory<'info> {
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::InvalidFactoryAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<PoolFactory>(),
+        payer = payer,
+        seeds = [ POOL_FACTORY_SEED ],
+        bump,
+    )]
+    pub factory: Box<Account<'info, PoolFactory>>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::InvalidFactoryAuthority,
+    )]
+    pub program: Program<'info, HydraLiquidityPools>,
+
+    /// program data account holding the upgrade authority of the program
+    pub program_data: Box<Account<'info, ProgramData>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<InitializeFactory>,
+    fee_tiers: Vec<u16>,
+    compensation_parameters: Vec<u8>,
+) -> Result<()> {
+    let factory = &mut ctx.accounts.factory;
+    factory.authority = ctx.accounts.authority.key();
+    factory.bump = *ctx.bumps.get("factory").unwrap();
+    factory.set_fee_tiers(&fee_tiers)?;
+    factory.set_compensation_parameters(&compensation_parameters)?;
+
+    msg!("factory: {:?}", factory);
+    Ok(())
+}
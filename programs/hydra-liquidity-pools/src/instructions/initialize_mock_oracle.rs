@@ -0,0 +1,33 @@
+use crate::state::mock_oracle::MockOracle;
+use anchor_lang::prelude::*;
+use std::mem;
+
+#[derive(Accounts)]
+pub struct InitializeMockOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<MockOracle>(),
+        payer = payer,
+    )]
+    pub mock_oracle: Box<Account<'info, MockOracle>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// create a mock price account the authority can set prices on
+pub fn handle(ctx: Context<InitializeMockOracle>, price: i64, expo: i32, conf: u64) -> Result<()> {
+    let mock_oracle = &mut ctx.accounts.mock_oracle;
+    mock_oracle.authority = ctx.accounts.authority.key();
+    mock_oracle.price = price;
+    mock_oracle.expo = expo;
+    mock_oracle.conf = conf;
+    mock_oracle.slot = Clock::get()?.slot;
+
+    msg!("mock_oracle: {:?}", mock_oracle);
+    Ok(())
+}
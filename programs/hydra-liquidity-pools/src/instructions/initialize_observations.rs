@@ -0,0 +1,34 @@
+use crate::constants::*;
+use crate::state::pool_state::PoolState;
+use crate::state::price_observations::PoolObservations;
+use anchor_lang::prelude::*;
+use std::mem;
+
+#[derive(Accounts)]
+pub struct InitializeObservations<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        space = 8 + mem::size_of::<PoolObservations>(),
+        payer = payer,
+        seeds = [ POOL_OBSERVATIONS_SEED, pool_state.key().as_ref() ],
+        bump,
+    )]
+    pub observations: Box<Account<'info, PoolObservations>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// create the observation ring buffer of the pool, anyone can pay for it
+pub fn handle(ctx: Context<InitializeObservations>) -> Result<()> {
+    ctx.accounts.observations.pool_state = ctx.accounts.pool_state.key();
+    Ok(())
+}
@@ -1,5 +1,35 @@
+pub mod accept_authority;
 pub mod add_first_liquidity;
 pub mod add_liquidity;
+pub mod cl_collect_fees;
+pub mod cl_deposit;
+pub mod cl_initialize;
+pub mod cl_initialize_tick_array;
+pub mod cl_open_position;
+pub mod cl_set_pause;
+pub mod cl_swap;
+pub mod cl_withdraw;
+pub mod flash_loan;
+pub mod flash_swap;
 pub mod initialize;
+pub mod initialize_factory;
+pub mod initialize_mock_oracle;
+pub mod initialize_observations;
+pub mod quote_swap;
+pub mod record_observation;
 pub mod remove_liquidity;
+pub mod route_swap;
+pub mod set_circuit_breaker;
+pub mod set_compensation_parameter;
+pub mod set_factory_config;
+pub mod set_fee_mode;
+pub mod set_fees;
+pub mod set_mock_oracle_price;
+pub mod set_oracle;
+pub mod set_oracle_guards;
+pub mod set_pause;
 pub mod swap;
+pub mod swap_exact_out;
+pub mod transfer_authority;
+pub mod unset_oracle;
+pub mod withdraw_protocol_fees;
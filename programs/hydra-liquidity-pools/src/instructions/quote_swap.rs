@@ -0,0 +1,155 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::swapped::SwapDirection;
+use crate::instructions::swap::get_host_fee_account;
+use crate::state::pool_state::{PoolReserves, PoolState, SwapMode};
+use crate::utils::oracle::{check_oracle_price_account, get_pool_oracle_price};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{Mint, TokenAccount};
+use std::convert::TryFrom;
+
+/// Result of a swap quoted by quote_swap, serialized into the return data of the instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SwapQuote {
+    pub direction: SwapDirection,
+    /// total amount debited from the user including the swap and owner trade fees
+    pub amount_in: u64,
+    /// amount credited to the user
+    pub amount_out: u64,
+    /// swap fee paid to liquidity providers
+    pub fee: u64,
+    /// owner trade fee, including the share of a host fee account
+    pub owner_trade_fee: u64,
+    /// swap fee fraction the quote was priced with, see [PoolState::get_swap_fee]
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+    /// oracle price the swap was priced with, none for a constant product swap
+    pub oracle_price: Option<u64>,
+    pub x_new: u64,
+    pub y_new: u64,
+    /// move of the pool spot price in bps, none for an empty pool
+    pub price_impact_bps: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    #[account(
+        constraint = token_x_mint.key() == pool_state.token_x_mint
+    )]
+    pub token_x_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_y_mint.key() == pool_state.token_y_mint
+    )]
+    pub token_y_mint: Box<Account<'info, Mint>>,
+
+    // not mut: the oracle price, fee and circuit breaker state a quote updates are never persisted
+    #[account(
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_x_vault_bump,
+        constraint = token_x_vault.key() == pool_state.token_x_vault,
+    )]
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [ TOKEN_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.token_y_vault_bump,
+        constraint = token_y_vault.key() == pool_state.token_y_vault,
+    )]
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> QuoteSwap<'info> {
+    /// Get the oracle price the same way a swap does, see [Swap::get_oracle_price](crate::instructions::swap::Swap::get_oracle_price)
+    pub fn get_oracle_price(&mut self, remaining_accounts: &[AccountInfo]) -> Result<Option<u64>> {
+        get_pool_oracle_price(&mut self.pool_state.oracle, remaining_accounts)
+    }
+
+    /// Quote a swap of amount in direction as swap or swap_exact_out would execute it now with the same
+    /// remaining accounts, including the host fee of a host fee account, without moving tokens.
+    /// Errors where the swap would, apart from its slippage check.
+    pub fn quote(
+        &mut self,
+        remaining_accounts: &[AccountInfo],
+        direction: SwapDirection,
+        mode: SwapMode,
+        amount: u64,
+    ) -> Result<SwapQuote> {
+        let mint_in = match direction {
+            SwapDirection::XToY => self.pool_state.token_x_mint,
+            SwapDirection::YToX => self.pool_state.token_y_mint,
+        };
+        let host_fee_account = get_host_fee_account(remaining_accounts, &mint_in)?;
+        let oracle_price = self.get_oracle_price(remaining_accounts)?;
+
+        let reserves = PoolReserves {
+            x: self.token_x_vault.amount,
+            x_decimals: self.token_x_mint.decimals,
+            y: self.token_y_vault.amount,
+            y_decimals: self.token_y_mint.decimals,
+        };
+        let priced_swap = self.pool_state.price_swap(
+            &reserves,
+            direction,
+            mode,
+            amount,
+            oracle_price,
+            host_fee_account.is_some(),
+        )?;
+        let swap_result = priced_swap.swap_result;
+        let owner_trade_fee = priced_swap
+            .protocol_fee
+            .checked_add(priced_swap.host_fee)
+            .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+        let price_impact_bps = swap_result
+            .price_impact_bps(
+                self.token_x_vault.amount,
+                self.token_y_vault.amount,
+                self.token_x_mint.decimals,
+                self.token_y_mint.decimals,
+            )
+            .map(|bps| u64::try_from(bps).unwrap_or(u64::MAX));
+
+        Ok(SwapQuote {
+            direction,
+            amount_in: priced_swap.amount_in,
+            amount_out: priced_swap.amount_out,
+            fee: swap_result.fees,
+            owner_trade_fee,
+            swap_fee_numerator: priced_swap.swap_fee_numerator,
+            swap_fee_denominator: priced_swap.swap_fee_denominator,
+            oracle_price,
+            x_new: swap_result.x_new,
+            y_new: swap_result.y_new,
+            price_impact_bps,
+        })
+    }
+}
+
+/// Quote a swap against the current vaults and oracle and return the [SwapQuote] through the return data.
+/// Meant to be simulated, nothing is persisted even when it is sent.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, QuoteSwap<'info>>,
+    direction: SwapDirection,
+    mode: SwapMode,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_swaps()?;
+    check_oracle_price_account(&ctx.accounts.pool_state, ctx.remaining_accounts)?;
+
+    let quote = ctx
+        .accounts
+        .quote(ctx.remaining_accounts, direction, mode, amount)?;
+    msg!("quote: {:?}", quote);
+
+    set_return_data(&quote.try_to_vec()?);
+    Ok(())
+}
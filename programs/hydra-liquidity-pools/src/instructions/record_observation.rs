@@ -0,0 +1,57 @@
+use crate::constants::*;
+use crate::state::pool_state::PoolState;
+use crate::state::price_observations::PoolObservations;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RecordObservation<'info> {
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = token_x_mint,
+        has_one = token_y_mint,
+        has_one = token_x_vault,
+        has_one = token_y_vault,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    pub token_x_mint: Box<Account<'info, Mint>>,
+    pub token_y_mint: Box<Account<'info, Mint>>,
+    pub token_x_vault: Box<Account<'info, TokenAccount>>,
+    pub token_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_OBSERVATIONS_SEED, pool_state.key().as_ref() ],
+        bump,
+        has_one = pool_state,
+    )]
+    pub observations: Box<Account<'info, PoolObservations>>,
+}
+
+/// accumulate the twap prices of the pool up to now and record them, at most once per slot.
+/// Anyone can crank it, the observations it records are the ones a twap is computed between.
+pub fn handle(ctx: Context<RecordObservation>) -> Result<()> {
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+
+    let recorded = ctx
+        .accounts
+        .observations
+        .record(ctx.accounts.pool_state.price_cumulatives);
+
+    if ctx.accounts.pool_state.debug {
+        msg!("recorded: {}", recorded);
+        msg!(
+            "price_cumulatives: {:?}",
+            ctx.accounts.pool_state.price_cumulatives
+        );
+    }
+    Ok(())
+}
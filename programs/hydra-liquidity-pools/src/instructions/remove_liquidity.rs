@@ -1,11 +1,14 @@
 use crate::constants::*;
+use crate::errors::{decimal_error, ErrorCode};
 use crate::events::liquidity_removed::LiquidityRemoved;
+use crate::events::remove_liquidity_slippage_exceeded::RemoveLiquiditySlippageExceeded;
 use crate::state::pool_state::PoolState;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::{Burn, Mint, Token, TokenAccount, Transfer};
 use hydra_math_rs::programs::liquidity_pools::hydra_lp_tokens::*;
+use hydra_math_rs::programs::liquidity_pools::protocol_fees::calculate_fee;
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
@@ -70,8 +73,6 @@ pub struct RemoveLiquidity<'info> {
 
     #[account(
         mut,
-        seeds = [ LP_TOKEN_MINT_SEED, pool_state.token_x_mint.as_ref(), pool_state.token_y_mint.as_ref() ],
-        bump,
         constraint = lp_token_mint.key() == pool_state.lp_token_mint,
     )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
@@ -88,6 +89,13 @@ pub struct RemoveLiquidity<'info> {
     )]
     pub token_y_mint: Box<Account<'info, Mint>>,
 
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.key().as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_lp_vault_bump,
+    )]
+    pub protocol_fee_lp_vault: Box<Account<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
@@ -143,10 +151,22 @@ impl<'info> RemoveLiquidity<'info> {
         CpiContext::new(cpi_program, cpi_accounts)
     }
 
+    pub fn transfer_owner_withdraw_fee_to_vault(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.user_redeemable_lp_tokens.to_account_info(),
+            to: self.protocol_fee_lp_vault.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
     pub fn calculate_a_and_b_tokens_to_credit_from_lp_tokens(
         &self,
         lp_tokens_to_burn: u64,
-    ) -> (u64, u64) {
+    ) -> Result<(u64, u64)> {
         calculate_x_y(
             lp_tokens_to_burn,
             self.lp_token_mint.decimals,
@@ -156,10 +176,24 @@ impl<'info> RemoveLiquidity<'info> {
             self.token_y_mint.decimals,
             self.lp_token_mint.supply,
         )
+        .map_err(decimal_error)
     }
 }
 
-pub fn handle(ctx: Context<RemoveLiquidity>, lp_tokens_to_burn: u64) -> Result<()> {
+pub fn handle(
+    ctx: Context<RemoveLiquidity>,
+    lp_tokens_to_burn: u64,
+    token_x_min_amount: u64,
+    token_y_min_amount: u64,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_withdrawals()?;
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+
     let seeds = &[
         POOL_STATE_SEED,
         ctx.accounts.pool_state.lp_token_mint.as_ref(),
@@ -167,9 +201,43 @@ pub fn handle(ctx: Context<RemoveLiquidity>, lp_tokens_to_burn: u64) -> Result<(
     ];
     let signer = [&seeds[..]];
 
+    // owner withdraw fees are taken in lp tokens, only the remainder is redeemed
+    let owner_withdraw_fee = calculate_fee(
+        lp_tokens_to_burn,
+        ctx.accounts.pool_state.fees.owner_withdraw_fee_numerator,
+        ctx.accounts.pool_state.fees.owner_withdraw_fee_denominator,
+    )
+    .ok_or(ErrorCode::CalculateFeesFailed)?;
+    let lp_tokens_to_burn = lp_tokens_to_burn - owner_withdraw_fee;
+
     let (token_x_to_credit, token_y_to_credit) = ctx
         .accounts
-        .calculate_a_and_b_tokens_to_credit_from_lp_tokens(lp_tokens_to_burn);
+        .calculate_a_and_b_tokens_to_credit_from_lp_tokens(lp_tokens_to_burn)?;
+
+    if (token_x_to_credit < token_x_min_amount) || (token_y_to_credit < token_y_min_amount) {
+        if ctx.accounts.pool_state.debug {
+            msg!("Error: SlippageExceeded");
+            msg!("token_x_to_credit: {}", token_x_to_credit);
+            msg!("token_x_min_amount: {}", token_x_min_amount);
+            msg!("token_y_to_credit: {}", token_y_to_credit);
+            msg!("token_y_min_amount: {}", token_y_min_amount);
+        }
+        emit!(RemoveLiquiditySlippageExceeded {
+            token_x_to_credit,
+            token_y_to_credit,
+            token_x_min_amount,
+            token_y_min_amount,
+        });
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+
+    if owner_withdraw_fee > 0 {
+        msg!("owner_withdraw_fee: {}", owner_withdraw_fee);
+        token::transfer(
+            ctx.accounts.transfer_owner_withdraw_fee_to_vault(),
+            owner_withdraw_fee,
+        )?;
+    }
 
     if ctx.accounts.pool_state.debug {
         msg!("lp_tokens_to_burn: {}", lp_tokens_to_burn);
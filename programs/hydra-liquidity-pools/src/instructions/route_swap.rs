@@ -0,0 +1,363 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::swapped::{SwapDirection, Swapped};
+use crate::state::pool_state::{PoolReserves, PoolState, SwapMode};
+use crate::utils::oracle::get_and_update_last_known_price;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use hydra_math_rs::programs::liquidity_pools::route_finder::MAX_ROUTE_HOPS;
+use hydra_math_rs::programs::liquidity_pools::swap_result::SwapResult;
+
+#[derive(Accounts)]
+pub struct RouteSwap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_from_token.owner == user.key()
+    )]
+    /// the token account to withdraw from
+    pub user_from_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_to_token.owner == user.key()
+    )]
+    /// the token account to send the output of the last pool to
+    pub user_to_token: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// The accounts of one pool of a route, passed in as remaining_accounts
+/// [pool_state, token_x_mint, token_y_mint, token_x_vault, token_y_vault, protocol_fee_x_vault, protocol_fee_y_vault, oracle?]
+/// where the oracle price account is required for pools with an oracle.
+pub struct RoutePool<'info> {
+    pub pool_state: Account<'info, PoolState>,
+    pub token_x_mint: Account<'info, Mint>,
+    pub token_y_mint: Account<'info, Mint>,
+    pub token_x_vault: Account<'info, TokenAccount>,
+    pub token_y_vault: Account<'info, TokenAccount>,
+    pub protocol_fee_x_vault: AccountInfo<'info>,
+    pub protocol_fee_y_vault: AccountInfo<'info>,
+    pub oracle: Option<AccountInfo<'info>>,
+}
+
+/// The result of swapping through one pool of a route
+pub struct RouteHop {
+    pub x_to_y: bool,
+    pub transfer_in_amount: u64,
+    pub protocol_fee: u64,
+    pub amount_out: u64,
+    pub oracle_price: Option<u64>,
+    pub swap_result: SwapResult,
+}
+
+impl<'info> RoutePool<'info> {
+    /// Load and validate the accounts of the next pool, advancing the remaining accounts past them
+    pub fn load(accounts: &mut &[AccountInfo<'info>]) -> Result<Self> {
+        if accounts.len() < 7 {
+            return Err(ErrorCode::InvalidRoute.into());
+        }
+
+        let pool_state = Account::<PoolState>::try_from(&accounts[0])?;
+        let token_x_mint = Account::<Mint>::try_from(&accounts[1])?;
+        let token_y_mint = Account::<Mint>::try_from(&accounts[2])?;
+        let token_x_vault = Account::<TokenAccount>::try_from(&accounts[3])?;
+        let token_y_vault = Account::<TokenAccount>::try_from(&accounts[4])?;
+        let protocol_fee_x_vault = accounts[5].clone();
+        let protocol_fee_y_vault = accounts[6].clone();
+
+        if token_x_mint.key() != pool_state.token_x_mint
+            || token_y_mint.key() != pool_state.token_y_mint
+            || token_x_vault.key() != pool_state.token_x_vault
+            || token_y_vault.key() != pool_state.token_y_vault
+            || protocol_fee_x_vault.key()
+                != pool_state.protocol_fee_vault(&pool_state.token_x_mint)?
+            || protocol_fee_y_vault.key()
+                != pool_state.protocol_fee_vault(&pool_state.token_y_mint)?
+        {
+            return Err(ErrorCode::InvalidRoute.into());
+        }
+
+        let mut consumed = 7;
+        let oracle = match &pool_state.oracle {
+            Some(oracle_settings) => {
+                let oracle = accounts.get(7).ok_or(ErrorCode::InvalidRoute)?;
+                if oracle.key() != oracle_settings.price_account {
+                    return Err(ErrorCode::InvalidRoute.into());
+                }
+                consumed += 1;
+                Some(oracle.clone())
+            }
+            None => None,
+        };
+
+        *accounts = &accounts[consumed..];
+
+        Ok(RoutePool {
+            pool_state,
+            token_x_mint,
+            token_y_mint,
+            token_x_vault,
+            token_y_vault,
+            protocol_fee_x_vault,
+            protocol_fee_y_vault,
+            oracle,
+        })
+    }
+
+    /// Swap amount_in of mint_in through the pool against its current vault balances
+    pub fn swap(&mut self, mint_in: &Pubkey, amount_in: u64) -> Result<RouteHop> {
+        let x_to_y = if *mint_in == self.pool_state.token_x_mint {
+            true
+        } else if *mint_in == self.pool_state.token_y_mint {
+            false
+        } else {
+            return Err(ErrorCode::InvalidRoute.into());
+        };
+
+        self.pool_state.pause.check_swaps()?;
+        self.pool_state.accumulate_prices(
+            self.token_x_vault.amount,
+            self.token_x_mint.decimals,
+            self.token_y_vault.amount,
+            self.token_y_mint.decimals,
+        )?;
+
+        let oracle_price = match &self.oracle {
+            Some(oracle) => get_and_update_last_known_price(oracle, &mut self.pool_state.oracle)?,
+            None => None,
+        };
+
+        // owner trade fees are taken from the amount swapped into each pool, routes pay no host fees
+        let reserves = PoolReserves {
+            x: self.token_x_vault.amount,
+            x_decimals: self.token_x_mint.decimals,
+            y: self.token_y_vault.amount,
+            y_decimals: self.token_y_mint.decimals,
+        };
+        let direction = if x_to_y {
+            SwapDirection::XToY
+        } else {
+            SwapDirection::YToX
+        };
+        let priced_swap = self.pool_state.price_swap(
+            &reserves,
+            direction,
+            SwapMode::ExactIn,
+            amount_in,
+            oracle_price,
+            false,
+        )?;
+
+        Ok(RouteHop {
+            x_to_y,
+            transfer_in_amount: priced_swap.transfer_in_amount,
+            protocol_fee: priced_swap.protocol_fee,
+            amount_out: priced_swap.amount_out,
+            oracle_price,
+            swap_result: priced_swap.swap_result,
+        })
+    }
+
+    pub fn mint_out(&self, hop: &RouteHop) -> Pubkey {
+        if hop.x_to_y {
+            self.pool_state.token_y_mint
+        } else {
+            self.pool_state.token_x_mint
+        }
+    }
+
+    pub fn vault_in(&self, hop: &RouteHop) -> AccountInfo<'info> {
+        if hop.x_to_y {
+            self.token_x_vault.to_account_info()
+        } else {
+            self.token_y_vault.to_account_info()
+        }
+    }
+
+    pub fn vault_out(&self, hop: &RouteHop) -> AccountInfo<'info> {
+        if hop.x_to_y {
+            self.token_y_vault.to_account_info()
+        } else {
+            self.token_x_vault.to_account_info()
+        }
+    }
+
+    pub fn protocol_fee_vault_in(&self, hop: &RouteHop) -> AccountInfo<'info> {
+        if hop.x_to_y {
+            self.protocol_fee_x_vault.clone()
+        } else {
+            self.protocol_fee_y_vault.clone()
+        }
+    }
+
+    /// Reload the vaults and confirm they match the swap result of the pool
+    pub fn post_transfer_checks(&mut self, swap_result: &SwapResult) -> Result<()> {
+        self.token_x_vault.reload()?;
+        self.token_y_vault.reload()?;
+
+        if swap_result.x_new != self.token_x_vault.amount
+            || swap_result.y_new != self.token_y_vault.amount
+        {
+            msg!("x_new: {:?}", swap_result.x_new);
+            msg!("token_x_vault.amount: {:?}", self.token_x_vault.amount);
+            msg!("y_new: {:?}", swap_result.y_new);
+            msg!("token_y_vault.amount: {:?}", self.token_y_vault.amount);
+            return Err(ErrorCode::InvalidVaultToSwapResultAmounts.into());
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, RouteSwap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut pools: Vec<RoutePool<'info>> = Vec::new();
+    while !remaining_accounts.is_empty() {
+        let pool = RoutePool::load(&mut remaining_accounts)?;
+        // a pool can only be swapped through once so its vault checks hold
+        if pools
+            .iter()
+            .any(|p| p.pool_state.key() == pool.pool_state.key())
+        {
+            return Err(ErrorCode::InvalidRoute.into());
+        }
+        pools.push(pool);
+    }
+
+    if pools.is_empty() || pools.len() > MAX_ROUTE_HOPS {
+        return Err(ErrorCode::InvalidRoute.into());
+    }
+
+    // compute every hop against the balances of the pools before any transfer
+    let mut hops = Vec::with_capacity(pools.len());
+    let mut mint_in = ctx.accounts.user_from_token.mint;
+    let mut amount = amount_in;
+    for pool in pools.iter_mut() {
+        let hop = pool.swap(&mint_in, amount)?;
+        mint_in = pool.mint_out(&hop);
+        amount = hop.amount_out;
+        hops.push(hop);
+    }
+
+    if mint_in != ctx.accounts.user_to_token.mint {
+        return Err(ErrorCode::InvalidRoute.into());
+    }
+
+    let amount_out = amount;
+    if amount_out < minimum_amount_out {
+        msg!("SlippageExceeded!");
+        msg!("amount_out: {:?}", amount_out);
+        msg!("minimum_amount_out: {:?}", minimum_amount_out);
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+
+    // the user funds the first pool, every later pool is funded from the out vault of the pool before it
+    for (index, hop) in hops.iter().enumerate() {
+        let pool = &pools[index];
+        let transfers = [
+            (pool.vault_in(hop), hop.transfer_in_amount),
+            (pool.protocol_fee_vault_in(hop), hop.protocol_fee),
+        ];
+
+        for (to, amount) in transfers {
+            if amount == 0 {
+                continue;
+            }
+            if index == 0 {
+                token::transfer(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.user_from_token.to_account_info(),
+                            to,
+                            authority: ctx.accounts.user.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )?;
+            } else {
+                transfer_from_pool(
+                    &ctx.accounts.token_program,
+                    &pools[index - 1],
+                    &hops[index - 1],
+                    to,
+                    amount,
+                )?;
+            }
+        }
+    }
+
+    // transfer the output of the last pool to the user
+    let last = pools.len() - 1;
+    transfer_from_pool(
+        &ctx.accounts.token_program,
+        &pools[last],
+        &hops[last],
+        ctx.accounts.user_to_token.to_account_info(),
+        amount_out,
+    )?;
+
+    let slot = Clock::get()?.slot;
+    for (pool, hop) in pools.iter_mut().zip(hops.iter()) {
+        emit!(Swapped {
+            direction: if hop.x_to_y {
+                SwapDirection::XToY
+            } else {
+                SwapDirection::YToX
+            },
+            amount_in: hop.transfer_in_amount + hop.protocol_fee,
+            amount_out: hop.amount_out,
+            fee: hop.swap_result.fees,
+            oracle_price: hop.oracle_price,
+            x_new: hop.swap_result.x_new,
+            y_new: hop.swap_result.y_new,
+            slot,
+        });
+
+        pool.post_transfer_checks(&hop.swap_result)?;
+        // persist oracle and fee state updated while swapping
+        pool.pool_state.exit(ctx.program_id)?;
+    }
+
+    msg!("route_amount_in: {}", amount_in);
+    msg!("route_amount_out: {}", amount_out);
+
+    Ok(())
+}
+
+/// Transfer from the out vault of a pool, signed by its pool_state
+fn transfer_from_pool<'info>(
+    token_program: &Program<'info, Token>,
+    pool: &RoutePool<'info>,
+    hop: &RouteHop,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        POOL_STATE_SEED,
+        pool.pool_state.lp_token_mint.as_ref(),
+        &[pool.pool_state.pool_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: pool.vault_out(hop),
+                to,
+                authority: pool.pool_state.to_account_info(),
+            },
+        )
+        .with_signer(&signer),
+        amount,
+    )
+}
@@ -0,0 +1,38 @@
+use crate::constants::*;
+use crate::state::circuit_breaker::CircuitBreaker;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+pub fn handle(
+    ctx: Context<SetCircuitBreaker>,
+    max_oracle_deviation_bps: u16,
+    max_slot_price_move_bps: u16,
+) -> Result<()> {
+    // the next swap records a fresh slot start price
+    ctx.accounts.pool_state.circuit_breaker = CircuitBreaker {
+        max_oracle_deviation_bps,
+        max_slot_price_move_bps,
+        ..CircuitBreaker::default()
+    };
+
+    if ctx.accounts.pool_state.debug {
+        msg!(
+            "circuit_breaker: {:?}",
+            ctx.accounts.pool_state.circuit_breaker
+        );
+    }
+    Ok(())
+}
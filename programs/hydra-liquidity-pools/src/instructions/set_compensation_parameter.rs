@@ -0,0 +1,36 @@
+use crate::constants::*;
+use crate::state::pool_factory::PoolFactory;
+use crate::state::pool_state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCompensationParameter<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [ POOL_FACTORY_SEED ],
+        bump = factory.bump,
+    )]
+    pub factory: Box<Account<'info, PoolFactory>>,
+}
+
+pub fn handle(ctx: Context<SetCompensationParameter>, compensation_parameter: u8) -> Result<()> {
+    validate_compensation_parameter(compensation_parameter)?;
+    ctx.accounts
+        .factory
+        .validate_compensation_parameter(compensation_parameter)?;
+    ctx.accounts.pool_state.compensation_parameter = compensation_parameter;
+
+    if ctx.accounts.pool_state.debug {
+        msg!("compensation_parameter: {}", compensation_parameter);
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::constants::*;
+use crate::state::pool_factory::PoolFactory;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFactoryConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_FACTORY_SEED ],
+        bump = factory.bump,
+        has_one = authority,
+    )]
+    pub factory: Box<Account<'info, PoolFactory>>,
+}
+
+pub fn handle(
+    ctx: Context<SetFactoryConfig>,
+    fee_tiers: Vec<u16>,
+    compensation_parameters: Vec<u8>,
+) -> Result<()> {
+    let factory = &mut ctx.accounts.factory;
+    factory.set_fee_tiers(&fee_tiers)?;
+    factory.set_compensation_parameters(&compensation_parameters)?;
+
+    msg!("factory: {:?}", factory);
+    Ok(())
+}
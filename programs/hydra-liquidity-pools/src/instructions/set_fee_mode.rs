@@ -0,0 +1,28 @@
+use crate::constants::*;
+use crate::state::fees::{FeeMode, VolAdjFeeState};
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeMode<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+pub fn handle(ctx: Context<SetFeeMode>, fee_mode: FeeMode) -> Result<()> {
+    ctx.accounts.pool_state.fee_mode = fee_mode;
+    // the next swap starts a fresh volatility observation from the default ewma
+    ctx.accounts.pool_state.vol_adj_fee = VolAdjFeeState::default();
+
+    if ctx.accounts.pool_state.debug {
+        msg!("fee_mode: {:?}", fee_mode);
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::constants::*;
+use crate::state::fees::Fees;
+use crate::state::pool_factory::validate_fee_tier;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+pub fn handle(ctx: Context<SetFees>, fees: Fees) -> Result<()> {
+    fees.validate()?;
+    validate_fee_tier(ctx.accounts.pool_state.fee_tier_bps, &fees)?;
+    ctx.accounts.pool_state.fees = fees;
+
+    if ctx.accounts.pool_state.debug {
+        msg!("fees: {:?}", ctx.accounts.pool_state.fees);
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::state::mock_oracle::MockOracle;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMockOraclePrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+    )]
+    pub mock_oracle: Box<Account<'info, MockOracle>>,
+}
+
+/// publish a new price on the mock oracle at the current slot, a zero price has the oracle report no live price
+pub fn handle(ctx: Context<SetMockOraclePrice>, price: i64, conf: u64) -> Result<()> {
+    let mock_oracle = &mut ctx.accounts.mock_oracle;
+    mock_oracle.price = price;
+    mock_oracle.conf = conf;
+    mock_oracle.slot = Clock::get()?.slot;
+
+    msg!("mock_oracle: {:?}", mock_oracle);
+    Ok(())
+}
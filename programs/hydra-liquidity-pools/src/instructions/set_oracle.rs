@@ -0,0 +1,35 @@
+use crate::constants::*;
+use crate::oracle_accounts_security_check;
+use crate::state::pool_state::PoolState;
+use crate::utils::oracle::OracleErrors::MissingOracleAccounts;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+/// attach or replace the oracle accounts passed in as remaining_accounts. [product, price] for pyth, [aggregator] for
+/// switchboard or [mock_oracle] for a mock oracle
+pub fn handle(ctx: Context<SetOracle>) -> Result<()> {
+    let mut oracle_settings =
+        oracle_accounts_security_check(ctx.remaining_accounts)?.ok_or(MissingOracleAccounts)?;
+    // replacing the accounts keeps the oracle guards configured for the pool
+    if let Some(previous) = &ctx.accounts.pool_state.oracle {
+        oracle_settings = oracle_settings.with_guards_of(previous);
+    }
+    ctx.accounts.pool_state.oracle = Some(oracle_settings);
+
+    if ctx.accounts.pool_state.debug {
+        msg!("oracle: {:?}", ctx.accounts.pool_state.oracle);
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::*;
+use crate::state::pool_state::PoolState;
+use crate::utils::oracle::OracleErrors::{InvalidMaxConfidence, InvalidSettingsForAccount};
+use crate::utils::oracle::OracleGuardPolicy;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOracleGuards<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+pub fn handle(
+    ctx: Context<SetOracleGuards>,
+    max_staleness_slots: u64,
+    max_confidence_bps: u16,
+    guard_policy: OracleGuardPolicy,
+) -> Result<()> {
+    if max_confidence_bps > 10_000 {
+        return Err(InvalidMaxConfidence.into());
+    }
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let oracle_settings = pool_state
+        .oracle
+        .as_mut()
+        .ok_or(InvalidSettingsForAccount)?;
+    oracle_settings.max_staleness_slots = max_staleness_slots;
+    oracle_settings.max_confidence_bps = max_confidence_bps;
+    oracle_settings.guard_policy = guard_policy;
+
+    if pool_state.debug {
+        msg!("oracle: {:?}", pool_state.oracle);
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::constants::*;
+use crate::state::circuit_breaker::PauseFlags;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+pub fn handle(ctx: Context<SetPause>, pause: PauseFlags) -> Result<()> {
+    ctx.accounts.pool_state.pause = pause;
+
+    msg!("pause: {:?}", pause);
+    Ok(())
+}
@@ -1,14 +1,12 @@
 use crate::constants::*;
 use crate::errors::ErrorCode;
-use crate::state::pool_state::PoolState;
-use crate::utils::pyth::get_and_update_last_known_price;
+use crate::events::swapped::{SwapDirection, Swapped};
+use crate::state::pool_state::{PoolReserves, PoolState, PricedSwap, SwapMode};
+use crate::utils::oracle::get_pool_oracle_price;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
-use hydra_math_rs::programs::liquidity_pools::swap_calculator_wasm::{
-    swap_x_to_y_hmm, swap_y_to_x_hmm,
-};
 use hydra_math_rs::programs::liquidity_pools::swap_result::SwapResult;
 
 #[derive(Accounts)]
@@ -35,8 +33,6 @@ pub struct Swap<'info> {
 
     #[account(
         mut,
-        seeds = [ LP_TOKEN_MINT_SEED, pool_state.token_x_mint.as_ref(), pool_state.token_y_mint.as_ref() ],
-        bump,
         constraint = lp_token_mint.key() == pool_state.lp_token_mint,
     )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
@@ -81,6 +77,20 @@ pub struct Swap<'info> {
     )]
     pub token_y_vault: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_x_vault_bump,
+    )]
+    pub protocol_fee_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_y_vault_bump,
+    )]
+    pub protocol_fee_y_vault: Box<Account<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
@@ -158,19 +168,126 @@ impl<'info> Swap<'info> {
 
     /// Get saved oracle price exponent if oracle settings exist for the pool_state
     pub fn get_oracle_price_exponent(&self) -> Option<u8> {
-        if let Some(pyth_settings) = &self.pool_state.pyth {
-            return Some(pyth_settings.price_exponent);
-        }
-        None
+        self.pool_state.oracle_price_exponent()
     }
 
     /// Get Oracle price from either a live feed or last_known_price if the feed is offline.
-    pub fn get_oracle_price(&mut self, remaining_accounts: &[AccountInfo]) -> Option<u64> {
-        if remaining_accounts.len() == 1 {
-            return get_and_update_last_known_price(&remaining_accounts[0], &mut self.pool_state);
+    /// Errors when the price is outside the oracle tolerance of the pool and its guard policy rejects the swap.
+    pub fn get_oracle_price(&mut self, remaining_accounts: &[AccountInfo]) -> Result<Option<u64>> {
+        get_pool_oracle_price(&mut self.pool_state.oracle, remaining_accounts)
+    }
+
+    /// Current vault balances of the pool, the reserves a swap is priced against
+    pub fn reserves(&self) -> PoolReserves {
+        PoolReserves {
+            x: self.token_x_vault.amount,
+            x_decimals: self.token_x_mint.decimals,
+            y: self.token_y_vault.amount,
+            y_decimals: self.token_y_mint.decimals,
+        }
+    }
+
+    /// Price a swap against the current vault balances, see [PoolState::price_swap]
+    pub fn price_swap(
+        &mut self,
+        direction: SwapDirection,
+        mode: SwapMode,
+        amount: u64,
+        oracle_price: Option<u64>,
+        has_host_fee_account: bool,
+    ) -> Result<PricedSwap> {
+        let reserves = self.reserves();
+        self.pool_state.price_swap(
+            &reserves,
+            direction,
+            mode,
+            amount,
+            oracle_price,
+            has_host_fee_account,
+        )
+    }
+
+    /// Emit a [Swapped] event once the swap has been settled.
+    pub fn emit_swapped(
+        &self,
+        direction: SwapDirection,
+        amount_in: u64,
+        amount_out: u64,
+        oracle_price: Option<u64>,
+        swap_result: &SwapResult,
+    ) -> Result<()> {
+        emit!(Swapped {
+            direction,
+            amount_in,
+            amount_out,
+            fee: swap_result.fees,
+            oracle_price,
+            x_new: swap_result.x_new,
+            y_new: swap_result.y_new,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Get the optional host fee token account passed in the remaining accounts, it must hold the token swapped in.
+    pub fn get_host_fee_account(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Option<AccountInfo<'info>>> {
+        get_host_fee_account(remaining_accounts, &self.user_from_token.mint)
+    }
+
+    /// Transfer the owner trade fee from the user to the protocol fee vault and the host fee account.
+    pub fn transfer_owner_trade_fees(
+        &self,
+        protocol_fee_vault: AccountInfo<'info>,
+        protocol_fee: u64,
+        host_fee_account: Option<&AccountInfo<'info>>,
+        host_fee: u64,
+    ) -> Result<()> {
+        if protocol_fee > 0 {
+            msg!("protocol_fee: {}", protocol_fee);
+            token::transfer(
+                self.transfer_user_tokens_to_vault(protocol_fee_vault),
+                protocol_fee,
+            )?;
+        }
+
+        if let Some(host_fee_account) = host_fee_account {
+            if host_fee > 0 {
+                msg!("host_fee: {}", host_fee);
+                token::transfer(
+                    self.transfer_user_tokens_to_vault(host_fee_account.clone()),
+                    host_fee,
+                )?;
+            }
         }
-        None
+
+        Ok(())
+    }
+}
+
+/// Get the optional host fee token account among the remaining accounts of a swap or quote, it must hold mint_in.
+pub fn get_host_fee_account<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    mint_in: &Pubkey,
+) -> Result<Option<AccountInfo<'info>>> {
+    let mut host_fee_accounts = remaining_accounts
+        .iter()
+        .filter(|account| *account.owner == token::ID);
+
+    let host_fee_account = match host_fee_accounts.next() {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    let token_account = Account::<TokenAccount>::try_from(host_fee_account)?;
+    if host_fee_accounts.next().is_some() || token_account.mint != *mint_in {
+        return Err(ErrorCode::InvalidHostFeeAccount.into());
     }
+
+    Ok(Some(host_fee_account.clone()))
 }
 
 // security check mint addresses are both correct as per the pool state object.
@@ -202,8 +319,22 @@ pub fn mint_addresses_security_check(ctx: &Context<Swap>) -> Result<()> {
     Ok(())
 }
 
-pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
-    let transfer_in_amount = amount_in;
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_swaps()?;
+
+    // twap prices accumulate up to the reserves before the swap
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+
+    let host_fee_account = ctx.accounts.get_host_fee_account(ctx.remaining_accounts)?;
 
     // signer
     let lp_token_mint = ctx.accounts.pool_state.lp_token_mint.clone();
@@ -222,25 +353,16 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
             return Err(ErrorCode::InvalidMintAddress.into());
         }
 
-        let swap_result: SwapResult = From::from(
-            swap_x_to_y_hmm(
-                ctx.accounts.token_x_vault.amount,
-                ctx.accounts.token_x_mint.decimals,
-                ctx.accounts.token_y_vault.amount,
-                ctx.accounts.token_y_mint.decimals,
-                ctx.accounts.pool_state.compensation_parameter,
-                ctx.accounts
-                    .get_oracle_price(&ctx.remaining_accounts)
-                    .unwrap_or(0),
-                ctx.accounts.get_oracle_price_exponent().unwrap_or(0),
-                ctx.accounts.pool_state.fees.swap_fee_numerator,
-                ctx.accounts.pool_state.fees.swap_fee_denominator,
-                amount_in,
-            )
-            .expect("swap_result"),
-        );
-
-        let transfer_out_amount = swap_result.delta_y;
+        let oracle_price = ctx.accounts.get_oracle_price(ctx.remaining_accounts)?;
+        let priced_swap = ctx.accounts.price_swap(
+            SwapDirection::XToY,
+            SwapMode::ExactIn,
+            amount_in,
+            oracle_price,
+            host_fee_account.is_some(),
+        )?;
+        let transfer_in_amount = priced_swap.transfer_in_amount;
+        let transfer_out_amount = priced_swap.amount_out;
 
         check_slippage(
             &amount_in,
@@ -257,6 +379,13 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
             transfer_in_amount,
         )?;
 
+        ctx.accounts.transfer_owner_trade_fees(
+            ctx.accounts.protocol_fee_x_vault.to_account_info(),
+            priced_swap.protocol_fee,
+            host_fee_account.as_ref(),
+            priced_swap.host_fee,
+        )?;
+
         // transfer y to user
         msg!("transfer_out_amount: {:?}", transfer_out_amount);
         token::transfer(
@@ -265,6 +394,14 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
                 .with_signer(&signer),
             transfer_out_amount,
         )?;
+
+        ctx.accounts.emit_swapped(
+            SwapDirection::XToY,
+            amount_in,
+            transfer_out_amount,
+            oracle_price,
+            &priced_swap.swap_result,
+        )?;
     }
 
     // detect swap direction y to x
@@ -275,25 +412,16 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
             return Err(ErrorCode::InvalidMintAddress.into());
         }
 
-        let swap_result: SwapResult = From::from(
-            swap_y_to_x_hmm(
-                ctx.accounts.token_x_vault.amount,
-                ctx.accounts.token_x_mint.decimals,
-                ctx.accounts.token_y_vault.amount,
-                ctx.accounts.token_y_mint.decimals,
-                ctx.accounts.pool_state.compensation_parameter,
-                ctx.accounts
-                    .get_oracle_price(&ctx.remaining_accounts)
-                    .unwrap_or(0),
-                ctx.accounts.get_oracle_price_exponent().unwrap_or(0),
-                ctx.accounts.pool_state.fees.swap_fee_numerator,
-                ctx.accounts.pool_state.fees.swap_fee_denominator,
-                amount_in,
-            )
-            .expect("swap_result"),
-        );
-
-        let transfer_out_amount = swap_result.delta_x;
+        let oracle_price = ctx.accounts.get_oracle_price(ctx.remaining_accounts)?;
+        let priced_swap = ctx.accounts.price_swap(
+            SwapDirection::YToX,
+            SwapMode::ExactIn,
+            amount_in,
+            oracle_price,
+            host_fee_account.is_some(),
+        )?;
+        let transfer_in_amount = priced_swap.transfer_in_amount;
+        let transfer_out_amount = priced_swap.amount_out;
 
         check_slippage(
             &amount_in,
@@ -310,6 +438,13 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
             transfer_in_amount,
         )?;
 
+        ctx.accounts.transfer_owner_trade_fees(
+            ctx.accounts.protocol_fee_y_vault.to_account_info(),
+            priced_swap.protocol_fee,
+            host_fee_account.as_ref(),
+            priced_swap.host_fee,
+        )?;
+
         // transfer x to user
         msg!("transfer_out_amount: {:?}", transfer_out_amount);
         token::transfer(
@@ -319,7 +454,15 @@ pub fn handle(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Re
             transfer_out_amount,
         )?;
 
-        ctx.accounts.post_transfer_checks(swap_result)?;
+        ctx.accounts.emit_swapped(
+            SwapDirection::YToX,
+            amount_in,
+            transfer_out_amount,
+            oracle_price,
+            &priced_swap.swap_result,
+        )?;
+
+        ctx.accounts.post_transfer_checks(priced_swap.swap_result)?;
     }
 
     Ok(())
@@ -0,0 +1,156 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::swapped::SwapDirection;
+use crate::instructions::swap::Swap;
+use crate::state::pool_state::SwapMode;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_out: u64,
+    maximum_amount_in: u64,
+) -> Result<()> {
+    ctx.accounts.pool_state.pause.check_swaps()?;
+    ctx.accounts.pool_state.accumulate_prices(
+        ctx.accounts.token_x_vault.amount,
+        ctx.accounts.token_x_mint.decimals,
+        ctx.accounts.token_y_vault.amount,
+        ctx.accounts.token_y_mint.decimals,
+    )?;
+    let host_fee_account = ctx.accounts.get_host_fee_account(ctx.remaining_accounts)?;
+
+    // signer
+    let lp_token_mint = ctx.accounts.pool_state.lp_token_mint;
+    let seeds = &[
+        POOL_STATE_SEED,
+        lp_token_mint.as_ref(),
+        &[ctx.accounts.pool_state.pool_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    // detect swap direction. x to y
+    if ctx.accounts.user_from_token.mint == ctx.accounts.pool_state.token_x_mint {
+        msg!("Swapping exact out: x to y");
+        // confirm the other side matches pool state of y
+        if ctx.accounts.user_to_token.mint != ctx.accounts.pool_state.token_y_mint {
+            return Err(ErrorCode::InvalidMintAddress.into());
+        }
+
+        let oracle_price = ctx.accounts.get_oracle_price(ctx.remaining_accounts)?;
+        // owner trade fees are charged on top of the amount swapped into the vault
+        let priced_swap = ctx.accounts.price_swap(
+            SwapDirection::XToY,
+            SwapMode::ExactOut,
+            amount_out,
+            oracle_price,
+            host_fee_account.is_some(),
+        )?;
+        let transfer_in_amount = priced_swap.transfer_in_amount;
+        let amount_in = priced_swap.amount_in;
+        check_maximum_amount_in(&maximum_amount_in, &amount_in)?;
+
+        // transfer x to vault
+        msg!("transfer_in_amount: {}", transfer_in_amount);
+        token::transfer(
+            ctx.accounts
+                .transfer_user_tokens_to_vault(ctx.accounts.token_x_vault.to_account_info()),
+            transfer_in_amount,
+        )?;
+
+        ctx.accounts.transfer_owner_trade_fees(
+            ctx.accounts.protocol_fee_x_vault.to_account_info(),
+            priced_swap.protocol_fee,
+            host_fee_account.as_ref(),
+            priced_swap.host_fee,
+        )?;
+
+        // transfer y to user
+        msg!("transfer_out_amount: {:?}", amount_out);
+        token::transfer(
+            ctx.accounts
+                .transfer_tokens_to_user(ctx.accounts.token_y_vault.to_account_info())
+                .with_signer(&signer),
+            amount_out,
+        )?;
+
+        ctx.accounts.emit_swapped(
+            SwapDirection::XToY,
+            amount_in,
+            amount_out,
+            oracle_price,
+            &priced_swap.swap_result,
+        )?;
+
+        ctx.accounts.post_transfer_checks(priced_swap.swap_result)?;
+    }
+
+    // detect swap direction y to x
+    if ctx.accounts.user_from_token.mint == ctx.accounts.pool_state.token_y_mint {
+        msg!("Swapping exact out: y to x");
+        // confirm the other side matches the pool state of x
+        if ctx.accounts.user_to_token.mint != ctx.accounts.pool_state.token_x_mint {
+            return Err(ErrorCode::InvalidMintAddress.into());
+        }
+
+        let oracle_price = ctx.accounts.get_oracle_price(ctx.remaining_accounts)?;
+        // owner trade fees are charged on top of the amount swapped into the vault
+        let priced_swap = ctx.accounts.price_swap(
+            SwapDirection::YToX,
+            SwapMode::ExactOut,
+            amount_out,
+            oracle_price,
+            host_fee_account.is_some(),
+        )?;
+        let transfer_in_amount = priced_swap.transfer_in_amount;
+        let amount_in = priced_swap.amount_in;
+        check_maximum_amount_in(&maximum_amount_in, &amount_in)?;
+
+        // transfer y to vault
+        msg!("transfer_in_amount: {}", transfer_in_amount);
+        token::transfer(
+            ctx.accounts
+                .transfer_user_tokens_to_vault(ctx.accounts.token_y_vault.to_account_info()),
+            transfer_in_amount,
+        )?;
+
+        ctx.accounts.transfer_owner_trade_fees(
+            ctx.accounts.protocol_fee_y_vault.to_account_info(),
+            priced_swap.protocol_fee,
+            host_fee_account.as_ref(),
+            priced_swap.host_fee,
+        )?;
+
+        // transfer x to user
+        msg!("transfer_out_amount: {:?}", amount_out);
+        token::transfer(
+            ctx.accounts
+                .transfer_tokens_to_user(ctx.accounts.token_x_vault.to_account_info())
+                .with_signer(&signer),
+            amount_out,
+        )?;
+
+        ctx.accounts.emit_swapped(
+            SwapDirection::YToX,
+            amount_in,
+            amount_out,
+            oracle_price,
+            &priced_swap.swap_result,
+        )?;
+
+        ctx.accounts.post_transfer_checks(priced_swap.swap_result)?;
+    }
+
+    Ok(())
+}
+
+/// check the total amount debited from the user is within slippage or return an error
+fn check_maximum_amount_in(maximum_amount_in: &u64, total_amount_in: &u64) -> Result<()> {
+    if total_amount_in > maximum_amount_in {
+        msg!("SlippageExceeded!");
+        msg!("total_amount_in: {:?}", total_amount_in);
+        msg!("maximum_amount_in: {:?}", maximum_amount_in);
+        return Err(ErrorCode::SlippageExceeded.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::constants::*;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+/// first step of an authority transfer, the new authority has to call accept_authority to complete it.
+/// Passing Pubkey::default() cancels a pending transfer.
+pub fn handle(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.pool_state.pending_authority = new_authority;
+
+    msg!("pending_authority: {}", new_authority);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::constants::*;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnsetOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+}
+
+/// detach the oracle from the pool, swaps fall back to cpmm
+pub fn handle(ctx: Context<UnsetOracle>) -> Result<()> {
+    ctx.accounts.pool_state.oracle = None;
+
+    msg!("Oracle: detached");
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::constants::*;
+use crate::events::protocol_fees_withdrawn::ProtocolFeesWithdrawn;
+use crate::state::pool_state::PoolState;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ POOL_STATE_SEED, pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.pool_state_bump,
+        has_one = authority,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_x_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_x_vault_bump,
+    )]
+    pub protocol_fee_x_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.token_y_mint.as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_y_vault_bump,
+    )]
+    pub protocol_fee_y_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ PROTOCOL_FEE_VAULT_SEED, pool_state.key().as_ref(), pool_state.lp_token_mint.as_ref() ],
+        bump = pool_state.protocol_fee_lp_vault_bump,
+    )]
+    pub protocol_fee_lp_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_x_to.mint == pool_state.token_x_mint,
+    )]
+    /// the token account to send token_x fees to
+    pub token_x_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_y_to.mint == pool_state.token_y_mint,
+    )]
+    /// the token account to send token_y fees to
+    pub token_y_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = lp_token_to.mint == pool_state.lp_token_mint,
+    )]
+    /// the token account to send lp token fees to
+    pub lp_token_to: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawProtocolFees<'info> {
+    pub fn transfer_fees(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from,
+            to,
+            authority: self.pool_state.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// withdraw all accrued owner trade and owner withdraw fees
+pub fn handle(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+    let seeds = &[
+        POOL_STATE_SEED,
+        ctx.accounts.pool_state.lp_token_mint.as_ref(),
+        &[ctx.accounts.pool_state.pool_state_bump],
+    ];
+    let signer = [&seeds[..]];
+
+    let tokens_x_withdrawn = ctx.accounts.protocol_fee_x_vault.amount;
+    let tokens_y_withdrawn = ctx.accounts.protocol_fee_y_vault.amount;
+    let lp_tokens_withdrawn = ctx.accounts.protocol_fee_lp_vault.amount;
+
+    if tokens_x_withdrawn > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_fees(
+                    ctx.accounts.protocol_fee_x_vault.to_account_info(),
+                    ctx.accounts.token_x_to.to_account_info(),
+                )
+                .with_signer(&signer),
+            tokens_x_withdrawn,
+        )?;
+    }
+
+    if tokens_y_withdrawn > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_fees(
+                    ctx.accounts.protocol_fee_y_vault.to_account_info(),
+                    ctx.accounts.token_y_to.to_account_info(),
+                )
+                .with_signer(&signer),
+            tokens_y_withdrawn,
+        )?;
+    }
+
+    if lp_tokens_withdrawn > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_fees(
+                    ctx.accounts.protocol_fee_lp_vault.to_account_info(),
+                    ctx.accounts.lp_token_to.to_account_info(),
+                )
+                .with_signer(&signer),
+            lp_tokens_withdrawn,
+        )?;
+    }
+
+    emit!(ProtocolFeesWithdrawn {
+        tokens_x_withdrawn,
+        tokens_y_withdrawn,
+        lp_tokens_withdrawn,
+    });
+
+    Ok(())
+}
@@ -4,15 +4,48 @@ mod instructions;
 pub mod state;
 mod utils;
 
+use events::swapped::SwapDirection;
+use instructions::accept_authority::*;
 use instructions::add_first_liquidity::*;
 use instructions::add_liquidity::*;
+use instructions::cl_collect_fees::*;
+use instructions::cl_deposit::*;
+use instructions::cl_initialize::*;
+use instructions::cl_initialize_tick_array::*;
+use instructions::cl_open_position::*;
+use instructions::cl_set_pause::*;
+use instructions::cl_swap::*;
+use instructions::cl_withdraw::*;
+use instructions::flash_loan::*;
+use instructions::flash_swap::*;
 use instructions::initialize::*;
+use instructions::initialize_factory::*;
+use instructions::initialize_mock_oracle::*;
+use instructions::initialize_observations::*;
+use instructions::quote_swap::*;
+use instructions::record_observation::*;
 use instructions::remove_liquidity::*;
+use instructions::route_swap::*;
+use instructions::set_circuit_breaker::*;
+use instructions::set_compensation_parameter::*;
+use instructions::set_factory_config::*;
+use instructions::set_fee_mode::*;
+use instructions::set_fees::*;
+use instructions::set_mock_oracle_price::*;
+use instructions::set_oracle::*;
+use instructions::set_oracle_guards::*;
+use instructions::set_pause::*;
 use instructions::swap::mint_addresses_security_check;
 use instructions::swap::*;
-use state::fees::Fees;
-use utils::pyth::pyth_accounts_security_check;
-use utils::pyth::pyth_price_account_security_check;
+use instructions::transfer_authority::*;
+use instructions::unset_oracle::*;
+use instructions::withdraw_protocol_fees::*;
+use state::circuit_breaker::PauseFlags;
+use state::fees::{FeeMode, Fees};
+use state::pool_state::SwapMode;
+use utils::oracle::oracle_accounts_security_check;
+use utils::oracle::oracle_price_account_security_check;
+use utils::oracle::OracleGuardPolicy;
 
 use anchor_lang::prelude::*;
 
@@ -29,14 +62,40 @@ pub mod constants {
     pub const LP_TOKEN_MINT_SEED: &[u8] = b"lp_token_mint_seed";
     pub const TOKEN_VAULT_SEED: &[u8] = b"token_vault_seed";
     pub const POOL_STATE_SEED: &[u8] = b"pool_state_seed";
+    pub const PROTOCOL_FEE_VAULT_SEED: &[u8] = b"protocol_fee_vault_seed";
+    pub const CL_POOL_STATE_SEED: &[u8] = b"cl_pool_state_seed";
+    pub const CL_TOKEN_VAULT_SEED: &[u8] = b"cl_token_vault_seed";
+    pub const CL_TICK_ARRAY_SEED: &[u8] = b"cl_tick_array_seed";
+    pub const CL_POSITION_SEED: &[u8] = b"cl_position_seed";
+    pub const POOL_OBSERVATIONS_SEED: &[u8] = b"pool_observations_seed";
+    pub const POOL_FACTORY_SEED: &[u8] = b"pool_factory_seed";
+    pub const PAIR_REGISTRY_SEED: &[u8] = b"pair_registry_seed";
 }
 
 #[program]
 pub mod hydra_liquidity_pools {
     use super::*;
 
-    /// initialize a new empty pool
-    #[access_control(pyth_accounts_security_check(&ctx.remaining_accounts))]
+    /// create the pool factory defining the fee tiers and compensation parameters of new pools
+    pub fn initialize_factory(
+        ctx: Context<InitializeFactory>,
+        fee_tiers: Vec<u16>,
+        compensation_parameters: Vec<u8>,
+    ) -> Result<()> {
+        instructions::initialize_factory::handle(ctx, fee_tiers, compensation_parameters)
+    }
+
+    /// replace the fee tiers and compensation parameters offered by the pool factory
+    pub fn set_factory_config(
+        ctx: Context<SetFactoryConfig>,
+        fee_tiers: Vec<u16>,
+        compensation_parameters: Vec<u8>,
+    ) -> Result<()> {
+        instructions::set_factory_config::handle(ctx, fee_tiers, compensation_parameters)
+    }
+
+    /// initialize a new empty pool of a pair in a fee tier of the factory, recording it in the pair registry
+    #[access_control(oracle_accounts_security_check(&ctx.remaining_accounts))]
     pub fn initialize(
         ctx: Context<Initialize>,
         token_x_vault_bump: u8,
@@ -46,6 +105,7 @@ pub mod hydra_liquidity_pools {
         lp_token_mint_bump: u8,
         compensation_parameter: u8,
         fees: Fees,
+        fee_tier_bps: u16,
     ) -> Result<()> {
         instructions::initialize::handle(
             ctx,
@@ -56,6 +116,7 @@ pub mod hydra_liquidity_pools {
             lp_token_mint_bump,
             compensation_parameter,
             fees,
+            fee_tier_bps,
         )
     }
 
@@ -83,18 +144,272 @@ pub mod hydra_liquidity_pools {
         )
     }
 
+    /// remove liquidity from the pool by redeeming lp tokens
     pub fn remove_liquidity(
         ctx: Context<RemoveLiquidity>,
-        lp_tokens_to_burn: u64, // calculate the % client side
+        lp_tokens_to_burn: u64,  // calculate the % client side
+        token_x_min_amount: u64, // slippage handling: token_x_amount * (1 - TOLERATED_SLIPPAGE) --> calculated client side
+        token_y_min_amount: u64, // slippage handling: token_y_amount * (1 - TOLERATED_SLIPPAGE) --> calculated client side
     ) -> Result<()> {
-        instructions::remove_liquidity::handle(ctx, lp_tokens_to_burn)
+        instructions::remove_liquidity::handle(
+            ctx,
+            lp_tokens_to_burn,
+            token_x_min_amount,
+            token_y_min_amount,
+        )
     }
 
     #[access_control(
         mint_addresses_security_check(&ctx)
-        pyth_price_account_security_check(&ctx)
+        oracle_price_account_security_check(&ctx)
     )]
-    pub fn swap(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
         instructions::swap::handle(ctx, amount_in, minimum_amount_out)
     }
+
+    /// swap through an ordered list of pools passed in as remaining_accounts, see [RoutePool]
+    pub fn route_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, RouteSwap<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64, // slippage handling of the whole route
+    ) -> Result<()> {
+        instructions::route_swap::handle(ctx, amount_in, minimum_amount_out)
+    }
+
+    /// swap for an exact amount of the output token, debiting at most maximum_amount_in
+    #[access_control(
+        mint_addresses_security_check(&ctx)
+        oracle_price_account_security_check(&ctx)
+    )]
+    pub fn swap_exact_out<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_out: u64,
+        maximum_amount_in: u64, // slippage handling: amount_in * (1 + TOLERATED_SLIPPAGE) --> calculated client side
+    ) -> Result<()> {
+        instructions::swap_exact_out::handle(ctx, amount_out, maximum_amount_in)
+    }
+
+    /// quote a swap of an exact amount in or out against the current vaults and oracle, returning a [SwapQuote]
+    /// through the return data without moving tokens. Remaining accounts: [oracle price account?, host fee account?]
+    pub fn quote_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, QuoteSwap<'info>>,
+        direction: SwapDirection,
+        mode: SwapMode,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::quote_swap::handle(ctx, direction, mode, amount)
+    }
+
+    /// lend amount_x and amount_y from the vaults to a borrower program invoked with the remaining accounts and data,
+    /// which must repay them plus the pool swap fee before returning
+    pub fn flash_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+        amount_x: u64,
+        amount_y: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::flash_loan::handle(ctx, amount_x, amount_y, data)
+    }
+
+    /// send amount_out to the borrower token out account and invoke a borrower program with the remaining accounts and
+    /// data, which must repay the exact out swap amount in, in the other token, before returning.
+    /// Remaining accounts: [oracle price account?, ...borrower accounts]
+    pub fn flash_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashSwap<'info>>,
+        amount_out: u64,
+        maximum_amount_in: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::flash_swap::handle(ctx, amount_out, maximum_amount_in, data)
+    }
+
+    /// create the twap observation ring buffer of a pool
+    pub fn initialize_observations(ctx: Context<InitializeObservations>) -> Result<()> {
+        instructions::initialize_observations::handle(ctx)
+    }
+
+    /// record the cumulative twap prices of a pool in its observation ring buffer
+    pub fn record_observation(ctx: Context<RecordObservation>) -> Result<()> {
+        instructions::record_observation::handle(ctx)
+    }
+
+    /// update the fees charged by the pool
+    pub fn set_fees(ctx: Context<SetFees>, fees: Fees) -> Result<()> {
+        instructions::set_fees::handle(ctx, fees)
+    }
+
+    /// select how the swap fee of the pool is calculated
+    pub fn set_fee_mode(ctx: Context<SetFeeMode>, fee_mode: FeeMode) -> Result<()> {
+        instructions::set_fee_mode::handle(ctx, fee_mode)
+    }
+
+    /// update the hmm compensation parameter of the pool
+    pub fn set_compensation_parameter(
+        ctx: Context<SetCompensationParameter>,
+        compensation_parameter: u8,
+    ) -> Result<()> {
+        instructions::set_compensation_parameter::handle(ctx, compensation_parameter)
+    }
+
+    /// attach or replace the pyth, switchboard or mock oracle accounts of the pool
+    pub fn set_oracle(ctx: Context<SetOracle>) -> Result<()> {
+        instructions::set_oracle::handle(ctx)
+    }
+
+    /// set the staleness and confidence tolerance of the oracle and what a swap does outside of it
+    pub fn set_oracle_guards(
+        ctx: Context<SetOracleGuards>,
+        max_staleness_slots: u64,
+        max_confidence_bps: u16,
+        guard_policy: OracleGuardPolicy,
+    ) -> Result<()> {
+        instructions::set_oracle_guards::handle(
+            ctx,
+            max_staleness_slots,
+            max_confidence_bps,
+            guard_policy,
+        )
+    }
+
+    /// pause or resume swaps, deposits and withdrawals of the pool independently
+    pub fn set_pause(ctx: Context<SetPause>, pause: PauseFlags) -> Result<()> {
+        instructions::set_pause::handle(ctx, pause)
+    }
+
+    /// set how far a swap can move the pool price from the oracle price and within a slot, in bps (0 disables)
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        max_oracle_deviation_bps: u16,
+        max_slot_price_move_bps: u16,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker::handle(
+            ctx,
+            max_oracle_deviation_bps,
+            max_slot_price_move_bps,
+        )
+    }
+
+    /// detach the oracle from the pool
+    pub fn unset_oracle(ctx: Context<UnsetOracle>) -> Result<()> {
+        instructions::unset_oracle::handle(ctx)
+    }
+
+    /// propose a new authority for the pool
+    pub fn transfer_authority(
+        ctx: Context<TransferAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_authority::handle(ctx, new_authority)
+    }
+
+    /// accept a proposed authority transfer
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handle(ctx)
+    }
+
+    /// create a mock price account for testing pools against a local validator
+    pub fn initialize_mock_oracle(
+        ctx: Context<InitializeMockOracle>,
+        price: i64,
+        expo: i32,
+        conf: u64,
+    ) -> Result<()> {
+        instructions::initialize_mock_oracle::handle(ctx, price, expo, conf)
+    }
+
+    /// publish a new price on a mock price account
+    pub fn set_mock_oracle_price(
+        ctx: Context<SetMockOraclePrice>,
+        price: i64,
+        conf: u64,
+    ) -> Result<()> {
+        instructions::set_mock_oracle_price::handle(ctx, price, conf)
+    }
+
+    /// initialize a new empty concentrated liquidity pool priced at initial_tick
+    #[access_control(oracle_accounts_security_check(&ctx.remaining_accounts))]
+    pub fn cl_initialize(
+        ctx: Context<ClInitialize>,
+        initial_tick: u128,
+        tick_spacing: u128,
+        compensation_parameter: u8,
+        fee_rate_numerator: u64,
+        fee_rate_denominator: u64,
+    ) -> Result<()> {
+        instructions::cl_initialize::handle(
+            ctx,
+            initial_tick,
+            tick_spacing,
+            compensation_parameter,
+            fee_rate_numerator,
+            fee_rate_denominator,
+        )
+    }
+
+    /// create a tick array of a concentrated liquidity pool
+    pub fn cl_initialize_tick_array(
+        ctx: Context<ClInitializeTickArray>,
+        start_tick: u128,
+    ) -> Result<()> {
+        instructions::cl_initialize_tick_array::handle(ctx, start_tick)
+    }
+
+    /// open a position in the range [lower_tick, upper_tick) of a concentrated liquidity pool, minting its position NFT to the user
+    pub fn cl_open_position(
+        ctx: Context<ClOpenPosition>,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<()> {
+        instructions::cl_open_position::handle(ctx, lower_tick, upper_tick)
+    }
+
+    /// deposit into the position of a held position NFT, tick arrays of both its ticks are passed in as remaining_accounts
+    pub fn cl_deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClDeposit<'info>>,
+        tokens_x_max_amount: u64,
+        tokens_y_max_amount: u64,
+    ) -> Result<()> {
+        instructions::cl_deposit::handle(ctx, tokens_x_max_amount, tokens_y_max_amount)
+    }
+
+    /// withdraw liquidity and uncollected fees from the position of a held position NFT, tick arrays of both its ticks are passed in as remaining_accounts
+    pub fn cl_withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClWithdraw<'info>>,
+        liquidity: u128,
+        token_x_min_amount: u64,
+        token_y_min_amount: u64,
+    ) -> Result<()> {
+        instructions::cl_withdraw::handle(ctx, liquidity, token_x_min_amount, token_y_min_amount)
+    }
+
+    /// collect the uncollected fees of the position of a held position NFT without withdrawing its liquidity,
+    /// tick arrays of both its ticks are passed in as remaining_accounts
+    pub fn cl_collect_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClCollectFees<'info>>,
+    ) -> Result<()> {
+        instructions::cl_collect_fees::handle(ctx)
+    }
+
+    /// swap against a concentrated liquidity pool, see [instructions::cl_swap::handle] for its remaining_accounts
+    pub fn cl_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClSwap<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        instructions::cl_swap::handle(ctx, amount_in, minimum_amount_out)
+    }
+
+    /// pause or resume swaps, deposits and withdrawals of a concentrated liquidity pool independently
+    pub fn cl_set_pause(ctx: Context<ClSetPause>, pause: PauseFlags) -> Result<()> {
+        instructions::cl_set_pause::handle(ctx, pause)
+    }
+
+    /// withdraw the accrued protocol fees of the pool
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+        instructions::withdraw_protocol_fees::handle(ctx)
+    }
 }
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Operations of a pool paused by its authority, e.g. swaps during an oracle incident while LPs can still exit
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct PauseFlags {
+    pub swaps: bool,
+    pub deposits: bool,
+    pub withdrawals: bool,
+}
+
+impl PauseFlags {
+    pub fn check_swaps(&self) -> Result<()> {
+        match self.swaps {
+            true => Err(ErrorCode::SwapsPaused.into()),
+            false => Ok(()),
+        }
+    }
+
+    pub fn check_deposits(&self) -> Result<()> {
+        match self.deposits {
+            true => Err(ErrorCode::DepositsPaused.into()),
+            false => Ok(()),
+        }
+    }
+
+    pub fn check_withdrawals(&self) -> Result<()> {
+        match self.withdrawals {
+            true => Err(ErrorCode::WithdrawalsPaused.into()),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Limits on how far a swap can move the price of a pool, a zero limit disables its check
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Debug)]
+pub struct CircuitBreaker {
+    // Maximum deviation of the pool price from the oracle price after a swap moving away from it.
+    pub max_oracle_deviation_bps: u16,
+    // Maximum move of the pool price within a slot, from its price before the first swap of the slot.
+    pub max_slot_price_move_bps: u16,
+    pub slot: u64,
+    pub slot_start_price: u128, // y per x at COMPUTE_SCALE
+}
@@ -0,0 +1,207 @@
+use crate::errors::{decimal_error, ErrorCode};
+use crate::state::circuit_breaker::PauseFlags;
+use crate::state::cl_tick_array::ClTickArray;
+use crate::utils::oracle::OracleSettings;
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use hydra_math_rs::decimal::{Decimal, Div, COMPUTE_SCALE};
+use hydra_math_rs::programs::liquidity_pools::cl_pool::{ClPool, FeeAmounts, GlobalState};
+use hydra_math_rs::programs::liquidity_pools::error::ClPoolError;
+use std::collections::BTreeMap;
+
+/// Concentrated liquidity pool. Decimal amounts are saved as u128 values at COMPUTE_SCALE,
+/// initialized ticks are saved in [ClTickArray] accounts of the pool.
+#[account]
+#[derive(Default, Derivative, Debug)]
+pub struct ClPoolState {
+    pub authority: Pubkey,
+    pub token_x_vault: Pubkey,
+    pub token_y_vault: Pubkey,
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    pub pool_state_bump: u8,
+    pub token_x_vault_bump: u8,
+    pub token_y_vault_bump: u8,
+    pub tick_spacing: u128,
+    pub compensation_parameter: u8, // expects 0, 100, 125, or 150
+    pub fee_rate_numerator: u64,
+    pub fee_rate_denominator: u64,
+    pub oracle: Option<OracleSettings>,
+    #[derivative(Default(value = "false"))]
+    pub debug: bool,
+    pub tick_array_count: u32, // tick arrays initialized for the pool, all of them are required by a swap
+    pub liq: u128,             // liquidity in range
+    pub rp: u128,              // root price
+    pub tick: u128,
+    pub fee_growth: ClFeeGrowth,
+    pub x: u128,
+    pub y: u128,
+    pub x_adj: u128,
+    pub y_adj: u128,
+    pub x_fee: u128,
+    pub y_fee: u128,
+    pub pause: PauseFlags,
+}
+
+/// Swap fees and hmm adjustments per unit of liquidity, saved at COMPUTE_SCALE
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct ClFeeGrowth {
+    pub fee_x: u128,
+    pub fee_y: u128,
+    pub adj_x: u128,
+    pub adj_y: u128,
+}
+
+impl From<ClFeeGrowth> for FeeAmounts {
+    fn from(fee_growth: ClFeeGrowth) -> Self {
+        FeeAmounts {
+            fee_x: to_decimal(fee_growth.fee_x),
+            fee_y: to_decimal(fee_growth.fee_y),
+            adj_x: to_decimal(fee_growth.adj_x),
+            adj_y: to_decimal(fee_growth.adj_y),
+        }
+    }
+}
+
+impl TryFrom<FeeAmounts> for ClFeeGrowth {
+    type Error = Error;
+
+    fn try_from(fee_amounts: FeeAmounts) -> Result<Self> {
+        Ok(ClFeeGrowth {
+            fee_x: from_decimal(fee_amounts.fee_x)?,
+            fee_y: from_decimal(fee_amounts.fee_y)?,
+            adj_x: from_decimal(fee_amounts.adj_x)?,
+            adj_y: from_decimal(fee_amounts.adj_y)?,
+        })
+    }
+}
+
+impl ClPoolState {
+    /// Hmm compensation parameter as a decimal (c = compensation_parameter / 100)
+    pub fn c(&self) -> Decimal {
+        Decimal::from_scaled_amount_to_compute_scale(self.compensation_parameter.into(), 2)
+    }
+
+    pub fn fee_rate(&self) -> Result<Decimal> {
+        if self.fee_rate_denominator == 0 {
+            return Ok(Decimal::zero());
+        }
+        Decimal::from_u64_to_compute_scale(self.fee_rate_numerator)
+            .div(Decimal::from_u64_to_compute_scale(
+                self.fee_rate_denominator,
+            ))
+            .map_err(decimal_error)
+    }
+
+    /// Check the ticks of a position are initializable ticks with the lower tick below the upper tick
+    pub fn check_position_range(&self, lower_tick: u128, upper_tick: u128) -> Result<()> {
+        match (
+            lower_tick % self.tick_spacing,
+            upper_tick % self.tick_spacing,
+        ) {
+            (0, 0) if lower_tick < upper_tick => Ok(()),
+            _ => Err(ErrorCode::InvalidTickRange.into()),
+        }
+    }
+
+    /// Load the pool with the initialized ticks of the given tick arrays
+    pub fn to_cl_pool<'a>(
+        &self,
+        tick_arrays: impl Iterator<Item = &'a ClTickArray>,
+    ) -> Result<ClPool> {
+        let mut active_ticks = BTreeMap::new();
+        for tick_array in tick_arrays {
+            tick_array.load_ticks(self.tick_spacing, &mut active_ticks);
+        }
+
+        Ok(ClPool {
+            tick_spacing: self.tick_spacing,
+            global_state: GlobalState {
+                liq: to_decimal(self.liq),
+                rp: to_decimal(self.rp),
+                tick: self.tick,
+                fee_growth: self.fee_growth.into(),
+            },
+            active_ticks,
+            x: to_decimal(self.x),
+            y: to_decimal(self.y),
+            x_adj: to_decimal(self.x_adj),
+            y_adj: to_decimal(self.y_adj),
+            x_fee: to_decimal(self.x_fee),
+            y_fee: to_decimal(self.y_fee),
+            c: self.c(),
+            fee_rate: self.fee_rate()?,
+        })
+    }
+
+    /// Save the pool state and its initialized ticks back into the given tick arrays.
+    /// Errors if an initialized tick is not held by any of the tick arrays.
+    pub fn save<'a>(
+        &mut self,
+        cl_pool: &ClPool,
+        tick_arrays: impl Iterator<Item = &'a mut ClTickArray>,
+    ) -> Result<()> {
+        let mut saved_ticks = 0;
+        for tick_array in tick_arrays {
+            saved_ticks += tick_array.save_ticks(self.tick_spacing, &cl_pool.active_ticks)?;
+        }
+        if saved_ticks != cl_pool.active_ticks.len() {
+            msg!(
+                "tick arrays hold {} of {} initialized ticks",
+                saved_ticks,
+                cl_pool.active_ticks.len()
+            );
+            return Err(ErrorCode::InvalidTickArray.into());
+        }
+
+        let global_state = &cl_pool.global_state;
+        self.liq = from_decimal(global_state.liq)?;
+        self.rp = from_decimal(global_state.rp)?;
+        self.tick = global_state.tick;
+        self.fee_growth = global_state.fee_growth.try_into()?;
+        self.x = from_decimal(cl_pool.x)?;
+        self.y = from_decimal(cl_pool.y)?;
+        self.x_adj = from_decimal(cl_pool.x_adj)?;
+        self.y_adj = from_decimal(cl_pool.y_adj)?;
+        self.x_fee = from_decimal(cl_pool.x_fee)?;
+        self.y_fee = from_decimal(cl_pool.y_fee)?;
+
+        Ok(())
+    }
+}
+
+pub fn to_decimal(value: u128) -> Decimal {
+    Decimal::new(value, COMPUTE_SCALE, false)
+}
+
+pub fn from_decimal(decimal: Decimal) -> Result<u128> {
+    decimal
+        .to_compute_scale()
+        .map(|decimal| decimal.value)
+        .map_err(decimal_error)
+}
+
+/// Signed values such as the net liquidity of a tick
+pub fn to_signed_decimal(value: i128) -> Decimal {
+    Decimal::new(value.unsigned_abs(), COMPUTE_SCALE, value < 0)
+}
+
+pub fn from_signed_decimal(decimal: Decimal) -> Result<i128> {
+    let value = i128::try_from(from_decimal(decimal)?).map_err(|_| ErrorCode::MathOverflow)?;
+    if decimal.is_negative() {
+        Ok(-value)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Log the math error of a concentrated liquidity pool operation
+pub fn cl_pool_error(error: ClPoolError) -> Error {
+    match error {
+        ClPoolError::Decimal(error) => decimal_error(error),
+        error => {
+            msg!("cl_pool: {}", error);
+            ErrorCode::ClPoolCalculationFailed.into()
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::state::cl_pool_state::{from_decimal, to_decimal, ClFeeGrowth};
+use anchor_lang::prelude::*;
+use hydra_math_rs::programs::liquidity_pools::cl_pool::PositionState;
+
+/// Position in the range [lower_tick, upper_tick) of a concentrated liquidity pool, keyed by the mint of
+/// its position NFT. Whoever holds the position token manages the position.
+#[account]
+#[derive(Default, Debug)]
+pub struct ClPosition {
+    pub cl_pool_state: Pubkey,
+    pub position_mint: Pubkey,
+    pub lower_tick: u128,
+    pub upper_tick: u128,
+    pub liq: u128,
+    pub fee_growth_inside: ClFeeGrowth, // fee growth inside the range when the position was last touched
+    pub bump: u8,
+}
+
+impl ClPosition {
+    pub fn to_position_state(&self) -> PositionState {
+        PositionState {
+            liq: to_decimal(self.liq),
+            fee_growth_inside: self.fee_growth_inside.into(),
+        }
+    }
+
+    pub fn save(&mut self, position: &PositionState) -> Result<()> {
+        self.liq = from_decimal(position.liq)?;
+        self.fee_growth_inside = position.fee_growth_inside.try_into()?;
+        Ok(())
+    }
+}
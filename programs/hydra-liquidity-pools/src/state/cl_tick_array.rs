@@ -0,0 +1,134 @@
+use crate::errors::ErrorCode;
+use crate::state::cl_pool_state::{
+    from_decimal, from_signed_decimal, to_decimal, to_signed_decimal, ClFeeGrowth,
+};
+use anchor_lang::prelude::*;
+use hydra_math_rs::programs::liquidity_pools::cl_pool::TickState;
+use std::collections::BTreeMap;
+
+/// Number of ticks held by a tick array
+pub const TICK_ARRAY_SIZE: usize = 16;
+
+/// Consecutive initializable ticks of a concentrated liquidity pool, from start_tick every tick_spacing
+#[account]
+#[derive(Default, Debug)]
+pub struct ClTickArray {
+    pub cl_pool_state: Pubkey,
+    pub start_tick: u128, // multiple of TICK_ARRAY_SIZE * tick_spacing
+    pub ticks: [ClTick; TICK_ARRAY_SIZE],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct ClTick {
+    pub initialized: bool,
+    pub liq_net: i128,
+    pub liq_gross: u128,
+    pub fee_growth_outside: ClFeeGrowth,
+}
+
+impl TryFrom<&TickState> for ClTick {
+    type Error = Error;
+
+    fn try_from(tick_state: &TickState) -> Result<Self> {
+        Ok(ClTick {
+            initialized: true,
+            liq_net: from_signed_decimal(tick_state.liq_net)?,
+            liq_gross: from_decimal(tick_state.liq_gross)?,
+            fee_growth_outside: tick_state.fee_growth_outside.try_into()?,
+        })
+    }
+}
+
+impl From<&ClTick> for TickState {
+    fn from(tick: &ClTick) -> Self {
+        TickState {
+            liq_net: to_signed_decimal(tick.liq_net),
+            liq_gross: to_decimal(tick.liq_gross),
+            fee_growth_outside: tick.fee_growth_outside.into(),
+        }
+    }
+}
+
+impl ClTickArray {
+    /// Start tick of the tick array holding tick
+    pub fn start_tick_of(tick: u128, tick_spacing: u128) -> u128 {
+        let ticks_per_array = TICK_ARRAY_SIZE as u128 * tick_spacing;
+        tick / ticks_per_array * ticks_per_array
+    }
+
+    pub fn is_valid_start_tick(start_tick: u128, tick_spacing: u128) -> bool {
+        Self::start_tick_of(start_tick, tick_spacing) == start_tick
+    }
+
+    fn tick_at(&self, index: usize, tick_spacing: u128) -> u128 {
+        self.start_tick + index as u128 * tick_spacing
+    }
+
+    /// Insert the initialized ticks of the array into active_ticks
+    pub fn load_ticks(&self, tick_spacing: u128, active_ticks: &mut BTreeMap<u128, TickState>) {
+        for (index, tick) in self.ticks.iter().enumerate() {
+            if tick.initialized {
+                active_ticks.insert(self.tick_at(index, tick_spacing), tick.into());
+            }
+        }
+    }
+
+    /// Save the ticks of the array from active_ticks, ticks missing from active_ticks are uninitialized.
+    /// Returns the number of initialized ticks saved.
+    pub fn save_ticks(
+        &mut self,
+        tick_spacing: u128,
+        active_ticks: &BTreeMap<u128, TickState>,
+    ) -> Result<usize> {
+        for index in 0..TICK_ARRAY_SIZE {
+            let tick = self.tick_at(index, tick_spacing);
+            self.ticks[index] = match active_ticks.get(&tick) {
+                Some(tick_state) => tick_state.try_into()?,
+                None => ClTick::default(),
+            };
+        }
+        Ok(self.ticks.iter().filter(|tick| tick.initialized).count())
+    }
+}
+
+/// Load the tick arrays of a pool passed in as remaining accounts, each tick array can only be passed once
+pub fn load_tick_arrays<'info>(
+    accounts: &[AccountInfo<'info>],
+    cl_pool_state: &Pubkey,
+) -> Result<Vec<Account<'info, ClTickArray>>> {
+    let mut tick_arrays: Vec<Account<'info, ClTickArray>> = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let tick_array = Account::<ClTickArray>::try_from(account)?;
+        if tick_array.cl_pool_state != *cl_pool_state
+            || tick_arrays.iter().any(|a| a.key() == tick_array.key())
+        {
+            return Err(ErrorCode::InvalidTickArray.into());
+        }
+        tick_arrays.push(tick_array);
+    }
+    Ok(tick_arrays)
+}
+
+/// Check the tick arrays hold all of ticks, eg the lower and upper tick of a position
+pub fn check_tick_arrays_hold(
+    tick_arrays: &[Account<ClTickArray>],
+    ticks: &[u128],
+    tick_spacing: u128,
+) -> Result<()> {
+    for tick in ticks {
+        let start_tick = ClTickArray::start_tick_of(*tick, tick_spacing);
+        if !tick_arrays.iter().any(|a| a.start_tick == start_tick) {
+            msg!("no tick array for tick: {}", tick);
+            return Err(ErrorCode::InvalidTickArray.into());
+        }
+    }
+    Ok(())
+}
+
+/// Persist tick arrays loaded from remaining accounts, anchor only does so for the accounts of the instruction
+pub fn save_tick_arrays(tick_arrays: &[Account<ClTickArray>], program_id: &Pubkey) -> Result<()> {
+    for tick_array in tick_arrays {
+        tick_array.exit(program_id)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
+use hydra_math_rs::programs::fees::fee_calculator::VOL_ADJ_FEE_DEFAULT_LAST_EWMA;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Debug)]
 pub struct Fees {
@@ -7,19 +8,29 @@ pub struct Fees {
     pub swap_fee_numerator: u64,
     pub swap_fee_denominator: u64,
 
-    // TODO: Added due to solana's one time struct sizing onchain.
-    // TODO: Build out below features in due course.
+    // Owner trade fees are charged on the amount in of a swap and kept in the protocol fee vaults.
     pub owner_trade_fee_numerator: u64,
     pub owner_trade_fee_denominator: u64,
+    // Owner withdraw fees are charged on lp tokens redeemed and kept in the protocol lp fee vault.
     pub owner_withdraw_fee_numerator: u64,
     pub owner_withdraw_fee_denominator: u64,
+    // Host fees are a share of the owner trade fee credited to an optional host fee account on a swap.
     pub host_fee_numerator: u64,
     pub host_fee_denominator: u64,
 }
 
 impl Fees {
     pub fn validate(&self) -> Result<()> {
-        validate_fraction(self.swap_fee_numerator, self.swap_fee_denominator)
+        validate_fraction(self.swap_fee_numerator, self.swap_fee_denominator)?;
+        validate_fraction(
+            self.owner_trade_fee_numerator,
+            self.owner_trade_fee_denominator,
+        )?;
+        validate_fraction(
+            self.owner_withdraw_fee_numerator,
+            self.owner_withdraw_fee_denominator,
+        )?;
+        validate_fraction(self.host_fee_numerator, self.host_fee_denominator)
     }
 }
 
@@ -32,3 +43,40 @@ fn validate_fraction(numerator: u64, denominator: u64) -> Result<()> {
         Ok(())
     }
 }
+
+/// Selects how the swap fee charged to liquidity providers is calculated.
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub enum FeeMode {
+    // Swap fee fraction of the pool Fees.
+    #[default]
+    Percent,
+    // Swap fee adjusted to the volatility of the oracle price, falls back to Percent without an oracle price.
+    VolatilityAdjusted,
+}
+
+/// Volatility adjusted fee state carried from one swap to the next.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VolAdjFeeState {
+    pub last_update: u64, // unix timestamp of the last price observation
+    pub last_price: u64,  // oracle price of the last observation, in the pyth price exponent
+    pub last_ewma: u64,   // ewma variance of the oracle price, 12 decimal places
+}
+
+impl Default for VolAdjFeeState {
+    /// No price observed yet, the ewma starts from the default of the fee calculator
+    fn default() -> Self {
+        Self {
+            last_update: 0,
+            last_price: 0,
+            last_ewma: VOL_ADJ_FEE_DEFAULT_LAST_EWMA,
+        }
+    }
+}
+
+impl VolAdjFeeState {
+    pub fn update(&mut self, last_update: u64, last_price: u64, last_ewma: u64) {
+        self.last_update = last_update;
+        self.last_price = last_price;
+        self.last_ewma = last_ewma;
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Program owned price account for local validator tests, priced as price * 10^expo
+#[account]
+#[derive(Default, Debug)]
+pub struct MockOracle {
+    pub authority: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub conf: u64,
+    pub slot: u64,
+}
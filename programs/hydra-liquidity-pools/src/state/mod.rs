@@ -1,2 +1,9 @@
+pub mod circuit_breaker;
+pub mod cl_pool_state;
+pub mod cl_position;
+pub mod cl_tick_array;
 pub mod fees;
+pub mod mock_oracle;
+pub mod pool_factory;
 pub mod pool_state;
+pub mod price_observations;
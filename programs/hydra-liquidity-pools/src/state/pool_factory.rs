@@ -0,0 +1,136 @@
+use crate::errors::ErrorCode;
+use crate::state::fees::Fees;
+use crate::state::pool_state::validate_compensation_parameter;
+use anchor_lang::prelude::*;
+
+/// Number of fee tiers a factory can offer at once
+pub const MAX_FEE_TIERS: usize = 8;
+/// Number of compensation parameters a factory can allow at once
+pub const MAX_COMPENSATION_PARAMETERS: usize = 4;
+/// Number of pools a pair registry can record, tiers can be replaced so a pair may outlive them
+pub const MAX_PAIR_POOLS: usize = 16;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Fee tiers and compensation parameters pools are created with, a single factory per program
+#[account]
+#[derive(Default, Debug)]
+pub struct PoolFactory {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub fee_tier_count: u8,
+    pub fee_tiers: [u16; MAX_FEE_TIERS], // swap fees in bps, a pair has one pool per fee tier
+    pub compensation_parameter_count: u8,
+    pub compensation_parameters: [u8; MAX_COMPENSATION_PARAMETERS],
+    pub pool_count: u64,
+}
+
+impl PoolFactory {
+    pub fn fee_tiers(&self) -> &[u16] {
+        &self.fee_tiers[..self.fee_tier_count as usize]
+    }
+
+    pub fn compensation_parameters(&self) -> &[u8] {
+        &self.compensation_parameters[..self.compensation_parameter_count as usize]
+    }
+
+    /// Replace the fee tiers of the factory, existing pools keep the tier they were created with
+    pub fn set_fee_tiers(&mut self, fee_tiers: &[u16]) -> Result<()> {
+        if fee_tiers.len() > MAX_FEE_TIERS
+            || fee_tiers
+                .iter()
+                .enumerate()
+                .any(|(i, tier)| *tier as u128 >= BPS_DENOMINATOR || fee_tiers[..i].contains(tier))
+        {
+            return Err(ErrorCode::InvalidFeeTier.into());
+        }
+
+        self.fee_tiers = Default::default();
+        self.fee_tiers[..fee_tiers.len()].copy_from_slice(fee_tiers);
+        self.fee_tier_count = fee_tiers.len() as u8;
+        Ok(())
+    }
+
+    /// Replace the compensation parameters pools can be created with
+    pub fn set_compensation_parameters(&mut self, compensation_parameters: &[u8]) -> Result<()> {
+        if compensation_parameters.len() > MAX_COMPENSATION_PARAMETERS {
+            return Err(ErrorCode::InvalidCompensationParameter.into());
+        }
+        for compensation_parameter in compensation_parameters {
+            validate_compensation_parameter(*compensation_parameter)?;
+        }
+
+        self.compensation_parameters = Default::default();
+        self.compensation_parameters[..compensation_parameters.len()]
+            .copy_from_slice(compensation_parameters);
+        self.compensation_parameter_count = compensation_parameters.len() as u8;
+        Ok(())
+    }
+
+    /// Check a new pool against the factory, its swap fee must be the fee of its tier
+    pub fn validate_pool(
+        &self,
+        fee_tier_bps: u16,
+        compensation_parameter: u8,
+        fees: &Fees,
+    ) -> Result<()> {
+        if !self.fee_tiers().contains(&fee_tier_bps) {
+            return Err(ErrorCode::InvalidFeeTier.into());
+        }
+        validate_fee_tier(fee_tier_bps, fees)?;
+        self.validate_compensation_parameter(compensation_parameter)
+    }
+
+    /// Check a compensation parameter of a pool is allowed by the factory
+    pub fn validate_compensation_parameter(&self, compensation_parameter: u8) -> Result<()> {
+        if !self
+            .compensation_parameters()
+            .contains(&compensation_parameter)
+        {
+            return Err(ErrorCode::InvalidCompensationParameter.into());
+        }
+        Ok(())
+    }
+}
+
+/// Check the swap fee of fees is the fee of the tier of a pool, so pools stay in the tier seeding them
+pub fn validate_fee_tier(fee_tier_bps: u16, fees: &Fees) -> Result<()> {
+    if fees.swap_fee_numerator as u128 * BPS_DENOMINATOR
+        != fee_tier_bps as u128 * fees.swap_fee_denominator as u128
+    {
+        return Err(ErrorCode::InvalidFeeTier.into());
+    }
+    Ok(())
+}
+
+/// Pool of a pair recorded by its registry
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct RegisteredPool {
+    pub fee_tier_bps: u16,
+    pub pool_state: Pubkey,
+}
+
+/// Every pool created for a token pair, one per fee tier, so clients can find them on chain
+#[account]
+#[derive(Default, Debug)]
+pub struct PairRegistry {
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    pub pool_count: u8,
+    pub pools: [RegisteredPool; MAX_PAIR_POOLS],
+}
+
+impl PairRegistry {
+    pub fn pools(&self) -> &[RegisteredPool] {
+        &self.pools[..self.pool_count as usize]
+    }
+
+    pub fn register(&mut self, pool: RegisteredPool) -> Result<()> {
+        if self.pool_count as usize >= MAX_PAIR_POOLS {
+            return Err(ErrorCode::PairRegistryFull.into());
+        }
+
+        self.pools[self.pool_count as usize] = pool;
+        self.pool_count += 1;
+        Ok(())
+    }
+}
@@ -1,7 +1,25 @@
-use crate::state::fees::Fees;
-use crate::utils::pyth::PythSettings;
+use crate::constants::PROTOCOL_FEE_VAULT_SEED;
+use crate::errors::{circuit_breaker_error, decimal_error, swap_calculator_error, ErrorCode};
+use crate::events::swapped::SwapDirection;
+use crate::state::circuit_breaker::{CircuitBreaker, PauseFlags};
+use crate::state::fees::{FeeMode, Fees, VolAdjFeeState};
+use crate::state::price_observations::PriceCumulatives;
+use crate::utils::oracle::OracleSettings;
 use anchor_lang::prelude::*;
 use derivative::Derivative;
+use hydra_math_rs::decimal::{Decimal, COMPUTE_SCALE};
+use hydra_math_rs::programs::fees::fee_calculator_wasm::compute_volatility_adjusted_fee;
+use hydra_math_rs::programs::liquidity_pools::circuit_breaker::{
+    check_oracle_deviation, check_slot_price_move,
+};
+use hydra_math_rs::programs::liquidity_pools::protocol_fees::{
+    calculate_owner_trade_fees, calculate_owner_trade_fees_exact_out,
+};
+use hydra_math_rs::programs::liquidity_pools::swap_calculator::{
+    SwapCalculator, SwapCalculatorBuilder,
+};
+use hydra_math_rs::programs::liquidity_pools::swap_result::SwapResult;
+use hydra_math_rs::programs::liquidity_pools::twap::{spot_prices, PriceAccumulator};
 use std::io::Write;
 
 #[account]
@@ -20,21 +38,390 @@ pub struct PoolState {
     pub lp_token_mint_bump: u8,
     pub compensation_parameter: u8, // expects 0, 100, 125, or 150
     pub fees: Fees,
-    pub pyth: Option<PythSettings>,
+    pub oracle: Option<OracleSettings>,
     #[derivative(Default(value = "false"))]
     pub debug: bool,
+    pub pending_authority: Pubkey, // set by transfer_authority until accepted by the new authority
+    pub protocol_fee_x_vault_bump: u8,
+    pub protocol_fee_y_vault_bump: u8,
+    pub protocol_fee_lp_vault_bump: u8,
+    pub fee_mode: FeeMode,
+    pub vol_adj_fee: VolAdjFeeState,
+    pub price_cumulatives: PriceCumulatives,
+    pub pause: PauseFlags,
+    pub circuit_breaker: CircuitBreaker,
+    pub fee_tier_bps: u16, // factory fee tier seeding the lp token mint, the swap fee of the pool
     pub reserved: PoolStateReserve,
 }
 
 impl PoolState {
     pub fn update_oracle_price(&mut self, new_price: i64, valid_slot: u64) {
-        if let Some(p) = &mut self.pyth {
-            p.update_price(new_price, valid_slot)
+        if let Some(o) = &mut self.oracle {
+            o.update_price(new_price, valid_slot)
         }
     }
+
+    /// Address of the protocol fee vault of token_x_mint or token_y_mint
+    pub fn protocol_fee_vault(&self, mint: &Pubkey) -> Result<Pubkey> {
+        let bump = if *mint == self.token_x_mint {
+            self.protocol_fee_x_vault_bump
+        } else if *mint == self.token_y_mint {
+            self.protocol_fee_y_vault_bump
+        } else {
+            return Err(ErrorCode::InvalidMintAddress.into());
+        };
+
+        Pubkey::create_program_address(
+            &[
+                PROTOCOL_FEE_VAULT_SEED,
+                mint.as_ref(),
+                self.lp_token_mint.as_ref(),
+                &[bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidMintAddress.into())
+    }
+
+    /// Accumulate the twap prices of the vault amounts x and y up to the current slot.
+    /// Called before the vaults change, so the prices accumulated are the ones that held until now.
+    pub fn accumulate_prices(
+        &mut self,
+        x: u64,
+        x_decimals: u8,
+        y: u64,
+        y_decimals: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let mut accumulator = PriceAccumulator::from(&self.price_cumulatives);
+        accumulator.accumulate(
+            x,
+            x_decimals,
+            y,
+            y_decimals,
+            clock.slot,
+            clock.unix_timestamp,
+        );
+        self.price_cumulatives = accumulator.into();
+        Ok(())
+    }
+
+    /// Reject a swap moving the vaults from x0 and y0 to the reserves of its swap_result when it trips
+    /// a circuit breaker of the pool. The first swap of a slot records the price the slot started at.
+    /// The oracle deviation breaker of an oracle pool trips when the swap has no oracle price.
+    pub fn check_circuit_breakers(
+        &mut self,
+        x0: u64,
+        y0: u64,
+        swap_result: &SwapResult,
+        x_decimals: u8,
+        y_decimals: u8,
+        oracle_price: Option<u64>,
+    ) -> Result<()> {
+        let price_exponent = self.oracle_price_exponent().unwrap_or(0);
+        let has_oracle = self.oracle.is_some();
+        let breaker = &mut self.circuit_breaker;
+        if breaker.max_oracle_deviation_bps == 0 && breaker.max_slot_price_move_bps == 0 {
+            return Ok(());
+        }
+
+        // y per x, an empty pool has no price to protect
+        let (price_before, price_after) = match (
+            spot_prices(x0, x_decimals, y0, y_decimals),
+            spot_prices(swap_result.x_new, x_decimals, swap_result.y_new, y_decimals),
+        ) {
+            (Some((price_before, _)), Some((price_after, _))) => (price_before, price_after),
+            _ => return Ok(()),
+        };
+
+        let slot = Clock::get()?.slot;
+        if breaker.slot != slot {
+            breaker.slot = slot;
+            breaker.slot_start_price = price_before;
+        }
+        check_slot_price_move(
+            breaker.slot_start_price,
+            price_after,
+            breaker.max_slot_price_move_bps,
+        )
+        .map_err(circuit_breaker_error)?;
+
+        if breaker.max_oracle_deviation_bps == 0 || !has_oracle {
+            return Ok(());
+        }
+
+        // an oracle pool swapping without an oracle price trips the breaker rather than skipping it
+        let oracle_price = oracle_price.ok_or(ErrorCode::OraclePriceUnavailable)?;
+        let oracle_price = Decimal::new(oracle_price.into(), price_exponent, false)
+            .to_compute_scale()
+            .map_err(decimal_error)?;
+        check_oracle_deviation(
+            price_before,
+            price_after,
+            oracle_price.value,
+            breaker.max_oracle_deviation_bps,
+        )
+        .map_err(circuit_breaker_error)?;
+        Ok(())
+    }
+
+    /// Get saved oracle price exponent if oracle settings exist for the pool
+    pub fn oracle_price_exponent(&self) -> Option<u8> {
+        self.oracle.as_ref().map(|o| o.price_exponent)
+    }
+
+    /// Get the swap fee as (numerator, denominator) for the fee mode of the pool.
+    /// Volatility adjusted fees are computed from the oracle price and update the persisted ewma state.
+    pub fn get_swap_fee(
+        &mut self,
+        oracle_price: Option<u64>,
+        amount: u64,
+        amount_scale: u8,
+    ) -> Result<(u64, u64)> {
+        let oracle_price = match (self.fee_mode, oracle_price) {
+            (FeeMode::VolatilityAdjusted, Some(oracle_price)) => oracle_price,
+            _ => return Ok((self.fees.swap_fee_numerator, self.fees.swap_fee_denominator)),
+        };
+
+        let now = Clock::get()?.unix_timestamp as u64;
+        let fee_result = compute_volatility_adjusted_fee(
+            oracle_price,
+            self.vol_adj_fee.last_price,
+            self.oracle_price_exponent().unwrap_or(0),
+            now,
+            self.vol_adj_fee.last_update,
+            self.vol_adj_fee.last_ewma,
+            amount,
+            amount_scale,
+        )
+        .map_err(|_| ErrorCode::CalculateFeesFailed)?;
+
+        // [fee_amount, fee_percentage, amount_ex_fee, last_update, last_price, last_ewma]
+        let fee_percentage = fee_result[1];
+        if fee_result[3] == now {
+            self.vol_adj_fee.update(now, oracle_price, fee_result[5]);
+        }
+
+        if self.debug {
+            msg!("vol_adj_fee_percentage: {}", fee_percentage);
+            msg!("vol_adj_fee: {:?}", self.vol_adj_fee);
+        }
+
+        Ok((fee_percentage, 10u64.pow(COMPUTE_SCALE as u32)))
+    }
+
+    /// Hmm swap calculator builder of the pool for its oracle price and swap fee,
+    /// reserves and scales are set from the vaults of the swap
+    pub fn swap_calculator_builder(
+        &self,
+        oracle_price: Option<u64>,
+        swap_fee_numerator: u64,
+        swap_fee_denominator: u64,
+    ) -> SwapCalculatorBuilder {
+        let mut builder = SwapCalculatorBuilder::default();
+        builder
+            .c(self.compensation_parameter)
+            .i(
+                oracle_price.unwrap_or(0),
+                self.oracle_price_exponent().unwrap_or(0),
+            )
+            .percentage_fee_numerator(swap_fee_numerator)
+            .percentage_fee_denominator(swap_fee_denominator);
+        builder
+    }
+
+    /// Hmm swap calculator of the pool against its reserves for its oracle price and swap fee
+    pub fn swap_calculator(
+        &self,
+        reserves: &PoolReserves,
+        oracle_price: Option<u64>,
+        swap_fee_numerator: u64,
+        swap_fee_denominator: u64,
+    ) -> Result<SwapCalculator> {
+        self.swap_calculator_builder(oracle_price, swap_fee_numerator, swap_fee_denominator)
+            .x0(reserves.x, reserves.x_decimals)
+            .y0(reserves.y, reserves.y_decimals)
+            .scale(reserves.x_decimals, reserves.y_decimals)
+            .build()
+            .map_err(|e| swap_calculator_error(e.into()))
+    }
+
+    /// Price a swap of amount in direction against the reserves of the pool, the pricing shared by every swap
+    /// and quote: owner trade fees with the host share when the swap has a host fee account, the swap fee of
+    /// the fee mode, the hmm swap and the circuit breakers.
+    pub fn price_swap(
+        &mut self,
+        reserves: &PoolReserves,
+        direction: SwapDirection,
+        mode: SwapMode,
+        amount: u64,
+        oracle_price: Option<u64>,
+        has_host_fee_account: bool,
+    ) -> Result<PricedSwap> {
+        let (decimals_in, decimals_out, vault_out) = match direction {
+            SwapDirection::XToY => (reserves.x_decimals, reserves.y_decimals, reserves.y),
+            SwapDirection::YToX => (reserves.y_decimals, reserves.x_decimals, reserves.x),
+        };
+        let host_fee_numerator = if has_host_fee_account {
+            self.fees.host_fee_numerator
+        } else {
+            0
+        };
+
+        let priced_swap = match mode {
+            SwapMode::ExactIn => {
+                // owner trade fees are taken from amount before it is swapped
+                let (protocol_fee, host_fee) = calculate_owner_trade_fees(
+                    amount,
+                    self.fees.owner_trade_fee_numerator,
+                    self.fees.owner_trade_fee_denominator,
+                    host_fee_numerator,
+                    self.fees.host_fee_denominator,
+                )
+                .ok_or(ErrorCode::CalculateFeesFailed)?;
+                let transfer_in_amount = amount
+                    .checked_sub(protocol_fee)
+                    .and_then(|amount| amount.checked_sub(host_fee))
+                    .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+                let (swap_fee_numerator, swap_fee_denominator) =
+                    self.get_swap_fee(oracle_price, transfer_in_amount, decimals_in)?;
+                let calculator = self.swap_calculator(
+                    reserves,
+                    oracle_price,
+                    swap_fee_numerator,
+                    swap_fee_denominator,
+                )?;
+                let delta_in =
+                    Decimal::from_scaled_amount_to_compute_scale(transfer_in_amount, decimals_in);
+                let swap_result = match direction {
+                    SwapDirection::XToY => calculator.swap_x_to_y_hmm(&delta_in),
+                    SwapDirection::YToX => calculator.swap_y_to_x_hmm(&delta_in),
+                }
+                .map_err(swap_calculator_error)?;
+                let amount_out = match direction {
+                    SwapDirection::XToY => swap_result.delta_y,
+                    SwapDirection::YToX => swap_result.delta_x,
+                };
+
+                PricedSwap {
+                    amount_in: amount,
+                    transfer_in_amount,
+                    amount_out,
+                    protocol_fee,
+                    host_fee,
+                    swap_fee_numerator,
+                    swap_fee_denominator,
+                    swap_result,
+                }
+            }
+            SwapMode::ExactOut => {
+                if amount >= vault_out {
+                    return Err(ErrorCode::InsufficientLiquidity.into());
+                }
+
+                let (swap_fee_numerator, swap_fee_denominator) =
+                    self.get_swap_fee(oracle_price, amount, decimals_out)?;
+                let calculator = self.swap_calculator(
+                    reserves,
+                    oracle_price,
+                    swap_fee_numerator,
+                    swap_fee_denominator,
+                )?;
+                let delta_out = Decimal::from_scaled_amount_to_compute_scale(amount, decimals_out);
+                let swap_result = match direction {
+                    SwapDirection::XToY => calculator.swap_x_to_y_hmm_exact_out(&delta_out),
+                    SwapDirection::YToX => calculator.swap_y_to_x_hmm_exact_out(&delta_out),
+                }
+                .map_err(swap_calculator_error)?;
+
+                // owner trade fees are charged on top of the amount swapped into the vault
+                let delta_in = match direction {
+                    SwapDirection::XToY => swap_result.delta_x,
+                    SwapDirection::YToX => swap_result.delta_y,
+                };
+                let transfer_in_amount = delta_in
+                    .checked_add(swap_result.fees)
+                    .ok_or(ErrorCode::CalculateFeesFailed)?;
+                let (protocol_fee, host_fee) = calculate_owner_trade_fees_exact_out(
+                    transfer_in_amount,
+                    self.fees.owner_trade_fee_numerator,
+                    self.fees.owner_trade_fee_denominator,
+                    host_fee_numerator,
+                    self.fees.host_fee_denominator,
+                )
+                .ok_or(ErrorCode::CalculateFeesFailed)?;
+                let amount_in = transfer_in_amount
+                    .checked_add(protocol_fee)
+                    .and_then(|amount| amount.checked_add(host_fee))
+                    .ok_or(ErrorCode::CalculateFeesFailed)?;
+
+                PricedSwap {
+                    amount_in,
+                    transfer_in_amount,
+                    amount_out: amount,
+                    protocol_fee,
+                    host_fee,
+                    swap_fee_numerator,
+                    swap_fee_denominator,
+                    swap_result,
+                }
+            }
+        };
+
+        self.check_circuit_breakers(
+            reserves.x,
+            reserves.y,
+            &priced_swap.swap_result,
+            reserves.x_decimals,
+            reserves.y_decimals,
+            oracle_price,
+        )?;
+
+        Ok(priced_swap)
+    }
+}
+
+/// Amount a swap is fixed by: the amount swapped in or the amount swapped out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SwapMode {
+    ExactIn,
+    ExactOut,
+}
+
+/// Vault balances and mint decimals of a pool, the reserves a swap is priced against
+#[derive(Clone, Copy, Debug)]
+pub struct PoolReserves {
+    pub x: u64,
+    pub x_decimals: u8,
+    pub y: u64,
+    pub y_decimals: u8,
+}
+
+/// A swap priced by [PoolState::price_swap]
+#[derive(Debug)]
+pub struct PricedSwap {
+    /// total amount debited from the user, the amount swapped into the vault plus the owner trade fees
+    pub amount_in: u64,
+    /// amount swapped into the vault, including the swap fee
+    pub transfer_in_amount: u64,
+    pub amount_out: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+    pub swap_result: SwapResult,
+}
+
+/// Compensation parameters supported by the hmm swap calculator (c = compensation_parameter / 100)
+pub fn validate_compensation_parameter(compensation_parameter: u8) -> Result<()> {
+    match compensation_parameter {
+        0 | 100 | 125 | 150 => Ok(()),
+        _ => Err(ErrorCode::InvalidCompensationParameter.into()),
+    }
 }
 
-const POOL_STATE_RESERVE_SIZE: usize = 448;
+const POOL_STATE_RESERVE_SIZE: usize = 263;
 
 #[derive(Clone, Debug)]
 pub struct PoolStateReserve([u8; POOL_STATE_RESERVE_SIZE]);
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use hydra_math_rs::programs::liquidity_pools::twap::PriceAccumulator;
+
+/// Number of observations held by the ring buffer of a pool
+pub const OBSERVATIONS_SIZE: usize = 32;
+
+/// Cumulative prices of a pool, see [PriceAccumulator]
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct PriceCumulatives {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub y_per_x_slots: u128,
+    pub x_per_y_slots: u128,
+    pub y_per_x_seconds: u128,
+    pub x_per_y_seconds: u128,
+}
+
+impl From<&PriceCumulatives> for PriceAccumulator {
+    fn from(cumulatives: &PriceCumulatives) -> Self {
+        PriceAccumulator {
+            slot: cumulatives.slot,
+            timestamp: cumulatives.timestamp,
+            y_per_x_slots: cumulatives.y_per_x_slots,
+            x_per_y_slots: cumulatives.x_per_y_slots,
+            y_per_x_seconds: cumulatives.y_per_x_seconds,
+            x_per_y_seconds: cumulatives.x_per_y_seconds,
+        }
+    }
+}
+
+impl From<PriceAccumulator> for PriceCumulatives {
+    fn from(accumulator: PriceAccumulator) -> Self {
+        PriceCumulatives {
+            slot: accumulator.slot,
+            timestamp: accumulator.timestamp,
+            y_per_x_slots: accumulator.y_per_x_slots,
+            x_per_y_slots: accumulator.x_per_y_slots,
+            y_per_x_seconds: accumulator.y_per_x_seconds,
+            x_per_y_seconds: accumulator.x_per_y_seconds,
+        }
+    }
+}
+
+/// Ring buffer of the cumulative prices of a pool, recorded at most once per slot.
+/// The twap between two observations is computed with hydra-math-rs [twap](hydra_math_rs::programs::liquidity_pools::twap::twap).
+#[account]
+#[derive(Default, Debug)]
+pub struct PoolObservations {
+    pub pool_state: Pubkey,
+    pub index: u16, // index of the latest observation
+    pub count: u16, // observations recorded, up to OBSERVATIONS_SIZE
+    pub observations: [PriceCumulatives; OBSERVATIONS_SIZE],
+}
+
+impl PoolObservations {
+    pub fn latest(&self) -> Option<&PriceCumulatives> {
+        match self.count {
+            0 => None,
+            _ => Some(&self.observations[self.index as usize]),
+        }
+    }
+
+    pub fn oldest(&self) -> Option<&PriceCumulatives> {
+        match self.count as usize {
+            0 => None,
+            OBSERVATIONS_SIZE => {
+                Some(&self.observations[(self.index as usize + 1) % OBSERVATIONS_SIZE])
+            }
+            _ => Some(&self.observations[0]),
+        }
+    }
+
+    /// Record the cumulative prices of the pool over the oldest observation once the buffer is full.
+    /// Returns false without recording if an observation of their slot or a later one exists.
+    pub fn record(&mut self, cumulatives: PriceCumulatives) -> bool {
+        let index = match self.latest() {
+            None => 0,
+            Some(latest) if latest.slot >= cumulatives.slot => return false,
+            Some(_) => (self.index as usize + 1) % OBSERVATIONS_SIZE,
+        };
+
+        self.observations[index] = cumulatives;
+        self.index = index as u16;
+        self.count = (self.count + 1).min(OBSERVATIONS_SIZE as u16);
+        true
+    }
+}
@@ -0,0 +1,71 @@
+use crate::state::mock_oracle::MockOracle;
+use crate::utils::mock_oracle::MockOracleErrors::{InvalidMockOracleAccount, MockOracleDisabled};
+use crate::utils::oracle::{
+    is_slot_fresh, OracleAdapter, OraclePrice, OracleProvider, OracleSettings,
+};
+use crate::DEBUG_MODE;
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MockOracleErrors {
+    #[msg("Mock oracle accounts can't price a pool on mainnet")]
+    MockOracleDisabled,
+
+    #[msg("Mock oracle account is invalid")]
+    InvalidMockOracleAccount,
+}
+
+/// Reads prices from a program owned [MockOracle] account
+pub struct MockOracleAdapter;
+
+impl MockOracleAdapter {
+    fn load(price_account: &AccountInfo) -> Option<MockOracle> {
+        if *price_account.owner != crate::ID {
+            return None;
+        }
+        let data = price_account.try_borrow_data().ok()?;
+        MockOracle::try_deserialize(&mut &data[..]).ok()
+    }
+}
+
+impl OracleAdapter for MockOracleAdapter {
+    /// Checks the account is a mock oracle [mock_oracle] owned by this program, outside of mainnet only
+    fn load_settings(oracle_accounts: &[AccountInfo]) -> Result<OracleSettings> {
+        if !DEBUG_MODE {
+            return Err(MockOracleDisabled.into());
+        }
+
+        let mock_oracle_account = &oracle_accounts[0];
+        let mock_oracle = Self::load(mock_oracle_account).ok_or(InvalidMockOracleAccount)?;
+
+        msg!("Mock oracle: accounts detected");
+        Ok(OracleSettings {
+            provider: OracleProvider::Mock,
+            price_account: mock_oracle_account.key(),
+            price_exponent: mock_oracle.expo.unsigned_abs() as u8,
+            last_known_price: mock_oracle.price,
+            last_known_price_slot: mock_oracle.slot,
+            ..OracleSettings::default()
+        })
+    }
+
+    /// Price last set on the mock oracle, None once the price is set to zero or is stale
+    fn current_price(
+        price_account: &AccountInfo,
+        current_slot: u64,
+        max_staleness_slots: u64,
+    ) -> Option<OraclePrice> {
+        let mock_oracle = Self::load(price_account)?;
+        if mock_oracle.price <= 0
+            || !is_slot_fresh(mock_oracle.slot, current_slot, max_staleness_slots)
+        {
+            return None;
+        }
+        Some(OraclePrice {
+            price: mock_oracle.price,
+            expo: mock_oracle.expo,
+            conf: mock_oracle.conf,
+            slot: mock_oracle.slot,
+        })
+    }
+}
@@ -1 +1,4 @@
+pub mod mock_oracle;
+pub mod oracle;
 pub mod pyth;
+pub mod switchboard;
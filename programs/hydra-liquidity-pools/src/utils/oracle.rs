@@ -0,0 +1,339 @@
+use crate::state::pool_state::PoolState;
+use crate::utils::mock_oracle::MockOracleAdapter;
+use crate::utils::oracle::OracleErrors::{
+    InvalidOracleAccount, InvalidPriceAccount, InvalidSettingsForAccount, MissingOracleAccounts,
+    PriceOutsideTolerance,
+};
+use crate::utils::pyth::PythAdapter;
+use crate::utils::switchboard::{SwitchboardAdapter, SWITCHBOARD_V2_PROGRAM_ID};
+use crate::Swap;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+/// Default number of slots the last_known_price can be used for when the oracle has no live price
+pub const DEFAULT_MAX_STALENESS_SLOTS: u64 = 5;
+/// Default maximum confidence interval of a live price, in basis points of the price
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 200;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Oracle program the price account of a pool is read from
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub enum OracleProvider {
+    #[default]
+    Pyth,
+    Switchboard,
+    /// program owned price account set by its authority, only accepted outside of mainnet
+    Mock,
+}
+
+/// What a swap does when the oracle price is stale or its confidence interval is too wide
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub enum OracleGuardPolicy {
+    /// swap against the cpmm curve as if no oracle was configured
+    #[default]
+    FallbackToCpmm,
+    /// fail the swap
+    RejectSwap,
+}
+
+/// A price reading common to all oracle providers. The price is price * 10^expo.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub expo: i32,
+    pub conf: u64,
+    pub slot: u64,
+}
+
+impl OraclePrice {
+    /// Express the price and confidence with the given exponent, None if it doesn't fit
+    pub fn rescale(&self, expo: i32) -> Option<OraclePrice> {
+        let (price, conf) = if expo <= self.expo {
+            let factor = 10i128.checked_pow((self.expo - expo) as u32)?;
+            (
+                (self.price as i128).checked_mul(factor)?,
+                (self.conf as i128).checked_mul(factor)?,
+            )
+        } else {
+            let factor = 10i128.checked_pow((expo - self.expo) as u32)?;
+            (self.price as i128 / factor, self.conf as i128 / factor)
+        };
+
+        Some(OraclePrice {
+            price: price.try_into().ok()?,
+            expo,
+            conf: conf.try_into().ok()?,
+            slot: self.slot,
+        })
+    }
+}
+
+/// An oracle program a pool can be priced from
+pub trait OracleAdapter {
+    /// Validate the oracle accounts a pool is set up with and return its settings
+    fn load_settings(oracle_accounts: &[AccountInfo]) -> Result<OracleSettings>;
+
+    /// Read the live price of a price account, None if the oracle has no live price
+    /// or its price was published more than max_staleness_slots before current_slot
+    fn current_price(
+        price_account: &AccountInfo,
+        current_slot: u64,
+        max_staleness_slots: u64,
+    ) -> Option<OraclePrice>;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OracleSettings {
+    pub provider: OracleProvider,
+    pub product_account: Pubkey, // pyth product account, unused by other providers
+    pub price_account: Pubkey,
+    pub last_known_price: i64, // used to store the price as an oracle can sometime return no live price, e.g. pyth lacking enough valid publishes on a time slot.
+    pub last_known_price_slot: u64,
+    pub price_exponent: u8,
+    pub max_staleness_slots: u64, // slots the last_known_price can stand in for a missing live price
+    pub max_confidence_bps: u16,  // max confidence / price ratio of a live price in basis points
+    pub guard_policy: OracleGuardPolicy,
+}
+
+impl Default for OracleSettings {
+    fn default() -> Self {
+        OracleSettings {
+            provider: OracleProvider::default(),
+            product_account: Pubkey::default(),
+            price_account: Pubkey::default(),
+            last_known_price: 0,
+            last_known_price_slot: 0,
+            price_exponent: 0,
+            max_staleness_slots: DEFAULT_MAX_STALENESS_SLOTS,
+            max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+            guard_policy: OracleGuardPolicy::default(),
+        }
+    }
+}
+
+impl OracleSettings {
+    pub fn update_price(&mut self, new_price: i64, valid_slot: u64) {
+        self.last_known_price = new_price;
+        self.last_known_price_slot = valid_slot;
+    }
+
+    /// Keep the guard settings of previously saved settings, e.g. when the oracle accounts are replaced
+    pub fn with_guards_of(mut self, other: &OracleSettings) -> Self {
+        self.max_staleness_slots = other.max_staleness_slots;
+        self.max_confidence_bps = other.max_confidence_bps;
+        self.guard_policy = other.guard_policy;
+        self
+    }
+
+    /// Is the confidence interval of a price narrow enough to price the hmm curve
+    pub fn is_confidence_within_tolerance(&self, price: i64, conf: u64) -> bool {
+        if price <= 0 {
+            return false;
+        }
+        (conf as u128) * BPS_DENOMINATOR <= (price as u128) * (self.max_confidence_bps as u128)
+    }
+
+    /// Is the last_known_price recent enough to stand in for a missing live price
+    pub fn is_last_known_price_fresh(&self, current_slot: u64) -> bool {
+        self.last_known_price > 0
+            && is_slot_fresh(
+                self.last_known_price_slot,
+                current_slot,
+                self.max_staleness_slots,
+            )
+    }
+
+    /// Live price of the price account read with the adapter of the provider,
+    /// None if it was published more than max_staleness_slots ago
+    pub fn current_price(
+        &self,
+        price_account: &AccountInfo,
+        current_slot: u64,
+    ) -> Option<OraclePrice> {
+        let max_staleness_slots = self.max_staleness_slots;
+        match self.provider {
+            OracleProvider::Pyth => {
+                PythAdapter::current_price(price_account, current_slot, max_staleness_slots)
+            }
+            OracleProvider::Switchboard => {
+                SwitchboardAdapter::current_price(price_account, current_slot, max_staleness_slots)
+            }
+            OracleProvider::Mock => {
+                MockOracleAdapter::current_price(price_account, current_slot, max_staleness_slots)
+            }
+        }?
+        .rescale(-(self.price_exponent as i32))
+    }
+}
+
+/// Is a price published at price_slot recent enough to price the hmm curve at current_slot
+pub fn is_slot_fresh(price_slot: u64, current_slot: u64, max_staleness_slots: u64) -> bool {
+    current_slot.saturating_sub(price_slot) <= max_staleness_slots
+}
+
+#[error_code]
+pub enum OracleErrors {
+    #[msg("Oracle price account does not match the price account of the pool")]
+    InvalidPriceAccount,
+
+    #[msg("No oracle settings saved for account data")]
+    InvalidSettingsForAccount,
+
+    #[msg("Oracle accounts are required")]
+    MissingOracleAccounts,
+
+    #[msg("Oracle account is not owned by a supported oracle program")]
+    InvalidOracleAccount,
+
+    #[msg("Oracle price is stale or its confidence interval is too wide")]
+    PriceOutsideTolerance,
+
+    #[msg("Oracle max confidence must not exceed 10000 basis points")]
+    InvalidMaxConfidence,
+}
+
+/// This function validates the oracle accounts [product, price] for pyth or [price] for switchboard and mock oracles
+/// so one can't spoof the price account and therefore trick the hmm price oracle algo
+pub fn oracle_accounts_security_check(
+    remaining_accounts: &[AccountInfo],
+) -> Result<Option<OracleSettings>> {
+    match remaining_accounts.len() {
+        2 => Ok(Some(PythAdapter::load_settings(remaining_accounts)?)),
+        1 if *remaining_accounts[0].owner == SWITCHBOARD_V2_PROGRAM_ID => {
+            Ok(Some(SwitchboardAdapter::load_settings(remaining_accounts)?))
+        }
+        1 if *remaining_accounts[0].owner == crate::ID => {
+            Ok(Some(MockOracleAdapter::load_settings(remaining_accounts)?))
+        }
+        1 => Err(InvalidOracleAccount.into()),
+        _ => {
+            msg!("Oracle: no accounts detected");
+            Ok(None)
+        }
+    }
+}
+
+/// Remaining accounts of a swap that can be oracle accounts. Token accounts are host fee accounts and skipped.
+pub fn oracle_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Vec<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .filter(|account| *account.owner != token::ID)
+        .collect()
+}
+
+/// This function checks for a given price account matches the saved key in the pool_state.oracle onchain object for a swap instruction
+pub fn oracle_price_account_security_check(ctx: &Context<Swap>) -> Result<()> {
+    check_oracle_price_account(&ctx.accounts.pool_state, ctx.remaining_accounts)
+}
+
+/// Check the oracle account among the remaining accounts of a swap or quote is the price account of the pool.
+/// A pool with an oracle requires its price account, so that a swap can't skip the oracle by leaving it out.
+pub fn check_oracle_price_account(
+    pool_state: &PoolState,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let oracle_accounts = oracle_remaining_accounts(remaining_accounts);
+    match (&pool_state.oracle, oracle_accounts.as_slice()) {
+        // then check account against saved key settings
+        (Some(oracle_settings), [price_account])
+            if oracle_settings.price_account == price_account.key() =>
+        {
+            msg!("Oracle: Valid Price account detected");
+            Ok(())
+        }
+        (Some(_), []) => Err(MissingOracleAccounts.into()),
+        (Some(_), _) => Err(InvalidPriceAccount.into()),
+        // price account without settings saved for the oracle/hmm
+        (None, [_]) => Err(InvalidSettingsForAccount.into()),
+        // No optional accounts passed into contract. Swap will be cpmm.
+        (None, _) => Ok(()),
+    }
+}
+
+/// Get the oracle price of a pool from its price account, see [get_and_update_last_known_price].
+/// A pool with an oracle requires its saved price account, None for a pool without an oracle.
+pub fn get_oracle_price_from_account(
+    price_account: Option<&AccountInfo>,
+    oracle: &mut Option<OracleSettings>,
+) -> Result<Option<u64>> {
+    let price_account = match (oracle.as_ref(), price_account) {
+        (None, _) => return Ok(None),
+        (Some(_), None) => return Err(MissingOracleAccounts.into()),
+        (Some(oracle_settings), Some(price_account))
+            if oracle_settings.price_account != price_account.key() =>
+        {
+            return Err(InvalidPriceAccount.into())
+        }
+        (Some(_), Some(price_account)) => price_account,
+    };
+
+    get_and_update_last_known_price(price_account, oracle)
+}
+
+/// Get the oracle price of a pool from the remaining accounts of a swap or quote, see [check_oracle_price_account]
+/// and [get_oracle_price_from_account]. None for a pool without an oracle.
+pub fn get_pool_oracle_price(
+    oracle: &mut Option<OracleSettings>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Option<u64>> {
+    match oracle_remaining_accounts(remaining_accounts).as_slice() {
+        [] => get_oracle_price_from_account(None, oracle),
+        [price_account] => get_oracle_price_from_account(Some(price_account), oracle),
+        _ if oracle.is_none() => Ok(None),
+        _ => Err(InvalidPriceAccount.into()),
+    }
+}
+
+/// Get and update last known price will fetch a price from the Oracle if the feed is live, published within max_staleness_slots
+/// and its confidence interval is within tolerance. For a valid fetch the price is also saved as last_known_price.
+/// Otherwise the last_known_price is returned while it is within max_staleness_slots. Where no price is within tolerance the
+/// guard_policy either falls back to a cpmm swap (None) or rejects the swap. Where no oracle is enabled on a pool a None is returned.
+pub fn get_and_update_last_known_price(
+    price_account: &AccountInfo,
+    oracle: &mut Option<OracleSettings>,
+) -> Result<Option<u64>> {
+    let oracle_settings = match oracle {
+        Some(o) => o.clone(),
+        None => return Ok(None),
+    };
+
+    let current = Clock::get()?;
+
+    // Get a valid price from the oracle if feed is considered live
+    if let Some(p) = oracle_settings.current_price(price_account, current.slot) {
+        if oracle_settings.is_confidence_within_tolerance(p.price, p.conf) {
+            if let Some(o) = oracle {
+                o.update_price(p.price, p.slot);
+            }
+            msg!("Oracle Price: {}", p.price);
+            msg!("Valid slot: {}", p.slot);
+            return Ok(Some(p.price as u64));
+        }
+        msg!(
+            "Oracle: confidence {} outside tolerance of price {}",
+            p.conf,
+            p.price
+        );
+    }
+
+    // Otherwise get price from last_known_price
+    if oracle_settings.is_last_known_price_fresh(current.slot) {
+        msg!(
+            "last_known_price: {}",
+            oracle_settings.last_known_price as u64
+        );
+        msg!(
+            "last_known_price_slot: {}",
+            oracle_settings.last_known_price_slot
+        );
+        return Ok(Some(oracle_settings.last_known_price as u64));
+    }
+
+    msg!("Oracle: no live or last_known_price within tolerance");
+    match oracle_settings.guard_policy {
+        OracleGuardPolicy::FallbackToCpmm => Ok(None),
+        OracleGuardPolicy::RejectSwap => Err(PriceOutsideTolerance.into()),
+    }
+}
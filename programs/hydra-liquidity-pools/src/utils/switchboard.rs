@@ -0,0 +1,175 @@
+use crate::utils::oracle::{
+    is_slot_fresh, OracleAdapter, OraclePrice, OracleProvider, OracleSettings,
+};
+use crate::utils::switchboard::SwitchboardErrors::InvalidAggregatorAccount;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use std::convert::TryInto;
+
+pub mod switchboard_v2_program {
+    use super::*;
+    declare_id!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+}
+
+pub const SWITCHBOARD_V2_PROGRAM_ID: Pubkey = switchboard_v2_program::ID;
+
+// Offsets into a switchboard v2 AggregatorAccountData (packed, after the 8 byte account discriminator)
+const MIN_ORACLE_RESULTS_OFFSET: usize = 236;
+const LATEST_ROUND_OFFSET: usize = 341;
+const NUM_SUCCESS_OFFSET: usize = LATEST_ROUND_OFFSET;
+const ROUND_OPEN_SLOT_OFFSET: usize = LATEST_ROUND_OFFSET + 9;
+const RESULT_OFFSET: usize = LATEST_ROUND_OFFSET + 25;
+const STD_DEVIATION_OFFSET: usize = RESULT_OFFSET + SWITCHBOARD_DECIMAL_SIZE;
+const SWITCHBOARD_DECIMAL_SIZE: usize = 20; // i128 mantissa + u32 scale
+
+#[error_code]
+pub enum SwitchboardErrors {
+    #[msg("Switchboard account provided is not a v2 aggregator account")]
+    InvalidAggregatorAccount,
+}
+
+/// Reads prices from the latest confirmed round of a switchboard v2 aggregator account
+pub struct SwitchboardAdapter;
+
+impl OracleAdapter for SwitchboardAdapter {
+    /// Checks the account is a switchboard v2 aggregator [aggregator]. The price exponent of the pool is taken from the
+    /// scale of the latest confirmed round, later rounds are rescaled to it.
+    fn load_settings(oracle_accounts: &[AccountInfo]) -> Result<OracleSettings> {
+        let aggregator_account = &oracle_accounts[0];
+        if *aggregator_account.owner != SWITCHBOARD_V2_PROGRAM_ID {
+            return Err(InvalidAggregatorAccount.into());
+        }
+
+        let data = aggregator_account.try_borrow_data()?;
+        let discriminator = &hash(b"account:AggregatorAccountData").to_bytes()[..8];
+        if data.len() < STD_DEVIATION_OFFSET + SWITCHBOARD_DECIMAL_SIZE
+            || &data[..8] != discriminator
+        {
+            return Err(InvalidAggregatorAccount.into());
+        }
+
+        let (mantissa, scale) = read_decimal(&data, RESULT_OFFSET);
+
+        msg!("Switchboard: accounts detected");
+        Ok(OracleSettings {
+            provider: OracleProvider::Switchboard,
+            price_account: aggregator_account.key(),
+            price_exponent: scale as u8,
+            last_known_price: mantissa.try_into().unwrap_or_default(),
+            last_known_price_slot: read_u64(&data, ROUND_OPEN_SLOT_OFFSET),
+            ..OracleSettings::default()
+        })
+    }
+
+    /// Result of the latest confirmed round, None if the account can't be loaded, the round lacks enough oracle results
+    /// or it was opened more than max_staleness_slots before current_slot
+    fn current_price(
+        price_account: &AccountInfo,
+        current_slot: u64,
+        max_staleness_slots: u64,
+    ) -> Option<OraclePrice> {
+        let data = price_account.try_borrow_data().ok()?;
+        latest_round_price(&data, current_slot, max_staleness_slots)
+    }
+}
+
+/// Price of the latest confirmed round of aggregator account data, see [SwitchboardAdapter::current_price]
+fn latest_round_price(
+    data: &[u8],
+    current_slot: u64,
+    max_staleness_slots: u64,
+) -> Option<OraclePrice> {
+    if data.len() < STD_DEVIATION_OFFSET + SWITCHBOARD_DECIMAL_SIZE {
+        return None;
+    }
+
+    let min_oracle_results = read_u32(data, MIN_ORACLE_RESULTS_OFFSET);
+    if read_u32(data, NUM_SUCCESS_OFFSET) < min_oracle_results.max(1) {
+        return None;
+    }
+
+    let round_open_slot = read_u64(data, ROUND_OPEN_SLOT_OFFSET);
+    if !is_slot_fresh(round_open_slot, current_slot, max_staleness_slots) {
+        return None;
+    }
+
+    let (mantissa, scale) = read_decimal(data, RESULT_OFFSET);
+    let (std_mantissa, std_scale) = read_decimal(data, STD_DEVIATION_OFFSET);
+
+    // express the standard deviation with the scale of the result
+    let conf = if std_scale >= scale {
+        std_mantissa.checked_div(10i128.checked_pow(std_scale - scale)?)?
+    } else {
+        std_mantissa.checked_mul(10i128.checked_pow(scale - std_scale)?)?
+    };
+
+    Some(OraclePrice {
+        price: mantissa.try_into().ok()?,
+        expo: -(scale as i32),
+        conf: conf.unsigned_abs().try_into().ok()?,
+        slot: round_open_slot,
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// switchboard decimal as (mantissa, scale), the value is mantissa / 10^scale
+fn read_decimal(data: &[u8], offset: usize) -> (i128, u32) {
+    let mantissa = i128::from_le_bytes(data[offset..offset + 16].try_into().unwrap());
+    (mantissa, read_u32(data, offset + 16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// aggregator account data with a latest confirmed round of 110.50 +- 0.05 opened at round_open_slot
+    fn aggregator_data(num_success: u32, round_open_slot: u64) -> Vec<u8> {
+        let mut data = vec![0u8; STD_DEVIATION_OFFSET + SWITCHBOARD_DECIMAL_SIZE];
+        data[MIN_ORACLE_RESULTS_OFFSET..MIN_ORACLE_RESULTS_OFFSET + 4]
+            .copy_from_slice(&1u32.to_le_bytes());
+        data[NUM_SUCCESS_OFFSET..NUM_SUCCESS_OFFSET + 4]
+            .copy_from_slice(&num_success.to_le_bytes());
+        data[ROUND_OPEN_SLOT_OFFSET..ROUND_OPEN_SLOT_OFFSET + 8]
+            .copy_from_slice(&round_open_slot.to_le_bytes());
+        data[RESULT_OFFSET..RESULT_OFFSET + 16].copy_from_slice(&11_050i128.to_le_bytes());
+        data[RESULT_OFFSET + 16..RESULT_OFFSET + 20].copy_from_slice(&2u32.to_le_bytes());
+        data[STD_DEVIATION_OFFSET..STD_DEVIATION_OFFSET + 16].copy_from_slice(&5i128.to_le_bytes());
+        data[STD_DEVIATION_OFFSET + 16..STD_DEVIATION_OFFSET + 20]
+            .copy_from_slice(&2u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_latest_round_price() {
+        let expected = OraclePrice {
+            price: 11_050,
+            expo: -2,
+            conf: 5,
+            slot: 1_000,
+        };
+        let data = aggregator_data(1, 1_000);
+        assert_eq!(latest_round_price(&data, 1_000, 5), Some(expected));
+        assert_eq!(latest_round_price(&data, 1_005, 5), Some(expected));
+
+        // not enough oracle results
+        assert_eq!(
+            latest_round_price(&aggregator_data(0, 1_000), 1_000, 5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_latest_round_price_stale() {
+        // the round was opened more than max_staleness_slots ago
+        let data = aggregator_data(1, 1_000);
+        assert_eq!(latest_round_price(&data, 1_006, 5), None);
+        assert_eq!(latest_round_price(&data, 2_000, 0), None);
+    }
+}
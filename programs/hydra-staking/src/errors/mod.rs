@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Calculation of pool tokens failed")]
+    CalculatePoolTokensFailed,
+}
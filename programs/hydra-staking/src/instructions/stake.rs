@@ -1,4 +1,5 @@
 use crate::constants::*;
+use crate::errors::ErrorCode;
 use crate::events::*;
 use crate::state::pool_state::PoolState;
 use crate::utils::price::calculate_price;
@@ -114,7 +115,8 @@ pub fn handle(ctx: Context<Stake>, amount: u64) -> Result<()> {
         token::mint_to(cpi_tx, amount)?;
     } else {
         let mint_redeemable_amount: u64 =
-            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens);
+            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens)
+                .ok_or(ErrorCode::CalculatePoolTokensFailed)?;
 
         let mut cpi_tx = ctx.accounts.into_mint_redeemable();
         cpi_tx.signer_seeds = &signer;
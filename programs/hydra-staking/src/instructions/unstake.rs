@@ -1,4 +1,5 @@
 use crate::constants::*;
+use crate::errors::ErrorCode;
 use crate::events::*;
 use crate::state::pool_state::PoolState;
 use crate::utils::price::calculate_price;
@@ -99,7 +100,8 @@ pub fn handle(ctx: Context<UnStake>, amount: u64) -> Result<()> {
 
     // determine user share of vault
     let token_share =
-        calculate_pool_tokens_for_withdraw(amount, total_tokens, total_redeemable_token_supply);
+        calculate_pool_tokens_for_withdraw(amount, total_tokens, total_redeemable_token_supply)
+            .ok_or(ErrorCode::CalculatePoolTokensFailed)?;
 
     let token_mint_key = ctx.accounts.pool_state.token_mint;
     let redeemable_mint_key = ctx.accounts.pool_state.redeemable_mint;
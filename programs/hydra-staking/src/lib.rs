@@ -1,3 +1,4 @@
+mod errors;
 mod events;
 mod instructions;
 mod state;
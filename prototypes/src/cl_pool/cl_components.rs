@@ -1,4 +1,4 @@
-use hydra_math_rs::decimal::{Add, Decimal};
+use super::PoolNumber;
 
 // #[allow(dead_code)] // for indiv struct
 #[derive(Debug)]
@@ -14,27 +14,19 @@ impl<'a> PoolToken<'a> {
 }
 
 #[derive(Debug)]
-pub struct GlobalState {
+pub struct GlobalState<N: PoolNumber> {
     /// contract global state
-    liq: Decimal, // liquidity
-    rp: Decimal,   // sqrt price
-    tick: u128,    // current tick
-    fg_x: Decimal, // fee growth global
-    fg_y: Decimal, // fee growth global
-    hg_x: Decimal, // fee growth global
-    hg_y: Decimal, // fee growth global
+    liq: N, // liquidity
+    rp: N,      // sqrt price
+    tick: u128, // current tick
+    fg_x: N,    // fee growth global
+    fg_y: N,    // fee growth global
+    hg_x: N,    // fee growth global
+    hg_y: N,    // fee growth global
 }
 
-impl GlobalState {
-    pub fn new(
-        liq: Decimal,
-        rp: Decimal,
-        tick: u128,
-        fg_x: Decimal,
-        fg_y: Decimal,
-        hg_x: Decimal,
-        hg_y: Decimal,
-    ) -> Self {
+impl<N: PoolNumber> GlobalState<N> {
+    pub fn new(liq: N, rp: N, tick: u128, fg_x: N, fg_y: N, hg_x: N, hg_y: N) -> Self {
         if liq.is_negative() {
             panic!("global liquidity cannot be negative");
         }
@@ -54,21 +46,20 @@ impl GlobalState {
             hg_y,
         }
     }
-    pub fn liq(&self) -> Decimal {
+    pub fn liq(&self) -> N {
         self.liq
     }
-    pub fn add_liq(&mut self, liq_to_add: Decimal) {
-        // let new_liq_tup = signed_addition(&self.liq.amt, self.liq.neg, &liq.amt, liq.neg);
-        let new_liq = self.liq().add(liq_to_add).expect("new_liquidity");
+    pub fn add_liq(&mut self, liq_to_add: N) {
+        let new_liq = self.liq().add(liq_to_add);
         if new_liq.is_negative() {
             panic!("global liquidity cannot turn negative");
         }
         self.liq = new_liq;
     }
-    pub fn rp(&self) -> Decimal {
+    pub fn rp(&self) -> N {
         self.rp
     }
-    pub fn set_rp(&mut self, rp: Decimal) {
+    pub fn set_rp(&mut self, rp: N) {
         self.rp = rp;
     }
     pub fn tick(&self) -> u128 {
@@ -77,7 +68,7 @@ impl GlobalState {
     pub fn set_tick(&mut self, tick: u128) {
         self.tick = tick;
     }
-    pub fn fee(&self, token: char, f_or_h: char) -> Decimal {
+    pub fn fee(&self, token: char, f_or_h: char) -> N {
         match (token, f_or_h) {
             ('x', 'f') => self.fg_x,
             ('y', 'f') => self.fg_y,
@@ -88,7 +79,7 @@ impl GlobalState {
             }
         }
     }
-    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: Decimal) {
+    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: N) {
         match (token, f_or_h) {
             ('x', 'f') => self.fg_x = fee,
             ('y', 'f') => self.fg_y = fee,
@@ -99,31 +90,24 @@ impl GlobalState {
             }
         };
     }
-    pub fn all_fees(&self) -> (Decimal, Decimal, Decimal, Decimal) {
+    pub fn all_fees(&self) -> (N, N, N, N) {
         (self.fg_x, self.fg_y, self.hg_x, self.hg_y)
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct TickState {
+pub struct TickState<N: PoolNumber> {
     ///Tick Indexed State
-    liq_net: Decimal, // LiquidityNet
-    liq_gross: Decimal, // LiquidityGross
-    f0_x: Decimal,      // feegrowth outside
-    f0_y: Decimal,      // feegrowth outside
-    h0_x: Decimal,      // hmm adj-fee growth outside
-    h0_y: Decimal,      // hmm adj-fee growth outside
+    liq_net: N, // LiquidityNet
+    liq_gross: N, // LiquidityGross
+    f0_x: N,      // feegrowth outside
+    f0_y: N,      // feegrowth outside
+    h0_x: N,      // hmm adj-fee growth outside
+    h0_y: N,      // hmm adj-fee growth outside
 }
 
-impl TickState {
-    pub fn new(
-        liq_net: Decimal,
-        liq_gross: Decimal,
-        f0_x: Decimal,
-        f0_y: Decimal,
-        h0_x: Decimal,
-        h0_y: Decimal,
-    ) -> Self {
+impl<N: PoolNumber> TickState<N> {
+    pub fn new(liq_net: N, liq_gross: N, f0_x: N, f0_y: N, h0_x: N, h0_y: N) -> Self {
         if liq_gross.is_negative() {
             panic!("gross liquidity cannot be negative");
         }
@@ -139,23 +123,23 @@ impl TickState {
             h0_y,
         }
     }
-    pub fn liq_net(&self) -> Decimal {
+    pub fn liq_net(&self) -> N {
         self.liq_net
     }
-    pub fn add_liq_net(&mut self, liq_net_to_add: Decimal) {
-        self.liq_net = self.liq_net().add(liq_net_to_add).unwrap();
+    pub fn add_liq_net(&mut self, liq_net_to_add: N) {
+        self.liq_net = self.liq_net().add(liq_net_to_add);
     }
-    pub fn liq_gross(&self) -> Decimal {
+    pub fn liq_gross(&self) -> N {
         self.liq_gross
     }
-    pub fn add_liq_gross(&mut self, liq_gross_to_add: Decimal) {
-        let new_liq_gross = self.liq_gross().add(liq_gross_to_add).unwrap();
+    pub fn add_liq_gross(&mut self, liq_gross_to_add: N) {
+        let new_liq_gross = self.liq_gross().add(liq_gross_to_add);
         if new_liq_gross.is_negative() {
             panic!("gross liquidity cannot turn negative");
         }
         self.liq_gross = new_liq_gross;
     }
-    pub fn fee(&self, token: char, f_or_h: char) -> Decimal {
+    pub fn fee(&self, token: char, f_or_h: char) -> N {
         match (token, f_or_h) {
             ('x', 'f') => self.f0_x,
             ('y', 'f') => self.f0_y,
@@ -166,7 +150,7 @@ impl TickState {
             }
         }
     }
-    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: Decimal) {
+    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: N) {
         match (token, f_or_h) {
             ('x', 'f') => self.f0_x = fee,
             ('y', 'f') => self.f0_y = fee,
@@ -177,22 +161,22 @@ impl TickState {
             }
         };
     }
-    pub fn all_fees(&self) -> (Decimal, Decimal, Decimal, Decimal) {
+    pub fn all_fees(&self) -> (N, N, N, N) {
         (self.f0_x, self.f0_y, self.h0_x, self.h0_y)
     }
 }
 #[derive(Debug)]
-pub struct PositionState {
+pub struct PositionState<N: PoolNumber> {
     ///Position Indexed State
-    liq: Decimal, // liquidity
-    fr_x: Decimal, // feegrowth inside last
-    fr_y: Decimal, // feegrowth inside last
-    hr_x: Decimal, // hmm adj-fee growth inside last
-    hr_y: Decimal, // hmm adj-fee growth inside last
+    liq: N, // liquidity
+    fr_x: N, // feegrowth inside last
+    fr_y: N, // feegrowth inside last
+    hr_x: N, // hmm adj-fee growth inside last
+    hr_y: N, // hmm adj-fee growth inside last
 }
 
-impl PositionState {
-    pub fn new(liq: Decimal, fr_x: Decimal, fr_y: Decimal, hr_x: Decimal, hr_y: Decimal) -> Self {
+impl<N: PoolNumber> PositionState<N> {
+    pub fn new(liq: N, fr_x: N, fr_y: N, hr_x: N, hr_y: N) -> Self {
         if liq.is_negative() {
             panic!("position liquidity cannot be negative");
         }
@@ -207,18 +191,18 @@ impl PositionState {
             hr_y,
         }
     }
-    pub fn liq(&self) -> Decimal {
+    pub fn liq(&self) -> N {
         self.liq
     }
-    pub fn add_liq(&mut self, liq_to_add: Decimal) {
-        let new_liq = self.liq().add(liq_to_add).unwrap();
+    pub fn add_liq(&mut self, liq_to_add: N) {
+        let new_liq = self.liq().add(liq_to_add);
         if new_liq.is_negative() {
             panic!("position liquidity cannot turn negative");
         }
         self.liq = new_liq;
     }
 
-    pub fn fee(&self, token: char, f_or_h: char) -> Decimal {
+    pub fn fee(&self, token: char, f_or_h: char) -> N {
         match (token, f_or_h) {
             ('x', 'f') => self.fr_x,
             ('y', 'f') => self.fr_y,
@@ -229,7 +213,7 @@ impl PositionState {
             }
         }
     }
-    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: Decimal) {
+    pub fn set_fee(&mut self, token: char, f_or_h: char, fee: N) {
         match (token, f_or_h) {
             ('x', 'f') => self.fr_x = fee,
             ('y', 'f') => self.fr_y = fee,
@@ -240,41 +224,19 @@ impl PositionState {
             }
         };
     }
-    pub fn all_fees(&self) -> (Decimal, Decimal, Decimal, Decimal) {
+    pub fn all_fees(&self) -> (N, N, N, N) {
         (self.fr_x, self.fr_y, self.hr_x, self.hr_y)
     }
 }
 
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct Swp {
-//     // struct to represent swap token quantity (positive or negative) in PreciseNumber
-//     pub amt: PN,
-//     pub neg: bool,
-// }
-
-// impl Swp {
-//     pub fn new(amt: PN, neg: bool) -> Self {
-//         Self { amt, neg }
-//     }
-//     pub fn from_tuple(tup: (PN, bool)) -> Self {
-//         Self::new(tup.0, tup.1)
-//     }
-//     pub fn add(&self, rhs: &Swp) -> Swp {
-//         Swp::from_tuple(signed_addition(&self.amt, self.neg, &rhs.amt, rhs.neg))
-//     }
-//     pub fn flip_sign(&self) -> Self {
-//         Self::new(self.amt.clone(), !self.neg)
-//     }
-// }
-
 #[derive(Debug)]
-pub struct GetInRangeOutput {
+pub struct GetInRangeOutput<N: PoolNumber> {
     goal_tick: Option<u128>,
-    new_rp: Decimal,
+    new_rp: N,
 }
 
-impl GetInRangeOutput {
-    pub fn new(goal_tick: Option<u128>, new_rp: Decimal) -> Self {
+impl<N: PoolNumber> GetInRangeOutput<N> {
+    pub fn new(goal_tick: Option<u128>, new_rp: N) -> Self {
         if new_rp.is_negative() {
             panic!("root-price cannot be negative");
         }
@@ -283,32 +245,32 @@ impl GetInRangeOutput {
     pub fn goal_tick(&self) -> Option<u128> {
         self.goal_tick
     }
-    pub fn new_rp(&self) -> Decimal {
+    pub fn new_rp(&self) -> N {
         self.new_rp
     }
 }
 
 #[derive(Debug)]
-pub struct SwapWithinResult {
-    recv_amount: Decimal,
-    send_amount: Decimal,
+pub struct SwapWithinResult<N: PoolNumber> {
+    recv_amount: N,
+    send_amount: N,
     end_tick: u128,
-    end_rp: Decimal,
+    end_rp: N,
     cross: bool,
-    send_hmm_adj: Decimal,
-    recv_fee: Decimal,
+    send_hmm_adj: N,
+    recv_fee: N,
 }
 // (done_dx, done_dy, end_t, end_rp, cross, hmm_adj_y, fee_x)
 
-impl SwapWithinResult {
+impl<N: PoolNumber> SwapWithinResult<N> {
     pub fn new(
-        recv_amount: Decimal,
-        send_amount: Decimal,
+        recv_amount: N,
+        send_amount: N,
         end_tick: u128,
-        end_rp: Decimal,
+        end_rp: N,
         cross: bool,
-        send_hmm_adj: Decimal,
-        recv_fee: Decimal,
+        send_hmm_adj: N,
+        recv_fee: N,
     ) -> Self {
         if recv_amount.is_negative() {
             panic!("in-qty cannot be negative");
@@ -333,48 +295,48 @@ impl SwapWithinResult {
             recv_fee,
         }
     }
-    pub fn recv_amount(&self) -> Decimal {
+    pub fn recv_amount(&self) -> N {
         self.recv_amount
     }
-    pub fn send_amount(&self) -> Decimal {
+    pub fn send_amount(&self) -> N {
         self.send_amount
     }
     pub fn end_tick(&self) -> u128 {
         self.end_tick
     }
-    pub fn end_rp(&self) -> Decimal {
+    pub fn end_rp(&self) -> N {
         self.end_rp
     }
     pub fn cross(&self) -> bool {
         self.cross
     }
-    pub fn send_hmm_adj(&self) -> Decimal {
+    pub fn send_hmm_adj(&self) -> N {
         self.send_hmm_adj
     }
-    pub fn recv_fee(&self) -> Decimal {
+    pub fn recv_fee(&self) -> N {
         self.recv_fee
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub struct SwapOutput {
-    recv_amount: Decimal,
-    send_amount: Decimal,
-    send_hmm_adj: Decimal,
-    recv_fee: Decimal,
-    avg_price: Decimal,
-    end_price: Decimal,
+pub struct SwapOutput<N: PoolNumber> {
+    recv_amount: N,
+    send_amount: N,
+    send_hmm_adj: N,
+    recv_fee: N,
+    avg_price: N,
+    end_price: N,
 }
 // (swpd_dx, swpd_dy, adjusted_dy, total_fee_x, avg_p, end_p)
 
-impl SwapOutput {
+impl<N: PoolNumber> SwapOutput<N> {
     pub fn new(
-        recv_amount: Decimal,
-        send_amount: Decimal,
-        send_hmm_adj: Decimal,
-        recv_fee: Decimal,
-        avg_price: Decimal,
-        end_price: Decimal,
+        recv_amount: N,
+        send_amount: N,
+        send_hmm_adj: N,
+        recv_fee: N,
+        avg_price: N,
+        end_price: N,
     ) -> Self {
         if recv_amount.is_negative() {
             panic!("in-qty cannot be negative");
@@ -405,22 +367,22 @@ impl SwapOutput {
             end_price,
         }
     }
-    pub fn recv_amount(&self) -> Decimal {
+    pub fn recv_amount(&self) -> N {
         self.recv_amount
     }
-    pub fn send_amount(&self) -> Decimal {
+    pub fn send_amount(&self) -> N {
         self.send_amount
     }
-    pub fn send_hmm_adj(&self) -> Decimal {
+    pub fn send_hmm_adj(&self) -> N {
         self.send_hmm_adj
     }
-    pub fn recv_fee(&self) -> Decimal {
+    pub fn recv_fee(&self) -> N {
         self.recv_fee
     }
-    pub fn avg_price(&self) -> Decimal {
+    pub fn avg_price(&self) -> N {
         self.avg_price
     }
-    pub fn end_price(&self) -> Decimal {
+    pub fn end_price(&self) -> N {
         self.end_price
     }
 }
@@ -1,57 +1,33 @@
-use hydra_math_rs::decimal::*;
-use std::ops::Neg;
+use super::PoolNumber;
 
-pub trait PoolMath {
-    // * All Decimal inputs are assumed to be 'computable' scale on arrival
-    // * And all outputs 'computable' scale
+pub trait PoolMath<N: PoolNumber> {
+    // * All inputs are assumed to be 'computable' on arrival, and so are all outputs
     // * All tick are internally handled as u128 and returned as such
 
-    const FLOOR_LIQ: bool = false; //* no longer needed in PreciseNumber setting
-
-    const ADJ_WITHDRAWAL: Decimal = Decimal {
-        value: 1,
-        scale: COMPUTE_SCALE,
-        negative: false,
-    }; //* still NEEDED for rounding down withdrawals, avoid out_qty > reserve by tiny amt
-
-    fn zero() -> Decimal {
-        Decimal::default().to_compute_scale()
-    }
-    fn one() -> Decimal {
-        Decimal::one().to_compute_scale()
-    }
-    fn two() -> Decimal {
-        Decimal::two().to_compute_scale()
+    fn adj_withdrawal() -> N {
+        //* still NEEDED for rounding down withdrawals, avoid out_qty > reserve by tiny amt
+        N::from_scaled(1, 12)
     }
 
-    fn tick_base() -> Decimal {
-        Decimal::new(10001_u128, 4_u8, false).to_compute_scale()
+    fn tick_base() -> N {
+        N::from_scaled(10001, 4)
     }
 
-    fn tick_base_root() -> Decimal {
-        Self::tick_base().sqrt().unwrap()
+    fn tick_base_root() -> N {
+        Self::tick_base().sqrt()
     }
 
-    fn tick_to_rp(tick: u128) -> Decimal {
-        Self::tick_base_root().pow(tick)
+    fn tick_to_rp(tick: u128) -> N {
+        Self::tick_base_root().powi(tick)
     }
 
-    fn rp_to_tick(rp: Decimal, left_to_right: bool) -> u128 {
-        let base = Self::tick_base_root();
-        let tick_decimal = match left_to_right {
-            true => rp.ln().unwrap().div_up(base.ln().unwrap()),
-            false => rp.ln().unwrap().div(base.ln().unwrap()),
-        };
-        tick_decimal.to_scale(0).value as u128
-    }
-
-    fn rp_to_tick_loop(rp: Decimal, left_to_right: bool, start: u128) -> u128 {
+    fn rp_to_tick_loop(rp: N, left_to_right: bool, start: u128) -> u128 {
         let m = Self::tick_base_root();
-        let mut rez = m.pow(start);
+        let mut rez = m.powi(start);
         let mut x = start;
         let result = loop {
             rez = rez.mul(m);
-            if rez.gte(rp).unwrap() {
+            if rez.gte(rp) {
                 match left_to_right {
                     true => break x.checked_add(1).unwrap(),
                     false => break x,
@@ -66,60 +42,52 @@ pub trait PoolMath {
         // use tk_spacing to find allowable/ initializable tick that is <= tick
         // (if left_to_right is false) or >= tick (if left_to_right is true)
         // returns unchanged tick if self.tick_spacing is 1
-        let ts = Decimal::from_u128(spacing);
-        let tk = Decimal::from_u128(tick);
-        let result = match left_to_right {
-            false => tk.div(ts).mul(ts),
-            true => tk.div_up(ts).mul(ts),
-        };
-        result.into()
+        let rounded_down = tick / spacing * spacing;
+        match left_to_right {
+            true if rounded_down < tick => rounded_down + spacing,
+            _ => rounded_down,
+        }
     }
 
-    fn rp_to_possible_tk(rp: Decimal, spacing: u128, left_to_right: bool, start: u128) -> u128 {
+    fn rp_to_possible_tk(rp: N, spacing: u128, left_to_right: bool, start: u128) -> u128 {
         // find allowable tick from given rp
         let tick_theo = Self::rp_to_tick_loop(rp, left_to_right, start);
         Self::tk_to_possible_tk(tick_theo, spacing, left_to_right)
     }
 
-    fn liq_x_only(x: Decimal, rpa: Decimal, rpb: Decimal) -> Decimal {
+    fn liq_x_only(x: N, rpa: N, rpb: N) -> N {
         // Lx : liquidity amount when liquidity fully composed of  token x
         // e.g when price below lower bound of range and y=0. [5]
         // x : token x real reserves; rPa,rPb : range lower (upper) bound in root price
         // x * rpa * rpb / (rpb - rpa) //* should always be positive
 
-        let rpb_minus_rpa = rpb.sub(rpa).unwrap();
+        let rpb_minus_rpa = rpb.sub(rpa);
         if rpb_minus_rpa.is_negative() || rpb_minus_rpa.is_zero() {
             panic!("liq_x_only:rpb should be greater than rpa");
         }
         x.mul(rpa).mul(rpb).div(rpb_minus_rpa)
     }
 
-    fn liq_y_only(y: Decimal, rpa: Decimal, rpb: Decimal) -> Decimal {
+    fn liq_y_only(y: N, rpa: N, rpb: N) -> N {
         // Ly : liquidity amount when liquidity fully composed of  token y
         // e.g when price above upper bound of range, x=0. [9]
         //    y : token y real reserves;  rPa,rPb : range lower (upper) bound in root price
         // y / (rpb - rpa)
-        let rpb_minus_rpa = rpb.sub(rpa).unwrap();
+        let rpb_minus_rpa = rpb.sub(rpa);
         if rpb_minus_rpa.is_negative() || rpb_minus_rpa.is_zero() {
             panic!("liq_x_only:rpb should be greater than rpa");
         }
         y.div(rpb_minus_rpa)
     }
 
-    fn liq_from_x_y_rp_rng(
-        x: Decimal,
-        y: Decimal,
-        rp: Decimal,
-        rpa: Decimal,
-        rpb: Decimal,
-    ) -> Decimal {
+    fn liq_from_x_y_rp_rng(x: N, y: N, rp: N, rpa: N, rpb: N) -> N {
         // L : liquidity amount from real reserves based on where price is compared to price range
         //    x,y : real token reserves ; rP : current root price
         //    rPa,rPb : range lower (upper) bound in root price
-        if rp.lte(rpa).unwrap() {
+        if rp.lte(rpa) {
             // y = 0 and reserves entirely in x. [4]
             return Self::liq_x_only(x, rpa, rpb);
-        } else if rp.lt(rpb).unwrap() {
+        } else if rp.lt(rpb) {
             // [11,12]
             // x covers sub-range [P,Pb] and y covers the other side [Pa,P]
             let lx = Self::liq_x_only(x, rp, rpb);
@@ -132,7 +100,7 @@ pub trait PoolMath {
         }
     }
 
-    fn liq_from_x_y_tick_rng(x: Decimal, y: Decimal, t: u128, ta: u128, tb: u128) -> Decimal {
+    fn liq_from_x_y_tick_rng(x: N, y: N, t: u128, ta: u128, tb: u128) -> N {
         // tick as inputs instead of root prices
         let rp = Self::tick_to_rp(t);
         let rpa = Self::tick_to_rp(ta);
@@ -140,7 +108,7 @@ pub trait PoolMath {
         Self::liq_from_x_y_rp_rng(x, y, rp, rpa, rpb)
     }
 
-    fn x_from_l_rp_rng(l: Decimal, rp: Decimal, rpa: Decimal, rpb: Decimal) -> Decimal {
+    fn x_from_l_rp_rng(l: N, rp: N, rpa: N, rpb: N) -> N {
         // calculate X amount from L, price and bounds
         // if the price is outside the range, use range endpoints instead [11]
         if rp.is_zero() || rpb.is_zero() || rpa.is_zero() {
@@ -148,14 +116,14 @@ pub trait PoolMath {
         }
         let rp = rp.min(rpb).max(rpa);
 
-        let rpb_minus_rp = rpb.sub(rp).unwrap();
+        let rpb_minus_rp = rpb.sub(rp);
         let rp_mul_rpb = rp.mul(rpb);
 
         // l * (rpb - rp) / (rp * rpb)
         l.mul(rpb_minus_rp).div(rp_mul_rpb)
     }
 
-    fn x_from_l_tick_rng(l: Decimal, t: u128, ta: u128, tb: u128) -> Decimal {
+    fn x_from_l_tick_rng(l: N, t: u128, ta: u128, tb: u128) -> N {
         // tick as inputs instead of root prices
         let rp = Self::tick_to_rp(t);
         let rpa = Self::tick_to_rp(ta);
@@ -163,20 +131,20 @@ pub trait PoolMath {
         Self::x_from_l_rp_rng(l, rp, rpa, rpb)
     }
 
-    fn y_from_l_rp_rng(l: Decimal, rp: Decimal, rpa: Decimal, rpb: Decimal) -> Decimal {
+    fn y_from_l_rp_rng(l: N, rp: N, rpa: N, rpb: N) -> N {
         // calculate Y amount from L, price and bounds
         // if the price is outside the range, use range endpoints instead [11]
         let rp = rp.min(rpb).max(rpa);
 
         // l * (rp - rpa) //* should always be positive
-        let rp_minus_rpa = rp.sub(rpa).unwrap();
+        let rp_minus_rpa = rp.sub(rpa);
         if rp_minus_rpa.is_negative() {
             panic!("y_from_l_rp_rng: rp should be greater than or equal to rpa");
         }
         l.mul(rp_minus_rpa)
     }
 
-    fn y_from_l_tick_rng(l: Decimal, t: u128, ta: u128, tb: u128) -> Decimal {
+    fn y_from_l_tick_rng(l: N, t: u128, ta: u128, tb: u128) -> N {
         // tick as inputs instead of root prices
         let rp = Self::tick_to_rp(t);
         let rpa = Self::tick_to_rp(ta);
@@ -184,25 +152,25 @@ pub trait PoolMath {
         Self::y_from_l_rp_rng(l, rp, rpa, rpb)
     }
 
-    fn rpa_from_l_rp_y(l: Decimal, rp: Decimal, y: Decimal) -> Decimal {
+    fn rpa_from_l_rp_y(l: N, rp: N, y: N) -> N {
         // lower bound from L, price and y amount [13]
         // rp - (y / l)
         if l.is_zero() {
             panic!("rpa_from_l_rp_y : liquidity should not be nil")
         }
         let y_div_l = y.div(l);
-        let rez = rp.sub(y_div_l).unwrap();
+        let rez = rp.sub(y_div_l);
         if rez.is_negative() {
             panic!("rpa_from_l_rp_y : rp - (y/l) should always be positive");
         }
         rez
     }
 
-    fn rpb_from_l_rp_x(l: Decimal, rp: Decimal, x: Decimal) -> Decimal {
+    fn rpb_from_l_rp_x(l: N, rp: N, x: N) -> N {
         // upper bound from L, price and x amount [14]
         // l * rp / (l - rp * x)
         let rp_mul_x = rp.mul(x);
-        let denom = l.sub(rp_mul_x).unwrap();
+        let denom = l.sub(rp_mul_x);
         if denom.is_zero() {
             panic!("rpb_from_l_rp_x : l - (rp * x) should not be nil");
         }
@@ -214,7 +182,7 @@ pub trait PoolMath {
         rez
     }
 
-    fn rpa_from_x_y_rp_rpb(x: Decimal, y: Decimal, rp: Decimal, rpb: Decimal) -> Decimal {
+    fn rpa_from_x_y_rp_rpb(x: N, y: N, rp: N, rpb: N) -> N {
         // lower bound from x, y amounts, price and upper bound [15]
         // y / (rpb * x) + rp - y / (rp * x)
         let rpb_mul_x = rpb.mul(x);
@@ -225,7 +193,7 @@ pub trait PoolMath {
         let rp_mul_x = rp.mul(x);
         let last_term = y.div(rp_mul_x);
 
-        let rez = first_term.add(rp).unwrap().sub(last_term).unwrap();
+        let rez = first_term.add(rp).sub(last_term);
         if rez.is_negative() {
             panic!(
                 "rpa_from_x_y_rp_rpb : y / (rpb * x) + rp - y / (rp * x) should always be positive"
@@ -234,24 +202,24 @@ pub trait PoolMath {
         rez
     }
 
-    fn rpb_from_x_y_rp_rpa(x: Decimal, y: Decimal, rp: Decimal, rpa: Decimal) -> Decimal {
+    fn rpb_from_x_y_rp_rpa(x: N, y: N, rp: N, rpa: N) -> N {
         // upper bound from x, y amounts, price and lower bound [16]
         // (rp * y) / ((rpa - rp) * rp * x + y)
         let numer = rp.mul(y);
-        let rp_minus_rpa = rp.sub(rpa).unwrap();
+        let rp_minus_rpa = rp.sub(rpa);
         if rp_minus_rpa.is_negative() {
             panic!("rpb_from_x_y_rp_rpa: rpb_minus_rpa should always be positive");
         }
         let d1 = rp_minus_rpa.mul(rp).mul(x); // d1 shoud be positive
 
-        let denom = y.sub(d1).unwrap();
+        let denom = y.sub(d1);
         if denom.is_negative() || denom.is_zero() {
             panic!("rpb_from_x_y_rp_rpa: denom (rpa - rp) * rp * x + y) should always be positive");
         }
         numer.div(denom)
     }
 
-    fn dx_from_l_drp(l: Decimal, rp_old: Decimal, rp_new: Decimal) -> Decimal {
+    fn dx_from_l_drp(l: N, rp_old: N, rp_new: N) -> N {
         // Change of reserve X based of change of price
         // l * (1.0 / rp_new - 1.0 / rp_old) = l * (rp_old - rp_new) / (rp_old * rp_new)
         //? this way of calculating needs to be consistent with x_from_l_rp_rng
@@ -259,38 +227,32 @@ pub trait PoolMath {
         if rp_old.is_zero() || rp_new.is_zero() {
             panic!("root price should not be nil");
         }
-        let diff = rp_old.sub(rp_new).unwrap();
+        let diff = rp_old.sub(rp_new);
         let old_mul_new = rp_old.mul(rp_new);
 
         l.mul(diff).div(old_mul_new)
     }
 
-    fn dy_from_l_drp(l: Decimal, rp_old: Decimal, rp_new: Decimal) -> Decimal {
+    fn dy_from_l_drp(l: N, rp_old: N, rp_new: N) -> N {
         // Change of reserve Y based of change of price
         // l * (rp_new - rp_old)
-        rp_new.sub(rp_old).unwrap().mul(l)
+        rp_new.sub(rp_old).mul(l)
     }
 
-    fn dx_from_l_drp_hmm(
-        l: Decimal,
-        rp_old: Decimal,
-        rp_new: Decimal,
-        c: Decimal,
-        rp_oracle: Decimal,
-    ) -> Decimal {
+    fn dx_from_l_drp_hmm(l: N, rp_old: N, rp_new: N, c: N, rp_oracle: N) -> N {
         // chg of reserve x based of chg of price with hmm adj
-        let one = Self::one();
-        if c.to_compute_scale().lt(one).unwrap() {
+        let one = N::one();
+        if c.lt(one) {
             panic!("cannot handle hmm with C<1");
         }
-        if rp_old.eq(rp_new).unwrap() {
-            return Self::zero();
+        if rp_old == rp_new {
+            return N::zero();
         }
-        if c.to_compute_scale().eq(one).unwrap() {
+        if c == one {
             // return l / rp_oracle * (rp_old / rp_new).ln();
-            let ln_rp_old = rp_old.ln().unwrap();
-            let ln_rp_new = rp_new.ln().unwrap();
-            let log_of_ratio = ln_rp_old.sub(ln_rp_new).unwrap();
+            let ln_rp_old = rp_old.ln();
+            let ln_rp_new = rp_new.ln();
+            let log_of_ratio = ln_rp_old.sub(ln_rp_new);
 
             if rp_oracle.is_zero() {
                 panic!("dx_from_l_drp_hmm called with zero oracle price");
@@ -300,47 +262,41 @@ pub trait PoolMath {
             // let omc = 1.0 - c; // one minus c
             // let cmo = -omc; // c minus one
             // return l / rp_oracle.powf(c) * (rp_new.powf(cmo) - rp_old.powf(cmo)) / omc;
-            let cmo = c.sub(one).unwrap();
+            let cmo = c.sub(one);
             let omc = cmo.neg();
             let rp_oracle_pow_c = rp_oracle.pow(c);
             let rp_new_pow_cmo = rp_new.pow(cmo);
             let rp_old_pow_cmo = rp_old.pow(cmo);
 
-            let diff = rp_new_pow_cmo.sub(rp_old_pow_cmo).unwrap();
+            let diff = rp_new_pow_cmo.sub(rp_old_pow_cmo);
 
             return l.div(rp_oracle_pow_c).mul(diff).div(omc);
         }
     }
 
-    fn dy_from_l_drp_hmm(
-        l: Decimal,
-        rp_old: Decimal,
-        rp_new: Decimal,
-        c: Decimal,
-        rp_oracle: Decimal,
-    ) -> Decimal {
+    fn dy_from_l_drp_hmm(l: N, rp_old: N, rp_new: N, c: N, rp_oracle: N) -> N {
         // chg of reserve y based of chg of price with hmm adj
-        let one = Self::one();
-        if c.to_compute_scale().lt(one).unwrap() {
+        let one = N::one();
+        if c.lt(one) {
             panic!("cannot handle hmm with C<1");
         }
-        if rp_old.eq(rp_new).unwrap() {
-            return Self::zero();
+        if rp_old == rp_new {
+            return N::zero();
         }
         if rp_old.is_zero() || rp_new.is_zero() {
             panic!("root-prices should not be nil");
         }
-        if c.to_compute_scale().eq(one).unwrap() {
+        if c == one {
             // l * rp_oracle * (rp_old / rp_new).ln()
-            let ln_rp_old = rp_old.ln().unwrap();
-            let ln_rp_new = rp_new.ln().unwrap();
-            let log_of_ratio = ln_rp_old.sub(ln_rp_new).unwrap();
+            let ln_rp_old = rp_old.ln();
+            let ln_rp_new = rp_new.ln();
+            let log_of_ratio = ln_rp_old.sub(ln_rp_new);
 
             return l.mul(rp_oracle).mul(log_of_ratio);
         } else {
             // let omc = 1.0 - c; // one minus c
             // l * rp_oracle.powf(c) * (1.0/rp_new.powf(cmo) - 1.0/ rp_old.powf(cmo)) / omc
-            let cmo = c.sub(one).unwrap();
+            let cmo = c.sub(one);
             let omc = cmo.neg();
 
             let rp_oracle_pow_c = rp_oracle.pow(c);
@@ -350,19 +306,19 @@ pub trait PoolMath {
             let inv_rp_new_pow_cmo = one.div(rp_new_pow_cmo);
             let inv_rp_old_pow_cmo = one.div(rp_old_pow_cmo);
 
-            let diff = inv_rp_new_pow_cmo.sub(inv_rp_old_pow_cmo).unwrap();
+            let diff = inv_rp_new_pow_cmo.sub(inv_rp_old_pow_cmo);
 
             return l.mul(rp_oracle_pow_c).mul(diff).div(omc);
         }
     }
 
-    fn rp_new_from_l_dx(l: Decimal, rp_old: Decimal, dx: Decimal) -> Decimal {
+    fn rp_new_from_l_dx(l: N, rp_old: N, dx: N) -> N {
         // new price based of change of reserve x //*always positive
         // drp_inv = dx / l = (1/rp_new - 1/rp_old)
         // after solving for rp_new: rp_new = (l * rp_old) / (dx*rp_old + l)
 
         let numerator = l.mul(rp_old);
-        let denom = dx.mul(rp_old).add(l).unwrap();
+        let denom = dx.mul(rp_old).add(l);
         if denom.is_zero() {
             panic!("rp_new_from_l_dx : (dx*rp_old + l) should not be nil");
         }
@@ -374,13 +330,13 @@ pub trait PoolMath {
         rez
     }
 
-    fn rp_new_from_l_dy(l: Decimal, rp_old: Decimal, dy: Decimal) -> Decimal {
+    fn rp_new_from_l_dy(l: N, rp_old: N, dy: N) -> N {
         // new price based of change of reserve y //*always positive
         // dy / l + rp_old
         if l.is_zero() {
             panic!("rp_new_from_l_dy: liquidity should not be nil")
         }
-        let rez = dy.div(l).add(rp_old).unwrap();
+        let rez = dy.div(l).add(rp_old);
         if rez.is_negative() {
             panic!("rp_new_from_l_dy : should always be positive");
         }
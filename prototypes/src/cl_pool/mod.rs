@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 pub mod cl_components;
 pub mod cl_math;
+pub mod pool_number;
 
 pub use cl_components::{
     GetInRangeOutput, GlobalState, PoolToken, PositionState, SwapOutput, SwapWithinResult,
@@ -8,55 +9,47 @@ pub use cl_components::{
 };
 
 pub use cl_math::PoolMath;
-use hydra_math_rs::decimal::{Add, Compare, Decimal, Div, Mul, Pow, Sub};
-use std::{
-    collections::{BTreeMap, HashMap},
-    ops::Neg,
-};
+pub use pool_number::PoolNumber;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PositionKey<'a>(&'a str, u128, u128);
 
 #[derive(Debug)]
-pub struct Pool<'a> {
+pub struct Pool<'a, N: PoolNumber> {
     token_x: PoolToken<'a>,
     token_y: PoolToken<'a>,
     tick_spacing: u128,
-    global_state: GlobalState,
-    active_ticks: BTreeMap<u128, TickState>, // keep ordered
-    positions: HashMap<PositionKey<'a>, PositionState>,
-    x: Decimal,
-    y: Decimal,
-    x_adj: Decimal,
-    y_adj: Decimal,
-    x_fee: Decimal,
-    y_fee: Decimal,
-    c: Decimal,
-    fee_rate: Decimal,
+    global_state: GlobalState<N>,
+    active_ticks: BTreeMap<u128, TickState<N>>, // keep ordered
+    positions: HashMap<PositionKey<'a>, PositionState<N>>,
+    x: N,
+    y: N,
+    x_adj: N,
+    y_adj: N,
+    x_fee: N,
+    y_fee: N,
+    c: N,
+    fee_rate: N,
 }
 
-impl PoolMath for Pool<'_> {}
+impl<N: PoolNumber> PoolMath<N> for Pool<'_, N> {}
 
-impl<'a> Pool<'a> {
+impl<'a, N: PoolNumber> Pool<'a, N> {
     pub fn new(
         x_name: &'a str,
         x_decimals: u8,
         y_name: &'a str,
         y_decimals: u8,
-        bootstrap_rp: Decimal,
+        bootstrap_rp: N,
         tick_spacing: u128,
-        hmm_c: Decimal,
-        fee_rate: Decimal,
-    ) -> Pool<'a> {
+        hmm_c: N,
+        fee_rate: N,
+    ) -> Self {
         if bootstrap_rp.is_negative() {
             panic!("cannot bootstrap with negative root-price");
         }
-        if hmm_c.is_negative()
-            || hmm_c
-                .to_compute_scale()
-                .gt(Decimal::two().to_compute_scale())
-                .unwrap()
-        {
+        if hmm_c.is_negative() || hmm_c.gt(N::from_u128(2)) {
             panic!("hmm_c needs to be between 0 and 2");
         }
         if fee_rate.is_negative() {
@@ -66,27 +59,27 @@ impl<'a> Pool<'a> {
         let tk = Self::rp_to_possible_tk(bootstrap_rp, tick_spacing, false, 0);
         let rp = Self::tick_to_rp(tk);
 
-        Pool {
+        Self {
             token_x: PoolToken::new(x_name, x_decimals),
             token_y: PoolToken::new(y_name, y_decimals),
             tick_spacing,
             global_state: GlobalState::new(
-                Self::zero(),
+                N::zero(),
                 rp,
                 tk,
-                Self::zero(),
-                Self::zero(),
-                Self::zero(),
-                Self::zero(),
+                N::zero(),
+                N::zero(),
+                N::zero(),
+                N::zero(),
             ),
             active_ticks: BTreeMap::new(),
             positions: HashMap::new(),
-            x: Self::zero(),
-            y: Self::zero(),
-            x_adj: Self::zero(),
-            y_adj: Self::zero(),
-            x_fee: Self::zero(),
-            y_fee: Self::zero(),
+            x: N::zero(),
+            y: N::zero(),
+            x_adj: N::zero(),
+            y_adj: N::zero(),
+            x_fee: N::zero(),
+            y_fee: N::zero(),
             c: hmm_c,
             fee_rate,
         }
@@ -98,16 +91,16 @@ impl<'a> Pool<'a> {
     pub fn tick_count(&self) -> usize {
         self.active_ticks.len()
     }
-    pub fn x_info(&self) -> (Decimal, Decimal, Decimal) {
+    pub fn x_info(&self) -> (N, N, N) {
         (self.x, self.x_adj, self.x_fee)
     }
-    pub fn y_info(&self) -> (Decimal, Decimal, Decimal) {
+    pub fn y_info(&self) -> (N, N, N) {
         (self.y, self.y_adj, self.y_fee)
     }
-    pub fn glbl_liq(&self) -> Decimal {
+    pub fn glbl_liq(&self) -> N {
         self.global_state.liq()
     }
-    pub fn add_glbl_liq(&mut self, liq_to_add: Decimal) {
+    pub fn add_glbl_liq(&mut self, liq_to_add: N) {
         self.global_state.add_liq(liq_to_add);
     }
     pub fn glbl_tick(&self) -> u128 {
@@ -116,20 +109,20 @@ impl<'a> Pool<'a> {
     pub fn set_glbl_tick(&mut self, tick: u128) {
         self.global_state.set_tick(tick);
     }
-    pub fn glbl_rp(&self) -> Decimal {
+    pub fn glbl_rp(&self) -> N {
         self.global_state.rp()
     }
-    pub fn set_glbl_rp(&mut self, rp: Decimal) {
+    pub fn set_glbl_rp(&mut self, rp: N) {
         self.global_state.set_rp(rp)
     }
 
-    pub fn glbl_fee(&self, token: char, f_or_h: char) -> Decimal {
+    pub fn glbl_fee(&self, token: char, f_or_h: char) -> N {
         self.global_state.fee(token, f_or_h)
     }
-    pub fn set_fee_glbl(&mut self, token: char, f_or_h: char, fee: Decimal) {
+    pub fn set_fee_glbl(&mut self, token: char, f_or_h: char, fee: N) {
         self.global_state.set_fee(token, f_or_h, fee)
     }
-    pub fn glbl_fees(&self) -> (Decimal, Decimal, Decimal, Decimal) {
+    pub fn glbl_fees(&self) -> (N, N, N, N) {
         self.global_state.all_fees()
     }
 
@@ -140,7 +133,7 @@ impl<'a> Pool<'a> {
         Self::tk_to_possible_tk(tick, self.tick_spacing, left_to_right)
     }
 
-    fn rp_to_possible_tick(&self, rp: Decimal, left_to_right: bool, start: u128) -> u128 {
+    fn rp_to_possible_tick(&self, rp: N, left_to_right: bool, start: u128) -> u128 {
         // find allowable tick from given rp
         Self::rp_to_possible_tk(rp, self.tick_spacing, left_to_right, start)
     }
@@ -150,10 +143,10 @@ impl<'a> Pool<'a> {
         let (f0_x, f0_y, h0_x, h0_y) = if self.glbl_tick() >= tick {
             self.glbl_fees()
         } else {
-            (Self::zero(), Self::zero(), Self::zero(), Self::zero())
+            (N::zero(), N::zero(), N::zero(), N::zero())
         };
 
-        let ts = TickState::new(Self::zero(), Self::zero(), f0_x, f0_y, h0_x, h0_y);
+        let ts = TickState::new(N::zero(), N::zero(), f0_x, f0_y, h0_x, h0_y);
         self.active_ticks.insert(tick, ts);
     }
 
@@ -161,7 +154,7 @@ impl<'a> Pool<'a> {
         self.active_ticks.remove(&tick);
     }
 
-    fn update_tick(&mut self, tick: u128, liq_delta: Decimal, upper: bool) {
+    fn update_tick(&mut self, tick: u128, liq_delta: N, upper: bool) {
         // Update specific tick's liquidity delta for specific tick
         // get the tick state for tick if exists, else initialize one
         if self.active_ticks.get(&tick).is_none() {
@@ -203,10 +196,10 @@ impl<'a> Pool<'a> {
 
         // update tick state by flipping fee growth outside f0_X_Y [6.26]
         let (f0_x, f0_y, h0_x, h0_y) = ts.all_fees();
-        ts.set_fee('x', 'f', fg_x.sub(f0_x).unwrap());
-        ts.set_fee('y', 'f', fg_y.sub(f0_y).unwrap());
-        ts.set_fee('x', 'h', hg_x.sub(h0_x).unwrap());
-        ts.set_fee('y', 'h', hg_y.sub(h0_y).unwrap());
+        ts.set_fee('x', 'f', fg_x.sub(f0_x));
+        ts.set_fee('y', 'f', fg_y.sub(f0_y));
+        ts.set_fee('x', 'h', hg_x.sub(h0_x));
+        ts.set_fee('y', 'h', hg_y.sub(h0_y));
         // TODO: do the same for s0, i0, sl0 in Tick-state
 
         // update current tick in global state to reflect crossing; rP unchanged
@@ -285,7 +278,7 @@ impl<'a> Pool<'a> {
         }
     }
 
-    fn try_get_in_range(&mut self, left_to_right: bool) -> GetInRangeOutput {
+    fn try_get_in_range(&mut self, left_to_right: bool) -> GetInRangeOutput<N> {
         // During swap, when no liquidity in current state, find next active tick, cross it  to
         // kick-in some liquidity. return (new_goal_tick or None, glbl_tick and glbl_rP).
         if self.glbl_liq().is_positive() {
@@ -346,7 +339,7 @@ impl<'a> Pool<'a> {
     }
 
     //+ DEPOSITS AND WITHDRAWALS
-    fn fee_below_above(&self, tick: u128, token: char, f_or_h: char) -> (Decimal, Decimal) {
+    fn fee_below_above(&self, tick: u128, token: char, f_or_h: char) -> (N, N) {
         // Fees earned in a token below and above tick, as tuple.
         // can compute for either token: X if 'for_x' is true, else Y
         // & for either swap fees or hmm fees: swap fees id 'swp' is true else hmm fees)
@@ -355,15 +348,15 @@ impl<'a> Pool<'a> {
         let ts = self.active_ticks.get(&tick);
 
         match ts {
-            None => (fg, Self::zero()), // from [6.17 - 6.21], convention
+            None => (fg, N::zero()), // from [6.17 - 6.21], convention
             Some(ts) => {
                 let f0 = ts.fee(token, f_or_h);
                 let f_below = match i_c >= tick {
                     true => f0,
-                    false => fg.sub(f0).unwrap(),
+                    false => fg.sub(f0),
                 }; // [6.18]
                 let f_above = match i_c >= tick {
-                    true => fg.sub(f0).unwrap(),
+                    true => fg.sub(f0),
                     false => f0,
                 }; // [6.17]
                 (f_below, f_above)
@@ -371,18 +364,18 @@ impl<'a> Pool<'a> {
         }
     }
 
-    fn fee_rng(&self, lower_tick: u128, upper_tick: u128, token: char, f_or_h: char) -> Decimal {
+    fn fee_rng(&self, lower_tick: u128, upper_tick: u128, token: char, f_or_h: char) -> N {
         // Fees earned (per unit of liq) within a range of ticks (e.g. by a position)
         let (f_blw_lwr, f_abv_lwr) = self.fee_below_above(lower_tick, token, f_or_h);
         let (f_blw_upr, f_abv_upr) = self.fee_below_above(upper_tick, token, f_or_h);
 
         // retrieve fg by summing up either tuple, they should match
-        let sum_lwr = f_blw_lwr.add(f_abv_lwr).unwrap();
-        let sum_upr = f_blw_upr.add(f_abv_upr).unwrap();
+        let sum_lwr = f_blw_lwr.add(f_abv_lwr);
+        let sum_upr = f_blw_upr.add(f_abv_upr);
         assert_eq!(sum_lwr, sum_upr);
         let fg = sum_lwr;
 
-        let fee_earned = fg.sub(f_blw_lwr).unwrap().sub(f_abv_upr).unwrap();
+        let fee_earned = fg.sub(f_blw_lwr).sub(f_abv_upr);
         if fee_earned.is_negative() {
             panic!("fees earned cannot be negative");
         }
@@ -395,8 +388,8 @@ impl<'a> Pool<'a> {
         user_id: &'a str,
         lower_tick: u128,
         upper_tick: u128,
-        liq_delta: Decimal,
-    ) -> (Decimal, Decimal, Decimal, Decimal) {
+        liq_delta: N,
+    ) -> (N, N, N, N) {
         // handles all facets for updates a position for in the pool,
         // used for deposits (l>0), withdrawals (l<0)
         // compute uncollected fees f_u the poz is entitled to: first compute new FeeGrowthInside,
@@ -408,12 +401,12 @@ impl<'a> Pool<'a> {
 
         // then get old fee value from when position was last touched.(set below)
         // set to 0 as default for when new position
-        let mut old_fr_x = Self::zero();
-        let mut old_fr_y = Self::zero();
-        let mut old_hr_x = Self::zero();
-        let mut old_hr_y = Self::zero();
+        let mut old_fr_x = N::zero();
+        let mut old_fr_y = N::zero();
+        let mut old_hr_x = N::zero();
+        let mut old_hr_y = N::zero();
         // liquidity to use for computing fee amounts (set below)
-        let mut base = Self::zero();
+        let mut base = N::zero();
 
         // find position if exists
         // positions are uniquely identitfied by the (sender, lower, upper)
@@ -425,15 +418,11 @@ impl<'a> Pool<'a> {
                     // abort if withdrawal liq exceeds position liquidity
                     panic!("cannot newly provide negative liquidity");
                 }
+                // start from the current fee growth inside, fees earned before the
+                // position existed are not owed to it
                 self.positions.insert(
                     key,
-                    PositionState::new(
-                        liq_delta,
-                        Self::zero(),
-                        Self::zero(),
-                        Self::zero(),
-                        Self::zero(),
-                    ),
+                    PositionState::new(liq_delta, new_fr_x, new_fr_y, new_hr_x, new_hr_y),
                 );
             }
             Some(poz) => {
@@ -450,7 +439,7 @@ impl<'a> Pool<'a> {
                 }
 
                 // update existing position
-                let new_liq = base.add(liq_delta).unwrap();
+                let new_liq = base.add(liq_delta);
                 if liq_delta.is_negative() && new_liq.is_negative() {
                     // abort if withdrawal liq exceeds position liquidity
                     panic!("liquidity is position insufficient");
@@ -468,14 +457,8 @@ impl<'a> Pool<'a> {
             }
         }
         // now calulate uncollected fees to be applied to deposit/withdrawal
-        let (f_u_x, f_u_y) = (
-            new_fr_x.sub(old_fr_x).unwrap(),
-            new_fr_y.sub(old_fr_y).unwrap(),
-        );
-        let (h_u_x, h_u_y) = (
-            new_hr_x.sub(old_hr_x).unwrap(),
-            new_hr_y.sub(old_hr_y).unwrap(),
-        );
+        let (f_u_x, f_u_y) = (new_fr_x.sub(old_fr_x), new_fr_y.sub(old_fr_y));
+        let (h_u_x, h_u_y) = (new_hr_x.sub(old_hr_x), new_hr_y.sub(old_hr_y));
         assert!(!f_u_x.is_negative(), "fees earned cannot be negative");
         assert!(!f_u_y.is_negative(), "fees earned cannot be negative");
         assert!(!h_u_x.is_negative(), "fees earned cannot be negative");
@@ -498,14 +481,7 @@ impl<'a> Pool<'a> {
         )
     }
 
-    pub fn deposit(
-        &mut self,
-        user_id: &'a str,
-        x: Decimal,
-        y: Decimal,
-        rpa: Decimal,
-        rpb: Decimal,
-    ) {
+    pub fn deposit(&mut self, user_id: &'a str, x: N, y: N, rpa: N, rpb: N) {
         // interface to deposit liquidity in pool & give change if necessary
 
         // sign check. leave possibility of zero deposit
@@ -523,29 +499,25 @@ impl<'a> Pool<'a> {
             upper_tick > lower_tick,
             "upper bound needs to be above lower bound"
         );
-        let rpa_used = Pool::tick_to_rp(lower_tick);
-        let rpb_used = Pool::tick_to_rp(upper_tick);
+        let rpa_used = Self::tick_to_rp(lower_tick);
+        let rpb_used = Self::tick_to_rp(upper_tick);
         let rp_used = self.glbl_rp();
 
         // TODO should we use Oracle price here instead? or real price as param
         // ? only when no liquidity in range?
 
-        let mut liq = Pool::liq_from_x_y_rp_rng(x, y, rp_used, rpa_used, rpb_used);
+        // round down to avoid rounding vulnerabilities, where the backend needs it
+        let liq = Self::liq_from_x_y_rp_rng(x, y, rp_used, rpa_used, rpb_used).floor_liq();
         if liq.is_negative() {
             panic!("negative liquidity on deposit");
         }
-        // round down to avoid float rounding vulnerabilities
-        // TODO choose what precision to round down to
-        if Self::FLOOR_LIQ {
-            liq = liq.div(Self::one())
-        };
 
-        let x_in = Pool::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
-        if x_in.gt(x).unwrap() {
+        let x_in = Self::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
+        if x_in.gt(x) {
             panic!("used x amt cannot exceed provided amount");
         }
-        let y_in = Pool::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
-        if y_in.gt(y).unwrap() {
+        let y_in = Self::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
+        if y_in.gt(y) {
             panic!("used y amt cannot exceed provided amount");
         }
 
@@ -553,37 +525,37 @@ impl<'a> Pool<'a> {
             self._set_position(user_id, lower_tick, upper_tick, liq);
 
         // offset fee amounts from deposit amounts: this will be the amount debited from user
-        let x_debited = x_in.sub(fees_x).unwrap().sub(adj_x).unwrap();
-        let y_debited = y_in.sub(fees_y).unwrap().sub(adj_y).unwrap();
+        let x_debited = x_in.sub(fees_x).sub(adj_x);
+        let y_debited = y_in.sub(fees_y).sub(adj_y);
 
         // update state: reserves, fee pot , hmm-adj-fee pot
-        if self.x_fee.lt(fees_x).unwrap() || self.y_fee.lt(fees_y).unwrap() {
+        if self.x_fee.lt(fees_x) || self.y_fee.lt(fees_y) {
             panic!("cannot disburse more fees than present in pot");
         }
-        if self.x_adj.lt(adj_x).unwrap() || self.y_adj.lt(adj_y).unwrap() {
+        if self.x_adj.lt(adj_x) || self.y_adj.lt(adj_y) {
             panic!("cannot disburse more adj-fees than present in pot");
         }
-        self.x = self.x.add(x_in).unwrap();
-        self.y = self.y.add(y_in).unwrap();
-        self.x_fee = self.x_fee.sub(fees_x).unwrap();
-        self.x_adj = self.x_adj.sub(adj_x).unwrap();
-        self.y_fee = self.y_fee.sub(fees_y).unwrap();
-        self.y_adj = self.y_adj.sub(adj_y).unwrap();
+        self.x = self.x.add(x_in);
+        self.y = self.y.add(y_in);
+        self.x_fee = self.x_fee.sub(fees_x);
+        self.x_adj = self.x_adj.sub(adj_x);
+        self.y_fee = self.y_fee.sub(fees_y);
+        self.y_adj = self.y_adj.sub(adj_y);
 
         println!("x_debited={:?} y_debited {:?}", x_debited, y_debited);
         println!(
             "including fees_x+adj_x ={:?} and fees_y+adj_y={:?}",
-            fees_x.add(adj_x).unwrap(),
-            fees_y.add(adj_y).unwrap()
+            fees_x.add(adj_x),
+            fees_y.add(adj_y)
         );
         println!(
             "X returned {:?} Y returned {:?}",
-            x.sub(x_debited).unwrap(),
-            y.sub(y_debited).unwrap()
+            x.sub(x_debited),
+            y.sub(y_debited)
         );
     }
 
-    pub fn withdraw(&mut self, user_id: &'a str, liq: Decimal, rpa: Decimal, rpb: Decimal) {
+    pub fn withdraw(&mut self, user_id: &'a str, liq: N, rpa: N, rpb: N) {
         // interface to withdraw liquidity from pool
 
         // sign check . leave possibility of zero withdrawal
@@ -600,8 +572,8 @@ impl<'a> Pool<'a> {
             upper_tick > lower_tick,
             "upper bound needs to be above lower bound"
         );
-        let rpa_used = Pool::tick_to_rp(lower_tick);
-        let rpb_used = Pool::tick_to_rp(upper_tick);
+        let rpa_used = Self::tick_to_rp(lower_tick);
+        let rpb_used = Self::tick_to_rp(upper_tick);
 
         let (fees_x, fees_y, adj_x, adj_y) =
             self._set_position(user_id, lower_tick, upper_tick, liq.neg());
@@ -610,53 +582,113 @@ impl<'a> Pool<'a> {
         // TODO should we use Oracle price here instead? or real price as param
         // ? only when no liquidity in range?
 
-        let mut x_out = Pool::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
-        let mut y_out = Pool::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
+        let mut x_out = Self::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
+        let mut y_out = Self::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used);
 
         // round down amount withdrawn if necessary, as precation
-        let adj_factor = Self::one().sub(Self::ADJ_WITHDRAWAL).unwrap();
+        let adj_factor = N::one().sub(Self::adj_withdrawal());
         x_out = x_out.mul(adj_factor);
         y_out = y_out.mul(adj_factor);
 
         // add fees on top of what user will receive
-        let x_sent = x_out.add(fees_x).unwrap().add(adj_x).unwrap();
-        let y_sent = y_out.add(fees_y).unwrap().add(adj_y).unwrap();
+        let x_sent = x_out.add(fees_x).add(adj_x);
+        let y_sent = y_out.add(fees_y).add(adj_y);
 
         // update state: reserves, fee pot , hmm-adj-fee pot
         // first check x_out and y_out are not larger than reserves
-        if self.x.lt(x_out).unwrap() || self.y.lt(y_out).unwrap() {
+        if self.x.lt(x_out) || self.y.lt(y_out) {
             panic!("cannot withdraw more than reserves");
         }
-        if self.x_fee.lt(fees_x).unwrap() || self.y_fee.lt(fees_y).unwrap() {
+        if self.x_fee.lt(fees_x) || self.y_fee.lt(fees_y) {
             panic!("cannot disburse more fees than present in pot");
         }
-        if self.x_adj.lt(adj_x).unwrap() || self.y_adj.lt(adj_y).unwrap() {
+        if self.x_adj.lt(adj_x) || self.y_adj.lt(adj_y) {
             panic!("cannot disburse more add-fees than present in pot");
         }
-        self.x = self.x.sub(x_out).unwrap();
-        self.y = self.y.sub(y_out).unwrap();
-        self.x_fee = self.x_fee.sub(fees_x).unwrap();
-        self.x_adj = self.x_adj.sub(adj_x).unwrap();
-        self.y_fee = self.y_fee.sub(fees_y).unwrap();
-        self.y_adj = self.y_adj.sub(adj_y).unwrap();
+        self.x = self.x.sub(x_out);
+        self.y = self.y.sub(y_out);
+        self.x_fee = self.x_fee.sub(fees_x);
+        self.x_adj = self.x_adj.sub(adj_x);
+        self.y_fee = self.y_fee.sub(fees_y);
+        self.y_adj = self.y_adj.sub(adj_y);
 
         println!("x_sent: {:?}, y_sent: {:?}", x_sent, y_sent);
         println!(
             "including fees_x+adj_x ={:?} and fees_y+adj_y={:?}",
-            fees_x.add(adj_x).unwrap(),
-            fees_y.add(adj_y).unwrap()
+            fees_x.add(adj_x),
+            fees_y.add(adj_y)
+        );
+    }
+
+    pub fn collect_fees(&mut self, user_id: &'a str, rpa: N, rpb: N) -> (N, N, N, N) {
+        // interface to collect uncollected fees of a position without changing its liquidity
+        // ticks and global liquidity are untouched, only the position's fee checkpoints are reset
+
+        // calculate ticks that track the position
+        let lower_tick = self.rp_to_possible_tick(rpa, false, 0);
+        let upper_tick = self.rp_to_possible_tick(rpb, false, lower_tick);
+        assert!(
+            upper_tick > lower_tick,
+            "upper bound needs to be above lower bound"
+        );
+
+        // current fee growth inside the range, becomes the position's new checkpoint
+        let new_fr_x = self.fee_rng(lower_tick, upper_tick, 'x', 'f');
+        let new_fr_y = self.fee_rng(lower_tick, upper_tick, 'y', 'f');
+        let new_hr_x = self.fee_rng(lower_tick, upper_tick, 'x', 'h');
+        let new_hr_y = self.fee_rng(lower_tick, upper_tick, 'y', 'h');
+
+        let key = PositionKey(user_id, lower_tick, upper_tick);
+        let poz = match self.positions.get_mut(&key) {
+            None => panic!("no position to collect fees from"),
+            Some(poz) => poz,
+        };
+        let (old_fr_x, old_fr_y, old_hr_x, old_hr_y) = poz.all_fees();
+        let base = poz.liq();
+        poz.set_fee('x', 'f', new_fr_x);
+        poz.set_fee('y', 'f', new_fr_y);
+        poz.set_fee('x', 'h', new_hr_x);
+        poz.set_fee('y', 'h', new_hr_y);
+
+        // fees owed: liquidity times fee growth inside since the last checkpoint
+        let fees_x = base.mul(new_fr_x.sub(old_fr_x));
+        let fees_y = base.mul(new_fr_y.sub(old_fr_y));
+        let adj_x = base.mul(new_hr_x.sub(old_hr_x));
+        let adj_y = base.mul(new_hr_y.sub(old_hr_y));
+        assert!(!fees_x.is_negative(), "fees earned cannot be negative");
+        assert!(!fees_y.is_negative(), "fees earned cannot be negative");
+        assert!(!adj_x.is_negative(), "fees earned cannot be negative");
+        assert!(!adj_y.is_negative(), "fees earned cannot be negative");
+
+        // pay out of the fee pot and hmm-adj-fee pot
+        if self.x_fee.lt(fees_x) || self.y_fee.lt(fees_y) {
+            panic!("cannot disburse more fees than present in pot");
+        }
+        if self.x_adj.lt(adj_x) || self.y_adj.lt(adj_y) {
+            panic!("cannot disburse more adj-fees than present in pot");
+        }
+        self.x_fee = self.x_fee.sub(fees_x);
+        self.x_adj = self.x_adj.sub(adj_x);
+        self.y_fee = self.y_fee.sub(fees_y);
+        self.y_adj = self.y_adj.sub(adj_y);
+
+        println!(
+            "fees collected: fees_x+adj_x ={:?} and fees_y+adj_y={:?}",
+            fees_x.add(adj_x),
+            fees_y.add(adj_y)
         );
+        (fees_x, fees_y, adj_x, adj_y)
     }
 
     //+ SWAPPING
     fn swap_within_tick_from_x(
         &mut self,
-        start_rp: Decimal,
+        start_rp: N,
         goal_tick: u128,
-        liq: Decimal,
-        dx: Decimal,
-        rp_oracle: Decimal,
-    ) -> SwapWithinResult {
+        liq: N,
+        dx: N,
+        rp_oracle: N,
+    ) -> SwapWithinResult<N> {
         // portion of swap execution occurring within 2 consecutive active ticks
         // + no writing to state to occurs here, just compute and return to caller
         let (done_dx, end_t, end_rp, cross, hmm_adj_y, fee_x);
@@ -667,25 +699,25 @@ impl<'a> Pool<'a> {
         }
 
         // root-price at goal tick - here on the left
-        let rp_goal = Pool::tick_to_rp(goal_tick);
-        if rp_goal.gt(start_rp).unwrap() {
+        let rp_goal = Self::tick_to_rp(goal_tick);
+        if rp_goal.gt(start_rp) {
             panic!("expect price to go down when X supplied to pool");
             // we allow case when price exactly on the current tick ( i.e. rP_goal = start_rP)
             // this will lead to 0-qty swapped, and tick-crossing before next swap
         }
 
         // put aside max potential swap fees before affecting prices
-        let fee_factor = Self::one().sub(self.fee_rate).unwrap();
+        let fee_factor = N::one().sub(self.fee_rate);
         let dx_max = dx.mul(fee_factor);
 
         // chg of reserve X possible if we go all the way to goal tick
-        let doable_dx = Pool::dx_from_l_drp(liq, start_rp, rp_goal);
+        let doable_dx = Self::dx_from_l_drp(liq, start_rp, rp_goal);
         if doable_dx.is_negative() {
             // expect a positive number
             panic!("doable_dX > 0 when X supplied to pool");
         }
 
-        if doable_dx.lt(dx_max).unwrap() {
+        if doable_dx.lt(dx_max) {
             // we will have leftover quantity to swap. fill what we can. done_X = doableX
             done_dx = doable_dx;
             // reverse engineer how much fees charged based on how much done_dX
@@ -696,50 +728,55 @@ impl<'a> Pool<'a> {
         } else {
             // we have enough to fill full quantity. make all dX_max 'done', then calc end_rP
             done_dx = dx_max;
-            fee_x = dx.sub(dx_max).unwrap(); // fee as expected
+            fee_x = dx.sub(dx_max); // fee as expected
             cross = false;
-            end_rp = Pool::rp_new_from_l_dx(liq, start_rp, done_dx);
-            end_t = Pool::rp_to_tick_loop(end_rp, false, goal_tick - 1);
+            end_rp = Self::rp_new_from_l_dx(liq, start_rp, done_dx);
+            end_t = Self::rp_to_tick_loop(end_rp, false, goal_tick - 1);
             // * this loop happens only once per trade if price ends in-between ticks
             // * tick is always on the left (round down after 'log')
 
-            if end_rp.gt(start_rp).unwrap() {
+            if end_rp.gt(start_rp) {
                 panic!("expect end_rP < start_rP when pool given X");
             }
-            if end_rp.lt(rp_goal).unwrap() {
+            if end_rp.lt(rp_goal) {
                 panic!("dont expect end_rP go beyond rP_goal (left) when able to wholy fill dX")
             }
         }
         // now figure out how much done_dY ( done Y quantity) and hmm_adj_Y (hmm related fee)
-        let done_dy_cpmm = Pool::dy_from_l_drp(liq, start_rp, end_rp);
-        if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.gte(start_rp).unwrap() {
+        let done_dy_cpmm = Self::dy_from_l_drp(liq, start_rp, end_rp);
+        if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.gte(start_rp) {
             // also when rP_oracle is None (zero)
             // in cases where no oracle or no hmm c=0, we cannot adjust so we fall back to amm
             // * when trade will make pool price diverge even more from oracle,
             // * then we don't adjust (hmm adjust on convergence only)
             done_dy = done_dy_cpmm;
-        } else if rp_oracle.lt(start_rp).unwrap() && rp_oracle.gte(end_rp).unwrap() {
+        } else if rp_oracle.lt(start_rp) && rp_oracle.gte(end_rp) {
             // 1st part of this condition is redundant as it is implied from precious branch
             // it is left in for precision and readability
             // * when oracle is in between start_rP and end_rP prices, use hmm
             // * till we reach oracle price then use unadjusted amm till we reach end_rP
-            let done_dy_1 = Pool::dy_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle);
-            let done_dy_2 = Pool::dy_from_l_drp(liq, rp_oracle, end_rp);
+            let done_dy_1 = Self::dy_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle);
+            let done_dy_2 = Self::dy_from_l_drp(liq, rp_oracle, end_rp);
             if done_dy_1.is_positive() || done_dy_2.is_positive() {
                 panic!("expect (each component of) done_dY < 0 when X supplied to pool")
             }
-            done_dy = done_dy_1.add(done_dy_2).unwrap();
-        } else if rp_oracle.lt(end_rp).unwrap() {
+            done_dy = done_dy_1.add(done_dy_2);
+        } else if rp_oracle.lt(end_rp) {
             // * when trade will make pool price converge to oracle price
             // * and end_rP won't reach the oracle price
             // * then use hmm all the way
-            done_dy = Pool::dy_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle);
+            done_dy = Self::dy_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle);
         } else {
             // we don't expect to hit this. raise error if we do hit
             panic!("HMM adjstment: possibilities should have been exhausted by now");
         }
 
-        hmm_adj_y = done_dy.sub(done_dy_cpmm).unwrap();
+        // adjust conservatively to avoid rounding issues, where the backend needs it
+        let whole_fill = N::one().sub(N::adj_whole_fill());
+        let done_dy = done_dy.mul(whole_fill);
+        let done_dy_cpmm = done_dy_cpmm.mul(whole_fill);
+
+        hmm_adj_y = done_dy.sub(done_dy_cpmm);
 
         if done_dy_cpmm.is_positive() {
             panic!("expect done_dY < 0 when X supplied to pool")
@@ -749,13 +786,13 @@ impl<'a> Pool<'a> {
         if hmm_adj_y.is_negative() {
             panic!("hmm adj should be positive (conservative for pool i.e giving out less)");
         }
-        if self.y.add(done_dy_cpmm).unwrap().is_negative() {
+        if self.y.add(done_dy_cpmm).is_negative() {
             panic!("cannot swap out more Y than present in pool");
         }
         // avoid numerical noise for very small trades relative to liquidity
         if !done_dx.is_zero() && !done_dy.is_zero() {
-            let avg_price = done_dy.neg().div(done_dx.add(fee_x).unwrap());
-            if avg_price.gt(start_rp.pow(2)).unwrap() {
+            let avg_price = done_dy.neg().div(done_dx.add(fee_x));
+            if avg_price.gt(start_rp.powi(2)) {
                 panic!("pool cannot buy X at a greater avg price than starting price");
             }
         }
@@ -763,7 +800,7 @@ impl<'a> Pool<'a> {
         return SwapWithinResult::new(done_dx, done_dy, end_t, end_rp, cross, hmm_adj_y, fee_x);
     }
 
-    pub fn execute_swap_from_x(&mut self, dx: Decimal, rp_oracle: Decimal) -> SwapOutput {
+    pub fn execute_swap_from_x(&mut self, dx: N, rp_oracle: N) -> SwapOutput<N> {
         //  Swap algo when pool receives dX>0
         //  We go from right to left on the price line (price goes down) and manage tick crossings as needed.
         //  within 2 consecutive active ticks, we delegate to swap_within_tick_from_X(..)
@@ -780,12 +817,12 @@ impl<'a> Pool<'a> {
         // main case: where liquidity_in_range > 0 --> call swap_within_tick_from_X()
         // otherwise try to get soome liquidity into range.
         // repeat till full order filled or pool liquidity dries up, whichever comes first
-        let mut swpd_dx = Self::zero();
-        let mut swpd_dy = Self::zero();
-        let (mut adjusted_dy, mut total_fee_x) = (Self::zero(), Self::zero());
+        let mut swpd_dx = N::zero();
+        let mut swpd_dy = N::zero();
+        let (mut adjusted_dy, mut total_fee_x) = (N::zero(), N::zero());
         let (avg_p, end_p);
 
-        while swpd_dx.lt(dx).unwrap() {
+        while swpd_dx.lt(dx) {
             let goal_tick: Option<u128>;
             let (done_dx, done_dy, end_rp, hmm_adj_y, fee_x);
             let (end_t, cross);
@@ -806,9 +843,9 @@ impl<'a> Pool<'a> {
                     println!("no more active ticks (liquidity) in this direction");
                     avg_p = match swpd_dx.is_zero() {
                         false => swpd_dy.neg().div(swpd_dx),
-                        true => Self::zero(),
+                        true => N::zero(),
                     };
-                    end_p = self.glbl_rp().pow(2);
+                    end_p = self.glbl_rp().powi(2);
                     println!(
                         "swpd_dX={:?} swpd_dY={:?} pool_X={:?} pool_Y={:?} avg_P={:?}, end_P={:?}",
                         swpd_dx, swpd_dy, self.x, self.y, avg_p, end_p
@@ -832,7 +869,7 @@ impl<'a> Pool<'a> {
                         curr_rp,
                         gtk,
                         self.glbl_liq(),
-                        dx.sub(swpd_dx).unwrap(), //dx - swpd_dx,
+                        dx.sub(swpd_dx), //dx - swpd_dx,
                         rp_oracle,
                     );
                     done_dx = rez.recv_amount();
@@ -842,36 +879,30 @@ impl<'a> Pool<'a> {
                     cross = rez.cross();
                     hmm_adj_y = rez.send_hmm_adj();
                     fee_x = rez.recv_fee();
-                    let new_y = self.y.add(done_dy).unwrap().sub(hmm_adj_y).unwrap(); //self.y + done_dy - hmm_adj_y   >=0
+                    let new_y = self.y.add(done_dy).sub(hmm_adj_y); //self.y + done_dy - hmm_adj_y   >=0
                     assert!(!new_y.is_negative(), "cannot swap out more Y than present");
-                    let check = dx
-                        .sub(swpd_dx)
-                        .unwrap()
-                        .sub(done_dx)
-                        .unwrap()
-                        .sub(fee_x)
-                        .unwrap();
+                    let check = dx.sub(swpd_dx).sub(done_dx).sub(fee_x);
                     // dx - swpd_dx >= done_dx + fee_x
                     assert!(!check.is_negative(), "cannot fill more X than provided");
 
                     // update local totals
                     // gross (including fees) for input token: swpd_dx += done_dx + fee_x
-                    swpd_dx = swpd_dx.add(done_dx).unwrap().add(fee_x).unwrap();
+                    swpd_dx = swpd_dx.add(done_dx).add(fee_x);
                     // net total for output token: swpd_dy += done_dy
-                    swpd_dy = swpd_dy.add(done_dy).unwrap();
-                    adjusted_dy = adjusted_dy.add(hmm_adj_y).unwrap();
-                    total_fee_x = total_fee_x.add(fee_x).unwrap();
+                    swpd_dy = swpd_dy.add(done_dy);
+                    adjusted_dy = adjusted_dy.add(hmm_adj_y);
+                    total_fee_x = total_fee_x.add(fee_x);
                     curr_t = end_t;
                     curr_rp = end_rp;
 
                     // update global state to reflect price change (if any) & reserves
                     self.set_glbl_tick(curr_t);
                     self.set_glbl_rp(curr_rp);
-                    self.x = self.x.add(done_dx).unwrap();
+                    self.x = self.x.add(done_dx);
                     // hmm_adj comes out of reserves and into fees 'vault' self.y += done_dy - hmm_adj_y
                     self.y = new_y;
-                    self.x_fee = self.x_fee.add(fee_x).unwrap();
-                    self.y_adj = self.y_adj.add(hmm_adj_y).unwrap();
+                    self.x_fee = self.x_fee.add(fee_x);
+                    self.y_adj = self.y_adj.add(hmm_adj_y);
 
                     // set fees
                     let liq_glbl = self.glbl_liq();
@@ -879,8 +910,8 @@ impl<'a> Pool<'a> {
                     if liq_glbl.is_positive() {
                         // make sure not 0 liquidity (empty trade)
                         // * update fee growth to reflect latest swap_within()
-                        self.set_fee_glbl('x', 'f', fee_x.div(liq_glbl).add(fg_x).unwrap()); // fg_x + fee_x / liq_g
-                        self.set_fee_glbl('y', 'h', hmm_adj_y.div(liq_glbl).add(hg_y).unwrap());
+                        self.set_fee_glbl('x', 'f', fee_x.div(liq_glbl).add(fg_x)); // fg_x + fee_x / liq_g
+                        self.set_fee_glbl('y', 'h', hmm_adj_y.div(liq_glbl).add(hg_y));
                         // hg_y + hmm_adj_y / liq_glbl);
                     }
 
@@ -897,9 +928,9 @@ impl<'a> Pool<'a> {
 
         avg_p = match swpd_dx.is_zero() {
             false => swpd_dy.neg().div(swpd_dx),
-            true => Self::zero(),
+            true => N::zero(),
         };
-        end_p = self.glbl_rp().pow(2);
+        end_p = self.glbl_rp().powi(2);
         println!(
             "swpd_dX={:?} swpd_dY={:?} pool_X={:?} pool_Y={:?} avg_P={:?}, end_P={:?}",
             swpd_dx, swpd_dy, self.x, self.y, avg_p, end_p
@@ -913,12 +944,12 @@ impl<'a> Pool<'a> {
 
     fn swap_within_tick_from_y(
         &mut self,
-        start_rp: Decimal,
+        start_rp: N,
         goal_tick: u128,
-        liq: Decimal,
-        dy: Decimal,
-        rp_oracle: Decimal,
-    ) -> SwapWithinResult {
+        liq: N,
+        dy: N,
+        rp_oracle: N,
+    ) -> SwapWithinResult<N> {
         // portion of swap execution occurring within 2 consecutive active ticks
         // + no writing to state to occurs here, just calc and return to caller
         let (done_dy, end_t, end_rp, cross, hmm_adj_x, fee_y);
@@ -929,25 +960,25 @@ impl<'a> Pool<'a> {
         }
 
         // root-price at goal tick - here on the right
-        let rp_goal = Pool::tick_to_rp(goal_tick);
-        if rp_goal.lt(start_rp).unwrap() {
+        let rp_goal = Self::tick_to_rp(goal_tick);
+        if rp_goal.lt(start_rp) {
             panic!("expect price to go up when Y supplied to pool");
             // we allow case when price exactly on the current tick ( i.e. rP_goal = start_rP)
             // this will lead to 0-qty swapped, and tici-crossing before next swap
         }
 
         // put aside max potential swap fees before affecting prices
-        let fee_factor = Self::one().sub(self.fee_rate).unwrap();
+        let fee_factor = N::one().sub(self.fee_rate);
         let dy_max = dy.mul(fee_factor);
 
         // chg of reserve Y possible if we go all the way to goal tick
-        let doable_dy = Pool::dy_from_l_drp(liq, start_rp, rp_goal);
+        let doable_dy = Self::dy_from_l_drp(liq, start_rp, rp_goal);
         if doable_dy.is_negative() {
             // expect a positive number
             panic!("doable_dY >= 0 when Y supplied to pool");
         }
 
-        if doable_dy.lt(dy_max).unwrap() {
+        if doable_dy.lt(dy_max) {
             // we'll have leftover to swap. do what we can. done_Y = doableY
             done_dy = doable_dy;
             // reverse engineer how much fees charged based on how much done_dY
@@ -958,52 +989,57 @@ impl<'a> Pool<'a> {
         } else {
             // we have enough to fill full quantity, make all of dY_max 'done', then calc end_rP
             done_dy = dy_max;
-            fee_y = dy.sub(dy_max).unwrap(); // fee as expected
+            fee_y = dy.sub(dy_max); // fee as expected
             cross = false;
-            end_rp = Pool::rp_new_from_l_dy(liq, start_rp, done_dy);
+            end_rp = Self::rp_new_from_l_dy(liq, start_rp, done_dy);
             // start seeking the goal from tick under current tick, that can be initialized
             let seek_start = self.tick_to_possible_tick(self.glbl_tick() - 1, false);
-            end_t = Pool::rp_to_tick_loop(end_rp, false, seek_start);
+            end_t = Self::rp_to_tick_loop(end_rp, false, seek_start);
             // * this log is take only once per trade if price ends in-between ticks
             // * tick is always on the left (round down after log)
 
-            if end_rp.lt(start_rp).unwrap() {
+            if end_rp.lt(start_rp) {
                 panic!("expect end_rP > start_rP when pool given Y");
             }
-            if end_rp.gt(rp_goal).unwrap() {
+            if end_rp.gt(rp_goal) {
                 panic!("dont expect end_rP go beyond rP_goal (right) when able to wholy fill dY");
             }
         }
         // now figure out how much done_dX and hmm_adj_X
-        let done_dx_cpmm = Pool::dx_from_l_drp(liq, start_rp, end_rp);
+        let done_dx_cpmm = Self::dx_from_l_drp(liq, start_rp, end_rp);
 
-        if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.lte(start_rp).unwrap() {
+        if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.lte(start_rp) {
             // also also rP_oracle is None (zero)
             // in cases where no oracle or no hmm as c=0, we cannot adjust so we fall back to amm
             // * when trade will make pool price diverge more from oracle,
             // * then we don't adjust (hmm adjust on convergence only)
             done_dx = done_dx_cpmm;
-        } else if rp_oracle.gt(start_rp).unwrap() && rp_oracle.lte(end_rp).unwrap() {
+        } else if rp_oracle.gt(start_rp) && rp_oracle.lte(end_rp) {
             // 1st part of this condition is redundant as it is implied from precious branch.
             // it is added for precision and readability
             // * when oracle is in between start_rP and end_rP prices, use hmm
             // * till we reach oracle price then use unadjusted amm till end_rP
-            let done_dx_1 = Pool::dx_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle);
-            let done_dx_2 = Pool::dx_from_l_drp(liq, rp_oracle, end_rp);
+            let done_dx_1 = Self::dx_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle);
+            let done_dx_2 = Self::dx_from_l_drp(liq, rp_oracle, end_rp);
             if done_dx_1.is_positive() || done_dx_2.is_positive() {
                 panic!("expect (each component of) done_dX < 0 when Y supplied to pool")
             }
-            done_dx = done_dx_1.add(done_dx_2).unwrap();
-        } else if rp_oracle.gt(end_rp).unwrap() {
+            done_dx = done_dx_1.add(done_dx_2);
+        } else if rp_oracle.gt(end_rp) {
             // * when trade will make pool price converge to oracle price
             // * and end_rP won't reach the oracle price then use hmm all the way
-            done_dx = Pool::dx_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle);
+            done_dx = Self::dx_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle);
         } else {
             //we don't expect to hit this branch raise error if we do hit
             panic!("HMM adjstment: possibilities should have been exhausted by now");
         }
 
-        hmm_adj_x = done_dx.sub(done_dx_cpmm).unwrap();
+        // adjust conservatively to avoid rounding issues, where the backend needs it
+        let whole_fill = N::one().sub(N::adj_whole_fill());
+        let done_dx = done_dx.mul(whole_fill);
+        let done_dx_cpmm = done_dx_cpmm.mul(whole_fill);
+
+        hmm_adj_x = done_dx.sub(done_dx_cpmm);
 
         if done_dx_cpmm.is_positive() {
             panic!("expect done_dX < 0 when Y supplied to pool");
@@ -1013,13 +1049,13 @@ impl<'a> Pool<'a> {
         if hmm_adj_x.is_negative() {
             panic!("hmm adj should be positive (conservative for pool ie giviing out less)");
         }
-        if self.x.add(done_dx_cpmm).unwrap().is_negative() {
+        if self.x.add(done_dx_cpmm).is_negative() {
             panic!("cannot swap out more X than present in pool");
         }
         // avoid numerical noise due for very small trades relative to liquidity
         if !done_dx.is_zero() && done_dy.is_zero() {
-            let avg_price = done_dy.add(fee_y).unwrap().div(done_dx);
-            if avg_price.lt(start_rp.pow(2)).unwrap() {
+            let avg_price = done_dy.add(fee_y).div(done_dx);
+            if avg_price.lt(start_rp.powi(2)) {
                 panic!("pool cannot sell X at a lower avg price than starting price");
             }
         }
@@ -1027,7 +1063,7 @@ impl<'a> Pool<'a> {
         return SwapWithinResult::new(done_dy, done_dx, end_t, end_rp, cross, hmm_adj_x, fee_y);
     }
 
-    pub fn execute_swap_from_y(&mut self, dy: Decimal, rp_oracle: Decimal) -> SwapOutput {
+    pub fn execute_swap_from_y(&mut self, dy: N, rp_oracle: N) -> SwapOutput<N> {
         // Swap algo when pool provided with dY > 0
         // We go from left to right on the price line (price goes up) and manage tick-crossings as needed.
         // within 2 consecutive active ticks, we delegate to swap_within_tick_from_Y(..)
@@ -1043,12 +1079,12 @@ impl<'a> Pool<'a> {
         // main case: where liquidity_in_range > 0 --> call swap_within_tick_from_Y()
         // otherwise try to get some liquidity into range.
         // repeat till full order filled or pool liquidity dries up, comes whichever first
-        let mut swpd_dx = Self::zero();
-        let mut swpd_dy = Self::zero();
-        let (mut adjusted_dx, mut total_fee_y) = (Self::zero(), Self::zero());
+        let mut swpd_dx = N::zero();
+        let mut swpd_dy = N::zero();
+        let (mut adjusted_dx, mut total_fee_y) = (N::zero(), N::zero());
         let (avg_p, end_p);
 
-        while swpd_dy.lt(dy).unwrap() {
+        while swpd_dy.lt(dy) {
             let goal_tick: Option<u128>;
             let (done_dx, done_dy, end_rp, hmm_adj_x, fee_y);
             let (end_t, cross);
@@ -1069,9 +1105,9 @@ impl<'a> Pool<'a> {
                     println!("no more active ticks (liquidity) in this direction");
                     avg_p = match swpd_dx.is_zero() {
                         false => swpd_dy.div(swpd_dx.neg()),
-                        true => Self::zero(),
+                        true => N::zero(),
                     };
-                    end_p = self.glbl_rp().pow(2);
+                    end_p = self.glbl_rp().powi(2);
                     println!(
                         "swpd_dX={:?} swpd_dY={:?} pool_X={:?} pool_Y={:?} avg_P={:?}, end_P={:?}",
                         swpd_dx, swpd_dy, self.x, self.y, avg_p, end_p
@@ -1095,7 +1131,7 @@ impl<'a> Pool<'a> {
                         curr_rp,
                         gtk,
                         self.glbl_liq(),
-                        dy.sub(swpd_dy).unwrap(), // dy - swpd_dy
+                        dy.sub(swpd_dy), // dy - swpd_dy
                         rp_oracle,
                     );
                     done_dx = rez.send_amount();
@@ -1105,25 +1141,19 @@ impl<'a> Pool<'a> {
                     cross = rez.cross();
                     hmm_adj_x = rez.send_hmm_adj();
                     fee_y = rez.recv_fee();
-                    let new_x = self.x.add(done_dx).unwrap().sub(hmm_adj_x).unwrap(); // self.x + done_dx - hmm_adj_x   >= 0.0
+                    let new_x = self.x.add(done_dx).sub(hmm_adj_x); // self.x + done_dx - hmm_adj_x   >= 0.0
                     assert!(!new_x.is_negative(), "cannot swap out more Y than present");
-                    let check = dy
-                        .sub(swpd_dy)
-                        .unwrap()
-                        .sub(done_dy)
-                        .unwrap()
-                        .sub(fee_y)
-                        .unwrap();
+                    let check = dy.sub(swpd_dy).sub(done_dy).sub(fee_y);
                     // dy - swpd_dy >= done_dy + fee_y
                     assert!(!check.is_negative(), "cannot fill more Y than provided");
 
                     // update local totals
                     // net total for output token: swpd_dx += done_dx
-                    swpd_dx = swpd_dx.add(done_dx).unwrap();
+                    swpd_dx = swpd_dx.add(done_dx);
                     // gross (including fees) for input token: swpd_dy += done_dy + fee_y
-                    swpd_dy = swpd_dy.add(done_dy).unwrap().add(fee_y).unwrap();
-                    adjusted_dx = adjusted_dx.add(hmm_adj_x).unwrap();
-                    total_fee_y = total_fee_y.add(fee_y).unwrap();
+                    swpd_dy = swpd_dy.add(done_dy).add(fee_y);
+                    adjusted_dx = adjusted_dx.add(hmm_adj_x);
+                    total_fee_y = total_fee_y.add(fee_y);
                     curr_t = end_t;
                     curr_rp = end_rp;
 
@@ -1132,18 +1162,18 @@ impl<'a> Pool<'a> {
                     self.set_glbl_rp(curr_rp);
                     self.x = new_x;
                     // hmm_adj comes out of reserves into fees 'vault'
-                    self.y = self.y.add(done_dy).unwrap();
-                    self.x_adj = self.x_adj.add(hmm_adj_x).unwrap();
-                    self.y_fee = self.y_fee.add(fee_y).unwrap();
+                    self.y = self.y.add(done_dy);
+                    self.x_adj = self.x_adj.add(hmm_adj_x);
+                    self.y_fee = self.y_fee.add(fee_y);
 
                     let liq_glbl = self.glbl_liq();
                     let (_, fg_y, hg_x, _) = self.glbl_fees();
                     if liq_glbl.is_positive() {
                         // make sure not 0 liquidity (empty trade)
                         // * update fee growth to reflect latest swap_within
-                        self.set_fee_glbl('x', 'h', hmm_adj_x.div(liq_glbl).add(hg_x).unwrap()); // hg_x + hmm_adj_x / liq_glbl
+                        self.set_fee_glbl('x', 'h', hmm_adj_x.div(liq_glbl).add(hg_x)); // hg_x + hmm_adj_x / liq_glbl
 
-                        self.set_fee_glbl('y', 'f', fee_y.div(liq_glbl).add(fg_y).unwrap());
+                        self.set_fee_glbl('y', 'f', fee_y.div(liq_glbl).add(fg_y));
                         // fg_y + fee_y / liq_glbl
                     }
 
@@ -1160,9 +1190,9 @@ impl<'a> Pool<'a> {
 
         avg_p = match swpd_dx.is_zero() {
             false => swpd_dy.div(swpd_dx.neg()),
-            true => Self::zero(),
+            true => N::zero(),
         };
-        end_p = self.glbl_rp().pow(2);
+        end_p = self.glbl_rp().powi(2);
         println!(
             "swpd_dX={:?} swpd_dY={:?} pool_X={:?} pool_Y={:?} avg_P={:?}, end_P={:?}",
             swpd_dx, swpd_dy, self.x, self.y, avg_p, end_p
@@ -0,0 +1,138 @@
+use hydra_math_rs::decimal::{Decimal, Ln, Pow, Sqrt};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// Numeric backend of the concentrated liquidity [Pool](super::Pool).
+/// Operations are infallible: a backend panics when it cannot compute a result,
+/// just like the pool does on broken invariants.
+pub trait PoolNumber: Copy + Debug + PartialEq {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_u128(n: u128) -> Self;
+    /// value * 10^-scale, e.g. from_scaled(10001, 4) is 1.0001
+    fn from_scaled(value: u128, scale: u8) -> Self;
+    /// lossy conversion, used to compare backends with each other
+    fn to_f64(self) -> f64;
+
+    fn add(self, rhs: Self) -> Self;
+    fn sub(self, rhs: Self) -> Self;
+    fn mul(self, rhs: Self) -> Self;
+    fn div(self, rhs: Self) -> Self;
+    fn neg(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn ln(self) -> Self;
+    /// fractional power, backends may only support exponents in 0.25 steps
+    fn pow(self, exp: Self) -> Self;
+    fn powi(self, exp: u128) -> Self;
+
+    fn compare(self, other: Self) -> Ordering;
+
+    /// conservative rounding of deposited liquidity, for backends prone to rounding errors
+    fn floor_liq(self) -> Self {
+        self
+    }
+    /// fraction shaved off swapped amounts, same purpose as [PoolNumber::floor_liq]
+    fn adj_whole_fill() -> Self {
+        Self::zero()
+    }
+
+    fn lt(self, other: Self) -> bool {
+        self.compare(other) == Ordering::Less
+    }
+    fn gt(self, other: Self) -> bool {
+        self.compare(other) == Ordering::Greater
+    }
+    fn lte(self, other: Self) -> bool {
+        self.compare(other) != Ordering::Greater
+    }
+    fn gte(self, other: Self) -> bool {
+        self.compare(other) != Ordering::Less
+    }
+    fn min(self, other: Self) -> Self {
+        match self.gt(other) {
+            true => other,
+            false => self,
+        }
+    }
+    fn max(self, other: Self) -> Self {
+        match self.lt(other) {
+            true => other,
+            false => self,
+        }
+    }
+    fn is_zero(self) -> bool {
+        self.compare(Self::zero()) == Ordering::Equal
+    }
+    fn is_negative(self) -> bool {
+        self.compare(Self::zero()) == Ordering::Less
+    }
+    fn is_positive(self) -> bool {
+        self.compare(Self::zero()) == Ordering::Greater
+    }
+}
+
+/// hydra-math-rs [Decimal] at COMPUTE_SCALE, the backend ported on-chain
+impl PoolNumber for Decimal {
+    fn zero() -> Self {
+        Decimal::zero()
+    }
+    fn one() -> Self {
+        Decimal::one()
+    }
+    fn from_u128(n: u128) -> Self {
+        Decimal::from_u128(n).to_compute_scale().unwrap()
+    }
+    fn from_scaled(value: u128, scale: u8) -> Self {
+        Decimal::new(value, scale, false)
+            .to_compute_scale()
+            .unwrap()
+    }
+    fn to_f64(self) -> f64 {
+        let abs = f64::from(self);
+        match self.negative {
+            true => -abs,
+            false => abs,
+        }
+    }
+
+    fn add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+    fn sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+    fn mul(self, rhs: Self) -> Self {
+        self * rhs
+    }
+    fn div(self, rhs: Self) -> Self {
+        self / rhs
+    }
+    fn neg(self) -> Self {
+        -self
+    }
+    fn sqrt(self) -> Self {
+        Sqrt::sqrt(self).unwrap()
+    }
+    fn ln(self) -> Self {
+        Ln::ln(self).unwrap()
+    }
+    fn pow(self, exp: Self) -> Self {
+        Pow::<Decimal>::pow(self, exp).unwrap()
+    }
+    fn powi(self, exp: u128) -> Self {
+        Pow::<u128>::pow(self, exp).unwrap()
+    }
+
+    fn compare(self, other: Self) -> Ordering {
+        self.cmp(&other)
+    }
+    fn is_zero(self) -> bool {
+        Decimal::is_zero(self)
+    }
+    fn is_negative(self) -> bool {
+        Decimal::is_negative(self)
+    }
+    fn is_positive(self) -> bool {
+        Decimal::is_positive(self)
+    }
+}
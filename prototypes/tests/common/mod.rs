@@ -0,0 +1,102 @@
+//* helpers shared by the suites running against every numeric backend of the cl_pool prototype
+#![allow(dead_code)]
+
+use hydra_math_rs::decimal::Decimal;
+use prototypes::cl_pool::PoolNumber;
+use prototypes::cl_pool_pn::SignedPN;
+
+pub trait Backend: PoolNumber {
+    /// relative difference tolerated from the reference values, which are computed with Decimal
+    const TOLERANCE: f64;
+}
+
+impl Backend for Decimal {
+    const TOLERANCE: f64 = 0.0;
+}
+
+impl Backend for SignedPN {
+    const TOLERANCE: f64 = 1e-5;
+}
+
+impl Backend for f64 {
+    // liquidity is floored to a whole unit on deposit
+    const TOLERANCE: f64 = 5e-3;
+}
+
+pub fn num<N: PoolNumber>(n: u128) -> N {
+    N::from_u128(n)
+}
+
+pub fn rp_of<N: PoolNumber>(price: u128) -> N {
+    N::from_u128(price).sqrt()
+}
+
+/// Compare a backend's result with the reference value, the difference is printed
+/// so that backends can be diffed with `cargo test -- --nocapture`
+pub fn assert_close<N: Backend>(actual: N, expected: f64) {
+    let actual = actual.to_f64();
+    let diff = (actual - expected).abs() / expected.abs().max(1.0);
+    println!(
+        "{}: {} vs reference {} (diff {:e})",
+        std::any::type_name::<N>(),
+        actual,
+        expected,
+        diff
+    );
+    assert!(
+        diff <= N::TOLERANCE,
+        "{}: {} differs from reference {}",
+        std::any::type_name::<N>(),
+        actual,
+        expected
+    );
+}
+
+/// Check `lhs <= rhs`, up to the backend's tolerance
+pub fn assert_lte<N: Backend>(lhs: N, rhs: N) {
+    if lhs.lte(rhs) {
+        return;
+    }
+    let (lhs, rhs) = (lhs.to_f64(), rhs.to_f64());
+    let diff = (lhs - rhs) / rhs.abs().max(1.0);
+    assert!(
+        diff <= N::TOLERANCE,
+        "{}: {} is above {}",
+        std::any::type_name::<N>(),
+        lhs,
+        rhs
+    );
+}
+
+/// Instantiate the generic tests of a suite for every backend
+#[macro_export]
+macro_rules! backend_tests {
+    ($($test:ident),* $(,)?) => {
+        mod decimal {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<hydra_math_rs::decimal::Decimal>();
+                }
+            )*
+        }
+
+        mod pn {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<prototypes::cl_pool_pn::SignedPN>();
+                }
+            )*
+        }
+
+        mod float {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<f64>();
+                }
+            )*
+        }
+    };
+}
@@ -0,0 +1,369 @@
+//* these test correspond to all examples in pool.iypnb, (deposits and swaps)
+//* of the python prototype. They run against every numeric backend of the pool,
+//* expected values are computed with Decimal
+
+#[macro_use]
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use crate::common::*;
+    use prototypes::cl_pool::*;
+
+    fn problem1<N: Backend>() {
+        // A user has x = 2 ETH and wants to set up a liquidity position in an ETH/USDC pool.
+        // The current price of ETH is P =2000 USDC and target price range is from pa = 1500 to
+        // pb = 2500 USDC. How much USDC (y) do they need?
+        let (rpa, rp, rpb) = (rp_of::<N>(1500), rp_of::<N>(2000), rp_of::<N>(2500));
+        let lx = Pool::liq_x_only(num(2), rp, rpb);
+        assert_close(lx, 847.213595499815); // float 847.2135954999583
+
+        let y = Pool::y_from_l_rp_rng(lx, rp, rpa, rpb);
+        assert_close(y, 5076.102359478491); // float 5076.102359479882
+    }
+
+    fn problem2<N: Backend>() {
+        // A user has x=2 ETH and y=4000 USDC, and wants to use pb =3000USDC perETH as the top of
+        // the price range. What is the bottom of the range (pa) that ensures the opened position
+        // uses the full amount of their funds?
+        let (x, y) = (num::<N>(2), num::<N>(4000));
+        let rpb = rp_of::<N>(3000);
+        let rp = y.div(x).sqrt();
+
+        // method 1
+        let rpa = Pool::rpa_from_x_y_rp_rpb(x, y, rp, rpb);
+        assert_close(rpa, 36.51483716701); // float 36.51483716701107
+
+        // method 2
+        let lx = Pool::liq_x_only(x, rp, rpb);
+        let rpa_bis = Pool::rpa_from_l_rp_y(lx, rp, y);
+        assert_close(rpa_bis, 36.51483716701);
+    }
+
+    fn problem3<N: Backend>() {
+        // Using the liquidity position created in Problem 2, what are asset balances when the
+        // price changes to P = 2500 USDC per ETH?
+        let (x, y) = (num::<N>(2), num::<N>(4000));
+        let rp_orig = y.div(x).sqrt();
+        let rp_new = rp_of::<N>(2500);
+        let rpa = num::<N>(4000).div(num(3)).sqrt(); // 4000/3 = 1333.3333...
+        let rpb = rp_of::<N>(3000);
+
+        let l = Pool::liq_from_x_y_rp_rng(x, y, rp_orig, rpa, rpb);
+        // method 1:
+        let x_new = Pool::x_from_l_rp_rng(l, rp_new, rpa, rpb);
+        let y_new = Pool::y_from_l_rp_rng(l, rp_new, rpa, rpb);
+        assert_close(x_new, 0.849364120474); // python 0.8493641204744687
+        assert_close(y_new, 6572.900043969071); // python 6572.9000439693455
+
+        // method 2:
+        let dx = Pool::dx_from_l_drp(l, rp_orig, rp_new);
+        let dy = Pool::dy_from_l_drp(l, rp_orig, rp_new);
+        assert_close(x.add(dx), 0.849364120475); // python 0.8493641204744682
+        assert_close(y.add(dy), 6572.900043969612); // python 6572.900043969347
+    }
+
+    fn tick_is_under_rp<N: Backend>() {
+        let zero = N::zero();
+        let rp = rp_of::<N>(4000);
+        let pool = Pool::new("ETH", 18, "USDC", 6, rp, 30, zero, zero);
+
+        assert!(Pool::<N>::tick_to_rp(pool.glbl_tick()).lte(pool.glbl_rp()));
+        assert!(pool.glbl_rp().lte(rp));
+        assert_eq!(Pool::rp_to_tick_loop(rp, false, 0), 82944);
+        assert_eq!(Pool::rp_to_tick_loop(rp, true, 0), 82945);
+
+        assert_eq!(pool.glbl_tick(), 82920);
+        assert_eq!(Pool::rp_to_possible_tk(rp, 30, false, 0), 82920);
+        assert_eq!(Pool::rp_to_possible_tk(rp, 30, true, 0), 82950);
+    }
+
+    fn test_deposit<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+
+        pool.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+        let (x, x_adj, x_fee) = pool.x_info();
+        let (y, y_adj, y_fee) = pool.y_info();
+
+        assert_close(x, 1.999275813881); // float 1.999275544022923, PN 1_999275547735
+        assert_close(y, 3999.999999999996); // float 4000_f64, PN 3999_999999999998
+
+        assert_eq!(x_adj, zero);
+        assert_eq!(x_fee, zero);
+        assert_eq!(y_adj, zero);
+        assert_eq!(y_fee, zero);
+    }
+
+    fn test_single_x_swap_no_fees<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+
+        pool.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+        let (x_before, y_before) = (pool.x_info().0, pool.y_info().0);
+
+        let rez = pool.execute_swap_from_x(num(3), zero);
+        let (x, x_adj, x_fee) = pool.x_info();
+        let (y, y_adj, y_fee) = pool.y_info();
+
+        assert_close(rez.recv_amount(), 2.449955017843); // float 2.4499546960008143_f64, PN 2_449954700443
+        assert_close(rez.send_amount(), -3999.999999999996); // float  -4000_f64, PN 3999_999999999998
+        assert_eq!(x, x_before.add(rez.recv_amount()));
+        assert_eq!(y, y_before.add(rez.send_amount()));
+        assert_close(y, 0.0); // entire amount of y in pool swapped
+
+        assert_eq!(x_adj, zero);
+        assert_eq!(x_fee, zero);
+        assert_eq!(y_adj, zero);
+        assert_eq!(y_fee, zero);
+    }
+
+    fn test_breaking_trades_x<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+        pool.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+
+        pool.execute_swap_from_x(num(3), zero);
+        let (x, _, _) = pool.x_info();
+        let (y, _, _) = pool.y_info();
+
+        let mut pool_bis = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+        pool_bis.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+
+        pool_bis.execute_swap_from_x(num(1), zero);
+        pool_bis.execute_swap_from_x(num(1), zero);
+        pool_bis.execute_swap_from_x(num(1), zero);
+        let (x_bis, _, _) = pool_bis.x_info();
+        let (y_bis, _, _) = pool_bis.y_info();
+
+        // breaking up trade should be slighlty more advantageous for pool, due to rounding, if any
+        assert!(x_bis.gte(x));
+        assert!(y_bis.gte(y));
+
+        // out of liquidity, globally, as moved out of range while trying go get in new range
+        assert!(pool.glbl_liq().is_zero());
+        assert!(pool_bis.glbl_liq().is_zero());
+
+        let extra = pool.execute_swap_from_x(num(1), zero);
+        let extra_bis = pool_bis.execute_swap_from_x(num(1), zero);
+
+        // so swapped amount shoud be zero
+        assert!(extra.recv_amount().is_zero());
+        assert!(extra.send_amount().is_zero());
+        assert!(extra_bis.recv_amount().is_zero());
+        assert!(extra_bis.send_amount().is_zero());
+    }
+
+    fn test_pool_starts_w_no_liq_in_range_x<N: Backend>() {
+        let zero = N::zero();
+        let (rp, rpa, rpb) = (rp_of::<N>(150), rp_of::<N>(100), rp_of::<N>(140));
+        let mut pool = Pool::new("SOL", 12, "USDC", 6, rp, 1, zero, zero);
+
+        pool.deposit("dude", num(4), num(600), rpa, rpb);
+
+        // no x should be have been deposited in pool, given deposit range is below pool price.
+        assert!(pool.x_info().0.is_zero());
+        assert!(pool.y_info().0.is_positive());
+        assert!(pool.glbl_liq().is_zero()); // no liquidity in range
+
+        let rez = pool.execute_swap_from_x(num(2), zero);
+
+        // trades executed after getting in range
+        // swapped_x  > 0.0
+        assert!(rez.recv_amount().is_positive());
+        // swapped_y  < 0.0
+        assert!(rez.send_amount().is_negative());
+        // we should have liquidity in range after trade
+        assert!(pool.glbl_liq().is_positive());
+        // now we should have some x in the pool
+        assert!(pool.x_info().0.is_positive());
+        // price has moved down from start (deposit time)
+        assert!(pool.glbl_rp().lt(rp));
+        // price still above lower bound rpa as trade didnt drain all liquidity / y reserves
+        assert!(pool.glbl_rp().gt(rpa));
+
+        assert_eq!(rez.recv_amount(), num(2));
+
+        assert_close(pool.glbl_liq(), 327.576681858244); // PN 327_576662414490
+    }
+
+    fn test_liquidity_gap_on_the_way_down<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("SOL", 12, "USDC", 6, rp_of(200), 1, zero, zero);
+
+        // * [175,250] is deposit range for 'dude'
+        pool.deposit("dude", num(4), num(600), rp_of(175), rp_of(250));
+        // * [120,150] is deposit range for 'mate'
+        pool.deposit("mate", zero, num(400), rp_of(120), rp_of(150));
+
+        //* there is a gap of liquidity between 150 and 175
+
+        let rez = pool.execute_swap_from_x(num(7), zero);
+        // executed more X than 'available' in first range (i.e. 4 SOL)
+        assert!(rez.recv_amount().gt(num(4)));
+        // but did NOT fill the whole size of swap (i.e. 7 SOL)
+        assert!(rez.recv_amount().lt(num(7)));
+        // end_price in has at least entered lower range (120-150 ) in order to execute
+        assert!(rez.end_price().lt(num(120)));
+    }
+
+    fn test_single_y_swap_no_fees<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+
+        pool.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+        let (x_before, y_before) = (pool.x_info().0, pool.y_info().0);
+
+        let rez = pool.execute_swap_from_y(num(6000), zero);
+        let (x, x_adj, x_fee) = pool.x_info();
+        let (y, y_adj, y_fee) = pool.y_info();
+
+        assert_close(rez.send_amount(), -1.999275813881); // PN 1_999275547735
+        assert_close(rez.recv_amount(), 4896.836737493549); // PN 4896_836754800713
+        assert_eq!(x, x_before.add(rez.send_amount()));
+        assert_eq!(y, y_before.add(rez.recv_amount()));
+        assert_close(x, 0.0); // entire amount of X in pool swapped out
+
+        assert!(x_adj.is_zero());
+        assert!(x_fee.is_zero());
+        assert!(y_adj.is_zero());
+        assert!(y_fee.is_zero());
+    }
+
+    fn test_breaking_trades_y<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+        pool.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+
+        pool.execute_swap_from_y(num(6000), zero);
+        let (x, _, _) = pool.x_info();
+        let (y, _, _) = pool.y_info();
+
+        let mut pool_bis = Pool::new("ETH", 18, "USDC", 6, rp_of(2000), 1, zero, zero);
+        pool_bis.deposit("abc", num(2), num(4000), rp_of(1333), rp_of(3000));
+
+        pool_bis.execute_swap_from_y(num(2000), zero);
+        pool_bis.execute_swap_from_y(num(2000), zero);
+        pool_bis.execute_swap_from_y(num(2000), zero);
+        let (x_bis, _, _) = pool_bis.x_info();
+        let (y_bis, _, _) = pool_bis.y_info();
+
+        // breaking up trade should be slighlty more advantageous for pool, due to rounding, if any
+        assert!(x_bis.gte(x));
+        assert!(y_bis.gte(y));
+
+        // out of liquidity, globally, as moved out of range while trying go get in new range
+        assert!(pool.glbl_liq().is_zero());
+        assert!(pool_bis.glbl_liq().is_zero());
+
+        let extra = pool.execute_swap_from_y(num(500), zero);
+        let extra_bis = pool_bis.execute_swap_from_y(num(500), zero);
+
+        // so swapped amount shoud be zero
+        assert!(extra.recv_amount().is_zero());
+        assert!(extra.send_amount().is_zero());
+        assert!(extra_bis.recv_amount().is_zero());
+        assert!(extra_bis.send_amount().is_zero());
+    }
+
+    fn test_pool_starts_w_no_liq_in_range_y<N: Backend>() {
+        let zero = N::zero();
+        let (rp, rpa, rpb) = (rp_of::<N>(150), rp_of::<N>(180), rp_of::<N>(230));
+        let mut pool = Pool::new("SOL", 12, "USDC", 6, rp, 1, zero, zero);
+
+        pool.deposit("dude", num(4), num(600), rpa, rpb);
+
+        // no y should be have been deposited in pool, given range.
+        assert!(pool.x_info().0.is_positive());
+        assert!(pool.y_info().0.is_zero());
+        assert!(pool.glbl_liq().is_zero()); // no liquidity in range
+
+        let rez = pool.execute_swap_from_y(num(200), zero);
+
+        // trades executed after getting in range
+        // swapped_x  < 0.0
+        assert!(rez.send_amount().is_negative());
+        // swapped_y  > 0.0
+        assert!(rez.recv_amount().is_positive());
+        // we should have liquidity in range after trade
+        assert!(pool.glbl_liq().is_positive());
+        // now we should have some y in the pool
+        assert!(pool.y_info().0.is_positive());
+        // price has moved up from start (deposit time)
+        assert!(pool.glbl_rp().gt(rp));
+        // price still below upper bound rpb as trade didnt drain all liquidity / x reserves
+        assert!(pool.glbl_rp().lt(rpb));
+
+        // the whole demanded qty for y should be filled
+        assert_eq!(rez.recv_amount(), num(200));
+
+        assert_close(pool.glbl_liq(), 465.306804972182); // PN 465_306818467475
+    }
+
+    fn test_liquidity_gap_on_the_way_up<N: Backend>() {
+        let zero = N::zero();
+        let mut pool = Pool::new("SOL", 12, "USDC", 6, rp_of(175), 1, zero, zero);
+
+        // * [150,190] is deposit range for 'dude'
+        pool.deposit("dude", num(2), num(600), rp_of(150), rp_of(190));
+        // * [210,250] is deposit range for 'mate'
+        pool.deposit("mate", num(3), zero, rp_of(210), rp_of(250));
+
+        //*  gap of liquidity between 190 and 210
+
+        let rez = pool.execute_swap_from_y(num(1500), zero);
+
+        // executed more Y than 'available' in first range (600 USD)
+        assert!(rez.recv_amount().gt(num(600)));
+        // but did NOT fill the whole size of swap (1500)
+        assert!(rez.recv_amount().lt(num(1500)));
+        // end_price did at least enter upper range (210-250) to execute
+        assert!(pool.glbl_rp().powi(2).gt(num(210)));
+    }
+
+    fn test_withdraw<N: Backend>() {
+        let zero = N::zero();
+        let (rpa, rpb) = (rp_of::<N>(174), rp_of::<N>(250));
+        let mut pool = Pool::new("SOL", 12, "USDC", 6, rp_of(200), 1, zero, zero);
+
+        pool.deposit("dude", num(4), num(600), rpa, rpb);
+        assert_eq!(pool.position_count(), 1);
+        assert_eq!(pool.tick_count(), 2);
+
+        assert!(pool.x_info().0.is_positive());
+        assert!(pool.y_info().0.is_positive());
+
+        let deposited = pool.glbl_liq();
+        assert_close(deposited, 535.700094901963);
+
+        pool.execute_swap_from_x(num(20), zero);
+        // conservative adjustements at withdrawal
+        pool.withdraw("dude", deposited, rpa, rpb);
+
+        assert_eq!(pool.position_count(), 0); // no more positions
+        assert_eq!(pool.tick_count(), 0); // no initialized ticks
+        assert!(pool.glbl_liq().is_zero()); // liq zero
+
+        // leftovers from rounding and at withdrawal (for x),
+        assert!(pool.x_info().0.is_positive());
+        assert_close(pool.y_info().0, 0.0);
+    }
+
+    backend_tests!(
+        problem1,
+        problem2,
+        problem3,
+        tick_is_under_rp,
+        test_deposit,
+        test_single_x_swap_no_fees,
+        test_breaking_trades_x,
+        test_pool_starts_w_no_liq_in_range_x,
+        test_liquidity_gap_on_the_way_down,
+        test_single_y_swap_no_fees,
+        test_breaking_trades_y,
+        test_pool_starts_w_no_liq_in_range_y,
+        test_liquidity_gap_on_the_way_up,
+        test_withdraw,
+    );
+}
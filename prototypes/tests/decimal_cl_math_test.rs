@@ -2,11 +2,11 @@
 mod tests {
 
     use hydra_math_rs::decimal::*;
-    use prototypes::cl_pool::*;
+    use prototypes::cl_pool::{Pool, PoolMath};
 
     #[test]
     fn test_tick_to_tp_to_tick() {
-        let price = Decimal::from_u64(4000).to_compute_scale();
+        let price = Decimal::from_u64_to_compute_scale(4000);
         let rp = price.sqrt().unwrap();
         let td = Pool::rp_to_tick_loop(rp, false, 0u128);
         let tu = Pool::rp_to_tick_loop(rp, true, 0u128);
@@ -17,7 +17,7 @@ mod tests {
 
     #[test]
     fn tick_to_possible() {
-        let price = Decimal::from_u64(4000).to_compute_scale();
+        let price = Decimal::from_u64_to_compute_scale(4000);
         let rp = price.sqrt().unwrap();
 
         assert_eq!(Pool::rp_to_tick_loop(rp, false, 0), 82944);
@@ -30,10 +30,10 @@ mod tests {
         // A user has x = 2 ETH and wants to set up a liquidity position in an ETH/USDC pool.
         // The current price of ETH is P =2000 USDC and target price range is from pa = 1500 to
         // pb = 2500 USDC. How much USDC (y) do they need?
-        let x = Decimal::from_u64(2).to_compute_scale();
-        let rpa = Decimal::from_u64(1500).to_compute_scale().sqrt().unwrap();
-        let rp = Decimal::from_u64(2000).to_compute_scale().sqrt().unwrap();
-        let rpb = Decimal::from_u64(2500).to_compute_scale().sqrt().unwrap();
+        let x = Decimal::from_u64_to_compute_scale(2);
+        let rpa = Decimal::from_u64_to_compute_scale(1500).sqrt().unwrap();
+        let rp = Decimal::from_u64_to_compute_scale(2000).sqrt().unwrap();
+        let rpb = Decimal::from_u64_to_compute_scale(2500).sqrt().unwrap();
 
         let lx = Pool::liq_x_only(x, rp, rpb);
         // in float: 847.2135954999583
@@ -49,10 +49,10 @@ mod tests {
         // A user has x=2 ETH and y=4000 USDC, and wants to use pb =3000USDC perETH as the top of
         // the price range. What is the bottom of the range (pa) that ensures the opened position
         // uses the full amount of their funds?
-        let x = Decimal::from_u64(2).to_compute_scale();
-        let y = Decimal::from_u64(4000).to_compute_scale();
-        let rp = y.div(x).sqrt().unwrap();
-        let rpb = Decimal::from_u64(3000).to_compute_scale().sqrt().unwrap();
+        let x = Decimal::from_u64_to_compute_scale(2);
+        let y = Decimal::from_u64_to_compute_scale(4000);
+        let rp = y.div(x).unwrap().sqrt().unwrap();
+        let rpb = Decimal::from_u64_to_compute_scale(3000).sqrt().unwrap();
 
         //method 1
         let rpa = Pool::rpa_from_x_y_rp_rpb(x, y, rp, rpb);
@@ -70,17 +70,17 @@ mod tests {
     fn problem3() {
         // Using the liquidity position created in Problem 2, what are asset balances when the
         // price changes to P = 2500 USDC per ETH?
-        let x = Decimal::from_u64(2).to_compute_scale();
-        let y = Decimal::from_u64(4000).to_compute_scale();
-        let rp_orig = y.div(x).sqrt().unwrap();
-        let rp_new = Decimal::from_u64(2500).to_compute_scale().sqrt().unwrap();
+        let x = Decimal::from_u64_to_compute_scale(2);
+        let y = Decimal::from_u64_to_compute_scale(4000);
+        let rp_orig = y.div(x).unwrap().sqrt().unwrap();
+        let rp_new = Decimal::from_u64_to_compute_scale(2500).sqrt().unwrap();
 
-        let rpa = Decimal::from_u64(4000)
-            .to_compute_scale()
-            .div(Decimal::from_u64(3).to_compute_scale())
+        let rpa = Decimal::from_u64_to_compute_scale(4000)
+            .div(Decimal::from_u64_to_compute_scale(3))
+            .unwrap()
             .sqrt()
             .unwrap(); // 4000/3 = 1333.3333...
-        let rpb = Decimal::from_u64(3000).to_compute_scale().sqrt().unwrap();
+        let rpb = Decimal::from_u64_to_compute_scale(3000).sqrt().unwrap();
 
         let l = Pool::liq_from_x_y_rp_rng(x, y, rp_orig, rpa, rpb);
         // method 1:
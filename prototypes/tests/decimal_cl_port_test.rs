@@ -0,0 +1,214 @@
+//* these tests run the same deposits, swaps and withdrawals on the cl_pool prototype
+//* and on its port in hydra-math-rs (used on-chain), and expect the same results
+
+#[cfg(test)]
+mod tests {
+
+    use hydra_math_rs::decimal::*;
+    use hydra_math_rs::programs::liquidity_pools::cl_pool::{ClPool, ClPoolMath, SwapOutput};
+    use prototypes::cl_pool::{self, Pool};
+
+    fn rp(price: u64) -> Decimal {
+        Decimal::from_u64_to_compute_scale(price).sqrt().unwrap()
+    }
+
+    fn amount(amt: u64) -> Decimal {
+        Decimal::from_u64_to_compute_scale(amt)
+    }
+
+    fn tick(rp: Decimal) -> u128 {
+        ClPool::rp_to_possible_tk(rp, 1, false, 0).unwrap()
+    }
+
+    fn assert_same_state(proto: &Pool<Decimal>, port: &ClPool) {
+        assert_eq!(proto.x_info(), (port.x, port.x_adj, port.x_fee));
+        assert_eq!(proto.y_info(), (port.y, port.y_adj, port.y_fee));
+        assert_eq!(proto.glbl_liq(), port.global_state.liq);
+        assert_eq!(proto.glbl_tick(), port.global_state.tick);
+        assert_eq!(proto.glbl_rp(), port.global_state.rp);
+        let fg = port.global_state.fee_growth;
+        assert_eq!(proto.glbl_fees(), (fg.fee_x, fg.fee_y, fg.adj_x, fg.adj_y));
+        assert_eq!(
+            proto.tick_keys_cloned(false),
+            port.active_ticks.keys().cloned().collect::<Vec<u128>>()
+        );
+    }
+
+    fn assert_same_swap(proto: &cl_pool::SwapOutput<Decimal>, port: &SwapOutput) {
+        assert_eq!(proto.recv_amount(), port.recv_amount);
+        assert_eq!(proto.send_amount(), port.send_amount);
+        assert_eq!(proto.send_hmm_adj(), port.send_hmm_adj);
+        assert_eq!(proto.recv_fee(), port.recv_fee);
+        assert_eq!(proto.avg_price(), port.avg_price);
+        assert_eq!(proto.end_price(), port.end_price);
+    }
+
+    #[test]
+    fn test_port_hmm_swaps_with_fees() {
+        let hmm_c = Decimal::from_scaled_amount_to_compute_scale(150, 2); // 1.5
+        let fee_rate = Decimal::from_scaled_amount_to_compute_scale(30, 4); // 30bps
+        let (rpa, rpb) = (rp(1333), rp(3000));
+
+        let mut proto = Pool::new("ETH", 18, "USDC", 6, rp(2000), 1, hmm_c, fee_rate);
+        let mut port = ClPool::from_root_price(rp(2000), 1, hmm_c, fee_rate).unwrap();
+        let mut position = Default::default();
+        assert_same_state(&proto, &port);
+
+        proto.deposit("abc", amount(2), amount(4000), rpa, rpb);
+        let deposited = port
+            .deposit(&mut position, amount(2), amount(4000), tick(rpa), tick(rpb))
+            .unwrap();
+        assert_same_state(&proto, &port);
+        assert_eq!(position.liq, port.global_state.liq);
+
+        let rez_proto = proto.execute_swap_from_x(amount(1), rp(1500));
+        let rez_port = port.execute_swap_from_x(amount(1), rp(1500)).unwrap();
+        assert_same_swap(&rez_proto, &rez_port);
+        assert_same_state(&proto, &port);
+
+        let rez_proto = proto.execute_swap_from_y(amount(3955), rp(1700));
+        let rez_port = port.execute_swap_from_y(amount(3955), rp(1700)).unwrap();
+        assert_same_swap(&rez_proto, &rez_port);
+        assert_same_state(&proto, &port);
+
+        proto.withdraw("abc", deposited.liq, rpa, rpb);
+        let withdrawn = port
+            .withdraw(&mut position, deposited.liq, tick(rpa), tick(rpb))
+            .unwrap();
+        assert_same_state(&proto, &port);
+        assert!(position.liq.is_zero());
+        assert_eq!(proto.position_count(), 0);
+
+        // swap and hmm fees are paid out on top of the liquidity withdrawn
+        assert!(withdrawn.fees.fee_x.is_positive() && withdrawn.fees.fee_y.is_positive());
+        assert!(withdrawn.fees.adj_x.is_positive() && withdrawn.fees.adj_y.is_positive());
+        assert!(port.x_fee.add(port.y_adj).unwrap().value < 1000);
+    }
+
+    #[test]
+    fn test_port_liquidity_gaps() {
+        let zero = ClPool::zero();
+        let fee_rate = Decimal::from_scaled_amount_to_compute_scale(30, 4); // 30bps
+
+        let mut proto = Pool::new("ETH", 18, "USDC", 6, rp(2000), 1, zero, fee_rate);
+        let mut port = ClPool::from_root_price(rp(2000), 1, zero, fee_rate).unwrap();
+        let (mut below, mut above) = (Default::default(), Default::default());
+
+        // two ranges with a gap, none in range of the current price
+        proto.deposit("bob", zero, amount(4000), rp(1000), rp(1500));
+        port.deposit(
+            &mut below,
+            zero,
+            amount(4000),
+            tick(rp(1000)),
+            tick(rp(1500)),
+        )
+        .unwrap();
+        proto.deposit("carl", amount(2), zero, rp(2500), rp(3000));
+        port.deposit(&mut above, amount(2), zero, tick(rp(2500)), tick(rp(3000)))
+            .unwrap();
+        assert_same_state(&proto, &port);
+
+        // jump the gap up into the upper range, then past it
+        let rez_proto = proto.execute_swap_from_y(amount(1500), zero);
+        let rez_port = port.execute_swap_from_y(amount(1500), zero).unwrap();
+        assert_same_swap(&rez_proto, &rez_port);
+        assert_same_state(&proto, &port);
+
+        let rez_proto = proto.execute_swap_from_y(amount(10000), zero);
+        let rez_port = port.execute_swap_from_y(amount(10000), zero).unwrap();
+        assert_same_swap(&rez_proto, &rez_port);
+        assert_same_state(&proto, &port);
+
+        // all the way back down through both ranges
+        let rez_proto = proto.execute_swap_from_x(amount(7), zero);
+        let rez_port = port.execute_swap_from_x(amount(7), zero).unwrap();
+        assert_same_swap(&rez_proto, &rez_port);
+        assert_same_state(&proto, &port);
+    }
+
+    #[test]
+    fn test_port_shared_ticks() {
+        let zero = ClPool::zero();
+        let fee_rate = Decimal::from_scaled_amount_to_compute_scale(30, 4); // 30bps
+        let (rpa, rpb) = (rp(1333), rp(3000));
+
+        let mut proto = Pool::new("ETH", 18, "USDC", 6, rp(2000), 1, zero, fee_rate);
+        let mut port = ClPool::from_root_price(rp(2000), 1, zero, fee_rate).unwrap();
+        let (mut alice, mut bob) = (Default::default(), Default::default());
+
+        proto.deposit("alice", amount(2), amount(4000), rpa, rpb);
+        port.deposit(&mut alice, amount(2), amount(4000), tick(rpa), tick(rpb))
+            .unwrap();
+        proto.execute_swap_from_x(amount(1), zero);
+        port.execute_swap_from_x(amount(1), zero).unwrap();
+
+        // bob joins the ticks of alice after fees were earned in the range
+        proto.deposit("bob", amount(1), amount(1000), rpa, rpb);
+        port.deposit(&mut bob, amount(1), amount(1000), tick(rpa), tick(rpb))
+            .unwrap();
+        assert_same_state(&proto, &port);
+
+        proto.execute_swap_from_y(amount(3000), zero);
+        port.execute_swap_from_y(amount(3000), zero).unwrap();
+        assert_same_state(&proto, &port);
+
+        // zero deposits collect the fees of each position, bob earns only after joining
+        proto.deposit("alice", zero, zero, rpa, rpb);
+        let alice_fees = port
+            .deposit(&mut alice, zero, zero, tick(rpa), tick(rpb))
+            .unwrap()
+            .fees;
+        proto.deposit("bob", zero, zero, rpa, rpb);
+        let bob_fees = port
+            .deposit(&mut bob, zero, zero, tick(rpa), tick(rpb))
+            .unwrap()
+            .fees;
+        assert_same_state(&proto, &port);
+
+        assert!(alice_fees.fee_x.is_positive() && bob_fees.fee_x.is_zero());
+        assert!(alice_fees.fee_y.is_positive() && bob_fees.fee_y.is_positive());
+    }
+
+    #[test]
+    fn test_port_collect_fees() {
+        let hmm_c = Decimal::from_scaled_amount_to_compute_scale(150, 2); // 1.5
+        let fee_rate = Decimal::from_scaled_amount_to_compute_scale(30, 4); // 30bps
+        let (rpa, rpb) = (rp(1333), rp(3000));
+
+        let mut proto = Pool::new("ETH", 18, "USDC", 6, rp(2000), 1, hmm_c, fee_rate);
+        let mut port = ClPool::from_root_price(rp(2000), 1, hmm_c, fee_rate).unwrap();
+        let mut position = Default::default();
+
+        proto.deposit("abc", amount(2), amount(4000), rpa, rpb);
+        port.deposit(&mut position, amount(2), amount(4000), tick(rpa), tick(rpb))
+            .unwrap();
+        proto.execute_swap_from_x(amount(1), rp(1500));
+        port.execute_swap_from_x(amount(1), rp(1500)).unwrap();
+        let liq = position.liq;
+
+        // fees are collected while the liquidity stays in the range
+        let (fees_x, fees_y, adj_x, adj_y) = proto.collect_fees("abc", rpa, rpb);
+        let fees = port
+            .collect_fees(&mut position, tick(rpa), tick(rpb))
+            .unwrap()
+            .fees;
+        assert_eq!(
+            (fees_x, fees_y, adj_x, adj_y),
+            (fees.fee_x, fees.fee_y, fees.adj_x, fees.adj_y)
+        );
+        assert_same_state(&proto, &port);
+        assert_eq!(position.liq, liq);
+        assert!(fees.fee_x.is_positive() && fees.adj_y.is_positive());
+
+        // a withdrawal afterwards only pays the fees earned since the collection
+        proto.execute_swap_from_y(amount(3955), rp(1700));
+        port.execute_swap_from_y(amount(3955), rp(1700)).unwrap();
+        let withdrawn = port
+            .withdraw(&mut position, liq, tick(rpa), tick(rpb))
+            .unwrap();
+        proto.withdraw("abc", liq, rpa, rpb);
+        assert_same_state(&proto, &port);
+        assert!(withdrawn.fees.fee_x.is_zero() && withdrawn.fees.fee_y.is_positive());
+    }
+}
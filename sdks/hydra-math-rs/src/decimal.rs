@@ -1,16 +1,28 @@
-use ndarray::{arr2, Array2};
+use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::fmt;
-use std::iter::repeat;
+use std::hash::{Hash, Hasher};
+use std::iter::{repeat, Product, Sum};
 use std::ops::Neg;
 use std::str::FromStr;
 use thiserror::Error;
 
+use crate::uint::U256;
+
 /// Internal scale used for high precision compute operations
 pub const COMPUTE_SCALE: u8 = 12;
 
+/// Largest scale of a [Decimal] whose denominator fits in a u128
+pub const MAX_SCALE: u8 = 38;
+
+/// Length of the compact fixed-width encoding of a [Decimal], see [Decimal::to_bytes]
+pub const DECIMAL_BYTES: usize = 17;
+
+/// Bit of the last encoded byte holding the sign, the other bits hold the scale
+const NEGATIVE_FLAG: u8 = 0x80;
+
 /// Error codes related to [Decimal].
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum DecimalError {
     #[error("Unable to parse input")]
     ParseError,
@@ -26,11 +38,20 @@ pub enum DecimalError {
     ExceedsPrecisionRange,
     #[error("Signed decimals not supported for this function")]
     SignedDecimalsNotSupported,
+    #[error("Arithmetic overflow")]
+    Overflow,
+    #[error("Arithmetic underflow")]
+    Underflow,
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Exponent not supported")]
+    UnsupportedExponent,
 }
 
 /// [Decimal] representation of a number with a value, scale (precision in terms of number of decimal places
 /// and a negative boolean to handle signed arithmetic.
-#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Error)]
+/// Equality and ordering compare the numbers represented, whatever their scale and the sign of zero.
+#[derive(Clone, Copy, Debug, Error)]
 pub struct Decimal {
     pub value: u128,
     pub scale: u8,
@@ -59,15 +80,15 @@ impl Decimal {
     }
 
     pub fn zero() -> Decimal {
-        Decimal::from_u64(0).to_compute_scale()
+        Decimal::new(0, COMPUTE_SCALE, false)
     }
 
     pub fn one() -> Decimal {
-        Decimal::from_u64(1).to_compute_scale()
+        Decimal::from_u64_to_compute_scale(1)
     }
 
     pub fn two() -> Decimal {
-        Decimal::from_u64(2).to_compute_scale()
+        Decimal::from_u64_to_compute_scale(2)
     }
 
     /// Create a [Decimal] from an unsigned integer, assumed positive by default.
@@ -78,9 +99,14 @@ impl Decimal {
         }
     }
 
+    /// Create a [Decimal] at [COMPUTE_SCALE] from an unsigned integer, which always fits.
+    pub fn from_u64_to_compute_scale(integer: u64) -> Self {
+        Decimal::from_scaled_amount_to_compute_scale(integer, 0)
+    }
+
     /// Convert a [Decimal] to an unsigned integer, assumed positive by default.
-    pub fn to_u64(self) -> u64 {
-        self.value.try_into().unwrap()
+    pub fn to_u64(self) -> Result<u64, DecimalError> {
+        self.value.try_into().map_err(|_| DecimalError::Overflow)
     }
 
     /// Create a [Decimal] from an unsigned integer, assumed positive by default.
@@ -94,14 +120,14 @@ impl Decimal {
 
     /// Computes the absolute value of a [Decimal]
     /// and round down (floor) the value.
-    pub fn abs(self) -> u64 {
-        self.to_scale(0).to_u64()
+    pub fn abs(self) -> Result<u64, DecimalError> {
+        self.to_scale(0)?.to_u64()
     }
 
     /// Computes the absolute value of a [Decimal]
     /// and round up (ceiling) the value.
-    pub fn abs_up(self) -> u64 {
-        self.to_scale_up(0).to_u64()
+    pub fn abs_up(self) -> Result<u64, DecimalError> {
+        self.to_scale_up(0)?.to_u64()
     }
 
     /// Create a [Decimal] from an unsigned amount with scale, assumed positive by default.
@@ -113,47 +139,59 @@ impl Decimal {
         }
     }
 
+    /// Create a [Decimal] at [COMPUTE_SCALE] from an unsigned amount with scale.
+    /// Any u64 amount fits at [COMPUTE_SCALE], amounts too small for it round down to zero.
+    pub fn from_scaled_amount_to_compute_scale(amount: u64, scale: u8) -> Self {
+        Decimal::from_scaled_amount(amount, scale)
+            .to_compute_scale()
+            .unwrap_or_else(|_| Decimal::zero())
+    }
+
     /// Convert a [Decimal] back to a scaled u64 amount.
-    pub fn to_scaled_amount(self, scale: u8) -> u64 {
-        self.to_scale(scale).to_u64()
+    pub fn to_scaled_amount(self, scale: u8) -> Result<u64, DecimalError> {
+        self.to_scale(scale)?.to_u64()
     }
 
     /// Convert a [Decimal] back to a scaled u64 amount
     /// and round up (ceiling) the value.
-    pub fn to_scaled_amount_up(self, scale: u8) -> u64 {
-        self.to_scale_up(scale).to_u64()
+    pub fn to_scaled_amount_up(self, scale: u8) -> Result<u64, DecimalError> {
+        self.to_scale_up(scale)?.to_u64()
     }
 
     /// Modify the scale (precision) of a [Decimal] to a different scale.
-    pub fn to_scale(self, scale: u8) -> Self {
-        Self {
-            value: if self.scale > scale {
-                self.value
-                    .checked_div(10u128.pow((self.scale.checked_sub(scale).unwrap()).into()))
-                    .expect("scaled_down")
-            } else {
-                self.value
-                    .checked_mul(10u128.pow((scale.checked_sub(self.scale).unwrap()).into()))
-                    .expect("scaled_up")
-            },
+    pub fn to_scale(self, scale: u8) -> Result<Self, DecimalError> {
+        let value = if self.scale > scale {
+            match scale_factor(self.scale - scale) {
+                Ok(factor) => self.value / factor,
+                // scaling down by more than u128::MAX always rounds down to zero
+                Err(_) => 0,
+            }
+        } else {
+            self.value
+                .checked_mul(scale_factor(scale - self.scale)?)
+                .ok_or(DecimalError::Overflow)?
+        };
+
+        Ok(Self {
+            value,
             scale,
             negative: self.negative,
-        }
+        })
     }
 
     /// Modify the scale (precision) of a [Decimal] to a different scale
     /// and round up (ceiling) the value.
-    pub fn to_scale_up(self, scale: u8) -> Self {
+    pub fn to_scale_up(self, scale: u8) -> Result<Self, DecimalError> {
         let decimal = Self::new(self.value, scale, self.negative);
         if self.scale >= scale {
             decimal.div_up(Self::new(
-                10u128.pow((self.scale.checked_sub(scale).unwrap()).try_into().unwrap()),
+                scale_factor(self.scale - scale)?,
                 0,
                 self.negative,
             ))
         } else {
             decimal.mul_up(Self::new(
-                10u128.pow((scale.checked_sub(self.scale).unwrap()).try_into().unwrap()),
+                scale_factor(scale - self.scale)?,
                 0,
                 self.negative,
             ))
@@ -161,13 +199,13 @@ impl Decimal {
     }
 
     /// Convert to a higher precision compute scale
-    pub fn to_compute_scale(self) -> Self {
+    pub fn to_compute_scale(self) -> Result<Self, DecimalError> {
         self.to_scale(COMPUTE_SCALE)
     }
 
     /// Show the scale of a [Decimal] expressed as a power of 10.
-    pub fn denominator(self) -> u128 {
-        10u128.pow(self.scale.into())
+    pub fn denominator(self) -> Result<u128, DecimalError> {
+        scale_factor(self.scale)
     }
 
     /// Returns true if [Decimal] is positive and false if the number is zero or negative.
@@ -186,10 +224,42 @@ impl Decimal {
     }
 
     /// Returns true if and only if the [Decimal] is an exact integer.
-    pub fn is_integer(self) -> bool {
-        let integer = self.to_scale(0).to_scale(self.scale);
+    pub fn is_integer(self) -> Result<bool, DecimalError> {
+        let integer = self.to_scale(0)?.to_scale(self.scale)?;
 
-        self.sub(integer).expect("zero").is_zero()
+        Ok(self.sub(integer)?.is_zero())
+    }
+
+    /// Compact fixed-width encoding of a [Decimal]: the value as 16 little endian bytes
+    /// followed by one byte with the sign in its high bit and the scale in the others.
+    pub fn to_bytes(self) -> Result<[u8; DECIMAL_BYTES], DecimalError> {
+        if self.scale > MAX_SCALE {
+            return Err(DecimalError::ExceedsPrecisionRange);
+        }
+
+        let mut bytes = [0u8; DECIMAL_BYTES];
+        bytes[..16].copy_from_slice(&self.value.to_le_bytes());
+        bytes[16] = match self.negative {
+            true => self.scale | NEGATIVE_FLAG,
+            false => self.scale,
+        };
+        Ok(bytes)
+    }
+
+    /// Decode a [Decimal] from its compact fixed-width encoding, see [Decimal::to_bytes].
+    pub fn from_bytes(bytes: [u8; DECIMAL_BYTES]) -> Result<Self, DecimalError> {
+        let scale = bytes[16] & !NEGATIVE_FLAG;
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ExceedsPrecisionRange);
+        }
+
+        let mut value = [0u8; 16];
+        value.copy_from_slice(&bytes[..16]);
+        Ok(Decimal::new(
+            u128::from_le_bytes(value),
+            scale,
+            bytes[16] & NEGATIVE_FLAG != 0,
+        ))
     }
 
     /// Converts a string slice in a given base to a [Decimal].
@@ -213,7 +283,10 @@ impl Decimal {
             Some(loc) => {
                 // slice up to `loc` and 1 after to skip the 'e' char
                 let (base, exp) = (&s[..loc], &s[loc + 1..]);
-                (base, i64::from_str(exp).unwrap())
+                (
+                    base,
+                    i64::from_str(exp).map_err(|_| DecimalError::ParseError)?,
+                )
             }
         };
 
@@ -255,21 +328,24 @@ impl Decimal {
             }
         };
 
-        let scale = (decimal_offset - exp).abs() as u8;
+        let scale: u8 = (decimal_offset - exp)
+            .abs()
+            .try_into()
+            .map_err(|_| DecimalError::ExceedsPrecisionRange)?;
 
         if exp.is_positive() {
-            Ok(Decimal::new(
-                Decimal::from_str(base.as_str())
-                    .expect("decimal of base")
-                    .to_scale(exp.abs() as u8)
-                    .value,
+            let exp: u8 = exp
+                .try_into()
+                .map_err(|_| DecimalError::ExceedsPrecisionRange)?;
+            Decimal::new(
+                Decimal::from_str(base.as_str())?.to_scale(exp)?.value,
                 0,
                 negative,
             )
-            .to_scale(exp.abs() as u8))
+            .to_scale(exp)
         } else {
             Ok(Decimal::new(
-                u128::from_str_radix(&digits, radix).unwrap(),
+                u128::from_str_radix(&digits, radix).map_err(|_| DecimalError::ParseError)?,
                 scale,
                 negative,
             ))
@@ -277,51 +353,67 @@ impl Decimal {
     }
 }
 
+/// 10^scale, the denominator of a [Decimal] of that scale.
+fn scale_factor(scale: u8) -> Result<u128, DecimalError> {
+    10u128
+        .checked_pow(scale.into())
+        .ok_or(DecimalError::Overflow)
+}
+
+/// Compute `value * multiplier / divisor` with a 256-bit intermediate product,
+/// only the result needs to fit in a u128.
+fn mul_div(value: u128, multiplier: u128, divisor: u128) -> Result<u128, DecimalError> {
+    if divisor == 0 {
+        return Err(DecimalError::DivisionByZero);
+    }
+    // u128 * u128 always fits in 256 bits
+    (U256::from(value) * U256::from(multiplier) / U256::from(divisor))
+        .try_into()
+        .map_err(|_| DecimalError::Overflow)
+}
+
+/// Same as [mul_div] and round up (ceiling) the value.
+fn mul_div_up(value: u128, multiplier: u128, divisor: u128) -> Result<u128, DecimalError> {
+    if divisor == 0 {
+        return Err(DecimalError::DivisionByZero);
+    }
+    let divisor = U256::from(divisor);
+    ((U256::from(value) * U256::from(multiplier) + divisor - 1) / divisor)
+        .try_into()
+        .map_err(|_| DecimalError::Overflow)
+}
+
 /// Multiply another [Decimal] value against itself, including signed multiplication.
 impl Mul<Decimal> for Decimal {
-    fn mul(self, rhs: Decimal) -> Self {
-        Self {
-            value: self
-                .value
-                .checked_mul(rhs.value)
-                .expect("checked_mul")
-                .checked_div(rhs.denominator())
-                .expect("checked_div"),
+    fn mul(self, rhs: Decimal) -> Result<Self, DecimalError> {
+        Ok(Self {
+            value: mul_div(self.value, rhs.value, rhs.denominator()?)?,
             scale: self.scale,
             negative: !(self.negative == rhs.negative),
-        }
+        })
     }
 }
 
 /// Multiply an unsigned integer value against itself.
 impl Mul<u128> for Decimal {
-    fn mul(self, rhs: u128) -> Self {
-        Self {
-            value: self.value.checked_mul(rhs).expect("checked_mul"),
+    fn mul(self, rhs: u128) -> Result<Self, DecimalError> {
+        Ok(Self {
+            value: self.value.checked_mul(rhs).ok_or(DecimalError::Overflow)?,
             scale: self.scale,
             negative: self.negative,
-        }
+        })
     }
 }
 
 /// Multiply another [Decimal] value against itself, including signed multiplication
 /// and round up (ceiling) the value.
 impl MulUp<Decimal> for Decimal {
-    fn mul_up(self, rhs: Decimal) -> Self {
-        let denominator = rhs.denominator();
-
-        Self {
-            value: self
-                .value
-                .checked_mul(rhs.value)
-                .expect("checked_mul")
-                .checked_add(denominator.checked_sub(1).unwrap())
-                .expect("checked_add")
-                .checked_div(denominator)
-                .expect("checked_div"),
+    fn mul_up(self, rhs: Decimal) -> Result<Self, DecimalError> {
+        Ok(Self {
+            value: mul_div_up(self.value, rhs.value, rhs.denominator()?)?,
             scale: self.scale,
             negative: !(self.negative == rhs.negative),
-        }
+        })
     }
 }
 
@@ -335,7 +427,10 @@ impl Add<Decimal> for Decimal {
                 // covers when both positive, and both negative.
                 // just add the add absolute values and use common sign
                 Ok(Self {
-                    value: self.value.checked_add(rhs.value).expect("checked_add"),
+                    value: self
+                        .value
+                        .checked_add(rhs.value)
+                        .ok_or(DecimalError::Overflow)?,
                     scale: self.scale,
                     negative: self.negative,
                 })
@@ -347,14 +442,14 @@ impl Add<Decimal> for Decimal {
                 if self.value > rhs.value {
                     // e.g: 4 + (-3) = 1 ; -4 + 3 = -1;
                     Ok(Self {
-                        value: self.value.checked_sub(rhs.value).expect("checked_sub"),
+                        value: self.value - rhs.value,
                         scale: self.scale,
                         negative: self.negative,
                     })
                 } else if self.value < rhs.value {
                     // e.g: 2 + (-5) = -3 ; -2 + 5 = 3;
                     Ok(Self {
-                        value: rhs.value.checked_sub(self.value).expect("checked_sub"),
+                        value: rhs.value - self.value,
                         scale: self.scale,
                         negative: rhs.negative,
                     })
@@ -386,125 +481,114 @@ impl Sub<Decimal> for Decimal {
 
 /// Divide a [Decimal] over another [Decimal], including signed division.
 impl Div<Decimal> for Decimal {
-    fn div(self, rhs: Decimal) -> Self {
-        Self {
-            value: self
-                .value
-                .checked_mul(rhs.denominator())
-                .expect("checked_mul")
-                .checked_div(rhs.value)
-                .expect("checked_div"),
+    fn div(self, rhs: Decimal) -> Result<Self, DecimalError> {
+        Ok(Self {
+            value: mul_div(self.value, rhs.denominator()?, rhs.value)?,
             scale: self.scale,
             negative: !(self.negative == rhs.negative),
-        }
+        })
     }
 }
 
 /// Divide a [Decimal] over another [Decimal], including signed division.
 /// and round up (ceiling) the value.
 impl DivUp<Decimal> for Decimal {
-    fn div_up(self, rhs: Decimal) -> Self {
-        Self {
-            value: self
-                .value
-                .checked_mul(rhs.denominator())
-                .expect("checked_mul")
-                .checked_add(rhs.value.checked_sub(1).unwrap())
-                .expect("checked_add")
-                .checked_div(rhs.value)
-                .unwrap(),
+    fn div_up(self, rhs: Decimal) -> Result<Self, DecimalError> {
+        Ok(Self {
+            value: mul_div_up(self.value, rhs.denominator()?, rhs.value)?,
             scale: self.scale,
             negative: !(self.negative == rhs.negative),
-        }
+        })
     }
 }
 
 /// Divide another [Decimal] value under itself, including signed division
 /// and modify the scale (precision)
 impl DivScale<Decimal> for Decimal {
-    fn div_to_scale(self, rhs: Decimal, to_scale: u8) -> Self {
-        let decimal_difference = (self.scale as i32)
-            .checked_sub(to_scale.into())
-            .expect("checked_sub")
-            .checked_sub(rhs.scale.into())
-            .expect("checked_sub");
+    fn div_to_scale(self, rhs: Decimal, to_scale: u8) -> Result<Self, DecimalError> {
+        if rhs.is_zero() {
+            return Err(DecimalError::DivisionByZero);
+        }
+
+        let decimal_difference = i32::from(self.scale) - i32::from(to_scale) - i32::from(rhs.scale);
 
         let value = if decimal_difference > 0 {
-            self.value
-                .checked_div(rhs.value)
-                .expect("checked_div")
-                .checked_div(10u128.pow(decimal_difference.try_into().unwrap()))
-                .expect("checked_div")
+            let factor = scale_factor(
+                decimal_difference
+                    .try_into()
+                    .map_err(|_| DecimalError::Overflow)?,
+            );
+            match factor {
+                Ok(factor) => self.value / rhs.value / factor,
+                // dividing by more than u128::MAX always rounds down to zero
+                Err(_) => 0,
+            }
         } else {
-            self.value
-                .checked_mul(10u128.pow((-decimal_difference).try_into().unwrap()))
-                .expect("checked_mul")
-                .checked_div(rhs.value)
-                .expect("checked_div")
+            let factor = scale_factor(
+                (-decimal_difference)
+                    .try_into()
+                    .map_err(|_| DecimalError::Overflow)?,
+            )?;
+            mul_div(self.value, factor, rhs.value)?
         };
 
-        Self {
+        Ok(Self {
             value,
             scale: to_scale,
             negative: !(self.negative == rhs.negative),
-        }
+        })
     }
 }
 
 /// Calculate the power of a [Decimal] with another [Decimal] as the exponent.
+/// Quarter exponents are computed from square roots and integer exponents by repeated squaring,
+/// any other exponent as e^(y * ln(x)) with the precision of [Exp].
 impl Pow<Decimal> for Decimal {
-    fn pow(self, exp: Decimal) -> Self {
-        let one = Decimal::from_u64(1).to_scale(self.scale);
-        let zero_point_two_five = Decimal::from_u64(1)
-            .to_scale(self.scale)
-            .div(Decimal::from_u64(4).to_scale(self.scale));
-        let zero_point_five = Decimal::from_u64(1)
-            .to_scale(self.scale)
-            .div(Decimal::from_u64(2).to_scale(self.scale));
-        let one_point_two_five = Decimal::from_u64(5)
-            .to_scale(self.scale)
-            .div(Decimal::from_u64(4).to_scale(self.scale));
-        let one_point_five = Decimal::from_u64(3)
-            .to_scale(self.scale)
-            .div(Decimal::from_u64(2).to_scale(self.scale));
-
-        let exp = Option::Some(exp);
+    fn pow(self, exp: Decimal) -> Result<Self, DecimalError> {
+        let one = Decimal::from_u64(1).to_scale(self.scale)?;
+        let four = Decimal::from_u64(4).to_scale(self.scale)?;
+        let two = Decimal::from_u64(2).to_scale(self.scale)?;
+        let zero_point_two_five = one.div(four)?;
+        let zero_point_five = one.div(two)?;
+        let one_point_two_five = Decimal::from_u64(5).to_scale(self.scale)?.div(four)?;
+        let one_point_five = Decimal::from_u64(3).to_scale(self.scale)?.div(two)?;
+
         match exp {
             // e.g. x^0 = 1
-            Some(x) if x.is_zero() => one,
+            x if x.is_zero() => Ok(one),
             // e.g. x^0.25 = ⁴√x = √(√x) = sqrt(sqrt(x))
-            Some(x) if x.eq(zero_point_two_five).unwrap() => self.sqrt().unwrap().sqrt().unwrap(),
+            x if x.eq(zero_point_two_five)? => self.sqrt()?.sqrt(),
             // e.g. x^0.5 = √x = sqrt(x)
-            Some(x) if x.eq(zero_point_five).unwrap() => self.sqrt().unwrap(),
+            x if x.eq(zero_point_five)? => self.sqrt(),
             // e.g. x^1 = x
-            Some(x) if x.eq(one).unwrap() => self.clone(),
+            x if x.eq(one)? => Ok(self),
             // e.g. x^1.25 = x(√(√x)) = x(sqrt(sqrt(x)))
-            Some(x) if x.eq(one_point_two_five).unwrap() => {
-                self.mul(self.sqrt().unwrap().sqrt().unwrap())
-            }
+            x if x.eq(one_point_two_five)? => self.mul(self.sqrt()?.sqrt()?),
             // e.g. x^1.50 = x(√x) = x(sqrt(x))
-            Some(x) if x.eq(one_point_five).unwrap() => self.mul(self.sqrt().unwrap()),
+            x if x.eq(one_point_five)? => self.mul(self.sqrt()?),
             // e.g. x^2
-            Some(x) if x.is_integer() && x.is_positive() => self.pow(x.abs() as u128),
+            x if x.is_integer()? && x.is_positive() => self.pow(u128::from(x.abs()?)),
             // e.g. x^-2 == 1/x^2
-            Some(x) if x.is_integer() && x.is_negative() => one.div(self.pow(x.abs() as u128)),
+            x if x.is_integer()? && x.is_negative() => one.div(self.pow(u128::from(x.abs()?))?),
             // e.g. x^-0.5 = 1/x^0.5
-            Some(x) if x.is_negative() => one.div(self.pow(Decimal::new(x.value, x.scale, false))),
-            _ => panic!(
-                "pow not implemented for exponent: {}",
-                exp.unwrap().to_string()
-            ),
+            x if x.is_negative() => one.div(self.pow(Decimal::new(x.value, x.scale, false))?),
+            // e.g. 0^0.3 = 0
+            _ if self.is_zero() => Ok(Decimal::new(0, self.scale, false)),
+            // negative bases only have real powers for integer exponents
+            _ if self.is_negative() => Err(DecimalError::UnsupportedExponent),
+            // e.g. x^0.3 = e^(0.3 * ln(x))
+            x => pow_fixed_point(self, x),
         }
     }
 }
 
 /// Calculate the power of a [Decimal] with an unsigned integer as the exponent.
 impl Pow<u128> for Decimal {
-    fn pow(self, exp: u128) -> Self {
-        let one = Decimal::one().to_scale(self.scale);
+    fn pow(self, exp: u128) -> Result<Self, DecimalError> {
+        let one = Decimal::one().to_scale(self.scale)?;
 
         if exp == 0 {
-            return one;
+            return Ok(one);
         }
 
         let mut current_exp = exp;
@@ -513,50 +597,60 @@ impl Pow<u128> for Decimal {
 
         while current_exp > 0 {
             if current_exp % 2 != 0 {
-                result = result.mul(base);
+                result = result.mul(base)?;
             }
             current_exp /= 2;
-            base = base.mul(base);
+            // squaring past the last bit could overflow for nothing
+            if current_exp > 0 {
+                base = base.mul(base)?;
+            }
         }
-        return result;
+        return Ok(result);
     }
 }
 
 /// Convert a [Decimal] into an unsigned 64-bit integer.
-impl Into<u64> for Decimal {
-    fn into(self) -> u64 {
-        self.value.try_into().unwrap()
+impl TryFrom<Decimal> for u64 {
+    type Error = DecimalError;
+
+    fn try_from(decimal: Decimal) -> Result<Self, Self::Error> {
+        decimal.to_u64()
     }
 }
 
 /// Convert a [Decimal] into an unsigned 128-bit integer.
-impl Into<u128> for Decimal {
-    fn into(self) -> u128 {
-        self.value.try_into().unwrap()
+impl From<Decimal> for u128 {
+    fn from(decimal: Decimal) -> Self {
+        decimal.value
     }
 }
 
 /// Convert a [Decimal] into an usize.
-impl Into<usize> for Decimal {
-    fn into(self) -> usize {
-        self.value.try_into().unwrap()
+impl TryFrom<Decimal> for usize {
+    type Error = DecimalError;
+
+    fn try_from(decimal: Decimal) -> Result<Self, Self::Error> {
+        decimal.value.try_into().map_err(|_| DecimalError::Overflow)
     }
 }
 
 /// Convert a [Decimal] into an unsigned 64-bit float.
-impl Into<f64> for Decimal {
-    fn into(self) -> f64 {
-        self.value as f64 / self.denominator() as f64
+impl From<Decimal> for f64 {
+    fn from(decimal: Decimal) -> Self {
+        decimal.value as f64 / 10f64.powi(decimal.scale.into())
     }
 }
 
 /// Convert a [Decimal] into a signed 32-bit integer.
-impl Into<i32> for Decimal {
-    fn into(self) -> i32 {
-        let sign = if self.negative { -1i32 } else { 1i32 };
-        (self.value as i32)
-            .checked_mul(sign)
-            .expect("signed integer")
+impl TryFrom<Decimal> for i32 {
+    type Error = DecimalError;
+
+    fn try_from(decimal: Decimal) -> Result<Self, Self::Error> {
+        let value: i32 = decimal
+            .value
+            .try_into()
+            .map_err(|_| DecimalError::Overflow)?;
+        Ok(if decimal.negative { -value } else { value })
     }
 }
 
@@ -643,21 +737,66 @@ impl Compare<Decimal> for Decimal {
             }
         }
     }
+}
 
-    fn min(self, other: Decimal) -> Decimal {
-        if self.lte(other).unwrap() {
-            self
-        } else {
-            other
+/// Private function comparing the magnitudes of two [Decimal] values of any scale.
+fn cmp_magnitude(a: Decimal, b: Decimal) -> Ordering {
+    if a.value == 0 || b.value == 0 || a.scale == b.scale {
+        return a.value.cmp(&b.value);
+    }
+
+    // scale the value with the lower scale up, a product beyond 256 bits is above any u128
+    let scale_up = |value: u128, scale_difference: u8| {
+        U256::from(10)
+            .checked_pow(U256::from(scale_difference))
+            .and_then(|factor| factor.checked_mul(U256::from(value)))
+    };
+    match a.scale < b.scale {
+        true => scale_up(a.value, b.scale - a.scale).map_or(Ordering::Greater, |a_value| {
+            a_value.cmp(&U256::from(b.value))
+        }),
+        false => scale_up(b.value, a.scale - b.scale)
+            .map_or(Ordering::Less, |b_value| U256::from(a.value).cmp(&b_value)),
+    }
+}
+
+/// Order [Decimal] values by the numbers they represent, e.g. 1.50 == 1.5 and -0 == 0.
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => cmp_magnitude(*self, *other),
+            (true, true) => cmp_magnitude(*other, *self),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
         }
     }
+}
 
-    fn max(self, other: Decimal) -> Decimal {
-        if self.gte(other).unwrap() {
-            self
-        } else {
-            other
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+/// Hash [Decimal] values consistently with [Eq], trailing zeros are dropped and zero is unsigned.
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (mut value, mut scale) = (self.value, self.scale);
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
         }
+        value.hash(state);
+        scale.hash(state);
+        self.is_negative().hash(state);
     }
 }
 
@@ -683,6 +822,66 @@ impl Neg for Decimal {
     }
 }
 
+/// Implement a std operator and its assignment form with the fallible [Decimal] trait of the same
+/// name, panicking on errors like integer operators on overflow, and the matching checked method.
+macro_rules! impl_std_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $checked:ident) => {
+        impl std::ops::$trait for Decimal {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                $trait::$method(self, rhs)
+                    .unwrap_or_else(|e| panic!("{} {} {}: {}", self, stringify!($method), rhs, e))
+            }
+        }
+
+        impl std::ops::$assign_trait for Decimal {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = std::ops::$trait::$method(*self, rhs);
+            }
+        }
+
+        impl Decimal {
+            #[doc = concat!("Checked `", stringify!($method), "`, None where the operator panics.")]
+            pub fn $checked(self, rhs: Self) -> Option<Self> {
+                $trait::$method(self, rhs).ok()
+            }
+        }
+    };
+}
+
+impl_std_op!(Add, add, AddAssign, add_assign, checked_add);
+impl_std_op!(Sub, sub, SubAssign, sub_assign, checked_sub);
+impl_std_op!(Mul, mul, MulAssign, mul_assign, checked_mul);
+impl_std_op!(Div, div, DivAssign, div_assign, checked_div);
+
+/// Sum of [Decimal] values of the same scale, zero at [COMPUTE_SCALE] when empty.
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(std::ops::Add::add)
+            .unwrap_or_else(Decimal::zero)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Product of [Decimal] values at the scale of the first one, one at [COMPUTE_SCALE] when empty.
+impl Product for Decimal {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(std::ops::Mul::mul).unwrap_or_else(Decimal::one)
+    }
+}
+
+impl<'a> Product<&'a Decimal> for Decimal {
+    fn product<I: Iterator<Item = &'a Decimal>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
 impl FromStr for Decimal {
     type Err = DecimalError;
 
@@ -722,276 +921,413 @@ impl fmt::Display for Decimal {
     }
 }
 
-/// Private function of pre calculated log table values.
-/// These can be calculated from an index expressed as:
-// 1.1	1.01	1.001	1.0001	1.00001	1.000001	1.0000001	1.00000001	1.000000001
-// 1.2	1.02	1.002	1.0002	1.00002	1.000002	1.0000002	1.00000002	1.000000002
-// 1.3	1.03	1.003	1.0003	1.00003	1.000003	1.0000003	1.00000003	1.000000003
-// 1.4	1.04	1.004	1.0004	1.00004	1.000004	1.0000004	1.00000004	1.000000004
-// 1.5	1.05	1.005	1.0005	1.00005	1.000005	1.0000005	1.00000005	1.000000005
-// 1.6	1.06	1.006	1.0006	1.00006	1.000006	1.0000006	1.00000006	1.000000006
-// 1.7	1.07	1.007	1.0007	1.00007	1.000007	1.0000007	1.00000007	1.000000007
-// 1.8	1.08	1.008	1.0008	1.00008	1.000008	1.0000008	1.00000008	1.000000008
-// 1.9	1.09	1.009	1.0009	1.00009	1.000009	1.0000009	1.00000009	1.000000009
-// with each column, row determined by the function:
-// INT(LN(index)*scale)
-// where scale is a predetermined precision e.g. 10^12
-fn log_table(row: usize, col: usize) -> u128 {
-    let table: Array2<u128> = arr2(&[
-        [
-            95310179804,
-            9950330853,
-            999500333,
-            99995000,
-            9999950,
-            999999,
-            99999,
-            9999,
-            1000,
-            100,
-            10,
-            1,
-        ],
-        [
-            182321556793,
-            19802627296,
-            1998002662,
-            199980002,
-            19999800,
-            1999998,
-            199999,
-            19999,
-            1999,
-            200,
-            20,
-            1,
-        ],
-        [
-            262364264467,
-            29558802241,
-            2995508979,
-            299955008,
-            29999550,
-            2999995,
-            299999,
-            29999,
-            3000,
-            300,
-            30,
-            3,
-        ],
-        [
-            336472236621,
-            39220713153,
-            3992021269,
-            399920021,
-            39999200,
-            3999991,
-            399999,
-            39999,
-            4000,
-            400,
-            40,
-            3,
-        ],
-        [
-            405465108108,
-            48790164169,
-            4987541511,
-            499875041,
-            49998750,
-            4999987,
-            499999,
-            49999,
-            4999,
-            500,
-            50,
-            5,
-        ],
-        [
-            470003629245,
-            58268908123,
-            5982071677,
-            599820071,
-            59998200,
-            5999982,
-            599999,
-            59999,
-            6000,
-            600,
-            60,
-            6,
-        ],
-        [
-            530628251062,
-            67658648473,
-            6975613736,
-            699755114,
-            69997550,
-            6999975,
-            699999,
-            69999,
-            6999,
-            700,
-            70,
-            6,
-        ],
-        [
-            587786664902,
-            76961041136,
-            7968169649,
-            799680170,
-            79996800,
-            7999968,
-            799999,
-            79999,
-            7999,
-            800,
-            80,
-            8,
-        ],
-        [
-            641853886172,
-            86177696241,
-            8959741371,
-            899595242,
-            89995950,
-            8999959,
-            899999,
-            89999,
-            9000,
-            900,
-            90,
-            8,
-        ],
-    ]);
-
-    table[[row, col]]
-}
-
-/// Private function to return a pre calculated log table value based on scale, column
-/// and s and t values.
-fn log_table_value(
-    s_value: Decimal,
-    t_value: Decimal,
-    log_table_col: usize,
-) -> (Decimal, Decimal, u128) {
-    let s_value = s_value.div(t_value);
-    let place_value = 10u128.checked_pow((log_table_col + 1) as u32).unwrap();
-    let f_value = Decimal::new(place_value, s_value.scale, false);
-    let t_value = s_value.mul(f_value).div(f_value);
-
-    let log_table_row: usize = t_value.mul(f_value).sub(f_value).unwrap().into();
-    let log_table_row = log_table_row.checked_sub(1);
-
-    let mut lx_value = 0u128;
-
-    // Ensure within array of shape [9, 12]
-    let log_table_row_range = 0..9;
-    let log_table_col_range = 0..12;
-
-    match log_table_row {
-        Some(log_table_row) => {
-            if log_table_row_range.contains(&log_table_row)
-                && log_table_col_range.contains(&log_table_col)
-            {
-                lx_value = log_table(log_table_row, log_table_col);
-            }
-        }
-        None => lx_value = 0,
+/// Borsh encoding of a [Decimal], the compact fixed-width encoding of [Decimal::to_bytes].
+/// anchor-lang 0.24 re-exports borsh 0.9 as AnchorSerialize, so that [Decimal] can be stored
+/// in account state and passed as an instruction argument.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Decimal {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let bytes = self
+            .to_bytes()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        writer.write_all(&bytes)
     }
+}
 
-    (s_value, t_value, lx_value)
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Decimal {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = <[u8; DECIMAL_BYTES]>::deserialize(buf)?;
+        Decimal::from_bytes(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Serde string form of a [Decimal], e.g. "-1.500000", which keeps its scale.
+/// See [serde_structured] for the `{ value, scale, negative }` form.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Decimal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Decimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Decimal::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde structured form of a [Decimal], to use on fields with
+/// `#[serde(with = "hydra_math_rs::decimal::serde_structured")]`
+#[cfg(feature = "serde")]
+pub mod serde_structured {
+    use super::Decimal;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "Decimal")]
+    struct DecimalParts {
+        value: u128,
+        scale: u8,
+        negative: bool,
+    }
+
+    pub fn serialize<S: Serializer>(decimal: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        DecimalParts::serialize(decimal, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        DecimalParts::deserialize(deserializer)
+    }
+}
+
+/// Scale of the fixed-point intermediates of [Ln] and [Exp], 18 decimal places
+const FIXED_POINT_SCALE: u8 = 18;
+
+/// One at [FIXED_POINT_SCALE]
+const FIXED_POINT_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// ln(2) at [FIXED_POINT_SCALE], truncated (0.693147180559945309417...)
+const LN_2_FIXED_POINT: u128 = 693_147_180_559_945_309;
+
+/// ln(10) at [FIXED_POINT_SCALE], truncated (2.302585092994045684017...)
+const LN_10_FIXED_POINT: u128 = 2_302_585_092_994_045_684;
+
+/// Largest magnitude of x for which e^x is computed: e^100 exceeds the range of
+/// a u128 at any scale and e^-100 truncates to zero at any scale.
+const MAX_EXP_ARGUMENT: u128 = 100 * FIXED_POINT_ONE;
+
+/// Private function returning floor(log2(value / denominator)) of a positive fraction,
+/// computed with integer operations only.
+fn floor_log2(value: u128, denominator: u128) -> i32 {
+    let bits = |n: u128| 128 - n.leading_zeros() as i32;
+
+    if value >= denominator {
+        // floor(log2(x)) == floor(log2(floor(x))) for x >= 1
+        return bits(value / denominator) - 1;
+    }
+
+    // smallest shift m such that value * 2^m >= denominator, then log2 is -m
+    let shift = bits(denominator) - bits(value);
+    if value << shift >= denominator {
+        -shift
+    } else {
+        -(shift + 1)
+    }
 }
 
 /// Function that determines the bit length of a positive [Decimal]
-/// based on the formula: int(log(value)/log(2))
+/// based on the formula: floor(log2(value)), computed with integer operations only
 impl BitLength<Decimal> for Decimal {
     fn bit_length(self) -> Result<Self, DecimalError> {
         if self.is_negative() {
-            return Err(DecimalError::SignedDecimalsNotSupported.into());
-        } else {
-            if self.is_zero() {
-                Ok(Decimal::from_u64(0))
-            } else {
-                let value: f64 = self.into();
-                let log_value_div_log_two = value.log(2.0);
+            return Err(DecimalError::SignedDecimalsNotSupported);
+        }
 
-                let value = log_value_div_log_two.abs() * (self.denominator() as f64);
-                let scale = self.scale;
-                let negative = log_value_div_log_two.is_sign_negative();
+        if self.is_zero() {
+            return Ok(Decimal::from_u64(0));
+        }
 
-                let value = Decimal::new(value as u128, scale, negative);
-                let value = if negative {
-                    value.to_scale_up(0)
-                } else {
-                    value.to_scale(0)
-                };
+        let log2 = floor_log2(self.value, self.denominator()?);
+        Ok(Decimal::new(log2.unsigned_abs().into(), 0, log2 < 0))
+    }
+}
 
-                Ok(Decimal::new(value.into(), 0, negative))
-            }
+/// Private function returning the magnitude of a [Decimal] at [FIXED_POINT_SCALE],
+/// or None when it does not fit in a u128.
+fn fixed_point_magnitude(decimal: Decimal) -> Option<u128> {
+    if decimal.scale <= FIXED_POINT_SCALE {
+        10u128
+            .checked_pow((FIXED_POINT_SCALE - decimal.scale).into())
+            .and_then(|factor| decimal.value.checked_mul(factor))
+    } else {
+        Some(decimal.value / scale_factor(decimal.scale - FIXED_POINT_SCALE).ok()?)
+    }
+}
+
+/// Private function returning a signed value at [FIXED_POINT_SCALE] as a [Decimal] of the given
+/// scale, rounded half away from zero to the nearest unit of that scale.
+fn round_fixed_point(value: i128, scale: u8) -> Result<Decimal, DecimalError> {
+    let magnitude = value.unsigned_abs();
+    if scale >= FIXED_POINT_SCALE {
+        return Decimal::new(magnitude, FIXED_POINT_SCALE, value < 0).to_scale(scale);
+    }
+    let factor = scale_factor(FIXED_POINT_SCALE - scale)?;
+    Ok(Decimal::new(
+        (magnitude + factor / 2) / factor,
+        scale,
+        value < 0,
+    ))
+}
+
+/// Private function returning ln(x) of a positive [Decimal] at [FIXED_POINT_SCALE],
+/// see [Ln] for the algorithm.
+fn ln_fixed_point(x: Decimal) -> Result<i128, DecimalError> {
+    if x.is_negative() {
+        return Err(DecimalError::SignedDecimalsNotSupported);
+    }
+
+    if x.is_zero() {
+        return Err(DecimalError::ExceedsRange);
+    }
+
+    let denominator = x.denominator()?;
+    let k = floor_log2(x.value, denominator);
+
+    // m = value / 2^k in [1, 2) at FIXED_POINT_SCALE, 256 bits hold every intermediate
+    let value_fixed_point = U256::from(x.value) * U256::from(FIXED_POINT_ONE);
+    let m = if k >= 0 {
+        value_fixed_point / (U256::from(denominator) << k as usize)
+    } else {
+        (value_fixed_point << k.unsigned_abs() as usize) / U256::from(denominator)
+    };
+    let m: u128 = m.try_into().map_err(|_| DecimalError::Overflow)?;
+
+    // atanh series, every term is below one so that products fit in a u128
+    let z = (m - FIXED_POINT_ONE) * FIXED_POINT_ONE / (m + FIXED_POINT_ONE);
+    let z_squared = z * z / FIXED_POINT_ONE;
+    let mut power = z;
+    let mut atanh = 0u128;
+    let mut n = 1u128;
+    while power > 0 {
+        atanh += power / n;
+        power = power * z_squared / FIXED_POINT_ONE;
+        n += 2;
+    }
+    let ln_m = (2 * atanh) as i128;
+
+    // |k| <= 128 so that k * ln(2) stays far below i128::MAX
+    Ok(i128::from(k) * LN_2_FIXED_POINT as i128 + ln_m)
+}
+
+/// Private function returning e^x of an exponent at [FIXED_POINT_SCALE] as a [Decimal] of the
+/// given scale, see [Exp] for the algorithm.
+fn exp_fixed_point(x: i128, scale: u8) -> Result<Decimal, DecimalError> {
+    if x.unsigned_abs() > MAX_EXP_ARGUMENT {
+        return match x < 0 {
+            true => Ok(Decimal::new(0, scale, false)),
+            false => Err(DecimalError::Overflow),
+        };
+    }
+
+    // k = round(x / ln(2)) and the remainder r
+    let ln_2 = LN_2_FIXED_POINT as i128;
+    let half_ln_2 = if x < 0 { -ln_2 / 2 } else { ln_2 / 2 };
+    let k = (x + half_ln_2) / ln_2;
+    let r = x - k * ln_2;
+
+    // Taylor series, terms stay below 2 so that products fit in an i128
+    let one = FIXED_POINT_ONE as i128;
+    let mut term = one;
+    let mut exp_r = one;
+    let mut n = 1i128;
+    while term != 0 {
+        term = term * r / (n * one);
+        exp_r += term;
+        n += 1;
+    }
+
+    // e^x = 2^k * e^r, scaled from FIXED_POINT_SCALE to the requested scale
+    let numerator = U256::from(exp_r as u128) * U256::from(scale_factor(scale)?);
+    let k_abs = k.unsigned_abs() as usize;
+    let value = if k >= 0 {
+        if numerator.bits() + k_abs > 256 {
+            return Err(DecimalError::Overflow);
+        }
+        (numerator << k_abs) / U256::from(FIXED_POINT_ONE)
+    } else {
+        numerator / (U256::from(FIXED_POINT_ONE) << k_abs)
+    };
+
+    Ok(Decimal::new(
+        value.try_into().map_err(|_| DecimalError::Overflow)?,
+        scale,
+        false,
+    ))
+}
+
+/// Private function returning x^y = e^(y * ln(x)) of a positive base, with ln(x) and the
+/// product kept at [FIXED_POINT_SCALE] so that the result is as precise as [Exp].
+fn pow_fixed_point(base: Decimal, exp: Decimal) -> Result<Decimal, DecimalError> {
+    exp_mul_fixed_point(ln_fixed_point(base)?, exp, base.scale)
+}
+
+/// Private function returning e^(y * ln_base) of a logarithm at [FIXED_POINT_SCALE] as a
+/// [Decimal] of the given scale, see [pow_fixed_point].
+fn exp_mul_fixed_point(ln_base: i128, exp: Decimal, scale: u8) -> Result<Decimal, DecimalError> {
+    let exp_magnitude = fixed_point_magnitude(exp).map(U256::from);
+
+    // y * ln(x), any product beyond MAX_EXP_ARGUMENT saturates exp_fixed_point
+    let product = match exp_magnitude {
+        Some(magnitude) => {
+            U256::from(ln_base.unsigned_abs()) * magnitude / U256::from(FIXED_POINT_ONE)
         }
+        None if ln_base == 0 => U256::zero(),
+        None => U256::MAX,
+    };
+    let product = product.min(U256::from(MAX_EXP_ARGUMENT + 1)).as_u128() as i128;
+
+    match (ln_base < 0) != exp.negative {
+        true => exp_fixed_point(-product, scale),
+        false => exp_fixed_point(product, scale),
     }
 }
 
-/// Calculate the natural logarithm of a [Decimal] value. For full algorithm please refer to:
-// https://docs.google.com/spreadsheets/d/19mgYjGQlpsuaTk1zXujn-yCSdbAL25sP/edit?pli=1#gid=2070648638
+/// Calculate the natural logarithm of a [Decimal] value with fixed-point integer math only.
+/// The value is reduced to x = 2^k * m with m in [1, 2), so that ln(x) = k * ln(2) + ln(m), and
+/// ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1) in [0, 1/3).
+/// Intermediates are kept at 18 decimal places, the absolute error is below 2 * 10^-16
+/// before the result is truncated toward zero to the scale of the value.
 impl Ln<Decimal> for Decimal {
     fn ln(self) -> Result<Self, DecimalError> {
+        let ln = ln_fixed_point(self)?;
+        Decimal::new(ln.unsigned_abs(), FIXED_POINT_SCALE, ln < 0).to_scale(self.scale)
+    }
+}
+
+/// Calculate log2(x) = ln(x) / ln(2) of a [Decimal] value, see [Ln] for the algorithm.
+/// The result is rounded to the nearest unit at the scale of the value, so that exact
+/// powers of two give exact results.
+impl Log2<Decimal> for Decimal {
+    fn log2(self) -> Result<Self, DecimalError> {
+        let ln = ln_fixed_point(self)?;
+        // |ln| < 89 * 10^18 so that the product fits in an i128
+        round_fixed_point(
+            ln * FIXED_POINT_ONE as i128 / LN_2_FIXED_POINT as i128,
+            self.scale,
+        )
+    }
+}
+
+/// Calculate log10(x) = ln(x) / ln(10) of a [Decimal] value, see [Ln] for the algorithm.
+/// The result is rounded to the nearest unit at the scale of the value, so that exact
+/// powers of ten give exact results.
+impl Log10<Decimal> for Decimal {
+    fn log10(self) -> Result<Self, DecimalError> {
+        let ln = ln_fixed_point(self)?;
+        // |ln| < 89 * 10^18 so that the product fits in an i128
+        round_fixed_point(
+            ln * FIXED_POINT_ONE as i128 / LN_10_FIXED_POINT as i128,
+            self.scale,
+        )
+    }
+}
+
+/// Calculate e^x of a [Decimal] value with fixed-point integer math only.
+/// The exponent is reduced to x = k * ln(2) + r with |r| <= ln(2) / 2, so that e^x = 2^k * e^r,
+/// and e^r is summed from its Taylor series. Intermediates are kept at 18 decimal places, the
+/// relative error is below 10^-16 before the result is truncated to the scale of the exponent.
+impl Exp<Decimal> for Decimal {
+    fn exp(self) -> Result<Self, DecimalError> {
+        // out of range magnitudes saturate exp_fixed_point
+        let magnitude = fixed_point_magnitude(self)
+            .unwrap_or(MAX_EXP_ARGUMENT + 1)
+            .min(MAX_EXP_ARGUMENT + 1) as i128;
+
+        match self.negative {
+            true => exp_fixed_point(-magnitude, self.scale),
+            false => exp_fixed_point(magnitude, self.scale),
+        }
+    }
+}
+
+/// Products with a function of the ratio of two positive [Decimal] values. The function of the
+/// ratio is kept at [FIXED_POINT_SCALE] and computed from the difference of their logarithms,
+/// so that ratios close to one keep their precision when multiplied by a large value.
+impl Decimal {
+    /// Calculate self * ln(numerator / denominator), truncated to the scale of self.
+    pub fn mul_ln_ratio(
+        self,
+        numerator: Decimal,
+        denominator: Decimal,
+    ) -> Result<Self, DecimalError> {
+        let ln = ln_fixed_point(numerator)? - ln_fixed_point(denominator)?;
+        let value = mul_div(self.value, ln.unsigned_abs(), FIXED_POINT_ONE)?;
+
+        Ok(Self {
+            value,
+            scale: self.scale,
+            negative: value != 0 && self.negative != (ln < 0),
+        })
+    }
+
+    /// Calculate self * (numerator / denominator)^exp, truncated to the scale of self.
+    pub fn mul_pow_ratio(
+        self,
+        numerator: Decimal,
+        denominator: Decimal,
+        exp: Decimal,
+    ) -> Result<Self, DecimalError> {
+        let ln = ln_fixed_point(numerator)? - ln_fixed_point(denominator)?;
+        let power = exp_mul_fixed_point(ln, exp, FIXED_POINT_SCALE)?;
+
+        self.mul(power)
+    }
+}
+
+/// Calculate the n-th root of a [Decimal] value, truncated to the scale of the value.
+/// The root is computed exactly with integer Newton iterations while value * 10^(scale * (n - 1))
+/// fits in 256 bits, and as e^(ln(x) / n) with the precision of [Exp] beyond that.
+impl NthRoot<u32> for Decimal {
+    fn nth_root(self, n: u32) -> Result<Self, DecimalError> {
+        match n {
+            0 => return Err(DecimalError::UnsupportedExponent),
+            1 => return Ok(self),
+            _ => {}
+        }
+
         if self.is_negative() {
-            return Err(DecimalError::SignedDecimalsNotSupported.into());
-        }
-
-        let scaled_out = self.to_compute_scale();
-
-        let ln_2_decimal = Decimal::new(693_147_180_559u128, 12, false);
-
-        let bit_length_decimal = self.bit_length().expect("bit_length");
-
-        // TODO: calculate with higher compute scale when big_mul is implemented otherwise overflow
-        let max = Decimal::from_u64(2)
-            .to_scale(6)
-            .pow(bit_length_decimal.to_scale(6))
-            .to_compute_scale();
-
-        let (s_0, t_0, lx_0) = log_table_value(scaled_out, max, 0);
-        let (s_1, t_1, lx_1) = log_table_value(s_0, t_0, 1);
-        let (s_2, t_2, lx_2) = log_table_value(s_1, t_1, 2);
-        let (s_3, t_3, lx_3) = log_table_value(s_2, t_2, 3);
-        let (s_4, t_4, lx_4) = log_table_value(s_3, t_3, 4);
-        let (s_5, t_5, lx_5) = log_table_value(s_4, t_4, 5);
-        let (s_6, t_6, lx_6) = log_table_value(s_5, t_5, 6);
-        let (s_7, t_7, lx_7) = log_table_value(s_6, t_6, 7);
-        let (s_8, t_8, lx_8) = log_table_value(s_7, t_7, 8);
-        let (_s_9, _t_9, lx_9) = log_table_value(s_8, t_8, 9);
-
-        let lx_sum = lx_0
-            .checked_add(lx_1)
-            .unwrap()
-            .checked_add(lx_2)
-            .unwrap()
-            .checked_add(lx_3)
-            .unwrap()
-            .checked_add(lx_4)
-            .unwrap()
-            .checked_add(lx_5)
-            .unwrap()
-            .checked_add(lx_6)
-            .unwrap()
-            .checked_add(lx_7)
-            .unwrap()
-            .checked_add(lx_8)
-            .unwrap()
-            .checked_add(lx_9)
-            .unwrap();
+            // odd roots of negative numbers are the opposite of the root of their magnitude
+            return match n % 2 {
+                1 => Ok(Decimal::new(self.value, self.scale, false)
+                    .nth_root(n)?
+                    .neg()),
+                _ => Err(DecimalError::SignedDecimalsNotSupported),
+            };
+        }
+
+        if self.is_zero() {
+            return Ok(self);
+        }
 
-        let lx_sum_decimal = Decimal::new(lx_sum, 12, scaled_out.negative);
+        // root = (value * 10^(scale * (n - 1)))^(1/n) at the scale of the value
+        let radicand = U256::from(self.scale)
+            .checked_mul(U256::from(n - 1))
+            .filter(|exponent| *exponent < U256::from(78))
+            .and_then(|exponent| U256::from(10).checked_pow(exponent))
+            .and_then(|factor| factor.checked_mul(U256::from(self.value)));
 
-        Ok(ln_2_decimal
-            .mul(bit_length_decimal)
-            .add(lx_sum_decimal)
-            .unwrap()
-            .to_scale(self.scale))
+        match radicand {
+            Some(radicand) => Ok(Decimal::new(
+                integer_nth_root(radicand, n)
+                    .try_into()
+                    .map_err(|_| DecimalError::Overflow)?,
+                self.scale,
+                false,
+            )),
+            None => exp_fixed_point(ln_fixed_point(self)? / i128::from(n), self.scale),
+        }
+    }
+}
+
+/// Private function returning floor(radicand^(1/n)) for n >= 2 with Newton iterations,
+/// which decrease monotonically to the root from an estimate above it.
+fn integer_nth_root(radicand: U256, n: u32) -> U256 {
+    let n_minus_one = U256::from(n - 1);
+    let mut root = U256::one() << (radicand.bits() / n as usize + 1);
+    loop {
+        // a power beyond 256 bits is above the radicand, so the quotient is zero
+        let quotient = root
+            .checked_pow(n_minus_one)
+            .map_or(U256::zero(), |power| radicand / power);
+        let next = (root * n_minus_one + quotient) / U256::from(n);
+        if next >= root {
+            return root;
+        }
+        root = next;
     }
 }
 
@@ -1000,49 +1336,37 @@ impl Ln<Decimal> for Decimal {
 impl Sqrt<Decimal> for Decimal {
     fn sqrt(self) -> Result<Self, DecimalError> {
         let zero = Decimal::new(0, self.scale, false);
-        let one = Decimal::from_u128(1).to_scale(self.scale);
+        let one = Decimal::from_u128(1).to_scale(self.scale)?;
 
         if self.value.lt(&0u128) || self.value.gt(&u128::MAX) {
             return Err(DecimalError::ExceedsRange.into());
         }
 
-        if self.eq(zero).unwrap() || self.eq(one).unwrap() {
+        if self.eq(zero)? || self.eq(one)? {
             return Ok(self.clone());
         }
 
-        let value = self.value;
-        let value_scaled = match value.checked_mul(self.denominator()) {
-            Some(x) => x,
-            None => return Err(DecimalError::ExceedsPrecisionRange.into()),
-        };
-        let value_scaled_bit_length = 128u32
-            .checked_sub(value_scaled.leading_zeros())
-            .expect("bit_length");
-        let value_scaled_mid_length = value_scaled_bit_length
-            .checked_div(2)
-            .expect("value_scaled_mid_length");
-        let value_approx = 2u128
-            .checked_pow(value_scaled_mid_length)
-            .expect("value_approx");
-        let mut y = value_scaled.checked_div(value_approx).expect("y");
-        let mut y_0 = 0u128;
-        let threshold = 1u128;
+        // scale up in 256 bits, the root of a scaled u128 always fits back in a u128
+        let value_scaled = U256::from(self.value) * U256::from(self.denominator()?);
+        let value_scaled_mid_length = value_scaled.bits() / 2;
+        let value_approx = U256::one() << value_scaled_mid_length;
+        // value_approx <= value_scaled so every estimate is at least one
+        let mut y = value_scaled / value_approx;
+        let mut y_0 = U256::zero();
+        let threshold = U256::one();
 
         loop {
-            if y.gt(&y_0) && (y.checked_sub(y_0).unwrap()).gt(&threshold) {
-                let tmp_y = value_scaled.checked_div(y).unwrap();
+            if (y > y_0 && y - y_0 > threshold) || (y < y_0 && y_0 - y > threshold) {
+                let tmp_y = value_scaled / y;
                 y_0 = y;
-                y = y.checked_add(tmp_y).unwrap();
-                y = y >> 1;
-            } else if y.lt(&y_0) && (y_0.checked_sub(y).unwrap()).gt(&threshold) {
-                let tmp_y = value_scaled.checked_div(y).unwrap();
-                y_0 = y;
-                y = y.checked_add(tmp_y).unwrap();
-                y = y >> 1;
+                y = (y + tmp_y) >> 1;
             } else {
                 break;
             }
         }
+        let y: u128 = y
+            .try_into()
+            .map_err(|_| DecimalError::ExceedsPrecisionRange)?;
 
         Ok(Self {
             value: y,
@@ -1061,31 +1385,47 @@ pub trait Add<T>: Sized {
 }
 
 pub trait Div<T>: Sized {
-    fn div(self, rhs: T) -> Self;
+    fn div(self, rhs: T) -> Result<Self, DecimalError>;
 }
 
-pub trait DivScale<T> {
-    fn div_to_scale(self, rhs: T, to_scale: u8) -> Self;
+pub trait DivScale<T>: Sized {
+    fn div_to_scale(self, rhs: T, to_scale: u8) -> Result<Self, DecimalError>;
 }
 
 pub trait DivUp<T>: Sized {
-    fn div_up(self, rhs: T) -> Self;
+    fn div_up(self, rhs: T) -> Result<Self, DecimalError>;
 }
 
 pub trait Mul<T>: Sized {
-    fn mul(self, rhs: T) -> Self;
+    fn mul(self, rhs: T) -> Result<Self, DecimalError>;
 }
 
 pub trait MulUp<T>: Sized {
-    fn mul_up(self, rhs: T) -> Self;
+    fn mul_up(self, rhs: T) -> Result<Self, DecimalError>;
 }
 
 pub trait Ln<T>: Sized {
     fn ln(self) -> Result<Self, DecimalError>;
 }
 
+pub trait Exp<T>: Sized {
+    fn exp(self) -> Result<Self, DecimalError>;
+}
+
+pub trait Log2<T>: Sized {
+    fn log2(self) -> Result<Self, DecimalError>;
+}
+
+pub trait Log10<T>: Sized {
+    fn log10(self) -> Result<Self, DecimalError>;
+}
+
+pub trait NthRoot<T>: Sized {
+    fn nth_root(self, n: T) -> Result<Self, DecimalError>;
+}
+
 pub trait Pow<T>: Sized {
-    fn pow(self, rhs: T) -> Self;
+    fn pow(self, rhs: T) -> Result<Self, DecimalError>;
 }
 
 pub trait Sqrt<T>: Sized {
@@ -1103,14 +1443,13 @@ pub trait Compare<T>: Sized {
     fn gt(self, rhs: T) -> Result<bool, DecimalError>;
     fn gte(self, rhs: T) -> Result<bool, DecimalError>;
     fn lte(self, rhs: T) -> Result<bool, DecimalError>;
-    fn min(self, rhs: T) -> Self;
-    fn max(self, rhs: T) -> Self;
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    use hydra_math_simulator_rs::Model;
     use proptest::prelude::*;
 
     #[test]
@@ -1119,7 +1458,7 @@ mod test {
             // 1.000000 * 1.000000 = 1.000000
             let a = Decimal::from_scaled_amount(1_000000, 6);
             let b = Decimal::from_scaled_amount(1_000000, 6);
-            let actual = a.mul(b);
+            let actual = a.mul(b).unwrap();
             let expected = Decimal {
                 value: 1_000000,
                 scale: 6,
@@ -1129,10 +1468,10 @@ mod test {
         }
         {
             // 3/2 = 1.500000
-            let a = Decimal::from_u64(3).to_scale(6);
-            let b = Decimal::from_u64(2).to_scale(6);
+            let a = Decimal::from_u64(3).to_scale(6).unwrap();
+            let b = Decimal::from_u64(2).to_scale(6).unwrap();
 
-            let actual = a.div(b);
+            let actual = a.div(b).unwrap();
             let expected = Decimal {
                 value: 1_500000,
                 scale: 6,
@@ -1143,10 +1482,10 @@ mod test {
             assert_eq!(actual.scale, expected.scale);
 
             // 2/3 = 0.666667 rounded up
-            let a = Decimal::from_u64(2).to_scale(6);
-            let b = Decimal::from_u64(3).to_scale(6);
+            let a = Decimal::from_u64(2).to_scale(6).unwrap();
+            let b = Decimal::from_u64(3).to_scale(6).unwrap();
 
-            let actual = a.div_up(b);
+            let actual = a.div_up(b).unwrap();
             let expected = Decimal {
                 value: 666667,
                 scale: 6,
@@ -1157,10 +1496,10 @@ mod test {
             assert_eq!(actual.scale, expected.scale);
 
             // 2/3 = 0.666666 truncated (default)
-            let a = Decimal::from_u64(2).to_scale(6);
-            let b = Decimal::from_u64(3).to_scale(6);
+            let a = Decimal::from_u64(2).to_scale(6).unwrap();
+            let b = Decimal::from_u64(3).to_scale(6).unwrap();
 
-            let actual = a.div(b);
+            let actual = a.div(b).unwrap();
             let expected = Decimal {
                 value: 666666,
                 scale: 6,
@@ -1177,19 +1516,19 @@ mod test {
         // large number multiplication
         let lhs = Decimal::from_u128(17134659154348278833);
         let rhs = Decimal::from_u128(11676758639919526015);
-        let result = lhs.mul(rhs);
+        let result = lhs.mul(rhs).unwrap();
         let expected = Decimal::from_u128(200077279322612464128594731044417340495);
         assert_eq!(result, expected);
 
         let lhs = Decimal::from_u64(17134659154348278833);
         let rhs = Decimal::from_u64(11676758639919526015);
-        let result = lhs.mul(rhs);
+        let result = lhs.mul(rhs).unwrap();
         let expected = Decimal::from_u128(200077279322612464128594731044417340495);
         assert_eq!(result, expected);
 
         let lhs = Decimal::from_scaled_amount(17134659154348278833, 6);
         let rhs = Decimal::from_scaled_amount(11676758639919526015, 6);
-        let result = lhs.mul(rhs);
+        let result = lhs.mul(rhs).unwrap();
         let expected = Decimal::new(200077279322612464128594731044417, 6, false);
         assert_eq!(result, expected);
 
@@ -1197,7 +1536,7 @@ mod test {
         // 42^1.5 = 272.191109
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(1500000000000, 12, false);
-        let result = base.pow(exp).to_scale(6);
+        let result = base.pow(exp).unwrap().to_scale(6).unwrap();
         let expected = Decimal {
             value: 272_191109,
             scale: 6,
@@ -1209,7 +1548,7 @@ mod test {
         // 42^1.5 = 272.191110
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(1500000000000, 12, false);
-        let result = base.pow(exp).to_scale_up(6);
+        let result = base.pow(exp).unwrap().to_scale_up(6).unwrap();
         let expected = Decimal {
             value: 272_191110,
             scale: 6,
@@ -1218,21 +1557,21 @@ mod test {
         assert_eq!(result, expected);
 
         // square root of 2 with accuracy scaled to 12 decimal places
-        let n = Decimal::from_u64(2).to_compute_scale();
+        let n = Decimal::from_u64(2).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
         let expected = Decimal::new(1_414_213_562_373u128, 12, false);
         assert_eq!(result, expected);
 
         // square root of 2 with accuracy scaled to 8 decimal places
-        let n = Decimal::from_u64(2).to_scale(8);
+        let n = Decimal::from_u64(2).to_scale(8).unwrap();
         let result = n.sqrt().unwrap();
         let expected = Decimal::new(1_414_213_56_u128, 8, false);
         assert_eq!(result, expected);
 
         // square root of 2 with accuracy scaled to 6 decimal places
         // with last digit rounded up
-        let n = Decimal::from_u64(2).to_scale(8);
-        let result = n.sqrt().unwrap().to_scale_up(6);
+        let n = Decimal::from_u64(2).to_scale(8).unwrap();
+        let result = n.sqrt().unwrap().to_scale_up(6).unwrap();
         let expected = Decimal::new(1_414_214u128, 6, false);
         assert_eq!(result, expected);
     }
@@ -1248,20 +1587,20 @@ mod test {
             let lhs_decimal = Decimal::from_scaled_amount(lhs, scale);
             let rhs_decimal = Decimal::from_scaled_amount(rhs, scale);
             let lhs_f64: f64 = lhs_decimal.into();
-            let den_f64: f64 = lhs_decimal.denominator() as f64;
+            let den_f64: f64 = lhs_decimal.denominator().unwrap() as f64;
 
             // basic math both sides
             {
-                lhs_decimal.mul(rhs_decimal);
-                lhs_decimal.div(rhs_decimal);
+                lhs_decimal.mul(rhs_decimal).unwrap();
+                lhs_decimal.div(rhs_decimal).unwrap();
                 lhs_decimal.add(rhs_decimal).unwrap();
                 lhs_decimal.sub(rhs_decimal).unwrap();
             }
 
             // basic math one side
             {
-                lhs_decimal.mul(lhs_decimal);
-                lhs_decimal.div(lhs_decimal);
+                lhs_decimal.mul(lhs_decimal).unwrap();
+                lhs_decimal.div(lhs_decimal).unwrap();
                 lhs_decimal.add(lhs_decimal).unwrap();
                 lhs_decimal.sub(lhs_decimal).unwrap();
             }
@@ -1269,7 +1608,7 @@ mod test {
             // f64 sqrt == Decimal sqrt
             {
                 let sqrt_f64_u128 = (((lhs_f64.sqrt() * den_f64).round() / den_f64) * den_f64) as u128;
-                let sqrt_decimal_u128 = lhs_decimal.to_scale(scale).sqrt().unwrap().value;
+                let sqrt_decimal_u128 = lhs_decimal.to_scale(scale).unwrap().sqrt().unwrap().value;
                 let difference = sqrt_f64_u128.saturating_sub(sqrt_decimal_u128).lt(&precision);
 
                 assert!(difference, "sqrt compare\n{}\n{}", sqrt_f64_u128, sqrt_decimal_u128);
@@ -1278,7 +1617,7 @@ mod test {
             // f64 ln == Decimal ln
             {
                 let ln_f64_u128 = (((lhs_f64.ln() * den_f64).round() / den_f64) * den_f64) as u128;
-                let ln_decimal_u128 = lhs_decimal.to_scale(scale).ln().unwrap().value;
+                let ln_decimal_u128 = lhs_decimal.to_scale(scale).unwrap().ln().unwrap().value;
                 let difference = ln_f64_u128.saturating_sub(ln_decimal_u128).lt(&precision);
 
                 assert!(difference, "ln compare\n{}\n{}", ln_f64_u128, ln_decimal_u128);
@@ -1286,6 +1625,101 @@ mod test {
         }
     }
 
+    /// Signed value of a [Decimal] or of a (value, negative) reference at the same scale
+    fn signed_value((value, negative): (u128, bool)) -> i128 {
+        if negative {
+            -(value as i128)
+        } else {
+            value as i128
+        }
+    }
+
+    /// Python model computing reference values with 64 digits of precision
+    fn reference_model(scale: u8) -> Model {
+        Model::new("0".into(), "0".into(), 0, 0, "0".into(), scale)
+    }
+
+    proptest! {
+        #[test]
+        fn test_ln_against_reference(
+            value in prop_oneof![1..10u128.pow(18), 1..u128::MAX / 1_000_000],
+            scale in 0u8..=18,
+        ) {
+            let decimal = Decimal::new(value, scale, false);
+            let actual = decimal.ln().unwrap();
+            let expected = reference_model(scale).sim_ln(decimal.to_string());
+
+            // within 2 * 10^-16 and one unit of the truncated reference
+            let tolerance = 1 + 2 * 10i128.pow(scale.into()) / 10i128.pow(16);
+            let difference = signed_value((actual.value, actual.negative)) - signed_value(expected);
+            prop_assert!(difference.abs() <= tolerance, "ln({}) = {} vs reference {:?}", decimal, actual, expected);
+        }
+
+        #[test]
+        fn test_exp_against_reference(
+            value in 0..40_000_000_000_000u128, // |x| < 40
+            negative in any::<bool>(),
+        ) {
+            let decimal = Decimal::new(value, COMPUTE_SCALE, negative);
+            let actual = decimal.exp().unwrap().value;
+            let expected = reference_model(COMPUTE_SCALE).sim_exp(decimal.to_string());
+
+            // within a relative error of 10^-16 and one unit of the truncated reference
+            let difference = actual.max(expected) - actual.min(expected);
+            prop_assert!(difference <= expected / 10u128.pow(16) + 1, "exp({}) = {} vs reference {}", decimal, actual, expected);
+        }
+
+        #[test]
+        fn test_pow_against_reference(
+            base in 1..1_000_000_000_000_000u128, // x < 1000
+            exp in 0..4_000_000_000_000u128, // 0 <= y < 4
+        ) {
+            let base = Decimal::new(base, COMPUTE_SCALE, false);
+            let exp = Decimal::new(exp, COMPUTE_SCALE, false);
+            let actual = base.pow(exp).unwrap().value;
+            let expected = reference_model(COMPUTE_SCALE).sim_pow(base.to_string(), exp.to_string());
+
+            // within a relative error of 10^-15 and one unit of the truncated reference
+            let difference = actual.max(expected) - actual.min(expected);
+            prop_assert!(difference <= expected / 10u128.pow(15) + 1, "{}^{} = {} vs reference {}", base, exp, actual, expected);
+        }
+
+        #[test]
+        fn test_nth_root_bounds(
+            value in 1..10u128.pow(18),
+            scale in 0u8..=COMPUTE_SCALE,
+            n in 3u32..=5,
+        ) {
+            // root^n <= value * 10^(scale * (n - 1)) < (root + 1)^n at the scale of the value
+            let decimal = Decimal::new(value, scale, false);
+            let root = U256::from(decimal.nth_root(n).unwrap().value);
+            let radicand = U256::from(value) * U256::from(10).pow(U256::from(u32::from(scale) * (n - 1)));
+            let n = U256::from(n);
+            prop_assert!(root.pow(n) <= radicand && radicand < (root + 1).pow(n), "{}^(1/{}) = {}", decimal, n, root);
+        }
+
+        #[test]
+        fn test_bit_length_bounds(
+            value in 1..u128::MAX,
+            scale in 0u8..=COMPUTE_SCALE,
+        ) {
+            // 2^bit_length <= value / denominator < 2^(bit_length + 1)
+            let decimal = Decimal::new(value, scale, false);
+            let bit_length = decimal.bit_length().unwrap();
+            let bit_length = signed_value((bit_length.value, bit_length.negative));
+            let value = U256::from(value);
+            let denominator = U256::from(decimal.denominator().unwrap());
+            let in_bounds = if bit_length >= 0 {
+                let shift = bit_length as usize;
+                denominator << shift <= value && value < denominator << (shift + 1)
+            } else {
+                let shift = -bit_length as usize;
+                value << shift >= denominator && value << (shift - 1) < denominator
+            };
+            prop_assert!(in_bounds, "bit_length({}) = {}", decimal, bit_length);
+        }
+    }
+
     #[test]
     fn test_new() {
         {
@@ -1307,14 +1741,14 @@ mod test {
     fn test_denominator() {
         {
             let decimal = Decimal::new(42, 2, false);
-            let actual = decimal.denominator();
+            let actual = decimal.denominator().unwrap();
             let expected = 10u128.pow(2);
             assert_eq!(actual, expected);
         }
 
         {
             let decimal = Decimal::new(42, 0, false);
-            let actual = decimal.denominator();
+            let actual = decimal.denominator().unwrap();
             let expected = 1;
             assert_eq!(actual, expected);
         }
@@ -1352,7 +1786,7 @@ mod test {
     #[test]
     fn test_to_u64() {
         let decimal = Decimal::new(69420, 6, false);
-        let actual = decimal.to_u64();
+        let actual = decimal.to_u64().unwrap();
         let expected: u64 = 69420;
 
         assert_eq!(actual, expected);
@@ -1361,22 +1795,22 @@ mod test {
     #[test]
     fn test_abs() {
         let decimal = Decimal::new(0, 0, false);
-        assert_eq!(decimal.abs(), 0);
+        assert_eq!(decimal.abs().unwrap(), 0);
 
         let decimal = Decimal::new(42, 0, false);
-        assert_eq!(decimal.abs(), 42);
+        assert_eq!(decimal.abs().unwrap(), 42);
 
         let decimal = Decimal::new(4269420, 5, false);
-        assert_eq!(decimal.abs(), 42);
+        assert_eq!(decimal.abs().unwrap(), 42);
 
         let decimal = Decimal::new(4269420, 5, true);
-        assert_eq!(decimal.abs(), 42);
+        assert_eq!(decimal.abs().unwrap(), 42);
 
         let decimal = Decimal::new(4269420, 5, false);
-        assert_eq!(decimal.abs_up(), 43);
+        assert_eq!(decimal.abs_up().unwrap(), 43);
 
         let decimal = Decimal::new(4269420, 5, true);
-        assert_eq!(decimal.abs_up(), 43);
+        assert_eq!(decimal.abs_up().unwrap(), 43);
     }
 
     #[test]
@@ -1384,7 +1818,7 @@ mod test {
         // increase precision
         {
             let decimal = Decimal::new(42, 2, false);
-            let result = decimal.to_scale(3);
+            let result = decimal.to_scale(3).unwrap();
 
             assert_eq!(result.scale, 3);
             assert_eq!({ result.value }, 420);
@@ -1392,7 +1826,7 @@ mod test {
         // decrease precision
         {
             let decimal = Decimal::new(42, 2, false);
-            let result = decimal.to_scale(1);
+            let result = decimal.to_scale(1).unwrap();
 
             assert_eq!(result.scale, 1);
             assert_eq!({ result.value }, 4);
@@ -1400,7 +1834,7 @@ mod test {
         // decrease precision past value
         {
             let decimal = Decimal::new(123, 4, false);
-            let result = decimal.to_scale(0);
+            let result = decimal.to_scale(0).unwrap();
 
             assert_eq!(result.scale, 0);
             assert_eq!({ result.value }, 0);
@@ -1412,7 +1846,7 @@ mod test {
         // increase precision
         {
             let decimal = Decimal::new(42, 2, false);
-            let result = decimal.to_scale_up(3);
+            let result = decimal.to_scale_up(3).unwrap();
 
             assert_eq!(result.scale, 3);
             assert_eq!({ result.value }, 420);
@@ -1420,7 +1854,7 @@ mod test {
         // decrease precision
         {
             let decimal = Decimal::new(42, 2, false);
-            let result = decimal.to_scale_up(1);
+            let result = decimal.to_scale_up(1).unwrap();
 
             assert_eq!(result.scale, 1);
             assert_eq!({ result.value }, 5);
@@ -1428,7 +1862,7 @@ mod test {
         // decrease precision past value
         {
             let decimal = Decimal::new(123, 4, false);
-            let result = decimal.to_scale_up(0);
+            let result = decimal.to_scale_up(0).unwrap();
 
             assert_eq!(result.scale, 0);
             assert_eq!({ result.value }, 1);
@@ -1439,7 +1873,7 @@ mod test {
     fn test_mul_decimal() {
         let decimal = Decimal::new(1234, 3, false);
         let multiply_by = Decimal::new(4321, 5, false);
-        let actual = decimal.mul(multiply_by);
+        let actual = decimal.mul(multiply_by).unwrap();
         let expected = Decimal::new(53, 3, false);
 
         assert_eq!({ actual.value }, { expected.value });
@@ -1447,11 +1881,56 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    fn test_mul_decimal_panic() {
+    fn test_mul_decimal_no_overflow() {
+        // the product overflows a u128 but the 256 bits intermediate doesn't
         let decimal = Decimal::new(u128::MAX - 1, 3, false);
         let multiply_by = Decimal::new(2, 3, false);
-        decimal.mul(multiply_by);
+        let expected = Decimal::new((u128::MAX - 1) / 500, 3, false);
+        assert_eq!(decimal.mul(multiply_by).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_mul_decimal_overflow() {
+        let decimal = Decimal::new(u128::MAX - 1, 3, false);
+        let multiply_by = Decimal::new(2_000, 3, false);
+        assert_eq!(decimal.mul(multiply_by), Err(DecimalError::Overflow));
+    }
+
+    #[test]
+    fn test_mul_div_sqrt_u256_intermediate() {
+        // full u64 reserves of 18 decimals tokens, x * y overflows a u128 before scaling down
+        let x = Decimal::new(u64::MAX.into(), 18, false);
+        let y = Decimal::new(u64::MAX.into(), 18, false);
+
+        let k = x.mul(y).unwrap();
+        assert_eq!(k, Decimal::new(340_282_366_920_938_463_426, 18, false));
+        assert_eq!(x.pow(2u128).unwrap(), k);
+        assert_eq!(
+            x.mul_up(y).unwrap(),
+            Decimal::new(340_282_366_920_938_463_427, 18, false)
+        );
+
+        let x_from_k = k.div(y).unwrap();
+        assert_eq!(
+            x_from_k,
+            Decimal::new(18_446_744_073_709_551_614, 18, false)
+        );
+        assert_eq!(
+            k.div_up(y).unwrap(),
+            Decimal::new(18_446_744_073_709_551_615, 18, false)
+        );
+
+        let root_k = k.sqrt().unwrap();
+        assert_eq!(root_k, Decimal::new(18_446_744_073_709_551_614, 18, false));
+
+        // only the result has to fit in a u128
+        let doubled_down = Decimal::new(u128::MAX - 1, 3, false)
+            .mul(Decimal::new(2, 3, false))
+            .unwrap();
+        assert_eq!(
+            doubled_down.value,
+            680_564_733_841_876_926_926_749_214_863_536_422
+        );
     }
 
     #[test]
@@ -1459,7 +1938,7 @@ mod test {
         {
             let decimal = Decimal::new(9876, 2, false);
             let multiply_by: u128 = 555;
-            let actual = decimal.mul(multiply_by);
+            let actual = decimal.mul(multiply_by).unwrap();
             let expected = Decimal::new(5481180, 2, false);
 
             assert_eq!({ actual.value }, { expected.value });
@@ -1468,11 +1947,10 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    fn test_mul_u128_panic() {
+    fn test_mul_u128_overflow() {
         let decimal = Decimal::new(u128::MAX - 1, 2, false);
         let multiply_by = 2;
-        decimal.mul(multiply_by);
+        assert_eq!(decimal.mul(multiply_by), Err(DecimalError::Overflow));
     }
 
     #[test]
@@ -1556,11 +2034,10 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "checked_add")]
-    fn test_add_panic() {
+    fn test_add_overflow() {
         let decimal = Decimal::new(u128::MAX - 1, 2, false);
         let increase_by = Decimal::new(2, 2, false);
-        assert!(decimal.add(increase_by).is_err());
+        assert_eq!(decimal.add(increase_by), Err(DecimalError::Overflow));
     }
 
     #[test]
@@ -1668,7 +2145,7 @@ mod test {
         {
             let decimal = Decimal::new(20, 8, false);
             let divide_by = Decimal::new(2, 3, false);
-            let actual = decimal.div(divide_by);
+            let actual = decimal.div(divide_by).unwrap();
             let expected = Decimal::new(10000, 8, false);
 
             assert_eq!({ actual.value }, { expected.value });
@@ -1677,7 +2154,7 @@ mod test {
         {
             let decimal = Decimal::new(20, 8, false);
             let divide_by = Decimal::new(3, 3, false);
-            let actual = decimal.div(divide_by);
+            let actual = decimal.div(divide_by).unwrap();
             let expected = Decimal::new(6666, 8, false);
 
             assert_eq!({ actual.value }, { expected.value });
@@ -1685,18 +2162,17 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "checked_div")]
-    fn test_div_panic() {
+    fn test_div_by_zero() {
         let decimal = Decimal::new(10, 3, false);
         let divide_by = Decimal::new(0, 1, false);
-        decimal.div(divide_by);
+        assert_eq!(decimal.div(divide_by), Err(DecimalError::DivisionByZero));
     }
 
     #[test]
     fn test_into_u64() {
         {
             let decimal = Decimal::new(333333333333333, 15, false);
-            let actual: u64 = decimal.into();
+            let actual = u64::try_from(decimal).unwrap();
             let expected: u64 = 333333333333333;
 
             assert_eq!(actual, expected);
@@ -1800,11 +2276,92 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    #[allow(unused_variables)]
-    fn test_into_u64_panic() {
+    fn test_to_from_bytes() {
+        {
+            // value in little endian, then the scale with the sign in its high bit
+            let decimal = Decimal::new(1_500_000, 6, true);
+            let bytes = decimal.to_bytes().unwrap();
+            assert_eq!(&bytes[..16], &1_500_000u128.to_le_bytes());
+            assert_eq!(bytes[16], 0x86);
+            assert_eq!(Decimal::from_bytes(bytes).unwrap(), decimal);
+        }
+
+        {
+            // extremes round trip
+            for decimal in [
+                Decimal::new(0, 0, false),
+                Decimal::new(u128::MAX, MAX_SCALE, true),
+                Decimal::new(u128::MAX, COMPUTE_SCALE, false),
+            ] {
+                let bytes = decimal.to_bytes().unwrap();
+                assert_eq!(Decimal::from_bytes(bytes).unwrap(), decimal);
+            }
+        }
+
+        {
+            // scales without a u128 denominator aren't encoded
+            let decimal = Decimal::new(1, MAX_SCALE + 1, false);
+            assert_eq!(decimal.to_bytes(), Err(DecimalError::ExceedsPrecisionRange));
+
+            let mut bytes = [0u8; DECIMAL_BYTES];
+            bytes[16] = MAX_SCALE + 1;
+            assert_eq!(
+                Decimal::from_bytes(bytes),
+                Err(DecimalError::ExceedsPrecisionRange)
+            );
+        }
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh() {
+        use borsh::{BorshDeserialize, BorshSerialize};
+
+        let decimal = Decimal::new(178_367_579, COMPUTE_SCALE, false);
+        let encoded = decimal.try_to_vec().unwrap();
+        assert_eq!(encoded.len(), DECIMAL_BYTES);
+        assert_eq!(encoded, decimal.to_bytes().unwrap());
+        assert_eq!(Decimal::try_from_slice(&encoded).unwrap(), decimal);
+
+        // invalid scale and truncated input
+        assert!(Decimal::new(1, MAX_SCALE + 1, false).try_to_vec().is_err());
+        assert!(Decimal::try_from_slice(&encoded[..16]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Observation {
+            price: Decimal,
+            #[serde(with = "super::serde_structured")]
+            ewma: Decimal,
+        }
+
+        let observation = Observation {
+            price: Decimal::new(3_420_500_000, 6, true),
+            ewma: Decimal::new(178_367_579, COMPUTE_SCALE, false),
+        };
+        let json = serde_json::to_string(&observation).unwrap();
+        assert_eq!(
+            json,
+            r#"{"price":"-3420.500000","ewma":{"value":178367579,"scale":12,"negative":false}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Observation>(&json).unwrap(),
+            observation
+        );
+
+        // the string form keeps the scale
+        let decimal: Decimal = serde_json::from_str(r#""0.000100""#).unwrap();
+        assert_eq!(decimal, Decimal::new(100, 6, false));
+        assert!(serde_json::from_str::<Decimal>(r#""1.2.3""#).is_err());
+    }
+
+    #[test]
+    fn test_into_u64_overflow() {
         let decimal = Decimal::new(u128::MAX - 1, 15, false);
-        let result: u64 = decimal.into();
+        assert_eq!(u64::try_from(decimal), Err(DecimalError::Overflow));
     }
 
     #[test]
@@ -2147,6 +2704,147 @@ mod test {
 
             assert_eq!(decimal, result);
         }
+
+        {
+            // across scales, 0.10 < 0.11000
+            let decimal = Decimal::new(10, 2, false);
+            let other = Decimal::new(11_000, 5, false);
+            assert_eq!(decimal.min(other), decimal);
+            assert_eq!(decimal.max(other), other);
+        }
+    }
+
+    #[test]
+    fn test_ord_eq_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(decimal: Decimal) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            decimal.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        {
+            // the same number at different scales, and zero whatever its sign
+            let pairs = [
+                (
+                    Decimal::new(15, 1, false),
+                    Decimal::new(1_500_000, 6, false),
+                ),
+                (Decimal::new(15, 1, true), Decimal::new(1_500_000, 6, true)),
+                (Decimal::new(0, 0, true), Decimal::zero()),
+                (
+                    Decimal::new(42, 0, false),
+                    Decimal::from_u64_to_compute_scale(42),
+                ),
+            ];
+            for (a, b) in pairs {
+                assert_eq!(a, b);
+                assert_eq!(a.cmp(&b), Ordering::Equal);
+                assert_eq!(hash(a), hash(b));
+            }
+        }
+
+        {
+            // ordered by value rather than by fields
+            let mut decimals = vec![
+                Decimal::new(2, 0, false),
+                Decimal::new(15, 1, true),
+                Decimal::new(999_999, 6, false),
+                Decimal::new(0, 3, true),
+                Decimal::new(2, 0, true),
+                Decimal::new(1_000_001, 6, false),
+            ];
+            decimals.sort();
+            assert_eq!(
+                decimals,
+                vec![
+                    Decimal::new(2, 0, true),
+                    Decimal::new(15, 1, true),
+                    Decimal::zero(),
+                    Decimal::new(999_999, 6, false),
+                    Decimal::new(1_000_001, 6, false),
+                    Decimal::new(2, 0, false),
+                ]
+            );
+            assert!(Decimal::new(1, 0, false) > Decimal::new(999_999_999, 9, false));
+            assert!(Decimal::new(1, 0, true) < Decimal::new(999_999_999, 9, true));
+        }
+
+        {
+            // scale differences beyond 256 bits
+            let large = Decimal::new(1, 0, false);
+            let tiny = Decimal::new(u128::MAX, 255, false);
+            assert!(large > tiny);
+            assert!(tiny < large);
+        }
+    }
+
+    #[test]
+    fn test_std_ops() {
+        let a = Decimal::from_u64_to_compute_scale(6);
+        let b = Decimal::new(1_500_000_000_000, COMPUTE_SCALE, true);
+
+        assert_eq!(a + b, Decimal::new(4_500_000_000_000, COMPUTE_SCALE, false));
+        assert_eq!(a - b, Decimal::new(7_500_000_000_000, COMPUTE_SCALE, false));
+        assert_eq!(a * b, Decimal::new(9_000_000_000_000, COMPUTE_SCALE, true));
+        assert_eq!(a / b, Decimal::new(4_000_000_000_000, COMPUTE_SCALE, true));
+        assert_eq!(a * b / b - a, Decimal::zero());
+
+        let mut c = a;
+        c += b;
+        c *= b;
+        c -= b;
+        c /= b;
+        assert_eq!(c, Decimal::new(3_500_000_000_000, COMPUTE_SCALE, false));
+
+        // checked variants return None where the fallible traits return an error
+        assert_eq!(a.checked_div(b), Some(a / b));
+        assert_eq!(a.checked_div(Decimal::zero()), None);
+        assert_eq!(a.checked_add(Decimal::new(1, 6, false)), None);
+        assert_eq!(
+            Decimal::new(u128::MAX, 0, false).checked_add(Decimal::from_u128(1)),
+            None
+        );
+        assert_eq!(
+            Decimal::new(u128::MAX, 0, false).checked_mul(Decimal::from_u128(2)),
+            None
+        );
+        assert_eq!(a.checked_sub(a), Some(Decimal::zero()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn test_std_div_by_zero() {
+        let _ = Decimal::one() / Decimal::zero();
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let decimals = [
+            Decimal::new(1_500_000, 6, false),
+            Decimal::new(2_000_000, 6, false),
+            Decimal::new(500_000, 6, true),
+        ];
+
+        assert_eq!(
+            decimals.iter().sum::<Decimal>(),
+            Decimal::new(3_000_000, 6, false)
+        );
+        assert_eq!(
+            decimals.iter().copied().product::<Decimal>(),
+            Decimal::new(1_500_000, 6, true)
+        );
+
+        // zero and one when empty
+        assert_eq!(
+            std::iter::empty::<Decimal>().sum::<Decimal>(),
+            Decimal::zero()
+        );
+        assert_eq!(
+            std::iter::empty::<Decimal>().product::<Decimal>(),
+            Decimal::one()
+        );
     }
 
     #[test]
@@ -2206,19 +2904,19 @@ mod test {
     #[test]
     fn test_is_integer() {
         let decimal = Decimal::new(0, 0, false);
-        assert_eq!(decimal.is_integer(), true);
+        assert_eq!(decimal.is_integer().unwrap(), true);
 
         let decimal = Decimal::new(42, 0, false);
-        assert_eq!(decimal.is_integer(), true);
+        assert_eq!(decimal.is_integer().unwrap(), true);
 
         let decimal = Decimal::new(42, 0, true);
-        assert_eq!(decimal.is_integer(), true);
+        assert_eq!(decimal.is_integer().unwrap(), true);
 
         let decimal = Decimal::new(42420, 3, false);
-        assert_eq!(decimal.is_integer(), false);
+        assert_eq!(decimal.is_integer().unwrap(), false);
 
         let decimal = Decimal::new(42420, 3, true);
-        assert_eq!(decimal.is_integer(), false);
+        assert_eq!(decimal.is_integer().unwrap(), false);
     }
 
     #[test]
@@ -2228,26 +2926,26 @@ mod test {
             let scale: u8 = 6;
             let base = Decimal::new(0, scale, false);
             let exp: u128 = 100;
-            let result = base.pow(exp);
+            let result = base.pow(exp).unwrap();
             let expected = Decimal::new(0, scale, false);
             assert_eq!(result, expected);
         }
 
         // n**0 = 1
         let scale: u8 = 6;
-        let base = Decimal::from_u64(10).to_scale(scale);
+        let base = Decimal::from_u64(10).to_scale(scale).unwrap();
         let exp: u128 = 0;
-        let result = base.pow(exp);
-        let expected = Decimal::from_u64(1).to_scale(scale);
+        let result = base.pow(exp).unwrap();
+        let expected = Decimal::from_u64(1).to_scale(scale).unwrap();
         assert_eq!(result, expected);
 
         // 2**18 = 262,144
         {
             let scale: u8 = 6;
-            let base = Decimal::from_u64(2).to_scale(scale);
+            let base = Decimal::from_u64(2).to_scale(scale).unwrap();
             let exp: u128 = 18;
-            let result = base.pow(exp);
-            let expected = Decimal::from_u64(262_144).to_scale(scale);
+            let result = base.pow(exp).unwrap();
+            let expected = Decimal::from_u64(262_144).to_scale(scale).unwrap();
             assert_eq!(result, expected);
         }
 
@@ -2255,7 +2953,7 @@ mod test {
         {
             let base = Decimal::from_str("-0.001459854015").unwrap();
             let exp: u128 = 2;
-            let result = base.pow(exp);
+            let result = base.pow(exp).unwrap();
             let expected = Decimal::from_str("0.000002131173").unwrap();
             assert_eq!(result, expected);
         }
@@ -2264,10 +2962,12 @@ mod test {
         {
             let mut base = Decimal::from_u64(3420)
                 .to_compute_scale()
-                .div(Decimal::from_u64(3425).to_compute_scale());
+                .unwrap()
+                .div(Decimal::from_u64(3425).to_compute_scale().unwrap())
+                .unwrap();
             base = base.sub(Decimal::one()).unwrap();
             let exp: u128 = 2;
-            let result = base.pow(exp);
+            let result = base.pow(exp).unwrap();
             let expected = Decimal::from_str("0.000002131173").unwrap();
             assert_eq!(result, expected);
         }
@@ -2276,7 +2976,7 @@ mod test {
         {
             let base = Decimal::new(3_41200000, 8, false);
             let exp: u128 = 8;
-            let result = base.pow(exp);
+            let result = base.pow(exp).unwrap();
             let expected = Decimal::new(18368_43602280, 8, false);
             assert_eq!(result, expected);
         }
@@ -2287,65 +2987,97 @@ mod test {
         // 42^-0.25 = 0.3928146509
         let base = Decimal::new(42_000000, 6, false);
         let exp = Decimal::new(250000, 6, true);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(392814, 6, false);
         assert_eq!(result, expected);
 
         // 42^-1 = 0.02380952381
         let base = Decimal::new(42_000000, 6, false);
         let exp = Decimal::new(1_000000, 6, true);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(23809, 6, false);
         assert_eq!(result, expected);
 
         // 42^0 = 1
         let base = Decimal::new(42_000000, 6, false);
         let exp = Decimal::new(0, 6, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(1_000000, 6, false);
         assert_eq!(result, expected);
 
         // 42^0.25 = 2.545729895021831
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(250000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(2_545_729_895_021u128, 12, false);
         assert_eq!(result, expected);
 
         // 42^0.5 = 6.48074069840786
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(500000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(6_480_740_698_407u128, 12, false);
         assert_eq!(result, expected);
 
         // 42^1 = 42
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(1000000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(42_000000000000u128, 12, false);
         assert_eq!(result, expected);
 
         // 42^1.25 = 106.920655590916882
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(1250000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(106_920_655_590_882u128, 12, false);
         assert_eq!(result, expected);
 
         // 42^1.5 = 272.19110933313013
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(1500000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(272_191_109_333_094, 12, false);
         assert_eq!(result, expected);
 
         // 42^2 = 1764
         let base = Decimal::new(42_000000000000, 12, false);
         let exp = Decimal::new(2000000000000, 12, false);
-        let result = base.pow(exp);
+        let result = base.pow(exp).unwrap();
         let expected = Decimal::new(1764_000000000000u128, 12, false);
         assert_eq!(result, expected);
+
+        // 42^0.3 = 3.068843822095697
+        let base = Decimal::new(42_000000000000, 12, false);
+        let exp = Decimal::new(300000000000, 12, false);
+        let result = base.pow(exp).unwrap();
+        let expected = Decimal::new(3_068_843_822_095u128, 12, false);
+        assert_eq!(result, expected);
+
+        // 42^-0.3 = 1/42^0.3 = 0.325855617936
+        let base = Decimal::new(42_000000000000, 12, false);
+        let exp = Decimal::new(300000000000, 12, true);
+        let result = base.pow(exp).unwrap();
+        let expected = Decimal::new(325_855_617_936u128, 12, false);
+        assert_eq!(result, expected);
+
+        // 10^2.5 = 316.227766016837933
+        let base = Decimal::new(10_000000000000, 12, false);
+        let exp = Decimal::new(2500000000000, 12, false);
+        let result = base.pow(exp).unwrap();
+        let expected = Decimal::new(316_227_766_016_837u128, 12, false);
+        assert_eq!(result, expected);
+
+        // 0^0.3 = 0
+        let base = Decimal::new(0, 12, false);
+        let exp = Decimal::new(300000000000, 12, false);
+        let result = base.pow(exp).unwrap();
+        assert_eq!(result, Decimal::new(0, 12, false));
+
+        // (-8)^0.3 isn't a real number
+        let base = Decimal::new(8_000000000000, 12, true);
+        let exp = Decimal::new(300000000000, 12, false);
+        assert_eq!(base.pow(exp), Err(DecimalError::UnsupportedExponent));
     }
 
     #[test]
@@ -2354,7 +3086,7 @@ mod test {
         {
             let a = Decimal::new(1, 12, false);
             let b = Decimal::new(1, 12, false);
-            assert_eq!(a.mul_up(b), Decimal::new(1, 12, false));
+            assert_eq!(a.mul_up(b).unwrap(), Decimal::new(1, 12, false));
         }
 
         // mul same precision
@@ -2362,7 +3094,7 @@ mod test {
         {
             let a = Decimal::new(1000000, 6, false);
             let b = Decimal::new(300000, 6, false);
-            assert_eq!(a.mul_up(b), Decimal::new(300000, 6, false));
+            assert_eq!(a.mul_up(b).unwrap(), Decimal::new(300000, 6, false));
         }
 
         // mul by zero
@@ -2370,14 +3102,14 @@ mod test {
         {
             let a = Decimal::new(100, 2, false);
             let b = Decimal::new(0, 0, false);
-            assert_eq!(a.mul_up(b), Decimal::new(0, 2, false));
+            assert_eq!(a.mul_up(b).unwrap(), Decimal::new(0, 2, false));
         }
 
         // mul different decimals increases precision
         {
             let a = Decimal::new(1_000_000_000, 9, false);
             let b = Decimal::new(3, 6, false);
-            assert_eq!(a.mul_up(b), Decimal::new(3000, 9, false));
+            assert_eq!(a.mul_up(b).unwrap(), Decimal::new(3000, 9, false));
         }
     }
 
@@ -2387,14 +3119,14 @@ mod test {
         {
             let a = Decimal::new(0, 0, false);
             let b = Decimal::new(1, 0, false);
-            assert_eq!(a.div_up(b), Decimal::new(0, 0, false));
+            assert_eq!(a.div_up(b).unwrap(), Decimal::new(0, 0, false));
         }
 
         // 1/2 = 1 rounded up
         {
             let a = Decimal::new(1, 0, false);
             let b = Decimal::new(2, 0, false);
-            assert_eq!(a.div_up(b), Decimal::new(1, 0, false));
+            assert_eq!(a.div_up(b).unwrap(), Decimal::new(1, 0, false));
         }
 
         // 200,000.000001/2 = 100000.000001 rounded up
@@ -2403,6 +3135,7 @@ mod test {
             let b = Decimal::new(2_000, 3, false);
             assert!(!a
                 .div_up(b)
+                .unwrap()
                 .lt(Decimal::new(100_000_000_001, 6, false))
                 .unwrap());
         }
@@ -2411,7 +3144,7 @@ mod test {
         {
             let a = Decimal::new(42, 2, false);
             let b = Decimal::new(10, 0, false);
-            assert_eq!(a.div_up(b), Decimal::new(5, 2, false));
+            assert_eq!(a.div_up(b).unwrap(), Decimal::new(5, 2, false));
         }
     }
 
@@ -2424,20 +3157,20 @@ mod test {
 
             // to_scale == scale
             let to_scale = 8;
-            let result = nominator.div_to_scale(denominator, to_scale);
-            let expected = Decimal::from_u64(5_000).to_scale(to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
+            let expected = Decimal::from_u64(5_000).to_scale(to_scale).unwrap();
             assert_eq!(result, expected);
 
             // to_scale > scale
             let to_scale = 11;
-            let result = nominator.div_to_scale(denominator, to_scale);
-            let expected = Decimal::from_u64(5_000).to_scale(to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
+            let expected = Decimal::from_u64(5_000).to_scale(to_scale).unwrap();
             assert_eq!(result, expected);
 
             // to_scale < scale
             let to_scale = 5;
-            let result = nominator.div_to_scale(denominator, to_scale);
-            let expected = Decimal::from_u64(5_000).to_scale(to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
+            let expected = Decimal::from_u64(5_000).to_scale(to_scale).unwrap();
             assert_eq!(result, expected);
         }
 
@@ -2448,19 +3181,19 @@ mod test {
 
             // to_scale == nominator scale
             let to_scale = 7;
-            let result = nominator.div_to_scale(denominator, to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
             let expected = Decimal::new(7000, to_scale, false);
             assert_eq!(result, expected);
 
             // to_scale > nominator scale
             let to_scale = 9;
-            let result = nominator.div_to_scale(denominator, to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
             let expected = Decimal::new(700_000, to_scale, false);
             assert_eq!(result, expected);
 
             // to_scale < nominator scale
             let to_scale = 5;
-            let result = nominator.div_to_scale(denominator, to_scale);
+            let result = nominator.div_to_scale(denominator, to_scale).unwrap();
             let expected = Decimal::new(70, to_scale, false);
             assert_eq!(result, expected);
         }
@@ -2475,38 +3208,38 @@ mod test {
         // are given in the following specs.
 
         // 0**0.5 = 0
-        let n = Decimal::from_u64(0).to_compute_scale();
+        let n = Decimal::from_u64(0).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
-        let expected = Decimal::from_u64(0).to_compute_scale();
+        let expected = Decimal::from_u64(0).to_compute_scale().unwrap();
         assert_eq!(result, expected);
 
         // 1**0.5 = 1
-        let n = Decimal::from_u64(1).to_compute_scale();
+        let n = Decimal::from_u64(1).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
-        let expected = Decimal::from_u64(1).to_compute_scale();
+        let expected = Decimal::from_u64(1).to_compute_scale().unwrap();
         assert_eq!(result, expected);
 
         // 2**0.5 = 1.414213562373
-        let n = Decimal::from_u64(2).to_compute_scale();
+        let n = Decimal::from_u64(2).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
         let expected = Decimal::new(1_414_213_562_373u128, 12, false);
         assert_eq!(result, expected);
 
         // 3**0.5 = 1.7320508076
-        let n = Decimal::from_u64(3).to_compute_scale();
+        let n = Decimal::from_u64(3).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
         let expected = Decimal::new(1_732_050_807_568u128, 12, false);
         assert_eq!(result, expected);
 
         // 4**0.5 = 2
-        let n = Decimal::from_u64(4).to_compute_scale();
+        let n = Decimal::from_u64(4).to_compute_scale().unwrap();
         let result = n.sqrt().unwrap();
-        let expected = Decimal::from_u64(2).to_compute_scale();
+        let expected = Decimal::from_u64(2).to_compute_scale().unwrap();
         assert_eq!(result, expected);
 
         // MAX**0.5 = 4294967296
-        let n = Decimal::from_u64(u64::MAX).to_scale(6);
-        let result = n.sqrt().unwrap().to_scale_up(0).value;
+        let n = Decimal::from_u64(u64::MAX).to_scale(6).unwrap();
+        let result = n.sqrt().unwrap().to_scale_up(0).unwrap().value;
         let expected = 4294967296u128;
         assert_eq!(result, expected);
 
@@ -2580,7 +3313,7 @@ mod test {
             // ln(0.1) = -2.302_585_092_994
 
             let expected = Decimal {
-                value: 2_302_585_092_991u128,
+                value: 2_302_585_092_994u128,
                 scale: 12,
                 negative: true,
             };
@@ -2594,15 +3327,15 @@ mod test {
         }
 
         {
-            // ln(10) = 2.302_585_092_987
+            // ln(10) = 2.302_585_092_994
 
             let expected = Decimal {
-                value: 2_302_585_092_924u128,
+                value: 2_302_585_092_994u128,
                 scale: 12,
                 negative: false,
             };
 
-            let n = Decimal::from_u64(10).to_compute_scale();
+            let n = Decimal::from_u64(10).to_compute_scale().unwrap();
             let actual = n.ln().unwrap();
 
             assert_eq!({ actual.value }, { expected.value });
@@ -2610,21 +3343,233 @@ mod test {
         }
 
         // MAX u64
-        // TODO: work on accuracy at high end of u64 range
-        // {
-        //     // ln(18446744073709551615) = 44.36141956
-        //     let expected = Decimal {
-        //         value: 44_36141956u128,
-        //         scale: 6,
-        //         negative: false,
-        //     };
-        //
-        //     let n = Decimal::from_amount(u64::MAX);
-        //     let actual = n.ln().unwrap();
-        //
-        //     assert_eq!({ actual.value }, { expected.value });
-        //     assert_eq!(actual.scale, expected.scale);
-        // }
+        {
+            // ln(18446744073709551615) = 44.361419
+            let expected = Decimal {
+                value: 44_361419u128,
+                scale: 6,
+                negative: false,
+            };
+
+            let n = Decimal::from_u64(u64::MAX).to_scale(6).unwrap();
+            let actual = n.ln().unwrap();
+
+            assert_eq!({ actual.value }, { expected.value });
+            assert_eq!(actual.scale, expected.scale);
+        }
+
+        // ln(0) is undefined
+        {
+            let n = Decimal::zero();
+            assert_eq!(n.ln(), Err(DecimalError::ExceedsRange));
+        }
+    }
+
+    #[test]
+    fn test_exp() {
+        {
+            // e^0 = 1
+            let n = Decimal::zero();
+            assert_eq!(n.exp().unwrap(), Decimal::one());
+        }
+
+        {
+            // e^1 = 2.718_281_828_459
+            let n = Decimal::one();
+            let expected = Decimal::new(2_718_281_828_459, COMPUTE_SCALE, false);
+            assert_eq!(n.exp().unwrap(), expected);
+        }
+
+        {
+            // e^-2.5 = 0.082_084_998_623
+            let n = Decimal::new(2_500_000_000_000, COMPUTE_SCALE, true);
+            let expected = Decimal::new(82_084_998_623, COMPUTE_SCALE, false);
+            assert_eq!(n.exp().unwrap(), expected);
+        }
+
+        {
+            // e^42 = 1739274941520501047.39, within a relative error of 10^-16
+            let n = Decimal::from_u64(42);
+            let actual = n.exp().unwrap().value;
+            let expected = 1739274941520501047u128;
+            assert!(actual.max(expected) - actual.min(expected) <= expected / 10u128.pow(16));
+        }
+
+        {
+            // e^ln(x) = x, within the relative error of 10^-12 of ln(x) truncated to COMPUTE_SCALE
+            let n = Decimal::from_u64(3420).to_compute_scale().unwrap();
+            let actual = n.ln().unwrap().exp().unwrap().value;
+            assert!(actual.max(n.value) - actual.min(n.value) <= n.value / 10u128.pow(12));
+        }
+
+        {
+            // e^-100 truncates to zero, e^100 doesn't fit
+            let n = Decimal::new(100, 0, true);
+            assert_eq!(n.exp().unwrap(), Decimal::new(0, 0, false));
+
+            let n = Decimal::new(100, 0, false);
+            assert_eq!(n.exp(), Err(DecimalError::Overflow));
+        }
+    }
+
+    #[test]
+    fn test_mul_ln_ratio_and_pow_ratio() {
+        let within = |actual: Decimal, expected: u128, units: u128| {
+            actual.value.max(expected) - actual.value.min(expected) <= units
+        };
+        let k_div_q = Decimal::from_u64_to_compute_scale(3_000_000);
+        let q0 = Decimal::from_u64_to_compute_scale(1_000_000);
+        let q_new = Decimal::from_u64_to_compute_scale(1_000_001);
+
+        {
+            // 3_000_000 * ln(1_000_001/1_000_000) = 2.999_998_500_000_999...
+            // within 3_000_000 times the error of the logarithms at 18 decimals
+            let actual = k_div_q.mul_ln_ratio(q_new, q0).unwrap();
+            assert!(!actual.is_negative());
+            assert!(within(actual, 2_999_998_500_000, 2_000));
+
+            // ln(q0/q_new) is negative, ln(q0/q0) is zero
+            let actual = k_div_q.mul_ln_ratio(q0, q_new).unwrap();
+            assert!(actual.is_negative());
+            assert!(within(actual, 2_999_998_500_000, 2_000));
+            assert_eq!(
+                k_div_q.mul_ln_ratio(q0, q0).unwrap(),
+                Decimal::new(0, 12, false)
+            );
+        }
+
+        {
+            // 3_000_000 * (1_000_001/1_000_000)^0 = 3_000_000
+            let actual = k_div_q.mul_pow_ratio(q_new, q0, Decimal::zero()).unwrap();
+            assert_eq!(actual, k_div_q);
+
+            // 3_000_000 * (1_000_000/1_000_001)^1 = 2_999_997.000_002_999...
+            let actual = k_div_q
+                .mul_pow_ratio(q_new, q0, Decimal::one().neg())
+                .unwrap();
+            assert!(within(actual, 2_999_997_000_002_999_997, 1_000));
+
+            // 3_000_000 * (1_000_001/1_000_000)^1.5 = 3_000_004.500_001_124...
+            let exp = Decimal::new(1_500_000_000_000, COMPUTE_SCALE, false);
+            let actual = k_div_q.mul_pow_ratio(q_new, q0, exp).unwrap();
+            assert!(within(actual, 3_000_004_500_001_124_999, 1_000));
+        }
+    }
+
+    #[test]
+    fn test_log2() {
+        {
+            // log2(1024) = 10, exact powers of two are exact
+            let n = Decimal::from_u64_to_compute_scale(1024);
+            assert_eq!(n.log2().unwrap(), Decimal::from_u64_to_compute_scale(10));
+
+            // log2(0.5) = -1
+            let n = Decimal::new(500_000_000_000, COMPUTE_SCALE, false);
+            assert_eq!(n.log2().unwrap(), Decimal::one().neg());
+        }
+
+        {
+            // log2(3) = 1.584_962_500_721_156
+            let n = Decimal::from_u64_to_compute_scale(3);
+            let expected = Decimal::new(1_584_962_500_721, COMPUTE_SCALE, false);
+            assert_eq!(n.log2().unwrap(), expected);
+
+            // log2(0.1) = -3.321_928_094_887_362
+            let n = Decimal::new(100_000_000_000, COMPUTE_SCALE, false);
+            let expected = Decimal::new(3_321_928_094_887, COMPUTE_SCALE, true);
+            assert_eq!(n.log2().unwrap(), expected);
+        }
+
+        {
+            // log2 is only defined for positive numbers
+            assert_eq!(Decimal::zero().log2(), Err(DecimalError::ExceedsRange));
+            assert_eq!(
+                Decimal::one().neg().log2(),
+                Err(DecimalError::SignedDecimalsNotSupported)
+            );
+        }
+    }
+
+    #[test]
+    fn test_log10() {
+        {
+            // log10(1000) = 3 and log10(0.000001) = -6, exact powers of ten are exact
+            let n = Decimal::from_u64_to_compute_scale(1000);
+            assert_eq!(n.log10().unwrap(), Decimal::from_u64_to_compute_scale(3));
+
+            let n = Decimal::new(1, 6, false);
+            assert_eq!(n.log10().unwrap(), Decimal::new(6_000000, 6, true));
+
+            // log10(1) = 0
+            assert_eq!(Decimal::one().log10().unwrap(), Decimal::zero());
+        }
+
+        {
+            // log10(3420) = 3.534_026_106_056_135
+            let n = Decimal::from_u64_to_compute_scale(3420);
+            let expected = Decimal::new(3_534_026_106_056, COMPUTE_SCALE, false);
+            assert_eq!(n.log10().unwrap(), expected);
+        }
+
+        {
+            // log10 is only defined for positive numbers
+            assert_eq!(Decimal::zero().log10(), Err(DecimalError::ExceedsRange));
+        }
+    }
+
+    #[test]
+    fn test_nth_root() {
+        {
+            // ∛27 = 3 and ∛-27 = -3
+            let n = Decimal::from_u64_to_compute_scale(27);
+            assert_eq!(
+                n.nth_root(3).unwrap(),
+                Decimal::from_u64_to_compute_scale(3)
+            );
+            assert_eq!(
+                n.neg().nth_root(3).unwrap(),
+                Decimal::from_u64_to_compute_scale(3).neg()
+            );
+        }
+
+        {
+            // ∛2 = 1.259_921_049_894_873
+            let n = Decimal::from_u64_to_compute_scale(2);
+            let expected = Decimal::new(1_259_921_049_894, COMPUTE_SCALE, false);
+            assert_eq!(n.nth_root(3).unwrap(), expected);
+
+            // ⁵√3420 = 5.091_024_900_605
+            let n = Decimal::from_u64(3420).to_scale(6).unwrap();
+            let expected = Decimal::new(5_091024, 6, false);
+            assert_eq!(n.nth_root(5).unwrap(), expected);
+        }
+
+        {
+            // 2^(1/64) = 1.010_889_286_051_700, beyond 256 bits it is computed as e^(ln(2) / 64)
+            let n = Decimal::from_u64_to_compute_scale(2);
+            let expected = Decimal::new(1_010_889_286_051, COMPUTE_SCALE, false);
+            assert_eq!(n.nth_root(64).unwrap(), expected);
+        }
+
+        {
+            // square roots match Sqrt, e.g. √1.0001
+            let n = Decimal::new(1_000_100_000_000, COMPUTE_SCALE, false);
+            assert_eq!(n.nth_root(2).unwrap(), n.sqrt().unwrap());
+        }
+
+        {
+            // x^(1/1) = x and ⁿ√0 = 0
+            let n = Decimal::from_u64_to_compute_scale(42);
+            assert_eq!(n.nth_root(1).unwrap(), n);
+            assert_eq!(Decimal::zero().nth_root(7).unwrap(), Decimal::zero());
+        }
+
+        {
+            // even roots of negative numbers and the 0th root don't exist
+            let n = Decimal::from_u64_to_compute_scale(4).neg();
+            assert_eq!(n.nth_root(2), Err(DecimalError::SignedDecimalsNotSupported));
+            assert_eq!(n.nth_root(0), Err(DecimalError::UnsupportedExponent));
+        }
     }
 
     #[test]
@@ -2684,25 +3629,25 @@ mod test {
         let lhs = Decimal::new(4, 0, true);
         let rhs = Decimal::new(3, 0, true);
         let expected = Decimal::new(12, 0, false);
-        assert_eq!(lhs.mul(rhs), expected);
+        assert_eq!(lhs.mul(rhs).unwrap(), expected);
 
         // -4 * 3 = -12
         let lhs = Decimal::new(4, 0, true);
         let rhs = Decimal::new(3, 0, false);
         let expected = Decimal::new(12, 0, true);
-        assert_eq!(lhs.mul(rhs), expected);
+        assert_eq!(lhs.mul(rhs).unwrap(), expected);
 
         // 4 * -3 = -12
         let lhs = Decimal::new(4, 0, false);
         let rhs = Decimal::new(3, 0, true);
         let expected = Decimal::new(12, 0, true);
-        assert_eq!(lhs.mul(rhs), expected);
+        assert_eq!(lhs.mul(rhs).unwrap(), expected);
 
         // 4 * 3 = 12
         let lhs = Decimal::new(4, 0, false);
         let rhs = Decimal::new(3, 0, false);
         let expected = Decimal::new(12, 0, false);
-        assert_eq!(lhs.mul(rhs), expected);
+        assert_eq!(lhs.mul(rhs).unwrap(), expected);
     }
 
     #[test]
@@ -2711,25 +3656,25 @@ mod test {
         let lhs = Decimal::new(12, 0, true);
         let rhs = Decimal::new(3, 0, true);
         let expected = Decimal::new(4, 0, false);
-        assert_eq!(lhs.div(rhs), expected);
+        assert_eq!(lhs.div(rhs).unwrap(), expected);
 
         // -12 / 3 = -4
         let lhs = Decimal::new(12, 0, true);
         let rhs = Decimal::new(3, 0, false);
         let expected = Decimal::new(4, 0, true);
-        assert_eq!(lhs.div(rhs), expected);
+        assert_eq!(lhs.div(rhs).unwrap(), expected);
 
         // 12 / -3 = -4
         let lhs = Decimal::new(12, 0, false);
         let rhs = Decimal::new(3, 0, true);
         let expected = Decimal::new(4, 0, true);
-        assert_eq!(lhs.div(rhs), expected);
+        assert_eq!(lhs.div(rhs).unwrap(), expected);
 
         // 12 / 3 = 4
         let lhs = Decimal::new(12, 0, false);
         let rhs = Decimal::new(3, 0, false);
         let expected = Decimal::new(4, 0, false);
-        assert_eq!(lhs.div(rhs), expected);
+        assert_eq!(lhs.div(rhs).unwrap(), expected);
     }
 
     #[test]
@@ -2754,9 +3699,19 @@ mod test {
         let d = Decimal::new(1, 6, false);
         assert_eq!(d.bit_length().unwrap(), Decimal::new(20, 0, true));
 
-        // 18446744073709551615 bit length == 64
+        // 18446744073709551615 bit length == 63
         let d = Decimal::from_u64(u64::MAX);
+        assert_eq!(d.bit_length().unwrap(), Decimal::new(63, 0, false));
+
+        // 18446744073709551616 bit length == 64
+        let d = Decimal::from_u128(1 << 64);
         assert_eq!(d.bit_length().unwrap(), Decimal::new(64, 0, false));
+
+        // 0.5 bit length == -1, 0.499999999999 bit length == -2
+        let d = Decimal::new(500_000_000_000, 12, false);
+        assert_eq!(d.bit_length().unwrap(), Decimal::new(1, 0, true));
+        let d = Decimal::new(499_999_999_999, 12, false);
+        assert_eq!(d.bit_length().unwrap(), Decimal::new(2, 0, true));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 pub mod decimal;
 pub mod programs;
+pub mod uint;
 #[macro_use]
 extern crate derive_builder;
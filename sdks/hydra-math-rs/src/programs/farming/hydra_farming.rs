@@ -0,0 +1,155 @@
+//! Farming reward accounting based on a global reward per share accumulator
+use std::convert::TryInto;
+
+/// Precision the reward per share accumulator is stored with
+pub const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+/// update the reward per share accumulator with rewards emitted over an elapsed period
+// acc_reward_per_share + (elapsed * reward_rate * REWARD_PER_SHARE_PRECISION) / total_staked
+pub fn calculate_reward_per_share(
+    acc_reward_per_share: u128,
+    reward_rate: u64,
+    elapsed: u64,
+    total_staked: u64,
+) -> Option<u128> {
+    // nothing is emitted to an empty farm
+    if total_staked == 0 || elapsed == 0 {
+        return Some(acc_reward_per_share);
+    }
+
+    let rewards_emitted = (elapsed as u128).checked_mul(reward_rate as u128)?;
+
+    acc_reward_per_share.checked_add(
+        rewards_emitted
+            .checked_mul(REWARD_PER_SHARE_PRECISION)?
+            .checked_div(total_staked as u128)?,
+    )
+}
+
+/// calculate the reward debt of a staked amount at the current reward per share
+// (amount * acc_reward_per_share) / REWARD_PER_SHARE_PRECISION
+pub fn calculate_reward_debt(amount: u64, acc_reward_per_share: u128) -> Option<u128> {
+    (amount as u128)
+        .checked_mul(acc_reward_per_share)?
+        .checked_div(REWARD_PER_SHARE_PRECISION)
+}
+
+/// calculate rewards owed to a staked amount since its reward debt was last checkpointed
+// (amount * acc_reward_per_share) / REWARD_PER_SHARE_PRECISION - reward_debt
+pub fn calculate_pending_rewards(
+    amount: u64,
+    acc_reward_per_share: u128,
+    reward_debt: u128,
+) -> Option<u64> {
+    calculate_reward_debt(amount, acc_reward_per_share)?
+        .checked_sub(reward_debt)?
+        .try_into()
+        .ok()
+}
+
+/// calculate rewards emitted to stakers over an elapsed period, owed to them until paid out
+// elapsed * reward_rate
+pub fn calculate_rewards_emitted(reward_rate: u64, elapsed: u64, total_staked: u64) -> Option<u64> {
+    // nothing is emitted to an empty farm
+    if total_staked == 0 {
+        return Some(0);
+    }
+
+    elapsed.checked_mul(reward_rate)
+}
+
+/// calculate the reward tokens of the reward vault not owed to stakers, zero while it is underfunded
+// reward_vault_amount - rewards_owed
+pub fn calculate_recoverable_rewards(reward_vault_amount: u64, rewards_owed: u64) -> u64 {
+    reward_vault_amount.saturating_sub(rewards_owed)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn reward_per_share_specific_tests() {
+        // empty farm does not accrue
+        assert_eq!(calculate_reward_per_share(0, 100, 60, 0).unwrap(), 0);
+
+        // no time elapsed does not accrue
+        assert_eq!(calculate_reward_per_share(5, 100, 0, 1_000).unwrap(), 5);
+
+        // (60 * 100 * 10^12) / 1000 = 6_000_000_000_000
+        assert_eq!(
+            calculate_reward_per_share(0, 100, 60, 1_000).unwrap(),
+            6_000_000_000_000
+        );
+
+        // overflow is reported rather than wrapped
+        assert_eq!(calculate_reward_per_share(0, u64::MAX, u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn pending_rewards_specific_tests() {
+        // staker holding 25% of the farm for 60 seconds at 100 rewards/second
+        let acc_reward_per_share = calculate_reward_per_share(0, 100, 60, 1_000).unwrap();
+        assert_eq!(
+            calculate_pending_rewards(250, acc_reward_per_share, 0).unwrap(),
+            1_500
+        );
+
+        // rewards are only owed since the last checkpoint
+        let reward_debt = calculate_reward_debt(250, acc_reward_per_share).unwrap();
+        assert_eq!(
+            calculate_pending_rewards(250, acc_reward_per_share, reward_debt).unwrap(),
+            0
+        );
+
+        // Expect fractional rewards to be rounded down (floored)
+        // (10 * 100 * 10^12) / 3 = 333_333_333_333_333 -> 1 * 333.333333333333 = 333
+        let acc_reward_per_share = calculate_reward_per_share(0, 100, 10, 3).unwrap();
+        assert_eq!(
+            calculate_pending_rewards(1, acc_reward_per_share, 0).unwrap(),
+            333
+        );
+    }
+
+    #[test]
+    fn rewards_owed_specific_tests() {
+        // empty farm does not emit
+        assert_eq!(calculate_rewards_emitted(100, 60, 0).unwrap(), 0);
+
+        // 60 * 100 = 6_000
+        assert_eq!(calculate_rewards_emitted(100, 60, 1_000).unwrap(), 6_000);
+
+        // overflow is reported rather than wrapped
+        assert_eq!(calculate_rewards_emitted(u64::MAX, 2, 1), None);
+
+        // only rewards beyond those owed can be recovered
+        assert_eq!(calculate_recoverable_rewards(10_000, 6_000), 4_000);
+        assert_eq!(calculate_recoverable_rewards(6_000, 6_000), 0);
+
+        // an underfunded vault has nothing to recover
+        assert_eq!(calculate_recoverable_rewards(5_000, 6_000), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn pending_rewards_never_exceed_emitted(
+            reward_rate in 0..1_000_000_000u64,
+            elapsed in 0..100_000_000u64,
+            total_staked in 1..u64::MAX,
+            amount_share in 0..=100u64,
+        ) {
+            let amount = ((total_staked as u128) * (amount_share as u128) / 100) as u64;
+            let acc_reward_per_share =
+                calculate_reward_per_share(0, reward_rate, elapsed, total_staked).unwrap();
+            let pending = calculate_pending_rewards(amount, acc_reward_per_share, 0).unwrap();
+            let other =
+                calculate_pending_rewards(total_staked - amount, acc_reward_per_share, 0).unwrap();
+
+            let emitted = calculate_rewards_emitted(reward_rate, elapsed, total_staked).unwrap();
+
+            assert!((pending as u128) + (other as u128) <= emitted as u128);
+        }
+    }
+}
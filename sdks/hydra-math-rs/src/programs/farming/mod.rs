@@ -0,0 +1 @@
+pub mod hydra_farming;
@@ -1,4 +1,5 @@
 use crate::decimal::DecimalError;
+use crate::programs::fees::fee_calculator::FeeCalculatorBuilderError;
 use crate::programs::fees::fee_result::FeeResultBuilderError;
 use thiserror::Error;
 
@@ -11,4 +12,6 @@ pub enum FeeCalculatorError {
     DecimalError(#[from] DecimalError),
     #[error(transparent)]
     FeeResultBuilderError(#[from] FeeResultBuilderError),
+    #[error(transparent)]
+    FeeCalculatorBuilderError(#[from] FeeCalculatorBuilderError),
 }
@@ -1,8 +1,11 @@
-use crate::decimal::{Add, Compare, Decimal, DecimalError, Div, Mul, Pow, Sub, COMPUTE_SCALE};
+use crate::decimal::{Add, Compare, Decimal, DecimalError, Div, Exp, Mul, Pow, Sub, COMPUTE_SCALE};
 use crate::programs::fees::error::FeeCalculatorError;
 use crate::programs::fees::fee_result::{FeeResult, FeeResultBuilder};
 use std::ops::Neg;
-use std::str::FromStr;
+
+/// Ewma variance a volatility adjusted fee starts from before its first recomputation, 12 decimal places
+// 1.25^2/365/24
+pub const VOL_ADJ_FEE_DEFAULT_LAST_EWMA: u64 = 178_367_579;
 
 #[derive(Default, Builder, Debug)]
 #[builder(setter(into))]
@@ -19,36 +22,27 @@ pub struct FeeCalculator {
     vol_adj_fee_last_price: Decimal,
     #[builder(default = "Decimal::zero()")]
     vol_adj_fee_this_price: Decimal,
-    #[builder(default = "Decimal::from_u64(3600).to_compute_scale()")]
+    #[builder(default = "Decimal::from_u64_to_compute_scale(3600)")]
     vol_adj_fee_ewma_window: Decimal,
-    #[builder(default = r#"
-            // 1.25^2/365/24
-            Decimal::from_str("1.25")
-                .unwrap()
-                .to_compute_scale()
-                .pow(Decimal::two())
-                .div(Decimal::from_u64(24).to_compute_scale())
-                .div(Decimal::from_u64(365).to_compute_scale())
-        "#)]
+    #[builder(
+        default = "Decimal::from_scaled_amount(VOL_ADJ_FEE_DEFAULT_LAST_EWMA, COMPUTE_SCALE)"
+    )]
     vol_adj_fee_last_ewma: Decimal,
-    #[builder(default = r#"Decimal::from_str("0.545").unwrap().to_compute_scale()"#)]
+    #[builder(default = "Decimal::from_scaled_amount_to_compute_scale(545, 3)")]
     vol_adj_fee_lambda: Decimal,
-    #[builder(default = r#"
-            // 0.1 / 24 hours
-            Decimal::from_str("0.1")
-                .unwrap()
-                .to_compute_scale()
-                .div(Decimal::from_u64(24).to_compute_scale())
-        "#)]
+    // 0.1 / 24 hours
+    #[builder(default = "Decimal::new(4_166_666_666, COMPUTE_SCALE, false)")]
     vol_adj_fee_velocity: Decimal,
-    #[builder(default = r#"Decimal::from_str("0.0005").unwrap().to_compute_scale()"#)]
+    #[builder(default = "Decimal::from_scaled_amount_to_compute_scale(5, 4)")]
     vol_adj_fee_min_fee: Decimal,
-    #[builder(default = r#"Decimal::from_str("0.02").unwrap().to_compute_scale()"#)]
+    #[builder(default = "Decimal::from_scaled_amount_to_compute_scale(2, 2)")]
     vol_adj_fee_max_fee: Decimal,
 }
 
 impl FeeCalculator {
     /// Compute a volatility adjusted based fee for the [FeeCalculator]
+    /// The last update, price and ewma of the [FeeResult] are the state to persist for the next fee,
+    /// they only move forward on the first observation or once the ewma has been recomputed.
     pub fn compute_vol_adj_fee(&self, amount: &Decimal) -> Result<FeeResult, FeeCalculatorError> {
         if self.vol_adj_fee_min_fee.is_zero() || self.vol_adj_fee_max_fee.is_zero() {
             return Ok(FeeResultBuilder::default().amount_ex_fee(*amount).build()?);
@@ -57,23 +51,20 @@ impl FeeCalculator {
         let this_ewma = self.compute_ewma()?;
 
         // x = -ewma / 8
-        let x = this_ewma.neg().div(Decimal::from_u64(8).to_compute_scale());
+        let x = this_ewma.neg().div(Decimal::from_u64_to_compute_scale(8))?;
 
-        // exp(x) = 1+x+x^2/2
         // algo based on: https://docs.google.com/spreadsheets/d/1H5Kf5NIaV57KE3HOLbXTjcGDO-I_whhU/edit#gid=1189489672
-        let exp_x = Decimal::one()
-            .add(x)?
-            .add(x.pow(2u128).div(Decimal::two()))?;
+        let exp_x = x.exp()?;
 
-        let vol_adj_fee = Decimal::one().sub(exp_x)?.div(self.vol_adj_fee_velocity);
+        let vol_adj_fee = Decimal::one().sub(exp_x)?.div(self.vol_adj_fee_velocity)?;
 
         // fee = MAX(min_fee,MIN(max_fee, vol_adj_fee)
         let fee_percentage = self
             .vol_adj_fee_min_fee
             .max(self.vol_adj_fee_max_fee.min(vol_adj_fee));
 
-        let amount_scaled = amount.to_compute_scale();
-        let fee_amount = fee_percentage.mul(amount_scaled);
+        let amount_scaled = amount.to_compute_scale()?;
+        let fee_amount = fee_percentage.mul(amount_scaled)?;
 
         if fee_amount.gte(amount_scaled)? {
             return Err(FeeCalculatorError::FeesGreaterThanAmount.into());
@@ -88,13 +79,21 @@ impl FeeCalculator {
 
         let amount_ex_fee = amount_scaled.sub(fee_amount)?;
 
+        // keep the last observation until the ewma window has passed
+        let (last_update, last_price) =
+            if self.vol_adj_fee_last_update.is_zero() || self.should_update()? {
+                (self.vol_adj_fee_this_update, self.vol_adj_fee_this_price)
+            } else {
+                (self.vol_adj_fee_last_update, self.vol_adj_fee_last_price)
+            };
+
         Ok(FeeResultBuilder::default()
-            .fee_amount(fee_amount.to_scale(amount.scale))
-            .fee_percentage(fee_percentage.to_scale(amount.scale))
-            .amount_ex_fee(amount_ex_fee.to_scale(amount.scale))
-            .vol_adj_fee_last_update(self.vol_adj_fee_this_update.to_scale(0))
-            .vol_adj_fee_last_price(self.vol_adj_fee_this_price.to_scale(amount.scale))
-            .vol_adj_fee_last_ewma(this_ewma.to_scale(COMPUTE_SCALE))
+            .fee_amount(fee_amount.to_scale(amount.scale)?)
+            .fee_percentage(fee_percentage.to_scale(amount.scale)?)
+            .amount_ex_fee(amount_ex_fee.to_scale(amount.scale)?)
+            .vol_adj_fee_last_update(last_update.to_scale(0)?)
+            .vol_adj_fee_last_price(last_price.to_scale(amount.scale)?)
+            .vol_adj_fee_last_ewma(this_ewma.to_scale(COMPUTE_SCALE)?)
             .build()?)
     }
 
@@ -102,19 +101,19 @@ impl FeeCalculator {
     pub fn compute_percent_fee(&self, amount: &Decimal) -> Result<FeeResult, FeeCalculatorError> {
         if self.percentage_fee_numerator.is_zero() || self.percentage_fee_denominator.is_zero() {
             return Ok(FeeResultBuilder::default()
-                .fee_amount(Decimal::zero().to_scale(amount.scale))
-                .fee_percentage(Decimal::zero().to_scale(amount.scale))
+                .fee_amount(Decimal::zero().to_scale(amount.scale)?)
+                .fee_percentage(Decimal::zero().to_scale(amount.scale)?)
                 .amount_ex_fee(*amount)
                 .build()?);
         }
 
         let fee_percentage = self
             .percentage_fee_numerator
-            .to_compute_scale()
-            .div(self.percentage_fee_denominator.to_compute_scale());
+            .to_compute_scale()?
+            .div(self.percentage_fee_denominator.to_compute_scale()?)?;
 
-        let amount_scaled = amount.to_compute_scale();
-        let fee_amount = fee_percentage.mul(amount_scaled);
+        let amount_scaled = amount.to_compute_scale()?;
+        let fee_amount = fee_percentage.mul(amount_scaled)?;
 
         if fee_amount.gte(amount_scaled)? {
             return Err(FeeCalculatorError::FeesGreaterThanAmount.into());
@@ -123,22 +122,17 @@ impl FeeCalculator {
         let amount_ex_fee = amount_scaled.sub(fee_amount)?;
 
         Ok(FeeResultBuilder::default()
-            .fee_amount(fee_amount.to_scale(amount.scale))
-            .fee_percentage(fee_percentage.to_scale(amount.scale))
-            .amount_ex_fee(amount_ex_fee.to_scale(amount.scale))
+            .fee_amount(fee_amount.to_scale(amount.scale)?)
+            .fee_percentage(fee_percentage.to_scale(amount.scale)?)
+            .amount_ex_fee(amount_ex_fee.to_scale(amount.scale)?)
             .build()?)
     }
 
     /// Determine if variance should be computed based on window period for a volatility adjusted [FeeCalculator]
     fn should_update(&self) -> Result<bool, DecimalError> {
-        Ok(self
-            .vol_adj_fee_last_update
-            .to_compute_scale()
-            .gt(Decimal::zero())?
-            && self
-                .vol_adj_fee_this_update
-                .sub(self.vol_adj_fee_last_update.to_compute_scale())?
-                >= self.vol_adj_fee_ewma_window)
+        let last_update = self.vol_adj_fee_last_update.to_compute_scale()?;
+        Ok(last_update.gt(Decimal::zero())?
+            && self.vol_adj_fee_this_update.sub(last_update)? >= self.vol_adj_fee_ewma_window)
     }
 
     /// Compute exponentially weighted moving average (ewma) variance for a volatility adjusted [FeeCalculator]
@@ -149,27 +143,27 @@ impl FeeCalculator {
             // * ewma_window / (this_update - last_update)
 
             // ensure all scale is uniform
-            let lambda = self.vol_adj_fee_lambda.to_compute_scale();
-            let this_price = self.vol_adj_fee_this_price.to_compute_scale();
-            let last_price = self.vol_adj_fee_last_price.to_compute_scale();
-            let this_update = self.vol_adj_fee_this_update.to_compute_scale();
-            let last_update = self.vol_adj_fee_last_update.to_compute_scale();
-            let last_ewma = self.vol_adj_fee_last_ewma.to_compute_scale();
-            let ewma_window = self.vol_adj_fee_ewma_window.to_compute_scale();
+            let lambda = self.vol_adj_fee_lambda.to_compute_scale()?;
+            let this_price = self.vol_adj_fee_this_price.to_compute_scale()?;
+            let last_price = self.vol_adj_fee_last_price.to_compute_scale()?;
+            let this_update = self.vol_adj_fee_this_update.to_compute_scale()?;
+            let last_update = self.vol_adj_fee_last_update.to_compute_scale()?;
+            let last_ewma = self.vol_adj_fee_last_ewma.to_compute_scale()?;
+            let ewma_window = self.vol_adj_fee_ewma_window.to_compute_scale()?;
 
             // a = (1-lambda)
             let a = Decimal::one().sub(lambda)?;
 
             // b = (this_price / last_price - 1)**2
             let b = this_price
-                .div(last_price)
+                .div(last_price)?
                 .sub(Decimal::one())?
-                .pow(Decimal::two());
+                .pow(Decimal::two())?;
 
             // c = ewma_window / (this_update - last_update)
-            let c = ewma_window.div(this_update.sub(last_update)?);
+            let c = ewma_window.div(this_update.sub(last_update)?)?;
 
-            Ok(lambda.mul(last_ewma).add(a.mul(b).mul(c))?)
+            lambda.mul(last_ewma)?.add(a.mul(b)?.mul(c)?)
         } else {
             Ok(self.vol_adj_fee_last_ewma)
         }
@@ -183,6 +177,7 @@ mod tests {
     use crate::decimal::Decimal;
     use csv::ReaderBuilder;
     use indoc::indoc;
+    use std::str::FromStr;
 
     #[test]
     fn test_compute_percent_fee() {
@@ -240,10 +235,14 @@ mod tests {
 
         for record in reader.records() {
             let record = record.unwrap();
-            let percentage_fee_numerator =
-                Decimal::from_str(&record[0]).unwrap().to_compute_scale();
-            let percentage_fee_denominator =
-                Decimal::from_str(&record[1]).unwrap().to_compute_scale();
+            let percentage_fee_numerator = Decimal::from_str(&record[0])
+                .unwrap()
+                .to_compute_scale()
+                .unwrap();
+            let percentage_fee_denominator = Decimal::from_str(&record[1])
+                .unwrap()
+                .to_compute_scale()
+                .unwrap();
             let fee_percentage = Decimal::from_str(&record[2]).unwrap();
             let amount = Decimal::from_str(&record[3]).unwrap();
             let fee_amount = Decimal::from_str(&record[4]).unwrap();
@@ -280,7 +279,7 @@ mod tests {
             assert!(
                 fee_result
                     .fee_percentage
-                    .almost_eq(fee_percentage.to_compute_scale(), 1u128)
+                    .almost_eq(fee_percentage.to_compute_scale().unwrap(), 1u128)
                     .unwrap(),
                 "fee_percentage actual: {} expected: {}",
                 fee_result.fee_percentage.to_string(),
@@ -353,8 +352,14 @@ mod tests {
 
         for record in reader.records() {
             let record = record.unwrap();
-            let vol_adj_fee_last_update = Decimal::from_str(&record[0]).unwrap().to_compute_scale();
-            let vol_adj_fee_this_update = Decimal::from_str(&record[1]).unwrap().to_compute_scale();
+            let vol_adj_fee_last_update = Decimal::from_str(&record[0])
+                .unwrap()
+                .to_compute_scale()
+                .unwrap();
+            let vol_adj_fee_this_update = Decimal::from_str(&record[1])
+                .unwrap()
+                .to_compute_scale()
+                .unwrap();
             let vol_adj_fee_last_price = Decimal::from_str(&record[2]).unwrap();
             let vol_adj_fee_this_price = Decimal::from_str(&record[3]).unwrap();
             let vol_adj_fee_last_ewma = Decimal::from_str(&record[4]).unwrap();
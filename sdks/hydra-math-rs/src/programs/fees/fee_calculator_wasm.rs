@@ -1,35 +1,137 @@
-use crate::decimal::{Decimal, COMPUTE_SCALE};
+use crate::decimal::{Decimal, DecimalError, COMPUTE_SCALE};
 use crate::programs::fees::fee_calculator::FeeCalculatorBuilder;
 use wasm_bindgen::prelude::wasm_bindgen;
 
 /// Interface to be used by programs and front end
 /// these functions shadow functions of the implemented fee calculator
+// scalar arguments, the programs call this on chain where a wasm_bindgen struct causes an ELF error, see SwapResult
+#[allow(clippy::too_many_arguments)]
 #[wasm_bindgen]
 pub fn compute_volatility_adjusted_fee(
     this_price: u64,
     last_price: u64,
     price_scale: u8,
+    this_update: u64,
     last_update: u64,
     last_ewma: u64,
     amount: u64,
     amount_scale: u8,
 ) -> Result<Vec<u64>, String> {
-    let this_price = Decimal::from_scaled_amount(this_price, price_scale).to_compute_scale();
-    let last_price = Decimal::from_scaled_amount(last_price, price_scale).to_compute_scale();
-    let last_update = Decimal::from_u64(last_update).to_compute_scale();
+    let this_price = Decimal::from_scaled_amount_to_compute_scale(this_price, price_scale);
+    let last_price = Decimal::from_scaled_amount_to_compute_scale(last_price, price_scale);
+    let this_update = Decimal::from_u64_to_compute_scale(this_update);
+    let last_update = Decimal::from_u64_to_compute_scale(last_update);
     let last_ewma = Decimal::from_scaled_amount(last_ewma, COMPUTE_SCALE);
-    let amount = Decimal::from_scaled_amount(amount, amount_scale).to_compute_scale();
+    let amount = Decimal::from_scaled_amount_to_compute_scale(amount, amount_scale);
     let fee_calculator = FeeCalculatorBuilder::default()
         .vol_adj_fee_this_price(this_price)
         .vol_adj_fee_last_price(last_price)
+        .vol_adj_fee_this_update(this_update)
         .vol_adj_fee_last_update(last_update)
         .vol_adj_fee_last_ewma(last_ewma)
         .build()
-        .expect("failed to build FeeCalculator");
+        .map_err(|e| e.to_string())?;
 
-    let fee_result = fee_calculator.compute_vol_adj_fee(&amount).unwrap();
+    let fee_result = fee_calculator
+        .compute_vol_adj_fee(&amount)
+        .map_err(|e| e.to_string())?;
 
-    Ok(fee_result.into())
+    fee_result
+        .try_into()
+        .map_err(|e: DecimalError| e.to_string())
 }
 
-// TODO: add specs for compute_volatility_adjusted_fee via wasm scalar inputs
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::programs::fees::fee_calculator::VOL_ADJ_FEE_DEFAULT_LAST_EWMA;
+
+    #[test]
+    fn test_scalar_inputs() {
+        // first observation initializes the state with the default ewma
+        {
+            let actual = compute_volatility_adjusted_fee(
+                3400_000000,
+                0,
+                6,
+                1649113200,
+                0,
+                178_367_580,
+                1000_000000,
+                6,
+            )
+            .unwrap();
+            // [fee_amount, fee_percentage, amount_ex_fee, last_update, last_price, last_ewma]
+            // amounts, percentage and price are returned in the compute scale
+            assert_eq!(actual[3], 1649113200);
+            assert_eq!(actual[4], 3400_000000_000000);
+            assert_eq!(actual[5], 178_367_580);
+        }
+
+        // an hour later the ewma is recomputed and the state moves forward
+        {
+            let actual = compute_volatility_adjusted_fee(
+                3425_000000,
+                3400_000000,
+                6,
+                1649116800,
+                1649113200,
+                178_367_580,
+                1000_000000,
+                6,
+            )
+            .unwrap();
+            assert_eq!(actual[1], 3_654_279_600); // 0.003654
+            assert_eq!(actual[3], 1649116800);
+            assert_eq!(actual[4], 3425_000000_000000);
+            assert_eq!(actual[5], 121_810_244);
+        }
+
+        // within the ewma window the last observation is kept
+        {
+            let actual = compute_volatility_adjusted_fee(
+                3425_000000,
+                3400_000000,
+                6,
+                1649113260,
+                1649113200,
+                178_367_580,
+                1000_000000,
+                6,
+            )
+            .unwrap();
+            assert_eq!(actual[3], 1649113200);
+            assert_eq!(actual[4], 3400_000000_000000);
+            assert_eq!(actual[5], 178_367_580);
+        }
+    }
+    #[test]
+    fn test_first_swap_fee() {
+        let first_swap = |last_ewma| {
+            compute_volatility_adjusted_fee(
+                3400_000000,
+                0,
+                6,
+                1649113200,
+                0,
+                last_ewma,
+                1000_000000,
+                6,
+            )
+            .unwrap()
+        };
+
+        // the first swap after the fee mode is switched on is charged the fee of the default ewma
+        let actual = first_swap(VOL_ADJ_FEE_DEFAULT_LAST_EWMA);
+        // [fee_amount, fee_percentage, amount_ex_fee, last_update, last_price, last_ewma]
+        assert_eq!(actual[0], 5_350_967_760_000); // 5.350968
+        assert_eq!(actual[1], 5_350_967_760); // 0.005351
+        assert_eq!(actual[5], VOL_ADJ_FEE_DEFAULT_LAST_EWMA);
+
+        // an unseeded ewma would charge no more than the minimum fee
+        let actual = first_swap(0);
+        assert_eq!(actual[1], 500_000_000); // 0.0005
+        assert_eq!(actual[5], 0);
+    }
+}
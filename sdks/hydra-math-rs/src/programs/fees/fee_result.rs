@@ -1,4 +1,4 @@
-use crate::decimal::Decimal;
+use crate::decimal::{Decimal, DecimalError};
 
 #[derive(Default, Builder, Debug, PartialEq)]
 #[builder(setter(into))]
@@ -17,38 +17,48 @@ pub struct FeeResult {
     pub vol_adj_fee_last_ewma: Decimal,
 }
 
-impl Into<Vec<u64>> for FeeResult {
-    fn into(self) -> Vec<u64> {
-        vec![
-            self.fee_amount.to_scaled_amount(self.fee_amount.scale),
-            self.fee_percentage.to_scaled_amount(self.fee_amount.scale),
-            self.amount_ex_fee.to_scaled_amount(self.fee_amount.scale),
-            self.vol_adj_fee_last_update.to_scaled_amount(0),
-            self.vol_adj_fee_last_price
-                .to_scaled_amount(self.vol_adj_fee_last_price.scale),
-            self.vol_adj_fee_last_ewma
-                .to_scaled_amount(self.vol_adj_fee_last_ewma.scale),
-        ]
+impl TryFrom<FeeResult> for Vec<u64> {
+    type Error = DecimalError;
+
+    fn try_from(result: FeeResult) -> Result<Self, Self::Error> {
+        Ok(vec![
+            result
+                .fee_amount
+                .to_scaled_amount(result.fee_amount.scale)?,
+            result
+                .fee_percentage
+                .to_scaled_amount(result.fee_amount.scale)?,
+            result
+                .amount_ex_fee
+                .to_scaled_amount(result.fee_amount.scale)?,
+            result.vol_adj_fee_last_update.to_scaled_amount(0)?,
+            result
+                .vol_adj_fee_last_price
+                .to_scaled_amount(result.vol_adj_fee_last_price.scale)?,
+            result
+                .vol_adj_fee_last_ewma
+                .to_scaled_amount(result.vol_adj_fee_last_ewma.scale)?,
+        ])
     }
 }
 
-impl Into<Vec<String>> for FeeResult {
-    fn into(self) -> Vec<String> {
-        vec![
-            self.fee_amount.to_scale(self.fee_amount.scale).to_string(),
-            self.fee_percentage
-                .to_scale(self.fee_amount.scale)
-                .to_string(),
-            self.amount_ex_fee
-                .to_scale(self.fee_amount.scale)
-                .to_string(),
-            self.vol_adj_fee_last_update.to_scale(0).to_string(),
-            self.vol_adj_fee_last_price
-                .to_scale(self.vol_adj_fee_last_price.scale)
+impl TryFrom<FeeResult> for Vec<String> {
+    type Error = DecimalError;
+
+    fn try_from(result: FeeResult) -> Result<Self, Self::Error> {
+        Ok(vec![
+            result.fee_amount.to_string(),
+            result
+                .fee_percentage
+                .to_scale(result.fee_amount.scale)?
                 .to_string(),
-            self.vol_adj_fee_last_ewma
-                .to_scale(self.vol_adj_fee_last_ewma.scale)
+            result
+                .amount_ex_fee
+                .to_scale(result.fee_amount.scale)?
                 .to_string(),
-        ]
+            result.vol_adj_fee_last_update.to_scale(0)?.to_string(),
+            result.vol_adj_fee_last_price.to_string(),
+            result.vol_adj_fee_last_ewma.to_string(),
+        ])
     }
 }
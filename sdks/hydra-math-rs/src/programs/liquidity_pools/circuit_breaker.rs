@@ -0,0 +1,138 @@
+//! Circuit breakers rejecting swaps that move the price of a pool too far, prices are any
+//! consistent fixed point value such as the y per x [spot_prices](super::twap::spot_prices)
+use crate::programs::liquidity_pools::error::CircuitBreakerError;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Deviation of price from reference in basis points of reference, rounded down.
+/// Saturates at u128::MAX, None when the reference is zero.
+pub fn deviation_bps(price: u128, reference: u128) -> Option<u128> {
+    if reference == 0 {
+        return None;
+    }
+
+    let difference = price.max(reference) - price.min(reference);
+    Some(
+        difference
+            .checked_mul(BPS_DENOMINATOR)
+            .map_or(u128::MAX, |scaled| scaled / reference),
+    )
+}
+
+/// Check a swap moving the pool price from price_before to price_after against the oracle price.
+/// Trips when the price after the swap deviates from the oracle by more than max_deviation_bps and further
+/// than the price before did, so swaps bringing the pool back towards the oracle always pass.
+/// A zero max_deviation_bps disables the check.
+pub fn check_oracle_deviation(
+    price_before: u128,
+    price_after: u128,
+    oracle_price: u128,
+    max_deviation_bps: u16,
+) -> Result<(), CircuitBreakerError> {
+    if max_deviation_bps == 0 {
+        return Ok(());
+    }
+
+    let (deviation_before, deviation_after) = match (
+        deviation_bps(price_before, oracle_price),
+        deviation_bps(price_after, oracle_price),
+    ) {
+        (Some(before), Some(after)) => (before, after),
+        _ => return Ok(()),
+    };
+
+    if deviation_after > max_deviation_bps as u128 && deviation_after > deviation_before {
+        return Err(CircuitBreakerError::OracleDeviationExceeded(
+            deviation_after,
+        ));
+    }
+    Ok(())
+}
+
+/// Check the pool price after a swap against the price the pool had at the start of its slot.
+/// A zero max_move_bps disables the check.
+pub fn check_slot_price_move(
+    slot_start_price: u128,
+    price_after: u128,
+    max_move_bps: u16,
+) -> Result<(), CircuitBreakerError> {
+    if max_move_bps == 0 {
+        return Ok(());
+    }
+
+    match deviation_bps(price_after, slot_start_price) {
+        Some(price_move) if price_move > max_move_bps as u128 => {
+            Err(CircuitBreakerError::SlotPriceMoveExceeded(price_move))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deviation_bps() {
+        assert_eq!(deviation_bps(100, 100), Some(0));
+        assert_eq!(deviation_bps(101, 100), Some(100));
+        assert_eq!(deviation_bps(99, 100), Some(100));
+        assert_eq!(deviation_bps(0, 100), Some(10_000));
+        assert_eq!(deviation_bps(300, 100), Some(20_000));
+        // rounded down
+        assert_eq!(deviation_bps(1_000_199, 1_000_000), Some(1));
+        assert_eq!(deviation_bps(u128::MAX, 1), Some(u128::MAX));
+        assert_eq!(deviation_bps(100, 0), None);
+    }
+
+    #[test]
+    fn test_check_oracle_deviation() {
+        let oracle = 2_000_000_000_000;
+
+        // within 1%
+        assert!(check_oracle_deviation(oracle, 2_019_000_000_000, oracle, 100).is_ok());
+        assert!(check_oracle_deviation(oracle, 1_981_000_000_000, oracle, 100).is_ok());
+
+        // away from the oracle by more than 1%
+        assert!(matches!(
+            check_oracle_deviation(oracle, 2_030_000_000_000, oracle, 100),
+            Err(CircuitBreakerError::OracleDeviationExceeded(150))
+        ));
+        assert!(matches!(
+            check_oracle_deviation(2_030_000_000_000, 2_040_000_000_000, oracle, 100),
+            Err(CircuitBreakerError::OracleDeviationExceeded(200))
+        ));
+
+        // back towards the oracle, even if still outside of it
+        assert!(check_oracle_deviation(2_100_000_000_000, 2_040_000_000_000, oracle, 100).is_ok());
+        // across the oracle to the other side, further away than before
+        assert!(check_oracle_deviation(2_030_000_000_000, 1_960_000_000_000, oracle, 100).is_err());
+
+        // disabled and without a price
+        assert!(check_oracle_deviation(oracle, 4_000_000_000_000, oracle, 0).is_ok());
+        assert!(check_oracle_deviation(oracle, 4_000_000_000_000, 0, 100).is_ok());
+    }
+
+    #[test]
+    fn test_check_slot_price_move() {
+        let start = 1_000_000_000_000;
+
+        assert!(check_slot_price_move(start, 1_050_000_000_000, 500).is_ok());
+        assert!(check_slot_price_move(start, 950_000_000_000, 500).is_ok());
+        assert!(matches!(
+            check_slot_price_move(start, 1_050_100_000_000, 500),
+            Err(CircuitBreakerError::SlotPriceMoveExceeded(501))
+        ));
+        assert!(matches!(
+            check_slot_price_move(start, 1_060_000_000_000, 500),
+            Err(CircuitBreakerError::SlotPriceMoveExceeded(600))
+        ));
+        assert!(matches!(
+            check_slot_price_move(start, 900_000_000_000, 500),
+            Err(CircuitBreakerError::SlotPriceMoveExceeded(1_000))
+        ));
+
+        assert!(check_slot_price_move(start, 2_000_000_000_000, 0).is_ok());
+        assert!(check_slot_price_move(0, 2_000_000_000_000, 500).is_ok());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::decimal::{Add, Decimal, Mul, Sub};
+use crate::programs::liquidity_pools::error::ClPoolError;
+
+/// Swap fees and hmm adjustments of token x and y, either as amounts or as growth per unit of liquidity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeAmounts {
+    pub fee_x: Decimal,
+    pub fee_y: Decimal,
+    pub adj_x: Decimal,
+    pub adj_y: Decimal,
+}
+
+impl FeeAmounts {
+    pub fn zero() -> Self {
+        Self {
+            fee_x: Decimal::zero(),
+            fee_y: Decimal::zero(),
+            adj_x: Decimal::zero(),
+            adj_y: Decimal::zero(),
+        }
+    }
+
+    pub fn checked_add(self, rhs: FeeAmounts) -> Result<Self, ClPoolError> {
+        Ok(Self {
+            fee_x: self.fee_x.add(rhs.fee_x)?,
+            fee_y: self.fee_y.add(rhs.fee_y)?,
+            adj_x: self.adj_x.add(rhs.adj_x)?,
+            adj_y: self.adj_y.add(rhs.adj_y)?,
+        })
+    }
+
+    pub fn checked_sub(self, rhs: FeeAmounts) -> Result<Self, ClPoolError> {
+        Ok(Self {
+            fee_x: self.fee_x.sub(rhs.fee_x)?,
+            fee_y: self.fee_y.sub(rhs.fee_y)?,
+            adj_x: self.adj_x.sub(rhs.adj_x)?,
+            adj_y: self.adj_y.sub(rhs.adj_y)?,
+        })
+    }
+
+    /// Amounts earned by liquidity from a growth per unit of liquidity
+    pub fn earned_by(self, liq: Decimal) -> Result<Self, ClPoolError> {
+        Ok(Self {
+            fee_x: liq.mul(self.fee_x)?,
+            fee_y: liq.mul(self.fee_y)?,
+            adj_x: liq.mul(self.adj_x)?,
+            adj_y: liq.mul(self.adj_y)?,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.fee_x.is_negative()
+            || self.fee_y.is_negative()
+            || self.adj_x.is_negative()
+            || self.adj_y.is_negative()
+    }
+}
+
+/// Pool wide state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalState {
+    /// liquidity in range
+    pub liq: Decimal,
+    /// sqrt price
+    pub rp: Decimal,
+    /// current tick
+    pub tick: u128,
+    /// fee growth global
+    pub fee_growth: FeeAmounts,
+}
+
+/// Tick indexed state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickState {
+    pub liq_net: Decimal,
+    pub liq_gross: Decimal,
+    /// fee growth outside
+    pub fee_growth_outside: FeeAmounts,
+}
+
+/// Position indexed state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionState {
+    pub liq: Decimal,
+    /// fee growth inside the range of the position when last touched
+    pub fee_growth_inside: FeeAmounts,
+}
+
+impl Default for PositionState {
+    fn default() -> Self {
+        Self {
+            liq: Decimal::zero(),
+            fee_growth_inside: FeeAmounts::zero(),
+        }
+    }
+}
+
+/// Result of a deposit. Uncollected fees of the position are offset from the amounts debited,
+/// a negative amount debited is paid out to the depositor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepositResult {
+    pub liq: Decimal,
+    pub x_in: Decimal,
+    pub y_in: Decimal,
+    pub fees: FeeAmounts,
+    pub x_debited: Decimal,
+    pub y_debited: Decimal,
+}
+
+/// Result of a withdrawal, amounts sent include the uncollected fees of the position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WithdrawResult {
+    pub x_out: Decimal,
+    pub y_out: Decimal,
+    pub fees: FeeAmounts,
+    pub x_sent: Decimal,
+    pub y_sent: Decimal,
+}
+
+/// Result of a fee collection, swap fees and hmm adjustments are sent together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollectResult {
+    pub fees: FeeAmounts,
+    pub x_sent: Decimal,
+    pub y_sent: Decimal,
+}
+
+/// Portion of a swap within 2 consecutive active ticks
+#[derive(Debug)]
+pub struct SwapWithinResult {
+    pub recv_amount: Decimal,
+    pub send_amount: Decimal,
+    pub end_tick: u128,
+    pub end_rp: Decimal,
+    pub cross: bool,
+    pub send_hmm_adj: Decimal,
+    pub recv_fee: Decimal,
+}
+
+/// Result of a swap, recv_amount includes the fee and send_amount is negative
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapOutput {
+    pub recv_amount: Decimal,
+    pub send_amount: Decimal,
+    pub send_hmm_adj: Decimal,
+    pub recv_fee: Decimal,
+    pub avg_price: Decimal,
+    pub end_price: Decimal,
+}
+
+impl SwapOutput {
+    pub fn new(
+        recv_amount: Decimal,
+        send_amount: Decimal,
+        send_hmm_adj: Decimal,
+        recv_fee: Decimal,
+        avg_price: Decimal,
+        end_price: Decimal,
+    ) -> Result<Self, ClPoolError> {
+        if recv_amount.is_negative() || send_amount.is_positive() {
+            return Err(ClPoolError::InvariantViolated(
+                "swap needs a positive in-qty and negative out-qty",
+            ));
+        }
+        if recv_amount.is_positive() == send_amount.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "out-qty cannot be nil for positive in-qty or non-nil for nil in-qty",
+            ));
+        }
+        if send_hmm_adj.is_negative() || recv_fee.is_negative() {
+            return Err(ClPoolError::InvariantViolated("fees cannot be negative"));
+        }
+        Ok(Self {
+            recv_amount,
+            send_amount,
+            send_hmm_adj,
+            recv_fee,
+            avg_price,
+            end_price,
+        })
+    }
+}
@@ -0,0 +1,340 @@
+//! Concentrated liquidity math ported from the prototype PoolMath
+use crate::decimal::{Add, Compare, Decimal, Div, DivUp, Ln, Mul, Pow, Sub, COMPUTE_SCALE};
+use crate::programs::liquidity_pools::error::ClPoolError;
+use std::ops::Neg;
+
+/// Root of the tick base, sqrt(1.0001) at compute scale
+pub const TICK_BASE_ROOT: Decimal = Decimal {
+    value: 1_000_049_998_750,
+    scale: COMPUTE_SCALE,
+    negative: false,
+};
+
+pub trait ClPoolMath {
+    // * All Decimal inputs are assumed to be 'computable' scale on arrival
+    // * And all outputs 'computable' scale
+    // * All tick are internally handled as u128 and returned as such
+
+    /// Rounds down withdrawals, avoid out_qty > reserve by tiny amt
+    const ADJ_WITHDRAWAL: Decimal = Decimal {
+        value: 1,
+        scale: COMPUTE_SCALE,
+        negative: false,
+    };
+
+    fn zero() -> Decimal {
+        Decimal::zero()
+    }
+
+    fn one() -> Decimal {
+        Decimal::one()
+    }
+
+    /// 1.0001 at compute scale
+    fn tick_base() -> Decimal {
+        Decimal::new(1_000_100_000_000, COMPUTE_SCALE, false)
+    }
+
+    fn tick_to_rp(tick: u128) -> Result<Decimal, ClPoolError> {
+        Ok(TICK_BASE_ROOT.pow(tick)?)
+    }
+
+    fn rp_to_tick(rp: Decimal, left_to_right: bool) -> Result<u128, ClPoolError> {
+        let base = TICK_BASE_ROOT;
+        let tick_decimal = match left_to_right {
+            true => rp.ln()?.div_up(base.ln()?)?,
+            false => rp.ln()?.div(base.ln()?)?,
+        };
+        if tick_decimal.is_negative() {
+            return Ok(0);
+        }
+        Ok(tick_decimal.to_scale(0)?.value)
+    }
+
+    /// Lowest tick from start whose next tick is at or above rp (or that next tick when left_to_right).
+    /// Skips ahead with rp_to_tick before looping, so ticks far from start don't loop from start.
+    fn rp_to_tick_loop(rp: Decimal, left_to_right: bool, start: u128) -> Result<u128, ClPoolError> {
+        let m = TICK_BASE_ROOT;
+        let mut x = start.max(Self::rp_to_tick(rp, false)?.saturating_sub(2));
+        let mut rez = m.pow(x)?;
+        loop {
+            rez = rez.mul(m)?;
+            if rez.gte(rp)? {
+                return match left_to_right {
+                    true => x
+                        .checked_add(1)
+                        .ok_or(ClPoolError::InvariantViolated("tick overflow")),
+                    false => Ok(x),
+                };
+            }
+            x = x
+                .checked_add(1)
+                .ok_or(ClPoolError::InvariantViolated("tick overflow"))?;
+        }
+    }
+
+    /// Use tick spacing to find allowable/ initializable tick that is <= tick
+    /// (if left_to_right is false) or >= tick (if left_to_right is true)
+    fn tk_to_possible_tk(tick: u128, spacing: u128, left_to_right: bool) -> u128 {
+        match (left_to_right, tick % spacing) {
+            (false, rem) => tick - rem,
+            (true, 0) => tick,
+            (true, rem) => tick - rem + spacing,
+        }
+    }
+
+    /// Find allowable tick from given rp
+    fn rp_to_possible_tk(
+        rp: Decimal,
+        spacing: u128,
+        left_to_right: bool,
+        start: u128,
+    ) -> Result<u128, ClPoolError> {
+        let tick_theo = Self::rp_to_tick_loop(rp, left_to_right, start)?;
+        Ok(Self::tk_to_possible_tk(tick_theo, spacing, left_to_right))
+    }
+
+    /// Lx : liquidity amount when liquidity fully composed of token x
+    /// e.g when price below lower bound of range and y=0. [5]
+    /// x * rpa * rpb / (rpb - rpa)
+    fn liq_x_only(x: Decimal, rpa: Decimal, rpb: Decimal) -> Result<Decimal, ClPoolError> {
+        let rpb_minus_rpa = rpb.sub(rpa)?;
+        if !rpb_minus_rpa.is_positive() {
+            return Err(ClPoolError::InvalidTickRange);
+        }
+        Ok(x.mul(rpa)?.mul(rpb)?.div(rpb_minus_rpa)?)
+    }
+
+    /// Ly : liquidity amount when liquidity fully composed of token y
+    /// e.g when price above upper bound of range, x=0. [9]
+    /// y / (rpb - rpa)
+    fn liq_y_only(y: Decimal, rpa: Decimal, rpb: Decimal) -> Result<Decimal, ClPoolError> {
+        let rpb_minus_rpa = rpb.sub(rpa)?;
+        if !rpb_minus_rpa.is_positive() {
+            return Err(ClPoolError::InvalidTickRange);
+        }
+        Ok(y.div(rpb_minus_rpa)?)
+    }
+
+    /// L : liquidity amount from real reserves based on where price is compared to price range
+    fn liq_from_x_y_rp_rng(
+        x: Decimal,
+        y: Decimal,
+        rp: Decimal,
+        rpa: Decimal,
+        rpb: Decimal,
+    ) -> Result<Decimal, ClPoolError> {
+        if rp.lte(rpa)? {
+            // y = 0 and reserves entirely in x. [4]
+            Self::liq_x_only(x, rpa, rpb)
+        } else if rp.lt(rpb)? {
+            // [11,12]
+            // x covers sub-range [P,Pb] and y covers the other side [Pa,P]
+            let lx = Self::liq_x_only(x, rp, rpb)?;
+            let ly = Self::liq_y_only(y, rpa, rp)?;
+            // Lx Ly should be close to equal, by precaution take the minimum
+            Ok(lx.min(ly))
+        } else {
+            // x = 0 and reserves entirely in y. [8]
+            Self::liq_y_only(y, rpa, rpb)
+        }
+    }
+
+    /// X amount from L, price and bounds, the price is kept within the range [11]
+    /// l * (rpb - rp) / (rp * rpb)
+    fn x_from_l_rp_rng(
+        l: Decimal,
+        rp: Decimal,
+        rpa: Decimal,
+        rpb: Decimal,
+    ) -> Result<Decimal, ClPoolError> {
+        if rp.is_zero() || rpb.is_zero() || rpa.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "root price should not be nil",
+            ));
+        }
+        let rp = rp.min(rpb).max(rpa);
+
+        let rpb_minus_rp = rpb.sub(rp)?;
+        let rp_mul_rpb = rp.mul(rpb)?;
+
+        Ok(l.mul(rpb_minus_rp)?.div(rp_mul_rpb)?)
+    }
+
+    /// Y amount from L, price and bounds, the price is kept within the range [11]
+    /// l * (rp - rpa)
+    fn y_from_l_rp_rng(
+        l: Decimal,
+        rp: Decimal,
+        rpa: Decimal,
+        rpb: Decimal,
+    ) -> Result<Decimal, ClPoolError> {
+        let rp = rp.min(rpb).max(rpa);
+
+        let rp_minus_rpa = rp.sub(rpa)?;
+        if rp_minus_rpa.is_negative() {
+            return Err(ClPoolError::InvalidTickRange);
+        }
+        Ok(l.mul(rp_minus_rpa)?)
+    }
+
+    /// Change of reserve X based of change of price
+    /// l * (rp_old - rp_new) / (rp_old * rp_new)
+    fn dx_from_l_drp(l: Decimal, rp_old: Decimal, rp_new: Decimal) -> Result<Decimal, ClPoolError> {
+        if rp_old.is_zero() || rp_new.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "root price should not be nil",
+            ));
+        }
+        let diff = rp_old.sub(rp_new)?;
+        let old_mul_new = rp_old.mul(rp_new)?;
+
+        Ok(l.mul(diff)?.div(old_mul_new)?)
+    }
+
+    /// Change of reserve Y based of change of price
+    /// l * (rp_new - rp_old)
+    fn dy_from_l_drp(l: Decimal, rp_old: Decimal, rp_new: Decimal) -> Result<Decimal, ClPoolError> {
+        Ok(rp_new.sub(rp_old)?.mul(l)?)
+    }
+
+    /// Change of reserve X based of change of price with hmm adjustment
+    fn dx_from_l_drp_hmm(
+        l: Decimal,
+        rp_old: Decimal,
+        rp_new: Decimal,
+        c: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<Decimal, ClPoolError> {
+        let one = Self::one();
+        if c.lt(one)? {
+            return Err(ClPoolError::InvalidCompensationParameter);
+        }
+        if rp_old.eq(rp_new)? {
+            return Ok(Self::zero());
+        }
+        if rp_oracle.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "hmm adjustment requires an oracle price",
+            ));
+        }
+        if c.eq(one)? {
+            // l / rp_oracle * ln(rp_old / rp_new)
+            let log_of_ratio = rp_old.ln()?.sub(rp_new.ln()?)?;
+            Ok(l.div(rp_oracle)?.mul(log_of_ratio)?)
+        } else {
+            // l / rp_oracle^c * (rp_new^(c-1) - rp_old^(c-1)) / (1-c)
+            let cmo = c.sub(one)?;
+            let omc = cmo.neg();
+            let diff = rp_new.pow(cmo)?.sub(rp_old.pow(cmo)?)?;
+            Ok(l.div(rp_oracle.pow(c)?)?.mul(diff)?.div(omc)?)
+        }
+    }
+
+    /// Change of reserve Y based of change of price with hmm adjustment
+    fn dy_from_l_drp_hmm(
+        l: Decimal,
+        rp_old: Decimal,
+        rp_new: Decimal,
+        c: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<Decimal, ClPoolError> {
+        let one = Self::one();
+        if c.lt(one)? {
+            return Err(ClPoolError::InvalidCompensationParameter);
+        }
+        if rp_old.eq(rp_new)? {
+            return Ok(Self::zero());
+        }
+        if rp_old.is_zero() || rp_new.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "root price should not be nil",
+            ));
+        }
+        if c.eq(one)? {
+            // l * rp_oracle * ln(rp_old / rp_new)
+            let log_of_ratio = rp_old.ln()?.sub(rp_new.ln()?)?;
+            Ok(l.mul(rp_oracle)?.mul(log_of_ratio)?)
+        } else {
+            // l * rp_oracle^c * (1/rp_new^(c-1) - 1/rp_old^(c-1)) / (1-c)
+            let cmo = c.sub(one)?;
+            let omc = cmo.neg();
+            let inv_rp_new_pow_cmo = one.div(rp_new.pow(cmo)?)?;
+            let inv_rp_old_pow_cmo = one.div(rp_old.pow(cmo)?)?;
+            let diff = inv_rp_new_pow_cmo.sub(inv_rp_old_pow_cmo)?;
+            Ok(l.mul(rp_oracle.pow(c)?)?.mul(diff)?.div(omc)?)
+        }
+    }
+
+    /// New price based of change of reserve x
+    /// (l * rp_old) / (dx * rp_old + l)
+    fn rp_new_from_l_dx(l: Decimal, rp_old: Decimal, dx: Decimal) -> Result<Decimal, ClPoolError> {
+        let numerator = l.mul(rp_old)?;
+        let denom = dx.mul(rp_old)?.add(l)?;
+        if denom.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "(dx * rp_old + l) should not be nil",
+            ));
+        }
+        Ok(numerator.div(denom)?)
+    }
+
+    /// New price based of change of reserve y
+    /// dy / l + rp_old
+    fn rp_new_from_l_dy(l: Decimal, rp_old: Decimal, dy: Decimal) -> Result<Decimal, ClPoolError> {
+        if l.is_zero() {
+            return Err(ClPoolError::InvariantViolated(
+                "liquidity should not be nil",
+            ));
+        }
+        Ok(dy.div(l)?.add(rp_old)?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::decimal::Sqrt;
+
+    struct Math;
+    impl ClPoolMath for Math {}
+
+    #[test]
+    fn test_tick_base_root() {
+        assert_eq!(TICK_BASE_ROOT, Math::tick_base().sqrt().unwrap());
+    }
+
+    #[test]
+    fn test_rp_to_tick_loop() {
+        // skipping ahead finds the same tick as looping from zero
+        for tick in [0u128, 1, 2, 10, 1_000, 76_012] {
+            let rp = Math::tick_to_rp(tick)
+                .unwrap()
+                .add(Decimal::new(1, COMPUTE_SCALE, false))
+                .unwrap();
+            let from_zero = {
+                let m = TICK_BASE_ROOT;
+                let mut rez = Decimal::one();
+                let mut x = 0;
+                loop {
+                    rez = rez.mul(m).unwrap();
+                    if rez.gte(rp).unwrap() {
+                        break x;
+                    }
+                    x += 1;
+                }
+            };
+            assert_eq!(Math::rp_to_tick_loop(rp, false, 0).unwrap(), from_zero);
+            assert_eq!(Math::rp_to_tick_loop(rp, true, 0).unwrap(), from_zero + 1);
+        }
+    }
+
+    #[test]
+    fn test_tk_to_possible_tk() {
+        assert_eq!(Math::tk_to_possible_tk(105, 10, false), 100);
+        assert_eq!(Math::tk_to_possible_tk(105, 10, true), 110);
+        assert_eq!(Math::tk_to_possible_tk(100, 10, true), 100);
+        assert_eq!(Math::tk_to_possible_tk(105, 1, false), 105);
+    }
+}
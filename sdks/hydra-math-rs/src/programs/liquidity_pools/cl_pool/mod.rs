@@ -0,0 +1,1118 @@
+//! Concentrated liquidity pool with hmm adjusted swaps, ported from the cl_pool prototype.
+//! The pool holds its global state and initialized ticks, positions are owned by the caller
+//! so they can be stored and keyed independently of the pool (eg in their own accounts).
+pub mod cl_components;
+pub mod cl_math;
+
+pub use cl_components::{
+    CollectResult, DepositResult, FeeAmounts, GlobalState, PositionState, SwapOutput, TickState,
+    WithdrawResult,
+};
+pub use cl_math::{ClPoolMath, TICK_BASE_ROOT};
+
+use crate::decimal::{Add, Compare, Decimal, Div, Mul, Pow, Sub};
+use crate::programs::liquidity_pools::cl_pool::cl_components::SwapWithinResult;
+use crate::programs::liquidity_pools::error::ClPoolError;
+use std::collections::BTreeMap;
+use std::ops::Neg;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClPool {
+    pub tick_spacing: u128,
+    pub global_state: GlobalState,
+    /// initialized ticks, keep ordered
+    pub active_ticks: BTreeMap<u128, TickState>,
+    /// token x reserves
+    pub x: Decimal,
+    /// token y reserves
+    pub y: Decimal,
+    /// hmm adjustment pot of token x
+    pub x_adj: Decimal,
+    /// hmm adjustment pot of token y
+    pub y_adj: Decimal,
+    /// swap fee pot of token x
+    pub x_fee: Decimal,
+    /// swap fee pot of token y
+    pub y_fee: Decimal,
+    /// hmm compensation parameter, zero or between 1 and 2
+    pub c: Decimal,
+    pub fee_rate: Decimal,
+}
+
+impl ClPoolMath for ClPool {}
+
+impl ClPool {
+    /// Empty pool priced at the initializable tick at or below bootstrap_tick
+    pub fn new(
+        bootstrap_tick: u128,
+        tick_spacing: u128,
+        c: Decimal,
+        fee_rate: Decimal,
+    ) -> Result<Self, ClPoolError> {
+        if tick_spacing == 0 {
+            return Err(ClPoolError::InvalidTickSpacing);
+        }
+        if c.is_negative() || c.gt(Decimal::two())? || (c.is_positive() && c.lt(Self::one())?) {
+            return Err(ClPoolError::InvalidCompensationParameter);
+        }
+        if fee_rate.is_negative() || fee_rate.gte(Self::one())? {
+            return Err(ClPoolError::InvariantViolated(
+                "fee rate needs to be between 0 and 1",
+            ));
+        }
+
+        let tick = Self::tk_to_possible_tk(bootstrap_tick, tick_spacing, false);
+
+        Ok(Self {
+            tick_spacing,
+            global_state: GlobalState {
+                liq: Self::zero(),
+                rp: Self::tick_to_rp(tick)?,
+                tick,
+                fee_growth: FeeAmounts::zero(),
+            },
+            active_ticks: BTreeMap::new(),
+            x: Self::zero(),
+            y: Self::zero(),
+            x_adj: Self::zero(),
+            y_adj: Self::zero(),
+            x_fee: Self::zero(),
+            y_fee: Self::zero(),
+            c,
+            fee_rate,
+        })
+    }
+
+    /// Empty pool bootstrapped from a root price as in the prototype
+    pub fn from_root_price(
+        bootstrap_rp: Decimal,
+        tick_spacing: u128,
+        c: Decimal,
+        fee_rate: Decimal,
+    ) -> Result<Self, ClPoolError> {
+        if bootstrap_rp.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "cannot bootstrap with negative root-price",
+            ));
+        }
+        Self::new(
+            Self::rp_to_tick_loop(bootstrap_rp, false, 0)?,
+            tick_spacing,
+            c,
+            fee_rate,
+        )
+    }
+
+    /// Initializable (lower, upper) ticks of a position range, the upper tick needs to be above the lower tick
+    pub fn range_ticks(
+        &self,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<(u128, u128), ClPoolError> {
+        let lower_tick = self.tick_to_possible_tick(lower_tick, false);
+        let upper_tick = self.tick_to_possible_tick(upper_tick, false);
+        if upper_tick <= lower_tick {
+            return Err(ClPoolError::InvalidTickRange);
+        }
+        Ok((lower_tick, upper_tick))
+    }
+
+    fn tick_to_possible_tick(&self, tick: u128, left_to_right: bool) -> u128 {
+        Self::tk_to_possible_tk(tick, self.tick_spacing, left_to_right)
+    }
+
+    fn add_glbl_liq(&mut self, liq_to_add: Decimal) -> Result<(), ClPoolError> {
+        let new_liq = self.global_state.liq.add(liq_to_add)?;
+        if new_liq.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "global liquidity cannot turn negative",
+            ));
+        }
+        self.global_state.liq = new_liq;
+        Ok(())
+    }
+
+    fn update_tick(
+        &mut self,
+        tick: u128,
+        liq_delta: Decimal,
+        upper: bool,
+    ) -> Result<(), ClPoolError> {
+        // set fee growth outside of a new tick based on convention [6.21]
+        let glbl_tick = self.global_state.tick;
+        let glbl_fees = self.global_state.fee_growth;
+        let ts = self.active_ticks.entry(tick).or_insert_with(|| TickState {
+            liq_net: Self::zero(),
+            liq_gross: Self::zero(),
+            fee_growth_outside: if glbl_tick >= tick {
+                glbl_fees
+            } else {
+                FeeAmounts::zero()
+            },
+        });
+
+        let liq_net_to_add = match upper {
+            false => liq_delta,
+            true => liq_delta.neg(),
+        };
+        ts.liq_net = ts.liq_net.add(liq_net_to_add)?;
+        ts.liq_gross = ts.liq_gross.add(liq_delta)?;
+        if ts.liq_gross.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "gross liquidity cannot turn negative",
+            ));
+        }
+
+        if ts.liq_gross.is_zero() {
+            // de-initialize tick when no longer ref'ed by a position
+            self.active_ticks.remove(&tick);
+        }
+        Ok(())
+    }
+
+    /// Update global state and tick state when an initialized tick is crossed while performing a swap
+    fn cross_tick(&mut self, provided_tick: u128, left_to_right: bool) -> Result<(), ClPoolError> {
+        if !left_to_right && self.global_state.tick != provided_tick {
+            return Err(ClPoolError::InvariantViolated(
+                "can only cross current tick",
+            ));
+        }
+        let fg = self.global_state.fee_growth;
+
+        let ts =
+            self.active_ticks
+                .get_mut(&provided_tick)
+                .ok_or(ClPoolError::InvariantViolated(
+                    "cannot find tick for crossing",
+                ))?;
+
+        // add/subtract to global liq depending on direction of crossing
+        let liq_to_apply = match left_to_right {
+            true => ts.liq_net,
+            false => ts.liq_net.neg(),
+        };
+
+        // flip fee growth outside [6.26]
+        ts.fee_growth_outside = fg.checked_sub(ts.fee_growth_outside)?;
+
+        // update current tick to reflect crossing; rp unchanged
+        self.global_state.tick = if left_to_right {
+            provided_tick
+        } else {
+            let below = provided_tick
+                .checked_sub(1)
+                .ok_or(ClPoolError::InvariantViolated(
+                    "cannot cross below tick zero",
+                ))?;
+            self.tick_to_possible_tick(below, left_to_right)
+        };
+
+        self.add_glbl_liq(liq_to_apply)
+    }
+
+    /// Next active tick from a starting point going left
+    fn get_left_limit_of_swap_within(&self, start_t: u128) -> Option<u128> {
+        let tick = self.tick_to_possible_tick(start_t.min(self.global_state.tick), false);
+        // case when starting rp equals exactly the rp of the current tick
+        // is covered in swap (will do 0-qty and trigger cross)
+        self.active_ticks
+            .range(..=tick)
+            .next_back()
+            .map(|(tk, _)| *tk)
+    }
+
+    /// Next active tick from a starting point going right, limit of a swap_within_tick_from_y.
+    /// Not to be used for cross_tick.
+    fn get_right_limit_of_swap_within(
+        &self,
+        start_t: u128,
+        glbl_tick: u128,
+    ) -> Result<Option<u128>, ClPoolError> {
+        let start_tick = self.tick_to_possible_tick(start_t, false);
+
+        if start_tick == glbl_tick {
+            // the liquidity of [start_tick, next_tick) is already in range,
+            // looking for the 1st active tick strictly above it
+            Ok(self
+                .active_ticks
+                .range(start_tick + 1..)
+                .next()
+                .map(|(tk, _)| *tk))
+        } else if start_tick > glbl_tick {
+            // the global rp has travelled to the tick above the current global tick
+            // without crossing it, so we are still in the range of current tick.
+            // looking for the 1st active tick above and possibly including start_tick,
+            // when it is active the next swap_within will swap 0-qty and trigger a crossing
+            Ok(self
+                .active_ticks
+                .range(start_tick..)
+                .next()
+                .map(|(tk, _)| *tk))
+        } else {
+            Err(ClPoolError::InvariantViolated(
+                "global rp is not expected strictly below current global tick",
+            ))
+        }
+    }
+
+    /// During swap, when no liquidity is in range, find and cross the next active tick to kick in some liquidity.
+    /// Returns the new goal tick (None if no liquidity is left in the direction) and the global rp.
+    fn try_get_in_range(
+        &mut self,
+        left_to_right: bool,
+    ) -> Result<(Option<u128>, Decimal), ClPoolError> {
+        if self.global_state.liq.is_positive() {
+            return Err(ClPoolError::InvariantViolated(
+                "there already is liquidity in range",
+            ));
+        }
+
+        let ticks: Vec<u128> = if left_to_right {
+            self.active_ticks.keys().cloned().collect()
+        } else {
+            self.active_ticks.keys().rev().cloned().collect()
+        };
+
+        for tk in ticks {
+            // ignore ticks behind the current tick
+            if (!left_to_right && tk > self.global_state.tick)
+                || (left_to_right && tk <= self.global_state.tick)
+            {
+                continue;
+            }
+            self.global_state.tick = tk;
+            self.global_state.rp = Self::tick_to_rp(tk)?;
+            self.cross_tick(tk, left_to_right)?;
+
+            // set the next goal for swap
+            let new_goal = if left_to_right {
+                self.get_right_limit_of_swap_within(tk, tk)?
+            } else {
+                // crossing puts the global tick 1 (possible) tick under tk
+                self.get_left_limit_of_swap_within(self.global_state.tick)
+            };
+
+            if self.global_state.liq.is_positive() {
+                return Ok((new_goal, self.global_state.rp));
+            }
+        }
+        Ok((None, self.global_state.rp))
+    }
+
+    /// Fees earned below and above tick per unit of liquidity
+    fn fee_below_above(&self, tick: u128) -> Result<(FeeAmounts, FeeAmounts), ClPoolError> {
+        let fg = self.global_state.fee_growth;
+        match self.active_ticks.get(&tick) {
+            // from [6.17 - 6.21], convention
+            None => Ok((fg, FeeAmounts::zero())),
+            Some(ts) => {
+                let f0 = ts.fee_growth_outside;
+                if self.global_state.tick >= tick {
+                    Ok((f0, fg.checked_sub(f0)?)) // [6.18]
+                } else {
+                    Ok((fg.checked_sub(f0)?, f0)) // [6.17]
+                }
+            }
+        }
+    }
+
+    /// Fees earned per unit of liquidity within a range of ticks (eg by a position)
+    pub fn fee_growth_inside(
+        &self,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<FeeAmounts, ClPoolError> {
+        let (f_blw_lwr, _) = self.fee_below_above(lower_tick)?;
+        let (_, f_abv_upr) = self.fee_below_above(upper_tick)?;
+
+        let fee_earned = self
+            .global_state
+            .fee_growth
+            .checked_sub(f_blw_lwr)?
+            .checked_sub(f_abv_upr)?;
+        if fee_earned.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "fees earned cannot be negative",
+            ));
+        }
+        Ok(fee_earned)
+    }
+
+    /// Fees owed to a position since it was last touched
+    pub fn uncollected_fees(
+        &self,
+        position: &PositionState,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<FeeAmounts, ClPoolError> {
+        if position.liq.is_zero() {
+            return Ok(FeeAmounts::zero());
+        }
+        let uncollected = self
+            .fee_growth_inside(lower_tick, upper_tick)?
+            .checked_sub(position.fee_growth_inside)?;
+        if uncollected.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "fees earned cannot be negative",
+            ));
+        }
+        uncollected.earned_by(position.liq)
+    }
+
+    /// Handles all facets of updating a position, used for deposits (l>0) and withdrawals (l<0).
+    /// Returns the uncollected fee amounts of the position to offset/add in the deposit/withdrawal.
+    fn set_position(
+        &mut self,
+        position: &mut PositionState,
+        lower_tick: u128,
+        upper_tick: u128,
+        liq_delta: Decimal,
+    ) -> Result<FeeAmounts, ClPoolError> {
+        let new_liq = position.liq.add(liq_delta)?;
+        if new_liq.is_negative() {
+            // abort if withdrawal liq exceeds position liquidity
+            return Err(ClPoolError::InsufficientPositionLiquidity);
+        }
+
+        let fees = self.uncollected_fees(position, lower_tick, upper_tick)?;
+
+        // checkpoint the fee growth inside the range, new positions start from the current fee growth
+        position.fee_growth_inside = self.fee_growth_inside(lower_tick, upper_tick)?;
+        position.liq = new_liq;
+
+        // update tick states for lower and upper
+        self.update_tick(lower_tick, liq_delta, false)?;
+        self.update_tick(upper_tick, liq_delta, true)?;
+
+        // update global liquidity if current price in position's range
+        if self.global_state.tick >= lower_tick && self.global_state.tick < upper_tick {
+            self.add_glbl_liq(liq_delta)?;
+        }
+
+        Ok(fees)
+    }
+
+    /// Deposit up to x and y into the range [lower_tick, upper_tick) of position.
+    /// Zero deposits collect the fees of the position without changing its liquidity.
+    pub fn deposit(
+        &mut self,
+        position: &mut PositionState,
+        x: Decimal,
+        y: Decimal,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<DepositResult, ClPoolError> {
+        if x.is_negative() || y.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "can only deposit positive amounts",
+            ));
+        }
+
+        let (lower_tick, upper_tick) = self.range_ticks(lower_tick, upper_tick)?;
+        let rpa_used = Self::tick_to_rp(lower_tick)?;
+        let rpb_used = Self::tick_to_rp(upper_tick)?;
+        let rp_used = self.global_state.rp;
+
+        let liq = Self::liq_from_x_y_rp_rng(x, y, rp_used, rpa_used, rpb_used)?;
+        if liq.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "negative liquidity on deposit",
+            ));
+        }
+
+        let x_in = Self::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used)?;
+        let y_in = Self::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used)?;
+        if x_in.gt(x)? || y_in.gt(y)? {
+            return Err(ClPoolError::InvariantViolated(
+                "used amount cannot exceed provided amount",
+            ));
+        }
+
+        let fees = self.set_position(position, lower_tick, upper_tick, liq)?;
+
+        // offset fee amounts from deposit amounts: this will be the amount debited from user
+        let x_debited = x_in.sub(fees.fee_x)?.sub(fees.adj_x)?;
+        let y_debited = y_in.sub(fees.fee_y)?.sub(fees.adj_y)?;
+
+        self.disburse_fees(&fees)?;
+        self.x = self.x.add(x_in)?;
+        self.y = self.y.add(y_in)?;
+
+        Ok(DepositResult {
+            liq,
+            x_in,
+            y_in,
+            fees,
+            x_debited,
+            y_debited,
+        })
+    }
+
+    /// Withdraw liq from the range [lower_tick, upper_tick) of position, along with its uncollected fees.
+    /// Zero withdrawals collect the fees of the position without changing its liquidity.
+    pub fn withdraw(
+        &mut self,
+        position: &mut PositionState,
+        liq: Decimal,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<WithdrawResult, ClPoolError> {
+        if liq.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "only positive amount of liquidity can be withdrawn",
+            ));
+        }
+
+        let (lower_tick, upper_tick) = self.range_ticks(lower_tick, upper_tick)?;
+        let rpa_used = Self::tick_to_rp(lower_tick)?;
+        let rpb_used = Self::tick_to_rp(upper_tick)?;
+
+        let fees = self.set_position(position, lower_tick, upper_tick, liq.neg())?;
+        let rp_used = self.global_state.rp;
+
+        // round down amount withdrawn as precaution
+        let adj_factor = Self::one().sub(Self::ADJ_WITHDRAWAL)?;
+        let x_out = Self::x_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used)?.mul(adj_factor)?;
+        let y_out = Self::y_from_l_rp_rng(liq, rp_used, rpa_used, rpb_used)?.mul(adj_factor)?;
+
+        // add fees on top of what user will receive
+        let x_sent = x_out.add(fees.fee_x)?.add(fees.adj_x)?;
+        let y_sent = y_out.add(fees.fee_y)?.add(fees.adj_y)?;
+
+        if self.x.lt(x_out)? || self.y.lt(y_out)? {
+            return Err(ClPoolError::InsufficientReserves);
+        }
+        self.disburse_fees(&fees)?;
+        self.x = self.x.sub(x_out)?;
+        self.y = self.y.sub(y_out)?;
+
+        Ok(WithdrawResult {
+            x_out,
+            y_out,
+            fees,
+            x_sent,
+            y_sent,
+        })
+    }
+
+    /// Collect the uncollected fees of a position and reset its fee growth checkpoint,
+    /// the liquidity of the position, ticks and pool are untouched
+    pub fn collect_fees(
+        &mut self,
+        position: &mut PositionState,
+        lower_tick: u128,
+        upper_tick: u128,
+    ) -> Result<CollectResult, ClPoolError> {
+        let (lower_tick, upper_tick) = self.range_ticks(lower_tick, upper_tick)?;
+
+        let fees = self.uncollected_fees(position, lower_tick, upper_tick)?;
+        position.fee_growth_inside = self.fee_growth_inside(lower_tick, upper_tick)?;
+        self.disburse_fees(&fees)?;
+
+        Ok(CollectResult {
+            fees,
+            x_sent: fees.fee_x.add(fees.adj_x)?,
+            y_sent: fees.fee_y.add(fees.adj_y)?,
+        })
+    }
+
+    /// Take fees paid out to a position from the fee and hmm adjustment pots
+    fn disburse_fees(&mut self, fees: &FeeAmounts) -> Result<(), ClPoolError> {
+        if self.x_fee.lt(fees.fee_x)?
+            || self.y_fee.lt(fees.fee_y)?
+            || self.x_adj.lt(fees.adj_x)?
+            || self.y_adj.lt(fees.adj_y)?
+        {
+            return Err(ClPoolError::InsufficientReserves);
+        }
+        self.x_fee = self.x_fee.sub(fees.fee_x)?;
+        self.y_fee = self.y_fee.sub(fees.fee_y)?;
+        self.x_adj = self.x_adj.sub(fees.adj_x)?;
+        self.y_adj = self.y_adj.sub(fees.adj_y)?;
+        Ok(())
+    }
+
+    /// Portion of swap execution occurring within 2 consecutive active ticks when x is supplied to the pool.
+    /// No writing to state occurs here.
+    fn swap_within_tick_from_x(
+        &self,
+        start_rp: Decimal,
+        goal_tick: u128,
+        liq: Decimal,
+        dx: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<SwapWithinResult, ClPoolError> {
+        let (done_dx, end_t, end_rp, cross, fee_x);
+
+        // root-price at goal tick - here on the left
+        let rp_goal = Self::tick_to_rp(goal_tick)?;
+        if rp_goal.gt(start_rp)? {
+            // price exactly on the current tick (rp_goal = start_rp) swaps 0-qty and crosses the tick
+            return Err(ClPoolError::InvariantViolated(
+                "expect price to go down when X supplied to pool",
+            ));
+        }
+
+        // put aside max potential swap fees before affecting prices
+        let fee_factor = Self::one().sub(self.fee_rate)?;
+        let dx_max = dx.mul(fee_factor)?;
+
+        // change of reserve X possible if we go all the way to goal tick
+        let doable_dx = Self::dx_from_l_drp(liq, start_rp, rp_goal)?;
+        if doable_dx.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "doable_dX > 0 when X supplied to pool",
+            ));
+        }
+
+        if doable_dx.lt(dx_max)? {
+            // leftover quantity to swap. fill what we can and cross the goal tick
+            done_dx = doable_dx;
+            fee_x = done_dx.div(fee_factor)?.mul(self.fee_rate)?;
+            cross = true;
+            end_t = goal_tick;
+            end_rp = rp_goal; // ensure use same rp at tick borders, avoid log
+        } else {
+            // enough to fill full quantity. make all dX_max 'done', then calc end_rp
+            done_dx = dx_max;
+            fee_x = dx.sub(dx_max)?;
+            cross = false;
+            end_rp = Self::rp_new_from_l_dx(liq, start_rp, done_dx)?;
+            // tick is always on the left (round down)
+            end_t = Self::rp_to_tick_loop(end_rp, false, goal_tick.saturating_sub(1))?;
+
+            if end_rp.gt(start_rp)? || end_rp.lt(rp_goal)? {
+                return Err(ClPoolError::InvariantViolated(
+                    "expect end_rp between rp_goal and start_rp when pool given X",
+                ));
+            }
+        }
+
+        // now figure out how much done_dy and hmm_adj_y
+        let done_dy_cpmm = Self::dy_from_l_drp(liq, start_rp, end_rp)?;
+        let done_dy = if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.gte(start_rp)? {
+            // no oracle or no hmm (c=0), or trade makes pool price diverge more from oracle:
+            // fall back to amm (hmm adjusts on convergence only)
+            done_dy_cpmm
+        } else if rp_oracle.gte(end_rp)? {
+            // oracle in between start_rp and end_rp: use hmm till the oracle price
+            // then unadjusted amm till end_rp
+            let done_dy_1 = Self::dy_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle)?;
+            let done_dy_2 = Self::dy_from_l_drp(liq, rp_oracle, end_rp)?;
+            if done_dy_1.is_positive() || done_dy_2.is_positive() {
+                return Err(ClPoolError::InvariantViolated(
+                    "expect done_dY < 0 when X supplied to pool",
+                ));
+            }
+            done_dy_1.add(done_dy_2)?
+        } else {
+            // trade converges to the oracle price without reaching it: use hmm all the way
+            Self::dy_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle)?
+        };
+
+        let hmm_adj_y = done_dy.sub(done_dy_cpmm)?;
+
+        if done_dy_cpmm.is_positive() {
+            return Err(ClPoolError::InvariantViolated(
+                "expect done_dY < 0 when X supplied to pool",
+            ));
+        }
+        if hmm_adj_y.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "hmm adj should be positive (conservative for pool i.e giving out less)",
+            ));
+        }
+        if self.y.add(done_dy_cpmm)?.is_negative() {
+            return Err(ClPoolError::InsufficientReserves);
+        }
+        // avoid numerical noise for very small trades relative to liquidity
+        if !done_dx.is_zero() && !done_dy.is_zero() {
+            let avg_price = done_dy.neg().div(done_dx.add(fee_x)?)?;
+            if avg_price.gt(start_rp.pow(2u128)?)? {
+                return Err(ClPoolError::InvariantViolated(
+                    "pool cannot buy X at a greater avg price than starting price",
+                ));
+            }
+        }
+
+        Ok(SwapWithinResult {
+            recv_amount: done_dx,
+            send_amount: done_dy,
+            end_tick: end_t,
+            end_rp,
+            cross,
+            send_hmm_adj: hmm_adj_y,
+            recv_fee: fee_x,
+        })
+    }
+
+    /// Swap when the pool receives dx>0. We go from right to left on the price line (price goes down)
+    /// and manage tick crossings as needed, filling until the order is filled or liquidity dries up.
+    pub fn execute_swap_from_x(
+        &mut self,
+        dx: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<SwapOutput, ClPoolError> {
+        if !dx.is_positive() {
+            return Err(ClPoolError::InvalidSwapAmount);
+        }
+        let left_to_right = false;
+
+        let mut curr_t = self.global_state.tick;
+        let mut curr_rp = self.global_state.rp;
+
+        let mut swpd_dx = Self::zero();
+        let mut swpd_dy = Self::zero();
+        let (mut adjusted_dy, mut total_fee_x) = (Self::zero(), Self::zero());
+
+        while swpd_dx.lt(dx)? {
+            let goal_tick = if self.global_state.liq.is_positive() {
+                self.get_left_limit_of_swap_within(curr_t)
+            } else {
+                // try move into range; if not possible the swap ends
+                let (goal_tick, new_rp) = self.try_get_in_range(left_to_right)?;
+                curr_rp = new_rp;
+                goal_tick
+            };
+
+            // no more active ticks (liquidity) in this direction
+            let gtk = match goal_tick {
+                Some(gtk) => gtk,
+                None => break,
+            };
+
+            let rez = self.swap_within_tick_from_x(
+                curr_rp,
+                gtk,
+                self.global_state.liq,
+                dx.sub(swpd_dx)?,
+                rp_oracle,
+            )?;
+
+            // hmm_adj comes out of reserves and into the adj pot
+            let new_y = self.y.add(rez.send_amount)?.sub(rez.send_hmm_adj)?;
+            if new_y.is_negative() {
+                return Err(ClPoolError::InsufficientReserves);
+            }
+            if dx
+                .sub(swpd_dx)?
+                .sub(rez.recv_amount)?
+                .sub(rez.recv_fee)?
+                .is_negative()
+            {
+                return Err(ClPoolError::InvariantViolated(
+                    "cannot fill more X than provided",
+                ));
+            }
+
+            // gross (including fees) for input token, net for output token
+            swpd_dx = swpd_dx.add(rez.recv_amount)?.add(rez.recv_fee)?;
+            swpd_dy = swpd_dy.add(rez.send_amount)?;
+            adjusted_dy = adjusted_dy.add(rez.send_hmm_adj)?;
+            total_fee_x = total_fee_x.add(rez.recv_fee)?;
+            curr_t = rez.end_tick;
+            curr_rp = rez.end_rp;
+
+            // update global state to reflect price change (if any) & reserves
+            self.global_state.tick = curr_t;
+            self.global_state.rp = curr_rp;
+            self.x = self.x.add(rez.recv_amount)?;
+            self.y = new_y;
+            self.x_fee = self.x_fee.add(rez.recv_fee)?;
+            self.y_adj = self.y_adj.add(rez.send_hmm_adj)?;
+
+            // update fee growth to reflect latest swap_within, unless it was an empty trade
+            let liq_glbl = self.global_state.liq;
+            if liq_glbl.is_positive() {
+                let fg = &mut self.global_state.fee_growth;
+                fg.fee_x = rez.recv_fee.div(liq_glbl)?.add(fg.fee_x)?;
+                fg.adj_y = rez.send_hmm_adj.div(liq_glbl)?.add(fg.adj_y)?;
+            }
+
+            // perform crossing of tick, if necessary
+            if rez.cross && self.active_ticks.contains_key(&gtk) {
+                self.cross_tick(gtk, left_to_right)?;
+            }
+        }
+
+        let avg_p = match swpd_dx.is_zero() {
+            false => swpd_dy.neg().div(swpd_dx)?,
+            true => Self::zero(),
+        };
+        let end_p = self.global_state.rp.pow(2u128)?;
+        SwapOutput::new(swpd_dx, swpd_dy, adjusted_dy, total_fee_x, avg_p, end_p)
+    }
+
+    /// Portion of swap execution occurring within 2 consecutive active ticks when y is supplied to the pool.
+    /// No writing to state occurs here.
+    fn swap_within_tick_from_y(
+        &self,
+        start_rp: Decimal,
+        goal_tick: u128,
+        liq: Decimal,
+        dy: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<SwapWithinResult, ClPoolError> {
+        let (done_dy, end_t, end_rp, cross, fee_y);
+
+        // root-price at goal tick - here on the right
+        let rp_goal = Self::tick_to_rp(goal_tick)?;
+        if rp_goal.lt(start_rp)? {
+            // price exactly on the current tick (rp_goal = start_rp) swaps 0-qty and crosses the tick
+            return Err(ClPoolError::InvariantViolated(
+                "expect price to go up when Y supplied to pool",
+            ));
+        }
+
+        // put aside max potential swap fees before affecting prices
+        let fee_factor = Self::one().sub(self.fee_rate)?;
+        let dy_max = dy.mul(fee_factor)?;
+
+        // change of reserve Y possible if we go all the way to goal tick
+        let doable_dy = Self::dy_from_l_drp(liq, start_rp, rp_goal)?;
+        if doable_dy.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "doable_dY >= 0 when Y supplied to pool",
+            ));
+        }
+
+        if doable_dy.lt(dy_max)? {
+            // leftover quantity to swap. fill what we can and cross the goal tick
+            done_dy = doable_dy;
+            fee_y = done_dy.div(fee_factor)?.mul(self.fee_rate)?;
+            cross = true;
+            end_t = goal_tick;
+            end_rp = rp_goal; // ensure use same rp at tick borders, avoid log
+        } else {
+            // enough to fill full quantity. make all of dY_max 'done', then calc end_rp
+            done_dy = dy_max;
+            fee_y = dy.sub(dy_max)?;
+            cross = false;
+            end_rp = Self::rp_new_from_l_dy(liq, start_rp, done_dy)?;
+            // seek the end tick from the initializable tick under the current tick
+            let seek_start =
+                self.tick_to_possible_tick(self.global_state.tick.saturating_sub(1), false);
+            end_t = Self::rp_to_tick_loop(end_rp, false, seek_start)?;
+
+            if end_rp.lt(start_rp)? || end_rp.gt(rp_goal)? {
+                return Err(ClPoolError::InvariantViolated(
+                    "expect end_rp between start_rp and rp_goal when pool given Y",
+                ));
+            }
+        }
+
+        // now figure out how much done_dx and hmm_adj_x
+        let done_dx_cpmm = Self::dx_from_l_drp(liq, start_rp, end_rp)?;
+        let done_dx = if self.c.is_zero() || rp_oracle.is_zero() || rp_oracle.lte(start_rp)? {
+            // no oracle or no hmm (c=0), or trade makes pool price diverge more from oracle:
+            // fall back to amm (hmm adjusts on convergence only)
+            done_dx_cpmm
+        } else if rp_oracle.lte(end_rp)? {
+            // oracle in between start_rp and end_rp: use hmm till the oracle price
+            // then unadjusted amm till end_rp
+            let done_dx_1 = Self::dx_from_l_drp_hmm(liq, start_rp, rp_oracle, self.c, rp_oracle)?;
+            let done_dx_2 = Self::dx_from_l_drp(liq, rp_oracle, end_rp)?;
+            if done_dx_1.is_positive() || done_dx_2.is_positive() {
+                return Err(ClPoolError::InvariantViolated(
+                    "expect done_dX < 0 when Y supplied to pool",
+                ));
+            }
+            done_dx_1.add(done_dx_2)?
+        } else {
+            // trade converges to the oracle price without reaching it: use hmm all the way
+            Self::dx_from_l_drp_hmm(liq, start_rp, end_rp, self.c, rp_oracle)?
+        };
+
+        let hmm_adj_x = done_dx.sub(done_dx_cpmm)?;
+
+        if done_dx_cpmm.is_positive() {
+            return Err(ClPoolError::InvariantViolated(
+                "expect done_dX < 0 when Y supplied to pool",
+            ));
+        }
+        if hmm_adj_x.is_negative() {
+            return Err(ClPoolError::InvariantViolated(
+                "hmm adj should be positive (conservative for pool i.e giving out less)",
+            ));
+        }
+        if self.x.add(done_dx_cpmm)?.is_negative() {
+            return Err(ClPoolError::InsufficientReserves);
+        }
+
+        Ok(SwapWithinResult {
+            recv_amount: done_dy,
+            send_amount: done_dx,
+            end_tick: end_t,
+            end_rp,
+            cross,
+            send_hmm_adj: hmm_adj_x,
+            recv_fee: fee_y,
+        })
+    }
+
+    /// Swap when the pool receives dy>0. We go from left to right on the price line (price goes up)
+    /// and manage tick crossings as needed, filling until the order is filled or liquidity dries up.
+    pub fn execute_swap_from_y(
+        &mut self,
+        dy: Decimal,
+        rp_oracle: Decimal,
+    ) -> Result<SwapOutput, ClPoolError> {
+        if !dy.is_positive() {
+            return Err(ClPoolError::InvalidSwapAmount);
+        }
+        let left_to_right = true;
+
+        let mut curr_t = self.global_state.tick;
+        let mut curr_rp = self.global_state.rp;
+
+        let mut swpd_dx = Self::zero();
+        let mut swpd_dy = Self::zero();
+        let (mut adjusted_dx, mut total_fee_y) = (Self::zero(), Self::zero());
+
+        while swpd_dy.lt(dy)? {
+            let goal_tick = if self.global_state.liq.is_positive() {
+                self.get_right_limit_of_swap_within(curr_t, self.global_state.tick)?
+            } else {
+                // try move into range; if not possible the swap ends
+                let (goal_tick, new_rp) = self.try_get_in_range(left_to_right)?;
+                curr_rp = new_rp;
+                goal_tick
+            };
+
+            // no more active ticks (liquidity) in this direction
+            let gtk = match goal_tick {
+                Some(gtk) => gtk,
+                None => break,
+            };
+
+            let rez = self.swap_within_tick_from_y(
+                curr_rp,
+                gtk,
+                self.global_state.liq,
+                dy.sub(swpd_dy)?,
+                rp_oracle,
+            )?;
+
+            // hmm_adj comes out of reserves and into the adj pot
+            let new_x = self.x.add(rez.send_amount)?.sub(rez.send_hmm_adj)?;
+            if new_x.is_negative() {
+                return Err(ClPoolError::InsufficientReserves);
+            }
+            if dy
+                .sub(swpd_dy)?
+                .sub(rez.recv_amount)?
+                .sub(rez.recv_fee)?
+                .is_negative()
+            {
+                return Err(ClPoolError::InvariantViolated(
+                    "cannot fill more Y than provided",
+                ));
+            }
+
+            // net for output token, gross (including fees) for input token
+            swpd_dx = swpd_dx.add(rez.send_amount)?;
+            swpd_dy = swpd_dy.add(rez.recv_amount)?.add(rez.recv_fee)?;
+            adjusted_dx = adjusted_dx.add(rez.send_hmm_adj)?;
+            total_fee_y = total_fee_y.add(rez.recv_fee)?;
+            curr_t = rez.end_tick;
+            curr_rp = rez.end_rp;
+
+            // update global state to reflect price change (if any) & reserves
+            self.global_state.tick = curr_t;
+            self.global_state.rp = curr_rp;
+            self.x = new_x;
+            self.y = self.y.add(rez.recv_amount)?;
+            self.x_adj = self.x_adj.add(rez.send_hmm_adj)?;
+            self.y_fee = self.y_fee.add(rez.recv_fee)?;
+
+            // update fee growth to reflect latest swap_within, unless it was an empty trade
+            let liq_glbl = self.global_state.liq;
+            if liq_glbl.is_positive() {
+                let fg = &mut self.global_state.fee_growth;
+                fg.adj_x = rez.send_hmm_adj.div(liq_glbl)?.add(fg.adj_x)?;
+                fg.fee_y = rez.recv_fee.div(liq_glbl)?.add(fg.fee_y)?;
+            }
+
+            // perform crossing of tick, if necessary
+            if rez.cross && self.active_ticks.contains_key(&gtk) {
+                self.cross_tick(gtk, left_to_right)?;
+            }
+        }
+
+        let avg_p = match swpd_dx.is_zero() {
+            false => swpd_dy.div(swpd_dx.neg())?,
+            true => Self::zero(),
+        };
+        let end_p = self.global_state.rp.pow(2u128)?;
+        SwapOutput::new(swpd_dy, swpd_dx, adjusted_dx, total_fee_y, avg_p, end_p)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::decimal::{Sqrt, COMPUTE_SCALE};
+
+    fn rp(price: u64) -> Decimal {
+        Decimal::from_u64_to_compute_scale(price).sqrt().unwrap()
+    }
+
+    fn amount(amt: u64) -> Decimal {
+        Decimal::from_u64_to_compute_scale(amt)
+    }
+
+    fn tick(rp: Decimal) -> u128 {
+        ClPool::rp_to_possible_tk(rp, 1, false, 0).unwrap()
+    }
+
+    #[test]
+    fn test_new_checks_parameters() {
+        let zero = ClPool::zero();
+        assert!(matches!(
+            ClPool::new(0, 0, zero, zero),
+            Err(ClPoolError::InvalidTickSpacing)
+        ));
+        assert!(matches!(
+            ClPool::new(
+                0,
+                1,
+                Decimal::new(5, 1, false).to_compute_scale().unwrap(),
+                zero
+            ),
+            Err(ClPoolError::InvalidCompensationParameter)
+        ));
+        assert!(matches!(
+            ClPool::new(
+                0,
+                1,
+                Decimal::new(25, 1, false).to_compute_scale().unwrap(),
+                zero
+            ),
+            Err(ClPoolError::InvalidCompensationParameter)
+        ));
+        assert!(ClPool::new(0, 1, zero, ClPool::one()).is_err());
+
+        // bootstrap tick is rounded down to the tick spacing
+        let pool = ClPool::new(1234, 10, zero, zero).unwrap();
+        assert_eq!(pool.global_state.tick, 1230);
+        assert_eq!(pool.global_state.rp, ClPool::tick_to_rp(1230).unwrap());
+    }
+
+    #[test]
+    fn test_deposit_checks_range() {
+        let mut pool =
+            ClPool::from_root_price(rp(2000), 10, ClPool::zero(), ClPool::zero()).unwrap();
+        let mut position = PositionState::default();
+        let (lower, upper) = (tick(rp(1333)), tick(rp(3000)));
+
+        assert!(matches!(
+            pool.deposit(&mut position, amount(2), amount(4000), upper, lower),
+            Err(ClPoolError::InvalidTickRange)
+        ));
+        assert!(matches!(
+            pool.deposit(
+                &mut position,
+                amount(2),
+                amount(4000),
+                lower / 10 * 10,
+                lower / 10 * 10 + 9
+            ),
+            Err(ClPoolError::InvalidTickRange)
+        ));
+
+        // ticks are rounded down to the tick spacing
+        pool.deposit(&mut position, amount(2), amount(4000), lower, upper)
+            .unwrap();
+        let keys = pool.active_ticks.keys().cloned().collect::<Vec<u128>>();
+        assert_eq!(keys, vec![lower / 10 * 10, upper / 10 * 10]);
+
+        let too_much = position.liq.add(ClPool::one()).unwrap();
+        assert!(matches!(
+            pool.withdraw(&mut position, too_much, lower, upper),
+            Err(ClPoolError::InsufficientPositionLiquidity)
+        ));
+    }
+
+    #[test]
+    fn test_hmm_swaps() {
+        let zero = ClPool::zero();
+        let hmm_c = Decimal::new(150, 2, false).to_compute_scale().unwrap(); // 1.5
+        let (lower, upper) = (tick(rp(1333)), tick(rp(3000)));
+
+        let mut cpmm_pool = ClPool::from_root_price(rp(2000), 1, zero, zero).unwrap();
+        let mut hmm_pool = ClPool::from_root_price(rp(2000), 1, hmm_c, zero).unwrap();
+        let (mut cpmm_position, mut hmm_position) = Default::default();
+        cpmm_pool
+            .deposit(&mut cpmm_position, amount(2), amount(4000), lower, upper)
+            .unwrap();
+        hmm_pool
+            .deposit(&mut hmm_position, amount(2), amount(4000), lower, upper)
+            .unwrap();
+
+        let rez_a = cpmm_pool.execute_swap_from_x(amount(3), rp(1500)).unwrap();
+        let rez_h = hmm_pool.execute_swap_from_x(amount(3), rp(1500)).unwrap();
+        assert_eq!(rez_h.recv_amount, rez_a.recv_amount);
+        // hmm gives out less of asset y at a cheaper avg price, the difference is put into the adj pot
+        assert!(rez_h.send_amount.value < rez_a.send_amount.value);
+        assert!(rez_h.avg_price.lt(rez_a.avg_price).unwrap());
+        assert_eq!(
+            rez_h.send_hmm_adj,
+            rez_h.send_amount.sub(rez_a.send_amount).unwrap()
+        );
+        assert_eq!(hmm_pool.global_state, {
+            let mut state = cpmm_pool.global_state;
+            state.fee_growth = hmm_pool.global_state.fee_growth;
+            state
+        });
+        assert_eq!((hmm_pool.x, hmm_pool.y), (cpmm_pool.x, cpmm_pool.y));
+
+        assert_eq!(
+            hmm_pool.y_adj,
+            Decimal::new(300_041649746282, COMPUTE_SCALE, false)
+        );
+        assert_eq!(
+            hmm_pool.global_state.rp.pow(2u128).unwrap(),
+            Decimal::new(1332_937085253129, COMPUTE_SCALE, false)
+        );
+        assert_eq!(
+            rez_h.avg_price,
+            Decimal::new(1510_214809376886, COMPUTE_SCALE, false)
+        );
+
+        let res_a = cpmm_pool
+            .execute_swap_from_y(amount(3955), rp(1700))
+            .unwrap();
+        let res_h = hmm_pool
+            .execute_swap_from_y(amount(3955), rp(1700))
+            .unwrap();
+        assert_eq!(res_h.end_price, res_a.end_price);
+        assert_eq!(
+            hmm_pool.x_adj,
+            Decimal::new(133871212606, COMPUTE_SCALE, false)
+        );
+        assert_eq!(
+            res_h.avg_price,
+            Decimal::new(1724_412033919077, COMPUTE_SCALE, false)
+        );
+
+        // the adj pots are paid out to the position along with its liquidity
+        let liq = hmm_position.liq;
+        let out = hmm_pool
+            .withdraw(&mut hmm_position, liq, lower, upper)
+            .unwrap();
+        assert!(out.fees.adj_x.is_positive() && out.fees.adj_y.is_positive());
+        assert_eq!(out.x_sent, out.x_out.add(out.fees.adj_x).unwrap());
+        assert!(hmm_pool.global_state.liq.is_zero());
+    }
+
+    #[test]
+    fn test_swap_without_liquidity() {
+        let mut pool =
+            ClPool::from_root_price(rp(2000), 1, ClPool::zero(), ClPool::zero()).unwrap();
+        // nothing is swapped when there is no liquidity to reach
+        let rez = pool.execute_swap_from_x(amount(1), ClPool::zero()).unwrap();
+        assert!(rez.recv_amount.is_zero() && rez.send_amount.is_zero());
+        assert_eq!(pool.global_state.tick, tick(rp(2000)));
+    }
+}
@@ -1,7 +1,56 @@
+use crate::decimal::DecimalError;
+use crate::programs::fees::error::FeeCalculatorError;
+use crate::programs::liquidity_pools::swap_calculator::SwapCalculatorBuilderError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum SwapCalculatorError {
     #[error("Delta input provided was not positive or greater than zero")]
     DeltaNotPositive,
+    #[error("Delta output requested exceeds the liquidity available")]
+    InsufficientLiquidity,
+    #[error("Amount in of the exact out swap is too far from its estimate")]
+    ExactOutSearchExceeded,
+    #[error(transparent)]
+    DecimalError(#[from] DecimalError),
+    #[error(transparent)]
+    FeeCalculatorError(#[from] FeeCalculatorError),
+    #[error(transparent)]
+    SwapCalculatorBuilderError(#[from] SwapCalculatorBuilderError),
+}
+
+#[derive(Error, Debug)]
+pub enum ClPoolError {
+    #[error("Tick spacing needs to be greater than zero")]
+    InvalidTickSpacing,
+    #[error("Compensation parameter c needs to be zero or between 1 and 2")]
+    InvalidCompensationParameter,
+    #[error("Upper tick of a range needs to be above its lower tick")]
+    InvalidTickRange,
+    #[error("Swap amount needs to be greater than zero")]
+    InvalidSwapAmount,
+    #[error("Liquidity of the position is insufficient")]
+    InsufficientPositionLiquidity,
+    #[error("Reserves or fees of the pool are insufficient")]
+    InsufficientReserves,
+    #[error("Concentrated liquidity pool invariant violated: {0}")]
+    InvariantViolated(&'static str),
+    #[error(transparent)]
+    Decimal(#[from] DecimalError),
+}
+
+#[derive(Error, Debug)]
+pub enum TwapError {
+    #[error("Newer observation needs to be after the older one")]
+    ObservationsOutOfOrder,
+    #[error("Observations need to be apart to average prices between them")]
+    EmptyInterval,
+}
+
+#[derive(Error, Debug)]
+pub enum CircuitBreakerError {
+    #[error("Price after the swap deviates {0} bps from the oracle price")]
+    OracleDeviationExceeded(u128),
+    #[error("Price after the swap moved {0} bps within the slot")]
+    SlotPriceMoveExceeded(u128),
 }
@@ -0,0 +1,55 @@
+//! Fees of flash loans lent from the vaults of a pool and repaid within the same instruction
+use std::convert::TryInto;
+
+/// calculate the fee of a flash loan as a fraction of the amount lent, rounded up so no loan is free.
+/// A zero numerator or denominator means the fee is disabled.
+// ceil((amount * numerator) / denominator)
+pub fn calculate_flash_loan_fee(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 || numerator == 0 {
+        return Some(0);
+    }
+
+    let denominator = denominator as u128;
+    (amount as u128)
+        .checked_mul(numerator as u128)?
+        .checked_add(denominator - 1)?
+        .checked_div(denominator)?
+        .try_into()
+        .ok()
+}
+
+/// Balance a vault must hold once a flash loan has been repaid: its balance before the loan plus the fee.
+/// The fee stays in the vault, crediting the liquidity providers of the pool.
+pub fn repaid_balance(balance_before: u64, fee: u64) -> Option<u64> {
+    balance_before.checked_add(fee)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_flash_loan_fee() {
+        // disabled fees
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 0, 0), Some(0));
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 0, 1_000), Some(0));
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 1, 0), Some(0));
+
+        // 1_000_000 * 3 / 1_000 = 3_000
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 3, 1_000), Some(3_000));
+        // rounded up
+        assert_eq!(calculate_flash_loan_fee(1_001, 3, 1_000), Some(4));
+        assert_eq!(calculate_flash_loan_fee(1, 3, 1_000), Some(1));
+        assert_eq!(calculate_flash_loan_fee(0, 3, 1_000), Some(0));
+
+        assert_eq!(calculate_flash_loan_fee(u64::MAX, 1, 1), Some(u64::MAX));
+        assert_eq!(calculate_flash_loan_fee(u64::MAX, 2, 1), None);
+    }
+
+    #[test]
+    fn test_repaid_balance() {
+        assert_eq!(repaid_balance(1_000_000, 3_000), Some(1_003_000));
+        assert_eq!(repaid_balance(u64::MAX, 1), None);
+    }
+}
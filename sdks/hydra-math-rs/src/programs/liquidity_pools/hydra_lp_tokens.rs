@@ -1,4 +1,4 @@
-use crate::decimal::{Decimal, DivUp, Mul, Sqrt, Sub};
+use crate::decimal::{Decimal, DecimalError, DivUp, Mul, Sqrt, Sub};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 pub const MIN_LIQUIDITY: u64 = 100;
@@ -6,20 +6,17 @@ pub const LIQUIDITY_POOL_SCALE: u8 = 9;
 
 #[wasm_bindgen]
 pub fn calculate_k(x: u64, x_scale: u8, y: u64, y_scale: u8) -> Option<u64> {
-    let x = Decimal::from_scaled_amount(x, x_scale).to_compute_scale();
-    let y = Decimal::from_scaled_amount(y, y_scale).to_compute_scale();
+    let x = Decimal::from_scaled_amount_to_compute_scale(x, x_scale);
+    let y = Decimal::from_scaled_amount_to_compute_scale(y, y_scale);
     let min_liquidity =
-        Decimal::from_scaled_amount(MIN_LIQUIDITY, LIQUIDITY_POOL_SCALE).to_compute_scale();
+        Decimal::from_scaled_amount_to_compute_scale(MIN_LIQUIDITY, LIQUIDITY_POOL_SCALE);
 
     // sqrt(x * y) - min_liquidity
-    Some(
-        x.mul(y)
-            .sqrt()
-            .unwrap()
-            .sub(min_liquidity)
-            .unwrap()
-            .to_scaled_amount(LIQUIDITY_POOL_SCALE),
-    )
+    x.mul(y)
+        .and_then(|k| k.sqrt())
+        .and_then(|k| k.sub(min_liquidity))
+        .and_then(|k| k.to_scaled_amount(LIQUIDITY_POOL_SCALE))
+        .ok()
 }
 
 /// calculate x and y from k
@@ -31,7 +28,7 @@ pub fn calculate_x_y(
     y: u64,
     y_scale: u8,
     lp_tokens_total: u64,
-) -> (u64, u64) {
+) -> Result<(u64, u64), DecimalError> {
     let x_total = Decimal::from_scaled_amount(x, x_scale);
     let y_total = Decimal::from_scaled_amount(y, y_scale);
     let lp_total = Decimal::from_scaled_amount(lp_tokens_total, lp_tokens_scale);
@@ -39,15 +36,15 @@ pub fn calculate_x_y(
 
     // div up (ceiling) as we are receiving these amounts
     let x_debited = lp_tokens_to_mint
-        .mul(x_total)
-        .div_up(lp_total)
-        .to_scaled_amount(x_scale);
+        .mul(x_total)?
+        .div_up(lp_total)?
+        .to_scaled_amount(x_scale)?;
     let y_debited = lp_tokens_to_mint
-        .mul(y_total)
-        .div_up(lp_total)
-        .to_scaled_amount(y_scale);
+        .mul(y_total)?
+        .div_up(lp_total)?
+        .to_scaled_amount(y_scale)?;
 
-    (x_debited, y_debited)
+    Ok((x_debited, y_debited))
 }
 
 #[cfg(test)]
@@ -1,6 +1,12 @@
+pub mod circuit_breaker;
+pub mod cl_pool;
 pub mod error;
+pub mod flash_loan;
 pub mod hydra_lp_tokens;
+pub mod protocol_fees;
+pub mod route_finder;
 pub mod swap_calculator;
 pub mod swap_calculator_scale;
 pub mod swap_calculator_wasm;
 pub mod swap_result;
+pub mod twap;
@@ -0,0 +1,189 @@
+//! Protocol (owner) and host fees taken on top of the liquidity provider swap fee
+use std::convert::TryInto;
+
+/// calculate a fee as a fraction of an amount, rounded down.
+/// A zero denominator means the fee is disabled.
+// (amount * numerator) / denominator
+pub fn calculate_fee(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 || numerator == 0 {
+        return Some(0);
+    }
+
+    (amount as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?
+        .try_into()
+        .ok()
+}
+
+/// calculate the owner trade fee of an amount and split it between the protocol and the host.
+/// The host fee is a fraction of the owner trade fee. Returns (protocol_fee, host_fee).
+pub fn calculate_owner_trade_fees(
+    amount: u64,
+    owner_trade_fee_numerator: u64,
+    owner_trade_fee_denominator: u64,
+    host_fee_numerator: u64,
+    host_fee_denominator: u64,
+) -> Option<(u64, u64)> {
+    let owner_trade_fee = calculate_fee(
+        amount,
+        owner_trade_fee_numerator,
+        owner_trade_fee_denominator,
+    )?;
+    let host_fee = calculate_fee(owner_trade_fee, host_fee_numerator, host_fee_denominator)?;
+
+    Some((owner_trade_fee.checked_sub(host_fee)?, host_fee))
+}
+
+/// calculate the owner trade fee to charge on top of an amount swapped into the pool (exact out swaps)
+/// and split it between the protocol and the host. Returns (protocol_fee, host_fee).
+// owner_trade_fee = ceil((amount * denominator) / (denominator - numerator)) - amount
+pub fn calculate_owner_trade_fees_exact_out(
+    amount: u64,
+    owner_trade_fee_numerator: u64,
+    owner_trade_fee_denominator: u64,
+    host_fee_numerator: u64,
+    host_fee_denominator: u64,
+) -> Option<(u64, u64)> {
+    let owner_trade_fee = if owner_trade_fee_denominator == 0 || owner_trade_fee_numerator == 0 {
+        0
+    } else {
+        let denominator =
+            owner_trade_fee_denominator.checked_sub(owner_trade_fee_numerator)? as u128;
+        let amount_in: u64 = (amount as u128)
+            .checked_mul(owner_trade_fee_denominator as u128)?
+            .checked_add(denominator.checked_sub(1)?)?
+            .checked_div(denominator)?
+            .try_into()
+            .ok()?;
+        amount_in.checked_sub(amount)?
+    };
+    let host_fee = calculate_fee(owner_trade_fee, host_fee_numerator, host_fee_denominator)?;
+
+    Some((owner_trade_fee.checked_sub(host_fee)?, host_fee))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn calculate_fee_specific_tests() {
+        // disabled fees
+        assert_eq!(calculate_fee(1_000_000, 0, 0).unwrap(), 0);
+        assert_eq!(calculate_fee(1_000_000, 0, 1_000).unwrap(), 0);
+        assert_eq!(calculate_fee(1_000_000, 1, 0).unwrap(), 0);
+
+        // 1_000_000 * 5 / 10_000 = 500
+        assert_eq!(calculate_fee(1_000_000, 5, 10_000).unwrap(), 500);
+
+        // Expect fractional fees to be rounded down (floored)
+        // 1_999 * 5 / 10_000 = 0.9995
+        assert_eq!(calculate_fee(1_999, 5, 10_000).unwrap(), 0);
+
+        // no intermediate overflow
+        assert_eq!(calculate_fee(u64::MAX, 1, 2).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn calculate_owner_trade_fees_specific_tests() {
+        // no host: all of the owner trade fee goes to the protocol
+        assert_eq!(
+            calculate_owner_trade_fees(1_000_000, 5, 10_000, 0, 0).unwrap(),
+            (500, 0)
+        );
+
+        // host receives 20% of the owner trade fee
+        assert_eq!(
+            calculate_owner_trade_fees(1_000_000, 5, 10_000, 20, 100).unwrap(),
+            (400, 100)
+        );
+    }
+
+    #[test]
+    fn calculate_owner_trade_fees_exact_out_specific_tests() {
+        // disabled fees
+        assert_eq!(
+            calculate_owner_trade_fees_exact_out(1_000_000, 0, 0, 0, 0).unwrap(),
+            (0, 0)
+        );
+
+        // 999_500 * 10_000 / 9_995 = 1_000_000
+        assert_eq!(
+            calculate_owner_trade_fees_exact_out(999_500, 5, 10_000, 0, 0).unwrap(),
+            (500, 0)
+        );
+
+        // Expect the owner trade fee to be rounded up (ceiling)
+        // 1_000 * 10_000 / 9_995 = 1_000.5002
+        assert_eq!(
+            calculate_owner_trade_fees_exact_out(1_000, 5, 10_000, 20, 100).unwrap(),
+            (1, 0)
+        );
+
+        // host receives 20% of the owner trade fee
+        assert_eq!(
+            calculate_owner_trade_fees_exact_out(999_500, 5, 10_000, 20, 100).unwrap(),
+            (400, 100)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn owner_trade_fees_exact_out_cover_exact_in(
+            amount in 0..u64::MAX / 2,
+            owner_trade_fee_denominator in 1..1_000_000u64,
+            owner_trade_fee_numerator in 0..1_000_000u64,
+        ) {
+            // owner trade fees up to 50% so amount plus the fee fits a u64
+            prop_assume!(owner_trade_fee_numerator * 2 <= owner_trade_fee_denominator);
+
+            // charging the exact out fee on top of amount leaves at least amount after the exact in fee
+            let (owner_trade_fee, _) = calculate_owner_trade_fees_exact_out(
+                amount,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                0,
+                0,
+            )
+            .unwrap();
+            let (exact_in_fee, _) = calculate_owner_trade_fees(
+                amount + owner_trade_fee,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                0,
+                0,
+            )
+            .unwrap();
+
+            assert!(exact_in_fee <= owner_trade_fee);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn owner_trade_fees_never_exceed_amount(
+            amount in 0..u64::MAX,
+            owner_trade_fee_denominator in 1..1_000_000u64,
+            owner_trade_fee_numerator in 0..1_000_000u64,
+            host_fee_denominator in 1..1_000_000u64,
+            host_fee_numerator in 0..1_000_000u64,
+        ) {
+            prop_assume!(owner_trade_fee_numerator < owner_trade_fee_denominator);
+            prop_assume!(host_fee_numerator < host_fee_denominator);
+
+            let (protocol_fee, host_fee) = calculate_owner_trade_fees(
+                amount,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                host_fee_numerator,
+                host_fee_denominator,
+            )
+            .unwrap();
+
+            assert!(protocol_fee as u128 + host_fee as u128 <= amount as u128);
+        }
+    }
+}
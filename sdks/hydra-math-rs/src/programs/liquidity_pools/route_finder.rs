@@ -0,0 +1,278 @@
+//! Off-chain route finder for swaps through several liquidity pools
+use crate::decimal::Decimal;
+use crate::programs::liquidity_pools::protocol_fees::calculate_owner_trade_fees;
+use crate::programs::liquidity_pools::swap_calculator::SwapCalculatorBuilder;
+
+/// Maximum number of pools a route can swap through, also the limit of the on-chain route_swap
+pub const MAX_ROUTE_HOPS: usize = 4;
+
+/// Snapshot of the state of a liquidity pool used to simulate swaps off-chain
+#[derive(Default, Builder, Debug, Clone)]
+#[builder(setter(into))]
+pub struct PoolSnapshot {
+    /// Mint address of token x
+    pub token_x: String,
+    /// Mint address of token y
+    pub token_y: String,
+    /// Number of tokens x currently in liquidity pool
+    pub x0: u64,
+    pub x_scale: u8,
+    /// Number of tokens y currently in liquidity pool
+    pub y0: u64,
+    pub y_scale: u8,
+    /// Compensation parameter c, range c = [0, 100, 125, 150] / 100
+    #[builder(default)]
+    pub c: u8,
+    /// Oracle price relative to x, zero for pools without an oracle
+    #[builder(default)]
+    pub i: u64,
+    #[builder(default)]
+    pub i_scale: u8,
+    pub fee_numerator: u64,
+    pub fee_denominator: u64,
+    #[builder(default)]
+    pub owner_trade_fee_numerator: u64,
+    #[builder(default)]
+    pub owner_trade_fee_denominator: u64,
+}
+
+impl PoolSnapshot {
+    /// Simulate swapping amount_in of token_in through the pool, returns the token and amount out.
+    /// None if the token isn't traded by the pool or the swap fails.
+    pub fn swap(&self, token_in: &str, amount_in: u64) -> Option<(&str, u64)> {
+        let x_to_y = if token_in == self.token_x {
+            true
+        } else if token_in == self.token_y {
+            false
+        } else {
+            return None;
+        };
+
+        if self.x0 == 0 || self.y0 == 0 {
+            return None;
+        }
+
+        // owner trade fees are taken from the amount swapped in as on-chain
+        let (protocol_fee, _) = calculate_owner_trade_fees(
+            amount_in,
+            self.owner_trade_fee_numerator,
+            self.owner_trade_fee_denominator,
+            0,
+            0,
+        )?;
+        let amount_in = amount_in.checked_sub(protocol_fee)?;
+
+        let calculator = SwapCalculatorBuilder::default()
+            .x0(self.x0, self.x_scale)
+            .y0(self.y0, self.y_scale)
+            .c(self.c)
+            .i(self.i, self.i_scale)
+            .percentage_fee_numerator(self.fee_numerator)
+            .percentage_fee_denominator(self.fee_denominator)
+            .scale(self.x_scale, self.y_scale)
+            .build()
+            .ok()?;
+
+        if x_to_y {
+            let delta_x = Decimal::from_scaled_amount_to_compute_scale(amount_in, self.x_scale);
+            let result = calculator.swap_x_to_y_hmm(&delta_x).ok()?;
+            Some((&self.token_y, result.delta_y))
+        } else {
+            let delta_y = Decimal::from_scaled_amount_to_compute_scale(amount_in, self.y_scale);
+            let result = calculator.swap_y_to_x_hmm(&delta_y).ok()?;
+            Some((&self.token_x, result.delta_x))
+        }
+    }
+}
+
+/// A swap through an ordered list of pools
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Route {
+    /// Index of each pool swapped through in the snapshots the route was found in
+    pub pools: Vec<usize>,
+    /// Amount out of each pool
+    pub amounts_out: Vec<u64>,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Find the route from token_in to token_out through at most max_hops pools that returns the most token_out.
+/// Each pool is swapped through at most once. None if no route exists.
+pub fn find_best_route(
+    pools: &[PoolSnapshot],
+    token_in: &str,
+    token_out: &str,
+    amount_in: u64,
+    max_hops: usize,
+) -> Option<Route> {
+    if token_in == token_out || amount_in == 0 {
+        return None;
+    }
+
+    let mut best: Option<Route> = None;
+    let mut path = Route {
+        amount_in,
+        ..Route::default()
+    };
+    search_routes(
+        pools,
+        token_in,
+        token_out,
+        amount_in,
+        max_hops.min(MAX_ROUTE_HOPS),
+        &mut path,
+        &mut best,
+    );
+    best
+}
+
+/// Depth first search over the routes from token to token_out, keeping the best route found
+fn search_routes(
+    pools: &[PoolSnapshot],
+    token: &str,
+    token_out: &str,
+    amount: u64,
+    hops_left: usize,
+    path: &mut Route,
+    best: &mut Option<Route>,
+) {
+    if hops_left == 0 {
+        return;
+    }
+
+    for (index, pool) in pools.iter().enumerate() {
+        if path.pools.contains(&index) {
+            continue;
+        }
+
+        let (next_token, next_amount) = match pool.swap(token, amount) {
+            Some((next_token, next_amount)) if next_amount > 0 => (next_token, next_amount),
+            _ => continue,
+        };
+
+        path.pools.push(index);
+        path.amounts_out.push(next_amount);
+
+        if next_token == token_out {
+            if next_amount > best.as_ref().map_or(0, |b| b.amount_out) {
+                *best = Some(Route {
+                    amount_out: next_amount,
+                    ..path.clone()
+                });
+            }
+        } else {
+            search_routes(
+                pools,
+                next_token,
+                token_out,
+                next_amount,
+                hops_left - 1,
+                path,
+                best,
+            );
+        }
+
+        path.pools.pop();
+        path.amounts_out.pop();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn pool(token_x: &str, token_y: &str, x0: u64, y0: u64) -> PoolSnapshot {
+        PoolSnapshotBuilder::default()
+            .token_x(token_x)
+            .token_y(token_y)
+            .x0(x0)
+            .x_scale(6)
+            .y0(y0)
+            .y_scale(6)
+            .fee_numerator(1u64)
+            .fee_denominator(500u64)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pool_swap() {
+        let hyd_usdc = pool("HYD", "USDC", 1_000_000_000_000, 500_000_000_000);
+
+        // x to y
+        let (token, amount) = hyd_usdc.swap("HYD", 1_000_000_000).unwrap();
+        assert_eq!(token, "USDC");
+        assert!(amount > 0 && amount < 500_000_000);
+
+        // y to x
+        let (token, amount) = hyd_usdc.swap("USDC", 1_000_000_000).unwrap();
+        assert_eq!(token, "HYD");
+        assert!(amount > 1_000_000_000 && amount < 2_000_000_000);
+
+        // token not traded by the pool
+        assert_eq!(hyd_usdc.swap("SOL", 1_000_000_000), None);
+    }
+
+    #[test]
+    fn test_find_best_route() {
+        let pools = vec![
+            pool("HYD", "USDC", 1_000_000_000_000, 500_000_000_000),
+            pool("SOL", "USDC", 10_000_000_000, 1_000_000_000_000),
+            // shallow direct pool at the same 1 SOL = 200 HYD price
+            pool("HYD", "SOL", 10_000_000_000, 50_000_000),
+        ];
+
+        // deep two hop route beats the shallow direct pool for a large trade
+        let route = find_best_route(&pools, "HYD", "SOL", 5_000_000_000, 3).unwrap();
+        assert_eq!(route.pools, vec![0, 1]);
+        assert_eq!(route.amount_in, 5_000_000_000);
+        assert_eq!(route.amount_out, *route.amounts_out.last().unwrap());
+
+        // route output is the chained output of each pool
+        let (_, usdc) = pools[0].swap("HYD", 5_000_000_000).unwrap();
+        let (_, sol) = pools[1].swap("USDC", usdc).unwrap();
+        assert_eq!(route.amounts_out, vec![usdc, sol]);
+        assert_eq!(route.amount_out, sol);
+
+        // limited to one hop only the direct pool is available
+        let route = find_best_route(&pools, "HYD", "SOL", 5_000_000_000, 1).unwrap();
+        assert_eq!(route.pools, vec![2]);
+
+        // no route to a token without pools
+        assert_eq!(
+            find_best_route(&pools, "HYD", "BTC", 5_000_000_000, 3),
+            None
+        );
+        assert_eq!(
+            find_best_route(&pools, "HYD", "HYD", 5_000_000_000, 3),
+            None
+        );
+        assert_eq!(find_best_route(&pools, "HYD", "SOL", 0, 3), None);
+    }
+
+    proptest! {
+        #[test]
+        fn best_route_is_at_least_the_direct_pool(
+            direct_x0 in 1_000_000u64..1_000_000_000_000,
+            direct_y0 in 1_000_000u64..1_000_000_000_000,
+            hop_x0 in 1_000_000u64..1_000_000_000_000,
+            hop_y0 in 1_000_000u64..1_000_000_000_000,
+            amount_in in 1_000u64..1_000_000_000,
+        ) {
+            let pools = vec![
+                pool("HYD", "SOL", direct_x0, direct_y0),
+                pool("HYD", "USDC", hop_x0, hop_y0),
+                pool("SOL", "USDC", hop_y0, hop_x0),
+            ];
+
+            let direct = pools[0].swap("HYD", amount_in).map(|(_, amount)| amount).unwrap_or(0);
+            let best = find_best_route(&pools, "HYD", "SOL", amount_in, MAX_ROUTE_HOPS)
+                .map(|route| route.amount_out)
+                .unwrap_or(0);
+
+            prop_assert!(best >= direct);
+        }
+    }
+}
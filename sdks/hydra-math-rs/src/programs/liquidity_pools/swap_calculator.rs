@@ -1,14 +1,18 @@
 //! Swap calculator
-use crate::decimal::{Add, Compare, Decimal, Div, Ln, Mul, Pow, Sqrt, Sub};
+use crate::decimal::{Add, Compare, Decimal, DecimalError, Div, DivUp, Exp, Mul, Pow, Sqrt, Sub};
+use crate::programs::fees::error::FeeCalculatorError;
 use crate::programs::fees::fee_calculator::FeeCalculatorBuilder;
+use crate::programs::fees::fee_result::FeeResult;
 use crate::programs::liquidity_pools::error::SwapCalculatorError;
-use crate::programs::liquidity_pools::swap_calculator_scale::{
-    SwapCalculatorScale, SwapCalculatorScaleBuilder,
-};
+use crate::programs::liquidity_pools::swap_calculator_scale::SwapCalculatorScale;
 use crate::programs::liquidity_pools::swap_result::SwapResult;
 
 pub const MIN_LIQUIDITY: u64 = 100;
 
+/// Doubling steps the exact out search takes away from its estimate to bracket the amount in,
+/// the estimate is off by up to 2^16 - 1 units
+pub const MAX_EXACT_OUT_BRACKET_STEPS: usize = 16;
+
 #[derive(Default, Builder, Debug)]
 #[builder(setter(into))]
 pub struct SwapCalculator {
@@ -38,47 +42,41 @@ pub struct SwapCalculator {
 /// [SwapCalculator] custom setters
 impl SwapCalculatorBuilder {
     pub fn x0(&mut self, value: u64, scale: u8) -> &mut Self {
-        self.x0 = Some(Decimal::from_scaled_amount(value, scale).to_compute_scale());
+        self.x0 = Some(Decimal::from_scaled_amount_to_compute_scale(value, scale));
         self
     }
 
     pub fn y0(&mut self, value: u64, scale: u8) -> &mut Self {
-        self.y0 = Some(Decimal::from_scaled_amount(value, scale).to_compute_scale());
+        self.y0 = Some(Decimal::from_scaled_amount_to_compute_scale(value, scale));
         self
     }
 
     pub fn c(&mut self, value: u8) -> &mut Self {
-        self.c = Some(
-            Decimal::from_u64(value as u64)
-                .to_compute_scale()
-                .div(Decimal::from_u64(100).to_compute_scale()),
-        );
+        // c is expressed in hundredths
+        self.c = Some(Decimal::from_scaled_amount_to_compute_scale(
+            value.into(),
+            2,
+        ));
         self
     }
 
     pub fn i(&mut self, value: u64, scale: u8) -> &mut Self {
-        self.i = Some(Decimal::from_scaled_amount(value, scale).to_compute_scale());
+        self.i = Some(Decimal::from_scaled_amount_to_compute_scale(value, scale));
         self
     }
 
     pub fn percentage_fee_numerator(&mut self, value: u64) -> &mut Self {
-        self.percentage_fee_numerator = Some(Decimal::from_u64(value).to_compute_scale());
+        self.percentage_fee_numerator = Some(Decimal::from_u64_to_compute_scale(value));
         self
     }
 
     pub fn percentage_fee_denominator(&mut self, value: u64) -> &mut Self {
-        self.percentage_fee_denominator = Some(Decimal::from_u64(value).to_compute_scale());
+        self.percentage_fee_denominator = Some(Decimal::from_u64_to_compute_scale(value));
         self
     }
 
     pub fn scale(&mut self, x: u8, y: u8) -> &mut Self {
-        self.scale = Some(
-            SwapCalculatorScaleBuilder::default()
-                .x(x)
-                .y(y)
-                .build()
-                .unwrap(),
-        );
+        self.scale = Some(SwapCalculatorScale { x, y });
         self
     }
 }
@@ -90,49 +88,29 @@ impl SwapCalculator {
             return Err(SwapCalculatorError::DeltaNotPositive.into());
         }
 
-        // TODO: start using vol adj fee calculator, example in comments below
-        // let fee_calculator = FeeCalculatorBuilder::default()
-        // Note: time on chan can be obtained as follows
-        // sysvar::clock::Clock::get().unwrap().unix_timestamp as u64
-        //     .vol_adj_fee_last_update(Decimal::from_u64(1649113200).to_compute_scale())
-        //     .vol_adj_fee_this_update(Decimal::from_u64(1649113200).to_compute_scale())//     .vol_adj_fee_last_update(Decimal::from_u64(1649113200).to_compute_scale())
-        //     .vol_adj_fee_last_price(Decimal::from_scaled_amount(3425_000000, 6))
-        //     .vol_adj_fee_this_price(Decimal::from_scaled_amount(3400_000000, 6))
-        //     .vol_adj_fee_last_ewma(Decimal::from_scaled_amount(3400_000000, 6))
-        //     .build()
-        //     .unwrap();
-        //
-        // let fee_result = fee_calculator.compute_vol_adj_fee(&delta_x).unwrap();
-
-        let fee_calculator = FeeCalculatorBuilder::default()
-            .percentage_fee_numerator(self.percentage_fee_numerator.to_compute_scale())
-            .percentage_fee_denominator(self.percentage_fee_denominator.to_compute_scale())
-            .build()
-            .expect("failed to build FeeCalculator");
-
-        let fee_result = fee_calculator
-            .compute_percent_fee(&delta_x.to_compute_scale())
-            .unwrap();
+        // volatility adjusted fees are computed with FeeCalculator::compute_vol_adj_fee by the caller
+        // and passed in as a percentage fee
+        let fee_result = self.compute_percent_fee(delta_x)?;
 
-        let amount_ex_fee = fee_result.amount_ex_fee.to_scale(delta_x.scale);
-        let fee_amount = fee_result.fee_amount.to_scale(delta_x.scale);
+        let amount_ex_fee = fee_result.amount_ex_fee.to_scale(delta_x.scale)?;
+        let fee_amount = fee_result.fee_amount.to_scale(delta_x.scale)?;
 
-        let x_new = self.compute_x_new(&amount_ex_fee);
+        let x_new = self.compute_x_new(&amount_ex_fee)?;
 
-        let delta_x = x_new.sub(self.x0).unwrap();
+        let delta_x = x_new.sub(self.x0)?;
 
-        let delta_y = self.compute_delta_y_hmm(&amount_ex_fee);
+        let delta_y = self.compute_delta_y_hmm(&amount_ex_fee)?;
 
-        let y_new = self.y0.add(delta_y).unwrap();
+        let y_new = self.y0.add(delta_y)?;
 
-        let x_new = x_new.add(fee_amount).unwrap();
+        let x_new = x_new.add(fee_amount)?;
 
         Ok(SwapResult {
-            x_new: x_new.to_scaled_amount_up(self.scale.x),
-            y_new: y_new.to_scaled_amount_up(self.scale.y),
-            delta_x: delta_x.to_scaled_amount(self.scale.x),
-            delta_y: delta_y.to_scaled_amount(self.scale.y),
-            fees: fee_amount.to_scaled_amount(self.scale.x),
+            x_new: x_new.to_scaled_amount_up(self.scale.x)?,
+            y_new: y_new.to_scaled_amount_up(self.scale.y)?,
+            delta_x: delta_x.to_scaled_amount(self.scale.x)?,
+            delta_y: delta_y.to_scaled_amount(self.scale.y)?,
+            fees: fee_amount.to_scaled_amount(self.scale.x)?,
         })
     }
 
@@ -142,76 +120,356 @@ impl SwapCalculator {
             return Err(SwapCalculatorError::DeltaNotPositive.into());
         }
 
-        // TODO: start using vol adj fee calculator, example in comments below
-        // let fee_calculator = FeeCalculatorBuilder::default()
-        // Note: time on chan can be obtained as follows
-        // sysvar::clock::Clock::get().unwrap().unix_timestamp as u64
-        //     .vol_adj_fee_last_update(Decimal::from_u64(1649113200).to_compute_scale())
-        //     .vol_adj_fee_this_update(Decimal::from_u64(1649113200).to_compute_scale())
-        //     .vol_adj_fee_last_price(Decimal::from_scaled_amount(3425_000000, 6))
-        //     .vol_adj_fee_this_price(Decimal::from_scaled_amount(3400_000000, 6))
-        //     .vol_adj_fee_last_ewma(Decimal::from_scaled_amount(3400_000000, 6))
-        //     .build()
-        //     .unwrap();
-        //
-        // let fee_result = fee_calculator.compute_vol_adj_fee(&delta_x).unwrap();
+        let fee_result = self.compute_percent_fee(delta_y)?;
+
+        let amount_ex_fee = fee_result.amount_ex_fee.to_scale(delta_y.scale)?;
+        let fee_amount = fee_result.fee_amount.to_scale(delta_y.scale)?;
+
+        let y_new = self.compute_y_new(&amount_ex_fee)?;
+
+        let delta_y = y_new.sub(self.y0)?;
+
+        let delta_x = self.compute_delta_x_hmm(&amount_ex_fee)?;
+
+        let x_new = self.x0.add(delta_x)?;
+
+        let y_new = y_new.add(fee_amount)?;
+
+        Ok(SwapResult {
+            x_new: x_new.to_scaled_amount_up(self.scale.x)?,
+            y_new: y_new.to_scaled_amount_up(self.scale.y)?,
+            delta_x: delta_x.to_scaled_amount(self.scale.x)?,
+            delta_y: delta_y.to_scaled_amount(self.scale.y)?,
+            fees: fee_amount.to_scaled_amount(self.scale.y)?,
+        })
+    }
+
+    /// Compute swap result from x to y given the exact amount of y to receive (delta y)
+    pub fn swap_x_to_y_hmm_exact_out(
+        &self,
+        delta_y: &Decimal,
+    ) -> Result<SwapResult, SwapCalculatorError> {
+        if delta_y.is_negative() || delta_y.is_zero() {
+            return Err(SwapCalculatorError::DeltaNotPositive.into());
+        }
+
+        if delta_y.gte(self.y0)? {
+            return Err(SwapCalculatorError::InsufficientLiquidity);
+        }
+
+        let xi = if self.i.is_zero() {
+            None
+        } else {
+            Some(self.compute_xi()?)
+        };
+
+        let x_new = self.compute_q_new_hmm(&self.x0, &self.y0, xi, delta_y)?;
+        let amount_ex_fee = x_new.sub(self.x0)?;
+
+        self.compute_exact_out_result(
+            amount_ex_fee.to_scaled_amount_up(self.scale.x)?,
+            delta_y.to_scaled_amount(self.scale.y)?,
+            true,
+        )
+    }
+
+    /// Compute swap result from y to x given the exact amount of x to receive (delta x)
+    pub fn swap_y_to_x_hmm_exact_out(
+        &self,
+        delta_x: &Decimal,
+    ) -> Result<SwapResult, SwapCalculatorError> {
+        if delta_x.is_negative() || delta_x.is_zero() {
+            return Err(SwapCalculatorError::DeltaNotPositive.into());
+        }
+
+        if delta_x.gte(self.x0)? {
+            return Err(SwapCalculatorError::InsufficientLiquidity);
+        }
+
+        let yi = if self.i.is_zero() {
+            None
+        } else {
+            Some(self.compute_yi()?)
+        };
+
+        let y_new = self.compute_q_new_hmm(&self.y0, &self.x0, yi, delta_x)?;
+        let amount_ex_fee = y_new.sub(self.y0)?;
+
+        self.compute_exact_out_result(
+            amount_ex_fee.to_scaled_amount_up(self.scale.y)?,
+            delta_x.to_scaled_amount(self.scale.x)?,
+            false,
+        )
+    }
 
+    /// Compute the percentage fee of the [SwapCalculator] on the amount swapped in
+    fn compute_percent_fee(&self, amount: &Decimal) -> Result<FeeResult, SwapCalculatorError> {
         let fee_calculator = FeeCalculatorBuilder::default()
-            .percentage_fee_numerator(self.percentage_fee_numerator.to_compute_scale())
-            .percentage_fee_denominator(self.percentage_fee_denominator.to_compute_scale())
+            .percentage_fee_numerator(self.percentage_fee_numerator.to_compute_scale()?)
+            .percentage_fee_denominator(self.percentage_fee_denominator.to_compute_scale()?)
             .build()
-            .unwrap();
+            .map_err(FeeCalculatorError::from)?;
 
-        let fee_result = fee_calculator
-            .compute_percent_fee(&delta_y.to_compute_scale())
-            .unwrap();
+        Ok(fee_calculator.compute_percent_fee(&amount.to_compute_scale()?)?)
+    }
+
+    /// Gross up the amount in (ex fee) of an exact out swap by the percentage fee, search the
+    /// smallest amount in whose swap returns at least amount_out from that estimate
+    /// and build the [SwapResult] from the scaled amounts
+    fn compute_exact_out_result(
+        &self,
+        amount_in_ex_fee: u64,
+        amount_out: u64,
+        x_to_y: bool,
+    ) -> Result<SwapResult, SwapCalculatorError> {
+        // amount_in = amount_in_ex_fee / (1 - fee_percentage), rounded up
+        let estimate = if self.percentage_fee_numerator.is_zero()
+            || self.percentage_fee_denominator.is_zero()
+        {
+            amount_in_ex_fee
+        } else {
+            let numerator = u128::from(self.percentage_fee_numerator.abs()?);
+            let denominator = u128::from(self.percentage_fee_denominator.abs()?);
+            let denominator_ex_fee = denominator
+                .checked_sub(numerator)
+                .filter(|d| *d > 0)
+                .ok_or(FeeCalculatorError::FeesGreaterThanAmount)?;
+            let amount_in = u128::from(amount_in_ex_fee)
+                .checked_mul(denominator)
+                .and_then(|n| n.checked_add(denominator_ex_fee - 1))
+                .ok_or(DecimalError::Overflow)?
+                / denominator_ex_fee;
+            amount_in.try_into().map_err(|_| DecimalError::Overflow)?
+        };
+
+        let (amount_in, swap_result) =
+            self.find_exact_out_amount_in(estimate, amount_out, x_to_y)?;
+
+        // the fee takes the rounding of the swap so that amount_in = amount_in_ex_fee + fee_amount,
+        // any amount out above amount_out stays in the pool
+        let amount_in_ex_fee = if x_to_y {
+            swap_result.delta_x
+        } else {
+            swap_result.delta_y
+        };
+        let fee_amount = amount_in
+            .checked_sub(amount_in_ex_fee)
+            .ok_or(DecimalError::Overflow)?;
+
+        let x0 = self.x0.to_scaled_amount(self.scale.x)?;
+        let y0 = self.y0.to_scaled_amount(self.scale.y)?;
+
+        Ok(if x_to_y {
+            SwapResult {
+                x_new: x0.checked_add(amount_in).ok_or(DecimalError::Overflow)?,
+                y_new: y0
+                    .checked_sub(amount_out)
+                    .ok_or(SwapCalculatorError::InsufficientLiquidity)?,
+                delta_x: amount_in_ex_fee,
+                delta_y: amount_out,
+                fees: fee_amount,
+            }
+        } else {
+            SwapResult {
+                x_new: x0
+                    .checked_sub(amount_out)
+                    .ok_or(SwapCalculatorError::InsufficientLiquidity)?,
+                y_new: y0.checked_add(amount_in).ok_or(DecimalError::Overflow)?,
+                delta_x: amount_out,
+                delta_y: amount_in_ex_fee,
+                fees: fee_amount,
+            }
+        })
+    }
+
+    /// Swap the scaled amount_in, returning the scaled amount out and the [SwapResult]
+    fn swap_scaled_amount(
+        &self,
+        amount_in: u64,
+        x_to_y: bool,
+    ) -> Result<(u64, SwapResult), SwapCalculatorError> {
+        if x_to_y {
+            let delta_x =
+                Decimal::from_scaled_amount(amount_in, self.scale.x).to_scale(self.x0.scale)?;
+            let swap_result = self.swap_x_to_y_hmm(&delta_x)?;
+            Ok((swap_result.delta_y, swap_result))
+        } else {
+            let delta_y =
+                Decimal::from_scaled_amount(amount_in, self.scale.y).to_scale(self.y0.scale)?;
+            let swap_result = self.swap_y_to_x_hmm(&delta_y)?;
+            Ok((swap_result.delta_x, swap_result))
+        }
+    }
 
-        let amount_ex_fee = fee_result.amount_ex_fee.to_scale(delta_y.scale);
-        let fee_amount = fee_result.fee_amount.to_scale(delta_y.scale);
+    /// Find the smallest scaled amount in whose swap returns at least amount_out and its [SwapResult].
+    /// The amount is bracketed with steps doubling away from the estimate, then the bracket is bisected,
+    /// so that an estimate off by a few units only costs a few swaps.
+    /// Bracketing stops with an error after [MAX_EXACT_OUT_BRACKET_STEPS] steps.
+    fn find_exact_out_amount_in(
+        &self,
+        estimate: u64,
+        amount_out: u64,
+        x_to_y: bool,
+    ) -> Result<(u64, SwapResult), SwapCalculatorError> {
+        // the swap result of amount_in when it returns at least amount_out
+        let reaches = |amount_in: u64| -> Result<Option<SwapResult>, SwapCalculatorError> {
+            if amount_in == 0 {
+                return Ok(None);
+            }
+            let (swap_out, swap_result) = self.swap_scaled_amount(amount_in, x_to_y)?;
+            Ok(Some(swap_result).filter(|_| swap_out >= amount_out))
+        };
 
-        let y_new = self.compute_y_new(&amount_ex_fee);
+        // lo never returns amount_out, hi does
+        let estimate = estimate.max(1);
+        let mut steps = 0..MAX_EXACT_OUT_BRACKET_STEPS;
+        let (mut lo, mut hi, mut hi_result) = match reaches(estimate)? {
+            Some(swap_result) => {
+                let (mut hi, mut hi_result, mut step) = (estimate, swap_result, 1u64);
+                loop {
+                    steps
+                        .next()
+                        .ok_or(SwapCalculatorError::ExactOutSearchExceeded)?;
+                    let candidate = hi.saturating_sub(step);
+                    match reaches(candidate)? {
+                        Some(swap_result) => {
+                            hi = candidate;
+                            hi_result = swap_result;
+                            step = step.saturating_mul(2);
+                        }
+                        None => break (candidate, hi, hi_result),
+                    }
+                }
+            }
+            None => {
+                let (mut lo, mut step) = (estimate, 1u64);
+                loop {
+                    steps
+                        .next()
+                        .ok_or(SwapCalculatorError::ExactOutSearchExceeded)?;
+                    let candidate = lo
+                        .checked_add(step)
+                        .ok_or(SwapCalculatorError::InsufficientLiquidity)?;
+                    match reaches(candidate)? {
+                        Some(swap_result) => break (lo, candidate, swap_result),
+                        None => {
+                            lo = candidate;
+                            step = step.saturating_mul(2);
+                        }
+                    }
+                }
+            }
+        };
 
-        let delta_y = y_new.sub(self.y0).unwrap();
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            match reaches(mid)? {
+                Some(swap_result) => {
+                    hi = mid;
+                    hi_result = swap_result;
+                }
+                None => lo = mid,
+            }
+        }
 
-        let delta_x = self.compute_delta_x_hmm(&amount_ex_fee);
+        Ok((hi, hi_result))
+    }
+
+    /// Compute the new balance of the token swapped in (q) required to receive delta_out
+    /// of the token swapped out (p). This is the inverse of [compute_delta_y_hmm] and [compute_delta_x_hmm].
+    fn compute_q_new_hmm(
+        &self,
+        q0: &Decimal,
+        p0: &Decimal,
+        qi: Option<Decimal>,
+        delta_out: &Decimal,
+    ) -> Result<Decimal, DecimalError> {
+        let k = self.compute_k()?;
 
-        let x_new = self.x0.add(delta_x).unwrap();
+        let qi = match qi {
+            // Condition 1 - use AMM
+            // Oracle price is zero, the oracle price is better than the constant product price or c == 0
+            Some(qi) if qi.gt(*q0)? && !self.c.is_zero() => qi,
+            // Q_new = K/(P₀ - Δp)
+            _ => return k.div_up(p0.sub(*delta_out)?),
+        };
 
-        let y_new = y_new.add(fee_amount).unwrap();
+        // amount received when moving the curve from Q₀ up to Qᵢ
+        let integral = self.compute_integral(&k, q0, &qi, &qi, &self.c)?;
+        let integral = Decimal::new(integral.value, integral.scale, false);
 
-        Ok(SwapResult {
-            x_new: x_new.to_scaled_amount_up(self.scale.x),
-            y_new: y_new.to_scaled_amount_up(self.scale.y),
-            delta_x: delta_x.to_scaled_amount(self.scale.x),
-            delta_y: delta_y.to_scaled_amount(self.scale.y),
-            fees: fee_amount.to_scaled_amount(self.scale.y),
-        })
+        if delta_out.lte(integral)? {
+            // Condition 2 - use HMM
+            // Constant product price is better than the oracle price even after the full trade.
+            self.compute_inverse_integral(&k, q0, &qi, delta_out, &self.c)
+        } else {
+            // Condition 3 - use HMM
+            // Constant product price is better than the oracle price at the start of the trade.
+            // Q_new = K/(K/Qᵢ - (Δp - integral))
+            let remaining = delta_out.sub(integral)?;
+            k.div_up(k.div(qi)?.sub(remaining)?)
+        }
+    }
+
+    /// Compute Q_new given the amount received delta_out from the integral starting at Q₀,
+    /// the inverse of [compute_integral]
+    fn compute_inverse_integral(
+        &self,
+        k: &Decimal,
+        q0: &Decimal,
+        qi: &Decimal,
+        delta_out: &Decimal,
+        c: &Decimal,
+    ) -> Result<Decimal, DecimalError> {
+        // inverted at the compute scale, the exact amount in is searched from this estimate
+        let scale = q0.scale;
+        let k = k.to_compute_scale()?;
+        let q0 = q0.to_compute_scale()?;
+        let qi = qi.to_compute_scale()?;
+        let delta_out = delta_out.to_compute_scale()?;
+        let c = c.to_compute_scale()?;
+        let one = Decimal::one();
+        let q_new = if c == one {
+            // Δp = K/Qᵢ * ln(Q_new/Q₀) ∴ Q_new = Q₀ * e^(Δp/(K/Qᵢ))
+            let t = delta_out.div(k.div(qi)?)?;
+            q0.mul(t.exp()?)?
+        } else {
+            // Δp = K/((Qᵢ**c)*(c-1)) * (Q_new**(c-1) - Q₀**(c-1))
+            // Q_new = (Q₀**(c-1) + Δp*(c-1)/(K/(Qᵢ**c)))**(1/(c-1))
+            let c_sub_one = c.sub(one)?;
+            let k_div_qi_pow_c = k.div(qi.pow(c)?)?;
+            let rhs = delta_out.mul(c_sub_one)?.div(k_div_qi_pow_c)?;
+
+            q0.pow(c_sub_one)?.add(rhs)?.pow(one.div(c_sub_one)?)?
+        };
+
+        q_new.to_scale_up(scale)
     }
 
     /// Compute delta y using a constant product curve given delta x
-    fn compute_delta_y_amm(&self, delta_x: &Decimal) -> Decimal {
+    fn compute_delta_y_amm(&self, delta_x: &Decimal) -> Result<Decimal, DecimalError> {
         // Δy = K/(X₀ + Δx) - K/X₀
         // delta_y = k/(self.x0 + delta_x) - k/self.x0
-        let k = self.compute_k();
-        let x_new = self.compute_x_new(&delta_x);
+        let k = self.compute_k()?;
+        let x_new = self.compute_x_new(&delta_x)?;
 
-        k.div(*&x_new).sub(k.div(self.x0)).expect("k/self.x0")
+        k.div(x_new)?.sub(k.div(self.x0)?)
     }
 
     /// Compute delta x using a constant product curve given delta y
-    fn compute_delta_x_amm(&self, delta_y: &Decimal) -> Decimal {
+    fn compute_delta_x_amm(&self, delta_y: &Decimal) -> Result<Decimal, DecimalError> {
         // Δx = K/(Y₀ + Δy) - K/Y₀
         // delta_x = k/(sef.y0 + delta_y) - k/self.y0
-        let k = self.compute_k();
-        let y_new = self.compute_y_new(&delta_y);
+        let k = self.compute_k()?;
+        let y_new = self.compute_y_new(&delta_y)?;
 
-        k.div(y_new).sub(k.div(self.y0)).expect("k/self.y0")
+        k.div(y_new)?.sub(k.div(self.y0)?)
     }
 
     /// Compute delta y using a baseline curve given delta y
-    fn compute_delta_y_hmm(&self, delta_x: &Decimal) -> Decimal {
-        let x_new = self.compute_x_new(delta_x);
-        let k = self.compute_k();
+    fn compute_delta_y_hmm(&self, delta_x: &Decimal) -> Result<Decimal, DecimalError> {
+        let x_new = self.compute_x_new(delta_x)?;
+        let k = self.compute_k()?;
 
         if self.i.is_zero() {
             // Condition 0 - use AMM
@@ -219,13 +477,13 @@ impl SwapCalculator {
             return self.compute_delta_y_amm(delta_x);
         }
 
-        let xi = self.compute_xi();
-        if x_new.gt(self.x0).unwrap() && self.x0.gte(xi).unwrap() {
+        let xi = self.compute_xi()?;
+        if x_new.gt(self.x0)? && self.x0.gte(xi)? {
             // Condition 1 - use AMM
             // (Δx > 0 AND X₀ >= Xᵢ) [OR (Δx < 0 AND X₀ <= Xᵢ)] <= redundant because delta x always > 0
             // Oracle price is better than the constant product price
             self.compute_delta_y_amm(delta_x)
-        } else if x_new.gt(self.x0).unwrap() && x_new.lte(xi).unwrap() {
+        } else if x_new.gt(self.x0)? && x_new.lte(xi)? {
             // Condition 2 - use HMM
             // (Δx > 0 AND X_new <= Xᵢ) [OR (Δx < 0 AND X_new >= Xᵢ)]
             // Constant product price is better than the oracle price even after the full trade.
@@ -234,21 +492,21 @@ impl SwapCalculator {
             // Condition 3 - use HMM
             // Constant product price is better than the oracle price at the start of the trade.
             // delta_y = compute_integral(k, x0, xi, xi, c) + (k/x_new - k/xi)
-            let integral = self.compute_integral(&k, &self.x0, &xi, &xi, &self.c);
+            let integral = self.compute_integral(&k, &self.x0, &xi, &xi, &self.c)?;
 
             // rhs = (k/x_new - k/xi)
-            let k_div_x_new = k.div(x_new);
-            let k_div_xi = k.div(xi);
-            let rhs = k_div_x_new.sub(k_div_xi).unwrap();
+            let k_div_x_new = k.div(x_new)?;
+            let k_div_xi = k.div(xi)?;
+            let rhs = k_div_x_new.sub(k_div_xi)?;
 
-            integral.add(rhs).unwrap()
+            integral.add(rhs)
         }
     }
 
     /// Compute delta x using a baseline curve given delta y
-    fn compute_delta_x_hmm(&self, delta_y: &Decimal) -> Decimal {
-        let y_new = self.compute_y_new(delta_y);
-        let k = self.compute_k();
+    fn compute_delta_x_hmm(&self, delta_y: &Decimal) -> Result<Decimal, DecimalError> {
+        let y_new = self.compute_y_new(delta_y)?;
+        let k = self.compute_k()?;
 
         if self.i.is_zero() {
             // Condition 0 - use AMM
@@ -256,13 +514,13 @@ impl SwapCalculator {
             return self.compute_delta_x_amm(delta_y);
         }
 
-        let yi = self.compute_yi();
-        if y_new.gt(self.y0).unwrap() && self.y0.gte(yi).unwrap() {
+        let yi = self.compute_yi()?;
+        if y_new.gt(self.y0)? && self.y0.gte(yi)? {
             // Condition 1 - use AMM
             // (Δy > 0 AND Y₀ >= Yᵢ) [OR (Δy < 0 AND Y₀ <= Yᵢ)] <= redundant because delta y always > 0
             // Oracle price is better than the constant product price.
             self.compute_delta_x_amm(delta_y)
-        } else if y_new.gt(self.y0).unwrap() && y_new.lte(yi).unwrap() {
+        } else if y_new.gt(self.y0)? && y_new.lte(yi)? {
             // Condition 2 - use HMM
             // (Δy > 0 AND Y_new <= Yᵢ) [OR (Δy < 0 AND Y_new >= Yᵢ)] <= redundant because delta y always > 0
             // Constant product price is better than the oracle price even after the full trade.
@@ -271,14 +529,14 @@ impl SwapCalculator {
             // Condition 3 - use HMM
             // Constant product price is better than the oracle price at the start of the trade.
             // delta_x = compute_integral(k, y0, yi, yi, c) + (k/x_new - k/xi)
-            let integral = self.compute_integral(&k, &self.y0, &yi, &yi, &self.c);
+            let integral = self.compute_integral(&k, &self.y0, &yi, &yi, &self.c)?;
 
             // rhs = (k/x_new - k/xi)
-            let k_div_y_new = k.div(y_new);
-            let k_div_yi = k.div(yi);
-            let rhs = k_div_y_new.sub(k_div_yi).unwrap();
+            let k_div_y_new = k.div(y_new)?;
+            let k_div_yi = k.div(yi)?;
+            let rhs = k_div_y_new.sub(k_div_yi)?;
 
-            integral.add(rhs).unwrap()
+            integral.add(rhs)
         }
     }
 
@@ -290,49 +548,45 @@ impl SwapCalculator {
         q_new: &Decimal,
         qi: &Decimal,
         c: &Decimal,
-    ) -> Decimal {
-        let one = Decimal::from_u64(1).to_scale(self.x0.scale);
-        if c.eq(&one) {
+    ) -> Result<Decimal, DecimalError> {
+        // the integral is computed at the compute scale, so that k/q isn't truncated to the scale of
+        // the calculator before being multiplied, and returned at the scale of the calculator
+        let scale = self.x0.scale;
+        let (k, q0, q_new, qi, c) = (
+            k.to_compute_scale()?,
+            q0.to_compute_scale()?,
+            q_new.to_compute_scale()?,
+            qi.to_compute_scale()?,
+            c.to_compute_scale()?,
+        );
+        let one = Decimal::from_u64(1).to_compute_scale()?;
+        let integral = if c == one {
             // k/qi * (q0/q_new).ln()
-            let k_div_qi = k.to_scale(8).div(qi.clone().to_scale(self.x0.scale));
-            let q0_div_q_new = q0.to_scale(8).div(q_new.clone().to_scale(self.x0.scale));
-            let log_q0_div_q_new = q0_div_q_new.ln().unwrap();
-            k_div_qi.mul(log_q0_div_q_new).to_scale(self.x0.scale)
+            // ln(q0/q_new) is kept at 18 decimals, q0/q_new is close to one for small trades
+            k.div(qi)?.mul_ln_ratio(q0, q_new)?
         } else {
             // k/((qi**c)*(c-1)) * (q0**(c-1)-q_new**(c-1))
-            // k/(qi**c)/(c-1) * (q0**(c-1)-q_new**(c-1))
-            // (k*q0**(c-1) - k*q_new**(c-1)) /(qi**c)/(c-1)
-            // a = k/q0**(c-1)
-            // b = k/q_new**(c-1)
-            // (a - b) / (qi**c) / (c-1)
+            // (k/q0 * (q0/qi)**c - k/q_new * (q_new/qi)**c) / (c-1)
+            // a = k/q0 * (q0/qi)**c
+            // b = k/q_new * (q_new/qi)**c
+            // (a - b) / (c-1)
+            // a and b are large and close to each other, (q/qi)**c is kept at 18 decimals
+            // so that their difference keeps the precision of the scale
 
             // c-1
-            let c_sub_one = c.sub(one).unwrap();
-            // qi**c
-            let qi_pow_c = qi.pow(*c);
-
-            // a = k*q0**(c-1)
-            // b = k*q_new**(c-1)
-            // lhs = k/((qi**c)*(c-1))
-            // (qi**c)*(c-1)
-            let lhs_den = qi_pow_c.mul(c_sub_one);
-            let lhs = k.div(lhs_den);
-
-            // q0**(c-1)
-            let q0_pow_c_sub_one = q0.pow(c_sub_one);
-            // q_new**(c-1)
-            let q_new_pow_c_sub_one = q_new.pow(c_sub_one);
-
-            // rhs = q0**(c-1) - q_new**(c-1)
-            let rhs = q0_pow_c_sub_one.sub(q_new_pow_c_sub_one).unwrap();
-
-            // lhs * rhs
-            lhs.mul(rhs)
-        }
+            let c_sub_one = c.sub(one)?;
+
+            let a = k.div(q0)?.mul_pow_ratio(q0, qi, c)?;
+            let b = k.div(q_new)?.mul_pow_ratio(q_new, qi, c)?;
+
+            a.sub(b)?.div(c_sub_one)?
+        };
+
+        integral.to_scale(scale)
     }
 
     /// Compute constant product curve invariant k
-    fn compute_k(&self) -> Decimal {
+    fn compute_k(&self) -> Result<Decimal, DecimalError> {
         // k = x0 * y0
         self.x0.mul(self.y0)
     }
@@ -340,31 +594,31 @@ impl SwapCalculator {
     /// Compute the token balance of x assuming the constant product price
     /// is the same as the oracle price.
     /// i = K/Xᵢ² ∴ Xᵢ = √K/i
-    fn compute_xi(&self) -> Decimal {
+    fn compute_xi(&self) -> Result<Decimal, DecimalError> {
         // Xᵢ = √K/i
-        let k = self.compute_k();
-        k.div(self.i).sqrt().expect("xi")
+        let k = self.compute_k()?;
+        k.div(self.i)?.sqrt()
     }
 
     /// Compute the token balance of y assuming the constant product price
     /// is the same as the oracle price
     /// i = K/Yᵢ² ∴ Yᵢ = √(K/1/i) = √(K * i)
-    fn compute_yi(&self) -> Decimal {
+    fn compute_yi(&self) -> Result<Decimal, DecimalError> {
         // Yᵢ = √(K/1/i) = √(K * i)
-        let k = self.compute_k();
-        k.mul(self.i).sqrt().expect("yi")
+        let k = self.compute_k()?;
+        k.mul(self.i)?.sqrt()
     }
 
     /// Compute new amount for x
-    fn compute_x_new(&self, delta_x: &Decimal) -> Decimal {
+    fn compute_x_new(&self, delta_x: &Decimal) -> Result<Decimal, DecimalError> {
         // x_new = x0 + delta_x
-        self.x0.add(*delta_x).expect("compute_x_new")
+        self.x0.add(*delta_x)
     }
 
     /// Compute new amount for y
-    fn compute_y_new(&self, delta_y: &Decimal) -> Decimal {
+    fn compute_y_new(&self, delta_y: &Decimal) -> Result<Decimal, DecimalError> {
         // y_new = y0 + delta_y
-        self.y0.add(*delta_y).expect("compute_y_new")
+        self.y0.add(*delta_y)
     }
 }
 
@@ -383,8 +637,8 @@ mod tests {
 
     fn coefficient_allowed_values(scale: u8) -> HashMap<&'static str, (u64, u64, Decimal)> {
         HashMap::from([
-            ("0.0", (0, 0, Decimal::from_u64(0).to_scale(scale))),
-            ("1.0", (1, 1, Decimal::from_u64(1).to_scale(scale))),
+            ("0.0", (0, 0, Decimal::from_u64(0).to_scale(scale).unwrap())),
+            ("1.0", (1, 1, Decimal::from_u64(1).to_scale(scale).unwrap())),
             (
                 "1.25",
                 (
@@ -392,7 +646,9 @@ mod tests {
                     4,
                     Decimal::from_u64(5)
                         .to_scale(scale)
-                        .div(Decimal::from_u64(4).to_scale(scale)),
+                        .unwrap()
+                        .div(Decimal::from_u64(4).to_scale(scale).unwrap())
+                        .unwrap(),
                 ),
             ),
             (
@@ -402,7 +658,9 @@ mod tests {
                     2,
                     Decimal::from_u64(3)
                         .to_scale(scale)
-                        .div(Decimal::from_u64(2).to_scale(scale)),
+                        .unwrap()
+                        .div(Decimal::from_u64(2).to_scale(scale).unwrap())
+                        .unwrap(),
                 ),
             ),
         ])
@@ -421,7 +679,7 @@ mod tests {
                 y: DEFAULT_SCALE_TEST,
             },
         };
-        let result = swap.compute_k();
+        let result = swap.compute_k().unwrap();
         let value = model.sim_k();
         let expected = Decimal::new(value, DEFAULT_SCALE_TEST, false);
         assert_eq!(result, expected, "check_k");
@@ -440,7 +698,7 @@ mod tests {
                 y: DEFAULT_SCALE_TEST,
             },
         };
-        let result = swap.compute_xi();
+        let result = swap.compute_xi().unwrap();
         let (value, negative) = model.sim_xi();
         let expected = Decimal::new(value, DEFAULT_SCALE_TEST, negative);
         assert_eq!(result, expected, "check_xi");
@@ -459,8 +717,9 @@ mod tests {
                 y: DEFAULT_SCALE_TEST,
             },
         };
-        let result =
-            swap.compute_delta_x_hmm(&Decimal::from_scaled_amount(delta_y, DEFAULT_SCALE_TEST));
+        let result = swap
+            .compute_delta_x_hmm(&Decimal::from_scaled_amount(delta_y, DEFAULT_SCALE_TEST))
+            .unwrap();
         let (value, negative) = model.sim_delta_x_hmm(delta_y);
         let expected = Decimal::new(value, DEFAULT_SCALE_TEST, negative);
 
@@ -489,8 +748,9 @@ mod tests {
                 y: DEFAULT_SCALE_TEST,
             },
         };
-        let result =
-            swap.compute_delta_y_hmm(&Decimal::from_scaled_amount(delta_x, DEFAULT_SCALE_TEST));
+        let result = swap
+            .compute_delta_y_hmm(&Decimal::from_scaled_amount(delta_x, DEFAULT_SCALE_TEST))
+            .unwrap();
         let (value, negative) = model.sim_delta_y_hmm(delta_x);
         let expected = Decimal::new(value, DEFAULT_SCALE_TEST, negative);
 
@@ -560,6 +820,208 @@ mod tests {
         }
     }
 
+    fn check_exact_out_round_trip(x0: u64, y0: u64, c: Decimal, i: u64, delta_x: u64) {
+        let swap = SwapCalculator {
+            x0: Decimal::from_scaled_amount(x0, DEFAULT_SCALE_TEST),
+            y0: Decimal::from_scaled_amount(y0, DEFAULT_SCALE_TEST),
+            c,
+            i: Decimal::from_scaled_amount(i, DEFAULT_SCALE_TEST),
+            percentage_fee_numerator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
+            percentage_fee_denominator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
+            scale: SwapCalculatorScale {
+                x: DEFAULT_SCALE_TEST,
+                y: DEFAULT_SCALE_TEST,
+            },
+        };
+
+        let forward = swap
+            .swap_x_to_y_hmm(&Decimal::from_scaled_amount(delta_x, DEFAULT_SCALE_TEST))
+            .unwrap();
+        if forward.delta_y == 0 {
+            return;
+        }
+
+        let inverse = swap
+            .swap_x_to_y_hmm_exact_out(&Decimal::from_scaled_amount(
+                forward.delta_y,
+                DEFAULT_SCALE_TEST,
+            ))
+            .unwrap();
+        assert_eq!(inverse.delta_y, forward.delta_y);
+
+        // the amount in is the smallest one whose swap returns at least the amount out
+        let amount_in = inverse.delta_x + inverse.fees;
+        assert!(amount_in <= delta_x, "{} > {}", amount_in, delta_x);
+
+        // the inverse of the curve estimates the amount in within a few units
+        let estimate = swap
+            .compute_q_new_hmm(
+                &swap.x0,
+                &swap.y0,
+                Some(swap.compute_xi().unwrap()),
+                &Decimal::from_scaled_amount(forward.delta_y, DEFAULT_SCALE_TEST),
+            )
+            .unwrap()
+            .sub(swap.x0)
+            .unwrap()
+            .to_scaled_amount_up(DEFAULT_SCALE_TEST)
+            .unwrap();
+        let distance = estimate.max(amount_in) - estimate.min(amount_in);
+        // plus the precision of the compute scale relative to the reserves
+        let units = 4 + x0 / 10u64.pow(11);
+        assert!(distance <= units, "{} > {}", distance, units);
+
+        check_exact_out_amount_in(&swap, amount_in, forward.delta_y);
+    }
+
+    fn check_exact_out_amount_in(swap: &SwapCalculator, amount_in: u64, amount_out: u64) {
+        let swap_out = |amount_in: u64| {
+            swap.swap_x_to_y_hmm(
+                &Decimal::from_scaled_amount(amount_in, swap.scale.x)
+                    .to_scale(swap.x0.scale)
+                    .unwrap(),
+            )
+            .unwrap()
+            .delta_y
+        };
+        assert!(
+            swap_out(amount_in) >= amount_out,
+            "check_exact_out_amount_in\n{}\n{}",
+            amount_in,
+            amount_out,
+        );
+        assert!(
+            amount_in == 1 || swap_out(amount_in - 1) < amount_out,
+            "check_exact_out_amount_in\n{}\n{}",
+            amount_in,
+            amount_out,
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_exact_out_curve_math(
+            x0 in 10u64.pow(7)..10u64.pow(8),
+            y0 in 10u64.pow(7)..10u64.pow(8),
+            c in (0..=3usize).prop_map(|v| ["0.0", "1.0", "1.25", "1.5"][v]),
+            i in 1_000_000..=10_000_000u64,
+            delta_x in 1_000_000..=10_000_000u64,
+        ) {
+            for (_c_numer, _c_denom, c) in coefficient_allowed_values(DEFAULT_SCALE_TEST).get(c) {
+                check_exact_out_round_trip(x0, y0, c.clone(), i, delta_x);
+            }
+        }
+
+        #[test]
+        fn test_exact_out_curve_math_large_reserves(
+            x0 in 10u64.pow(12)..10u64.pow(15),
+            y0 in 10u64.pow(12)..10u64.pow(15),
+            c in (0..=3usize).prop_map(|v| ["0.0", "1.0", "1.25", "1.5"][v]),
+            i in 1_000_000..=10_000_000u64,
+            delta_x in 1..=10_000_000u64,
+        ) {
+            for (_c_numer, _c_denom, c) in coefficient_allowed_values(DEFAULT_SCALE_TEST).get(c) {
+                check_exact_out_round_trip(x0, y0, c.clone(), i, delta_x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_out_large_reserves() {
+        // 1,000,000 x / 3,000,000 y at 6 decimals, c = 1, i = 2.9
+        // exact out of 1.000000 y: dx = x0 * (e^(dy/(k/xi)) - 1) = 0.339031...
+        for (fee_numerator, fee_denominator, expected) in [(0, 0, 339_032), (3, 1_000, 340_052)] {
+            let swap = SwapCalculatorBuilder::default()
+                .x0(1_000_000_000_000, 6)
+                .y0(3_000_000_000_000, 6)
+                .c(100)
+                .i(2_900000, 6)
+                .percentage_fee_numerator(fee_numerator)
+                .percentage_fee_denominator(fee_denominator)
+                .scale(6, 6)
+                .build()
+                .unwrap();
+
+            let result = swap
+                .swap_x_to_y_hmm_exact_out(&Decimal::from_scaled_amount_to_compute_scale(
+                    1_000000, 6,
+                ))
+                .unwrap();
+            assert_eq!(result.delta_y, 1_000000);
+            assert_eq!(result.delta_x + result.fees, expected);
+            assert_eq!(result.x_new, 1_000_000_000_000 + expected);
+            assert_eq!(result.y_new, 3_000_000_000_000 - 1_000000);
+            check_exact_out_amount_in(&swap, expected, 1_000000);
+
+            // y to x, exact out of 1.000000 x is the smallest amount of y returning it
+            let result = swap
+                .swap_y_to_x_hmm_exact_out(&Decimal::from_scaled_amount_to_compute_scale(
+                    1_000000, 6,
+                ))
+                .unwrap();
+            assert_eq!(result.delta_x, 1_000000);
+            let swap_out = |amount_in: u64| {
+                swap.swap_y_to_x_hmm(&Decimal::from_scaled_amount_to_compute_scale(amount_in, 6))
+                    .unwrap()
+                    .delta_x
+            };
+            let amount_in = result.delta_y + result.fees;
+            assert!(swap_out(amount_in) >= 1_000000);
+            assert!(swap_out(amount_in - 1) < 1_000000);
+        }
+
+        // swap of a calculator at 6 decimals, its integral used to be truncated to 6 decimals
+        // and the swap of 3183302 returned less than the swap of 3183301
+        let c = coefficient_allowed_values(DEFAULT_SCALE_TEST)["1.25"].2;
+        check_exact_out_round_trip(505419020940156, 671260790266867, c, 1129454, 3183301);
+    }
+
+    #[test]
+    fn test_exact_out_failures() {
+        let swap = SwapCalculatorBuilder::default()
+            .x0(37_000000, 6)
+            .y0(126_000000, 6)
+            .c(100)
+            .i(3_000000, 6)
+            .percentage_fee_numerator(0)
+            .percentage_fee_denominator(0)
+            .scale(6, 6)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            swap.swap_x_to_y_hmm_exact_out(&Decimal::from_u64(0).to_compute_scale().unwrap()),
+            Err(SwapCalculatorError::DeltaNotPositive)
+        ));
+        assert!(matches!(
+            swap.swap_x_to_y_hmm_exact_out(&Decimal::from_u64(126).to_compute_scale().unwrap()),
+            Err(SwapCalculatorError::InsufficientLiquidity)
+        ));
+        assert!(matches!(
+            swap.swap_y_to_x_hmm_exact_out(&Decimal::from_u64(37).to_compute_scale().unwrap()),
+            Err(SwapCalculatorError::InsufficientLiquidity)
+        ));
+
+        // an estimate off by a thousand units is bracketed, one off by millions isn't
+        let amount_out = 10_000000;
+        let result = swap
+            .swap_x_to_y_hmm_exact_out(&Decimal::from_scaled_amount_to_compute_scale(amount_out, 6))
+            .unwrap();
+        let amount_in = result.delta_x + result.fees;
+        let (found, _) = swap
+            .find_exact_out_amount_in(amount_in + 1_000, amount_out, true)
+            .unwrap();
+        assert_eq!(found, amount_in);
+        let (found, _) = swap
+            .find_exact_out_amount_in(amount_in - 1_000, amount_out, true)
+            .unwrap();
+        assert_eq!(found, amount_in);
+        assert!(matches!(
+            swap.find_exact_out_amount_in(1, amount_out, true),
+            Err(SwapCalculatorError::ExactOutSearchExceeded)
+        ));
+    }
+
     #[test]
     fn test_specific_curve_math() {
         // compute_delta_y_hmm when c == 1
@@ -577,7 +1039,7 @@ mod tests {
                 },
             };
             let delta_x = Decimal::from_scaled_amount(3_000000, DEFAULT_SCALE_TEST);
-            let result = swap.compute_delta_y_hmm(&delta_x);
+            let result = swap.compute_delta_y_hmm(&delta_x).unwrap();
             // python: -9.207_401_794_786
 
             let expected = Decimal::new(9_207_401, DEFAULT_SCALE_TEST, true);
@@ -594,10 +1056,10 @@ mod tests {
         // compute_delta_y_hmm when c == 0
         {
             let swap = SwapCalculator {
-                x0: Decimal::from_u128(32).to_compute_scale(),
-                y0: Decimal::from_u128(33).to_compute_scale(),
-                c: Decimal::from_u128(0).to_compute_scale(),
-                i: Decimal::from_u128(1).to_compute_scale(),
+                x0: Decimal::from_u128(32).to_compute_scale().unwrap(),
+                y0: Decimal::from_u128(33).to_compute_scale().unwrap(),
+                c: Decimal::from_u128(0).to_compute_scale().unwrap(),
+                i: Decimal::from_u128(1).to_compute_scale().unwrap(),
                 percentage_fee_numerator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 percentage_fee_denominator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 scale: SwapCalculatorScale {
@@ -605,8 +1067,12 @@ mod tests {
                     y: DEFAULT_SCALE_TEST,
                 },
             };
-            let delta_x = Decimal::from_u128(1).to_compute_scale();
-            let result = swap.compute_delta_y_hmm(&delta_x).to_scale(8);
+            let delta_x = Decimal::from_u128(1).to_compute_scale().unwrap();
+            let result = swap
+                .compute_delta_y_hmm(&delta_x)
+                .unwrap()
+                .to_scale(8)
+                .unwrap();
             // python: -1.000_000_000_000
             let expected = Decimal::new(1_000_000_00, 8, true);
 
@@ -622,10 +1088,10 @@ mod tests {
         // compute_delta_x_hmm when c == 0
         {
             let swap = SwapCalculator {
-                x0: Decimal::from_u128(216).to_compute_scale(),
-                y0: Decimal::from_u128(193).to_compute_scale(),
-                c: Decimal::from_u128(0).to_compute_scale(),
-                i: Decimal::from_u128(1).to_compute_scale(),
+                x0: Decimal::from_u128(216).to_compute_scale().unwrap(),
+                y0: Decimal::from_u128(193).to_compute_scale().unwrap(),
+                c: Decimal::from_u128(0).to_compute_scale().unwrap(),
+                i: Decimal::from_u128(1).to_compute_scale().unwrap(),
                 percentage_fee_numerator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 percentage_fee_denominator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 scale: SwapCalculatorScale {
@@ -633,10 +1099,12 @@ mod tests {
                     y: DEFAULT_SCALE_TEST,
                 },
             };
-            let delta_y = Decimal::from_u128(4).to_compute_scale();
+            let delta_y = Decimal::from_u128(4).to_compute_scale().unwrap();
             let result = swap
                 .compute_delta_x_hmm(&delta_y)
-                .to_scale(DEFAULT_SCALE_TEST);
+                .unwrap()
+                .to_scale(DEFAULT_SCALE_TEST)
+                .unwrap();
             // python: -4.385_786_802_030
             let expected = Decimal::new(4_385_786, DEFAULT_SCALE_TEST, true);
 
@@ -652,10 +1120,10 @@ mod tests {
         // xi specific
         {
             let swap = SwapCalculator {
-                x0: Decimal::from_u128(1000).to_compute_scale(),
-                y0: Decimal::from_u128(1000).to_compute_scale(),
-                c: Decimal::from_u128(0).to_compute_scale(),
-                i: Decimal::from_u128(200).to_compute_scale(),
+                x0: Decimal::from_u128(1000).to_compute_scale().unwrap(),
+                y0: Decimal::from_u128(1000).to_compute_scale().unwrap(),
+                c: Decimal::from_u128(0).to_compute_scale().unwrap(),
+                i: Decimal::from_u128(200).to_compute_scale().unwrap(),
                 percentage_fee_numerator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 percentage_fee_denominator: Decimal::from_scaled_amount(0, DEFAULT_SCALE_TEST),
                 scale: SwapCalculatorScale {
@@ -665,7 +1133,7 @@ mod tests {
             };
             // ((1000*1000)/200)**0.5 = 70.710678118654752
             // https://www.wolframalpha.com/input/?i=%28%281000*1000%29%2F200%29**0.5
-            let result = swap.compute_xi().to_compute_scale();
+            let result = swap.compute_xi().unwrap().to_compute_scale().unwrap();
             let expected = Decimal::new(70_710_678_118_654u128, 12, false);
 
             assert!(
@@ -679,14 +1147,14 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_range_failures() {
-        // TODO: Figure out precision issues with higher end of u64 range
-        // minimal failing input: x0 = 101150628009, y0 = 325597609636963, c = "0.0", i = 1000000, delta_x = 65516038658, delta_y = 1000000
+        // higher end of the u64 range, the integral subtracts two values close to k/q0
+        // which used to lose their precision when q**(c-1) = 1/q was truncated for large q
+        // x0 = 101150628009, y0 = 325597609636963, c = "0.0", i = 1000000, delta_x = 65516038658
         {
             let x0 = 101150628009;
             let y0 = 325597609636963;
-            let c = Decimal::from_u64(0).to_scale(DEFAULT_SCALE_TEST);
+            let c = Decimal::from_u64(0).to_scale(DEFAULT_SCALE_TEST).unwrap();
             let i = 1000000;
             let delta_x = 65516038658;
             // let delta_y = 1000000;
@@ -700,5 +1168,31 @@ mod tests {
             );
             check_delta_y_hmm(&model, x0, y0, c, i, delta_x);
         }
+
+        // large reserves at the compute scale: 1,000,000 x / 3,000,000 y, i = 2.9, delta_x = 0.333334
+        // c = 0: delta_y = k/x_new - k/x0 = -1.000001666665...
+        // c = 1: delta_y = k/xi * ln(x0/x_new) = -0.983193882768...
+        for (c, expected) in [(0, 1_000_001_666_665u128), (100, 983_193_882_768)] {
+            let swap = SwapCalculatorBuilder::default()
+                .x0(1_000_000_000_000, 6)
+                .y0(3_000_000_000_000, 6)
+                .c(c)
+                .i(2_900000, 6)
+                .percentage_fee_numerator(0)
+                .percentage_fee_denominator(0)
+                .scale(6, 6)
+                .build()
+                .unwrap();
+
+            let delta_x = Decimal::from_scaled_amount_to_compute_scale(333334, 6);
+            let result = swap.compute_delta_y_hmm(&delta_x).unwrap();
+            assert!(result.is_negative());
+            assert!(
+                result.value.max(expected) - result.value.min(expected) <= 10,
+                "c = {}: {}",
+                c,
+                result
+            );
+        }
     }
 }
@@ -26,13 +26,13 @@ pub fn swap_x_to_y_hmm(
         .percentage_fee_denominator(fee_denom)
         .scale(x_scale, y_scale)
         .build()
-        .unwrap();
+        .map_err(|e| e.to_string())?;
 
-    let delta_x = Decimal::from_scaled_amount(amount, x_scale).to_compute_scale();
+    let delta_x = Decimal::from_scaled_amount_to_compute_scale(amount, x_scale);
 
     let result = calculator
         .swap_x_to_y_hmm(&delta_x)
-        .expect("failed to swap x to y");
+        .map_err(|e| e.to_string())?;
 
     Ok(result.into())
 }
@@ -59,13 +59,79 @@ pub fn swap_y_to_x_hmm(
         .percentage_fee_denominator(fee_denom)
         .scale(x_scale, y_scale)
         .build()
-        .unwrap();
+        .map_err(|e| e.to_string())?;
 
-    let delta_y = Decimal::from_scaled_amount(amount, y_scale).to_compute_scale();
+    let delta_y = Decimal::from_scaled_amount_to_compute_scale(amount, y_scale);
 
     let result = calculator
         .swap_y_to_x_hmm(&delta_y)
-        .expect("failed to swap y to x");
+        .map_err(|e| e.to_string())?;
+
+    Ok(result.into())
+}
+
+#[wasm_bindgen]
+pub fn swap_x_to_y_hmm_exact_out(
+    x0: u64,
+    x_scale: u8,
+    y0: u64,
+    y_scale: u8,
+    c: u8,
+    i: u64,
+    i_scale: u8,
+    fee_numer: u64,
+    fee_denom: u64,
+    amount_out: u64,
+) -> Result<Vec<u64>, String> {
+    let calculator = SwapCalculatorBuilder::default()
+        .x0(x0, x_scale)
+        .y0(y0, y_scale)
+        .c(c)
+        .i(i, i_scale)
+        .percentage_fee_numerator(fee_numer)
+        .percentage_fee_denominator(fee_denom)
+        .scale(x_scale, y_scale)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let delta_y = Decimal::from_scaled_amount_to_compute_scale(amount_out, y_scale);
+
+    let result = calculator
+        .swap_x_to_y_hmm_exact_out(&delta_y)
+        .map_err(|e| e.to_string())?;
+
+    Ok(result.into())
+}
+
+#[wasm_bindgen]
+pub fn swap_y_to_x_hmm_exact_out(
+    x0: u64,
+    x_scale: u8,
+    y0: u64,
+    y_scale: u8,
+    c: u8,
+    i: u64,
+    i_scale: u8,
+    fee_numer: u64,
+    fee_denom: u64,
+    amount_out: u64,
+) -> Result<Vec<u64>, String> {
+    let calculator = SwapCalculatorBuilder::default()
+        .x0(x0, x_scale)
+        .y0(y0, y_scale)
+        .c(c)
+        .i(i, i_scale)
+        .percentage_fee_numerator(fee_numer)
+        .percentage_fee_denominator(fee_denom)
+        .scale(x_scale, y_scale)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let delta_x = Decimal::from_scaled_amount_to_compute_scale(amount_out, x_scale);
+
+    let result = calculator
+        .swap_y_to_x_hmm_exact_out(&delta_x)
+        .map_err(|e| e.to_string())?;
 
     Ok(result.into())
 }
@@ -121,5 +187,58 @@ mod tests {
             let expected = 860_465u64;
             assert_eq!(actual.delta_x, expected);
         }
+
+        // x to y (given the delta Y wanted, what is delta X?)
+        {
+            let actual: SwapResult = From::from(
+                swap_x_to_y_hmm_exact_out(
+                    37_000000, 6, 126_000000, 6, 100, 3_000000, 6, 0, 0, 9_207_401,
+                )
+                .unwrap(),
+            );
+            assert_eq!(actual.delta_x, 3_000000u64);
+            assert_eq!(actual.delta_y, 9_207_401u64);
+        }
+
+        // y to x (given the delta X wanted, what is delta Y?)
+        {
+            let actual: SwapResult = From::from(
+                swap_y_to_x_hmm_exact_out(
+                    37_000000, 6, 126_000000, 6, 100, 3_000000, 6, 0, 0, 860_465,
+                )
+                .unwrap(),
+            );
+            assert_eq!(actual.delta_y, 3_000000u64);
+            assert_eq!(actual.delta_x, 860_465u64);
+        }
+
+        // fees are added on top of the amount in
+        {
+            let actual: SwapResult = From::from(
+                swap_x_to_y_hmm_exact_out(
+                    1000000_000000000,
+                    9,
+                    1000000_000000,
+                    6,
+                    0,
+                    0,
+                    0,
+                    1,
+                    500,
+                    99_59841,
+                )
+                .unwrap(),
+            );
+            assert_eq!(actual.delta_y, 99_59841u64);
+            assert_eq!(actual.delta_x + actual.fees, 9_979900000u64);
+        }
+
+        // more than the pool holds can not be received
+        {
+            let actual = swap_x_to_y_hmm_exact_out(
+                37_000000, 6, 126_000000, 6, 100, 3_000000, 6, 0, 0, 126_000000,
+            );
+            assert!(actual.is_err());
+        }
     }
 }
@@ -1,4 +1,6 @@
 //! Result storage
+use crate::programs::liquidity_pools::circuit_breaker::deviation_bps;
+use crate::programs::liquidity_pools::twap::spot_prices;
 // use wasm_bindgen::prelude::wasm_bindgen;
 
 // #[wasm_bindgen]
@@ -19,6 +21,16 @@ pub struct SwapResult {
     pub fees: u64,
 }
 
+impl SwapResult {
+    /// Price impact of the swap from the reserves x0 and y0: the move of the pool spot price (y per x)
+    /// to the new reserves in bps, as checked by the circuit breakers. None when either pool is empty.
+    pub fn price_impact_bps(&self, x0: u64, y0: u64, x_scale: u8, y_scale: u8) -> Option<u128> {
+        let (price_before, _) = spot_prices(x0, x_scale, y0, y_scale)?;
+        let (price_after, _) = spot_prices(self.x_new, x_scale, self.y_new, y_scale)?;
+        deviation_bps(price_after, price_before)
+    }
+}
+
 impl Into<Vec<u64>> for SwapResult {
     fn into(self) -> Vec<u64> {
         vec![
@@ -42,3 +54,41 @@ impl From<Vec<u64>> for SwapResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_impact_bps() {
+        let swap_result = SwapResult {
+            x_new: 1_100_000,
+            y_new: 1_818_182,
+            ..SwapResult::default()
+        };
+        // 2 y per x to 1.652893 y per x
+        assert_eq!(
+            swap_result.price_impact_bps(1_000_000, 2_000_000, 6, 6),
+            Some(1_735)
+        );
+
+        // no move
+        let swap_result = SwapResult {
+            x_new: 2_000_000,
+            y_new: 4_000_000,
+            ..SwapResult::default()
+        };
+        assert_eq!(
+            swap_result.price_impact_bps(1_000_000, 2_000_000, 6, 6),
+            Some(0)
+        );
+
+        // empty pools
+        assert_eq!(swap_result.price_impact_bps(0, 2_000_000, 6, 6), None);
+        let drained = SwapResult {
+            x_new: 2_000_000,
+            ..SwapResult::default()
+        };
+        assert_eq!(drained.price_impact_bps(1_000_000, 2_000_000, 6, 6), None);
+    }
+}
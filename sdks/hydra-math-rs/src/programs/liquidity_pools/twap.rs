@@ -0,0 +1,239 @@
+//! Time weighted average prices (TWAP) of a pool from its cumulative prices
+use crate::decimal::{Decimal, Div, COMPUTE_SCALE};
+use crate::programs::liquidity_pools::error::TwapError;
+
+/// Cumulative prices of a pool up to a slot and timestamp: the sums of its spot prices at
+/// [COMPUTE_SCALE], weighted by the slots and by the seconds each price held for.
+/// The sums wrap on overflow, only their differences between two observations are meaningful.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceAccumulator {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub y_per_x_slots: u128,
+    pub x_per_y_slots: u128,
+    pub y_per_x_seconds: u128,
+    pub x_per_y_seconds: u128,
+}
+
+/// Time unit the cumulative prices of a [twap] are averaged over
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwapWeight {
+    Slots,
+    Seconds,
+}
+
+/// Average prices of a pool between two observations of its [PriceAccumulator]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Twap {
+    pub y_per_x: Decimal,
+    pub x_per_y: Decimal,
+}
+
+impl PriceAccumulator {
+    /// Accumulate the spot prices of the reserves x and y, held since the last update, up to slot and timestamp.
+    /// Must be called before the reserves change. Nothing is accumulated for the first update or while a
+    /// reserve is empty, as the pool has no price, and a clock going backwards accumulates nothing.
+    pub fn accumulate(
+        &mut self,
+        x: u64,
+        x_scale: u8,
+        y: u64,
+        y_scale: u8,
+        slot: u64,
+        timestamp: i64,
+    ) {
+        let initialized = self.slot != 0 || self.timestamp != 0;
+        let slots = slot.saturating_sub(self.slot) as u128;
+        let seconds = timestamp.saturating_sub(self.timestamp).max(0) as u128;
+
+        if let (true, Some((y_per_x, x_per_y))) = (initialized, spot_prices(x, x_scale, y, y_scale))
+        {
+            self.y_per_x_slots = self.y_per_x_slots.wrapping_add(y_per_x.wrapping_mul(slots));
+            self.x_per_y_slots = self.x_per_y_slots.wrapping_add(x_per_y.wrapping_mul(slots));
+            self.y_per_x_seconds = self
+                .y_per_x_seconds
+                .wrapping_add(y_per_x.wrapping_mul(seconds));
+            self.x_per_y_seconds = self
+                .x_per_y_seconds
+                .wrapping_add(x_per_y.wrapping_mul(seconds));
+        }
+
+        self.slot = self.slot.max(slot);
+        self.timestamp = self.timestamp.max(timestamp);
+    }
+}
+
+/// Spot prices (y per x, x per y) of the reserves x and y in whole tokens, as values at [COMPUTE_SCALE].
+/// None when a reserve is empty at [COMPUTE_SCALE].
+pub fn spot_prices(x: u64, x_scale: u8, y: u64, y_scale: u8) -> Option<(u128, u128)> {
+    let x = Decimal::from_scaled_amount_to_compute_scale(x, x_scale);
+    let y = Decimal::from_scaled_amount_to_compute_scale(y, y_scale);
+    if x.is_zero() || y.is_zero() {
+        return None;
+    }
+
+    Some((y.div(x).ok()?.value, x.div(y).ok()?.value))
+}
+
+/// Time weighted average prices of a pool between an older and a newer observation of its cumulative prices
+pub fn twap(
+    older: &PriceAccumulator,
+    newer: &PriceAccumulator,
+    weight: TwapWeight,
+) -> Result<Twap, TwapError> {
+    let (elapsed, y_per_x, x_per_y) = match weight {
+        TwapWeight::Slots => (
+            newer.slot.checked_sub(older.slot).map(u128::from),
+            newer.y_per_x_slots.wrapping_sub(older.y_per_x_slots),
+            newer.x_per_y_slots.wrapping_sub(older.x_per_y_slots),
+        ),
+        TwapWeight::Seconds => (
+            newer
+                .timestamp
+                .checked_sub(older.timestamp)
+                .filter(|seconds| *seconds >= 0)
+                .map(|seconds| seconds as u128),
+            newer.y_per_x_seconds.wrapping_sub(older.y_per_x_seconds),
+            newer.x_per_y_seconds.wrapping_sub(older.x_per_y_seconds),
+        ),
+    };
+
+    let elapsed = match elapsed {
+        Some(0) => return Err(TwapError::EmptyInterval),
+        Some(elapsed) => elapsed,
+        None => return Err(TwapError::ObservationsOutOfOrder),
+    };
+
+    Ok(Twap {
+        y_per_x: Decimal::new(y_per_x / elapsed, COMPUTE_SCALE, false),
+        x_per_y: Decimal::new(x_per_y / elapsed, COMPUTE_SCALE, false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulator_at(slot: u64, timestamp: i64) -> PriceAccumulator {
+        PriceAccumulator {
+            slot,
+            timestamp,
+            ..PriceAccumulator::default()
+        }
+    }
+
+    #[test]
+    fn test_spot_prices() {
+        // 1 x (6 decimals) for 2000 y (6 decimals)
+        assert_eq!(
+            spot_prices(1_000_000, 6, 2_000_000_000, 6),
+            Some((2_000_000_000_000_000, 500_000_000))
+        );
+        // 2 x (9 decimals) for 3 y (6 decimals)
+        assert_eq!(
+            spot_prices(2_000_000_000, 9, 3_000_000, 6),
+            Some((1_500_000_000_000, 666_666_666_666))
+        );
+        assert_eq!(spot_prices(0, 6, 1_000_000, 6), None);
+        assert_eq!(spot_prices(1_000_000, 6, 0, 6), None);
+        // below the precision of COMPUTE_SCALE
+        assert_eq!(spot_prices(1, 18, 1_000_000, 6), None);
+    }
+
+    #[test]
+    fn test_accumulate() {
+        // first update only starts the clock
+        let mut accumulator = PriceAccumulator::default();
+        accumulator.accumulate(1_000_000, 6, 2_000_000, 6, 100, 1_000);
+        assert_eq!(accumulator, accumulator_at(100, 1_000));
+
+        // price of 2 y per x for 10 slots and 4 seconds
+        accumulator.accumulate(1_000_000, 6, 2_000_000, 6, 110, 1_004);
+        assert_eq!(
+            accumulator,
+            PriceAccumulator {
+                slot: 110,
+                timestamp: 1_004,
+                y_per_x_slots: 20_000_000_000_000,
+                x_per_y_slots: 5_000_000_000_000,
+                y_per_x_seconds: 8_000_000_000_000,
+                x_per_y_seconds: 2_000_000_000_000,
+            }
+        );
+
+        // same slot accumulates nothing
+        let expected = accumulator;
+        accumulator.accumulate(4_000_000, 6, 1_000_000, 6, 110, 1_004);
+        assert_eq!(accumulator, expected);
+
+        // empty reserves only move the clock
+        accumulator.accumulate(0, 6, 0, 6, 120, 1_008);
+        assert_eq!(
+            accumulator,
+            PriceAccumulator {
+                slot: 120,
+                timestamp: 1_008,
+                ..expected
+            }
+        );
+
+        // clock going backwards accumulates nothing and keeps the latest clock
+        accumulator.accumulate(1_000_000, 6, 2_000_000, 6, 119, 1_007);
+        assert_eq!(accumulator.slot, 120);
+        assert_eq!(accumulator.timestamp, 1_008);
+        assert_eq!(accumulator.y_per_x_slots, expected.y_per_x_slots);
+    }
+
+    #[test]
+    fn test_accumulate_wraps() {
+        let mut accumulator = PriceAccumulator {
+            y_per_x_slots: u128::MAX,
+            ..accumulator_at(1, 1)
+        };
+        accumulator.accumulate(1_000_000, 6, 1_000_000, 6, 2, 2);
+        assert_eq!(accumulator.y_per_x_slots, 999_999_999_999);
+    }
+
+    #[test]
+    fn test_twap() {
+        let mut accumulator = PriceAccumulator::default();
+        accumulator.accumulate(1_000_000, 6, 2_000_000, 6, 100, 1_000);
+        let older = accumulator;
+
+        // 2 y per x for 30 slots (12 seconds), then 4 y per x for 10 slots (4 seconds)
+        accumulator.accumulate(1_000_000, 6, 2_000_000, 6, 130, 1_012);
+        accumulator.accumulate(1_000_000, 6, 4_000_000, 6, 140, 1_016);
+        let newer = accumulator;
+
+        let by_slots = twap(&older, &newer, TwapWeight::Slots).unwrap();
+        assert_eq!(by_slots.y_per_x, Decimal::new(2_500_000_000_000, 12, false));
+        assert_eq!(by_slots.x_per_y, Decimal::new(437_500_000_000, 12, false));
+
+        let by_seconds = twap(&older, &newer, TwapWeight::Seconds).unwrap();
+        assert_eq!(by_seconds, by_slots);
+
+        // differences hold across a wrapped accumulator
+        let offset = u128::MAX - 10_000_000_000_000;
+        let wrapped = |accumulator: PriceAccumulator| PriceAccumulator {
+            y_per_x_slots: accumulator.y_per_x_slots.wrapping_add(offset),
+            ..accumulator
+        };
+        assert_eq!(
+            twap(&wrapped(older), &wrapped(newer), TwapWeight::Slots).unwrap(),
+            by_slots
+        );
+
+        assert!(matches!(
+            twap(&newer, &older, TwapWeight::Slots),
+            Err(TwapError::ObservationsOutOfOrder)
+        ));
+        assert!(matches!(
+            twap(&newer, &older, TwapWeight::Seconds),
+            Err(TwapError::ObservationsOutOfOrder)
+        ));
+        assert!(matches!(
+            twap(&newer, &newer, TwapWeight::Slots),
+            Err(TwapError::EmptyInterval)
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod farming;
 pub mod fees;
 pub mod liquidity_pools;
 pub mod staking;
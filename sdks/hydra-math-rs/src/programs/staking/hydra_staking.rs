@@ -8,15 +8,16 @@ pub fn calculate_pool_tokens_for_deposit(
     amount: u64,
     total_token_vault: u64,
     total_redeemable_tokens: u64,
-) -> u64 {
+) -> Option<u64> {
     let amount = Decimal::from_u64(amount);
     let total_token_vault = Decimal::from_u64(total_token_vault);
     let total_redeemable_tokens = Decimal::from_u64(total_redeemable_tokens);
 
     amount
         .mul(total_redeemable_tokens)
-        .div(total_token_vault)
-        .to_u64()
+        .and_then(|amount| amount.div(total_token_vault))
+        .and_then(|amount| amount.to_u64())
+        .ok()
 }
 
 /// withdraw tokens from pool
@@ -26,15 +27,16 @@ pub fn calculate_pool_tokens_for_withdraw(
     amount: u64,
     total_tokens: u64,
     total_redeemable_token_supply: u64,
-) -> u64 {
+) -> Option<u64> {
     let amount = Decimal::from_u64(amount);
     let total_tokens = Decimal::from_u64(total_tokens);
     let total_redeemable_token_supply = Decimal::from_u64(total_redeemable_token_supply);
 
     amount
         .mul(total_tokens)
-        .div(total_redeemable_token_supply)
-        .to_u64()
+        .and_then(|amount| amount.div(total_redeemable_token_supply))
+        .and_then(|amount| amount.to_u64())
+        .ok()
 }
 
 #[cfg(test)]
@@ -54,7 +56,8 @@ mod tests {
         let expected = 1000u64;
 
         let result =
-            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens);
+            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens)
+                .unwrap();
         assert_eq!(
             expected, result,
             "redeemable (1000 * 100000000) / 100000000 = ({} * {} / {})",
@@ -70,7 +73,8 @@ mod tests {
         let expected = 98u64;
 
         let result =
-            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens);
+            calculate_pool_tokens_for_deposit(amount, total_token_vault, total_redeemable_tokens)
+                .unwrap();
         assert_eq!(
             expected, result,
             "redeemable (987 * 9999000) / 100000000 = ({} * {} / {})",
@@ -89,7 +93,8 @@ mod tests {
         let expected = 1000u64;
 
         let result =
-            calculate_pool_tokens_for_withdraw(amount, total_token_vault, total_redeemable_tokens);
+            calculate_pool_tokens_for_withdraw(amount, total_token_vault, total_redeemable_tokens)
+                .unwrap();
         assert_eq!(
             expected, result,
             "redeemable (1000 * 100000000) / 100000000 = ({} * {} / {})",
@@ -105,7 +110,8 @@ mod tests {
         let expected = 9870u64;
 
         let result =
-            calculate_pool_tokens_for_withdraw(amount, total_token_vault, total_redeemable_tokens);
+            calculate_pool_tokens_for_withdraw(amount, total_token_vault, total_redeemable_tokens)
+                .unwrap();
         assert_eq!(
             expected, result,
             "redeemable (987 * 100000000) / 9999000 = ({} * {} / {})",
@@ -141,11 +147,11 @@ mod tests {
                 total_token_vault,
                 total_redeemable_tokens,
             };
-            let deposit_result = calculate_pool_tokens_for_deposit(deposit_amount, total_token_vault, total_redeemable_tokens);
+            let deposit_result = calculate_pool_tokens_for_deposit(deposit_amount, total_token_vault, total_redeemable_tokens).unwrap();
             prop_assume!(deposit_result > 0);
             stake_pool.total_token_vault += deposit_amount;
             stake_pool.total_redeemable_tokens += deposit_result;
-            let withdraw_result = calculate_pool_tokens_for_withdraw(deposit_result, total_token_vault, total_redeemable_tokens);
+            let withdraw_result = calculate_pool_tokens_for_withdraw(deposit_result, total_token_vault, total_redeemable_tokens).unwrap();
             assert!(withdraw_result <= deposit_amount);
         }
     }
@@ -0,0 +1,12 @@
+//! Large uint types, used for intermediate [Decimal](crate::decimal::Decimal) arithmetic
+
+// required for clippy
+#![allow(clippy::assign_op_pattern)]
+#![allow(clippy::ptr_offset_with_cast)]
+#![allow(clippy::manual_range_contains)]
+
+use uint::construct_uint;
+
+construct_uint! {
+    pub struct U256(4);
+}
@@ -138,3 +138,21 @@ class Curve:
 
   def sim_delta_x_hmm(self, delta_y):
     return self.to_int_signed(self.delta_x_hmm(Decimal(delta_y) / Decimal(10**self.scale)))
+
+  def sim_ln(self, x):
+    """
+    ln(x) at the precision of the context, x is a decimal string
+    """
+    return self.to_int_signed(Decimal(x).ln())
+
+  def sim_exp(self, x):
+    """
+    e^x at the precision of the context, x is a decimal string
+    """
+    return self.to_int(Decimal(x).exp())
+
+  def sim_pow(self, x, y):
+    """
+    x^y at the precision of the context, x and y are decimal strings
+    """
+    return self.to_int(Decimal(x) ** Decimal(y))
@@ -109,6 +109,36 @@ impl Model {
         return result;
     }
 
+    pub fn sim_ln(&self, x: String) -> (u128, bool) {
+        let gil = Python::acquire_gil();
+        let result: (u128, bool) = self
+            .call1(gil.python(), "sim_ln", (x,))
+            .unwrap()
+            .extract(gil.python())
+            .unwrap();
+        return result;
+    }
+
+    pub fn sim_exp(&self, x: String) -> u128 {
+        let gil = Python::acquire_gil();
+        let result: u128 = self
+            .call1(gil.python(), "sim_exp", (x,))
+            .unwrap()
+            .extract(gil.python())
+            .unwrap();
+        return result;
+    }
+
+    pub fn sim_pow(&self, x: String, y: String) -> u128 {
+        let gil = Python::acquire_gil();
+        let result: u128 = self
+            .call1(gil.python(), "sim_pow", (x, y))
+            .unwrap()
+            .extract(gil.python())
+            .unwrap();
+        return result;
+    }
+
     fn call0(&self, py: Python, method_name: &str) -> Result<PyObject, PyErr> {
         let sim = PyModule::from_code(py, &self.py_src, FILE_NAME, MODULE_NAME).unwrap();
         let model = sim
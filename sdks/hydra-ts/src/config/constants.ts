@@ -2,3 +2,15 @@ export const TOKEN_VAULT_SEED = "token_vault_seed";
 export const POOL_STATE_SEED = "pool_state_seed";
 export const LP_TOKEN_VAULT_SEED = "lp_token_vault_seed";
 export const LP_TOKEN_MINT_SEED = "lp_token_mint_seed";
+export const PROTOCOL_FEE_VAULT_SEED = "protocol_fee_vault_seed";
+export const CL_POOL_STATE_SEED = "cl_pool_state_seed";
+export const CL_TOKEN_VAULT_SEED = "cl_token_vault_seed";
+export const CL_TICK_ARRAY_SEED = "cl_tick_array_seed";
+export const CL_POSITION_SEED = "cl_position_seed";
+export const POOL_OBSERVATIONS_SEED = "pool_observations_seed";
+export const POOL_FACTORY_SEED = "pool_factory_seed";
+export const PAIR_REGISTRY_SEED = "pair_registry_seed";
+// fee tier (swap fee in bps) of pools looked up without one, the 0.2% swap fee pools have used so far
+export const DEFAULT_FEE_TIER_BPS = 20;
+// ticks held by a tick array of a concentrated liquidity pool
+export const CL_TICK_ARRAY_SIZE = 16n;
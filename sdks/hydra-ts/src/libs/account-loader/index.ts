@@ -38,7 +38,7 @@ export type MintLoader = ReturnType<typeof Mint>;
 export function PDAMint(
   ctx: Ctx,
   programId: PublicKey,
-  seeds: (PublicKey | string)[]
+  seeds: (PublicKey | string | Buffer)[]
 ) {
   return PDA(ctx, programId, seeds, TokenMint.Parser);
 }
@@ -57,7 +57,7 @@ export type AssociatedTokenLoader = ReturnType<typeof AssociatedToken>;
 export function PDA<T>(
   ctx: Ctx,
   programId: PublicKey,
-  seeds: (PublicKey | string)[],
+  seeds: (PublicKey | string | Buffer)[],
   parser: Parser<T>
 ) {
   return withBump(
@@ -4,16 +4,43 @@ import {
   POOL_STATE_SEED,
   TOKEN_VAULT_SEED,
   LP_TOKEN_MINT_SEED,
+  PROTOCOL_FEE_VAULT_SEED,
+  CL_POOL_STATE_SEED,
+  CL_TOKEN_VAULT_SEED,
+  CL_TICK_ARRAY_SEED,
+  CL_POSITION_SEED,
+  POOL_OBSERVATIONS_SEED,
+  POOL_FACTORY_SEED,
+  PAIR_REGISTRY_SEED,
+  DEFAULT_FEE_TIER_BPS,
 } from "../config/constants";
-import { PoolState } from "./types";
+import {
+  ClPoolState,
+  ClPosition,
+  ClTickArray,
+  PairRegistry,
+  PoolFactory,
+  PoolObservations,
+  PoolState,
+} from "./types";
+import { u128ToSeed, u16ToSeed } from "../utils";
 import { Ctx } from "../types";
 import * as AccountLoader from "../libs/account-loader";
 import { inject } from "../utils/meta-utils";
 
 export const getAccountLoaders =
-  (ctx: Ctx) => async (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+  (ctx: Ctx) =>
+  async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    feeTierBps: number = DEFAULT_FEE_TIER_BPS
+  ) => {
     const accounts = inject(LOADERS, ctx);
-    const lpTokenMint = accounts.lpTokenMint(tokenXMint, tokenYMint);
+    const lpTokenMint = accounts.lpTokenMint(
+      tokenXMint,
+      tokenYMint,
+      feeTierBps
+    );
     const lpTokenMintKey = await lpTokenMint.key();
     const poolState = accounts.poolState(lpTokenMintKey);
     const tokenXVault = accounts.tokenXVault(tokenXMint, lpTokenMintKey);
@@ -26,6 +53,18 @@ export const getAccountLoaders =
     const userTokenY = accounts.userYToken(tokenYMint);
     const lpTokenAssociatedAccount =
       accounts.lpTokenAssociatedAccount(lpTokenMintKey);
+    const protocolFeeXVault = accounts.protocolFeeTokenVault(
+      tokenXMint,
+      lpTokenMintKey
+    );
+    const protocolFeeYVault = accounts.protocolFeeTokenVault(
+      tokenYMint,
+      lpTokenMintKey
+    );
+    const protocolFeeLpVault = accounts.protocolFeeLpVault(
+      await poolState.key(),
+      lpTokenMintKey
+    );
     return {
       poolState,
       tokenXVault,
@@ -35,6 +74,9 @@ export const getAccountLoaders =
       userTokenX,
       userTokenY,
       lpTokenAssociatedAccount,
+      protocolFeeXVault,
+      protocolFeeYVault,
+      protocolFeeLpVault,
     };
   };
 
@@ -49,6 +91,17 @@ export const poolState = (ctx: Ctx) => (lpTokenMint: PublicKey) => {
   return AccountLoader.PDA(ctx, programId, seeds, parser);
 };
 
+export const poolObservations = (ctx: Ctx) => (poolState: PublicKey) => {
+  const programId = ctx.programs.hydraLiquidityPools.programId;
+  const seeds = [POOL_OBSERVATIONS_SEED, poolState];
+  const parser = ctx.getParser<PoolObservations>(
+    ctx.programs.hydraLiquidityPools,
+    "PoolObservations"
+  );
+
+  return AccountLoader.PDA(ctx, programId, seeds, parser);
+};
+
 export const tokenXVault =
   (ctx: Ctx) => (tokenXMint: PublicKey, lpTokenMint: PublicKey) => {
     const programId = ctx.programs.hydraLiquidityPools.programId;
@@ -70,6 +123,20 @@ export const lpTokenVault =
     return AccountLoader.PDAToken(ctx, programId, seeds);
   };
 
+export const protocolFeeTokenVault =
+  (ctx: Ctx) => (tokenMint: PublicKey, lpTokenMint: PublicKey) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [PROTOCOL_FEE_VAULT_SEED, tokenMint, lpTokenMint];
+    return AccountLoader.PDAToken(ctx, programId, seeds);
+  };
+
+export const protocolFeeLpVault =
+  (ctx: Ctx) => (poolState: PublicKey, lpTokenMint: PublicKey) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [PROTOCOL_FEE_VAULT_SEED, poolState, lpTokenMint];
+    return AccountLoader.PDAToken(ctx, programId, seeds);
+  };
+
 export const userXToken = (ctx: Ctx) => (tokenXMint: PublicKey) => {
   return AccountLoader.AssociatedToken(ctx, tokenXMint);
 };
@@ -79,24 +146,132 @@ export const userYToken = (ctx: Ctx) => (tokenYMint: PublicKey) => {
 };
 
 export const lpTokenMint =
-  (ctx: Ctx) => (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+  (ctx: Ctx) =>
+  (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    feeTierBps: number = DEFAULT_FEE_TIER_BPS
+  ) => {
     const programId = ctx.programs.hydraLiquidityPools.programId;
-    const seeds = [LP_TOKEN_MINT_SEED, tokenXMint, tokenYMint];
+    const seeds = [
+      LP_TOKEN_MINT_SEED,
+      tokenXMint,
+      tokenYMint,
+      u16ToSeed(feeTierBps),
+    ];
     return AccountLoader.PDAMint(ctx, programId, seeds);
   };
 
+export const poolFactory = (ctx: Ctx) => () => {
+  const programId = ctx.programs.hydraLiquidityPools.programId;
+  const seeds = [POOL_FACTORY_SEED];
+  const parser = ctx.getParser<PoolFactory>(
+    ctx.programs.hydraLiquidityPools,
+    "PoolFactory"
+  );
+
+  return AccountLoader.PDA(ctx, programId, seeds, parser);
+};
+
+export const pairRegistry =
+  (ctx: Ctx) => (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [PAIR_REGISTRY_SEED, tokenXMint, tokenYMint];
+    const parser = ctx.getParser<PairRegistry>(
+      ctx.programs.hydraLiquidityPools,
+      "PairRegistry"
+    );
+
+    return AccountLoader.PDA(ctx, programId, seeds, parser);
+  };
+
 export const lpTokenAssociatedAccount =
   (ctx: Ctx) => (lpTokenMint: PublicKey) => {
     return AccountLoader.AssociatedToken(ctx, lpTokenMint);
   };
 
+export const getClAccountLoaders =
+  (ctx: Ctx) => async (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const accounts = inject(LOADERS, ctx);
+    const clPoolState = accounts.clPoolState(tokenXMint, tokenYMint);
+    const clPoolStateKey = await clPoolState.key();
+    const tokenXVault = accounts.clTokenVault(tokenXMint, clPoolStateKey);
+    const tokenYVault = accounts.clTokenVault(tokenYMint, clPoolStateKey);
+    const userTokenX = accounts.userXToken(tokenXMint);
+    const userTokenY = accounts.userYToken(tokenYMint);
+    return {
+      clPoolState,
+      tokenXVault,
+      tokenYVault,
+      userTokenX,
+      userTokenY,
+    };
+  };
+
+export const clPoolState =
+  (ctx: Ctx) => (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [CL_POOL_STATE_SEED, tokenXMint, tokenYMint];
+    const parser = ctx.getParser<ClPoolState>(
+      ctx.programs.hydraLiquidityPools,
+      "ClPoolState"
+    );
+
+    return AccountLoader.PDA(ctx, programId, seeds, parser);
+  };
+
+export const clTokenVault =
+  (ctx: Ctx) => (tokenMint: PublicKey, clPoolState: PublicKey) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [CL_TOKEN_VAULT_SEED, tokenMint, clPoolState];
+    return AccountLoader.PDAToken(ctx, programId, seeds);
+  };
+
+export const clTickArray =
+  (ctx: Ctx) => (clPoolState: PublicKey, startTick: bigint) => {
+    const programId = ctx.programs.hydraLiquidityPools.programId;
+    const seeds = [CL_TICK_ARRAY_SEED, clPoolState, u128ToSeed(startTick)];
+    const parser = ctx.getParser<ClTickArray>(
+      ctx.programs.hydraLiquidityPools,
+      "ClTickArray"
+    );
+
+    return AccountLoader.PDA(ctx, programId, seeds, parser);
+  };
+
+export const clPosition = (ctx: Ctx) => (positionMint: PublicKey) => {
+  const programId = ctx.programs.hydraLiquidityPools.programId;
+  const seeds = [CL_POSITION_SEED, positionMint];
+  const parser = ctx.getParser<ClPosition>(
+    ctx.programs.hydraLiquidityPools,
+    "ClPosition"
+  );
+
+  return AccountLoader.PDA(ctx, programId, seeds, parser);
+};
+
+export const clPositionTokenAccount =
+  (ctx: Ctx) => (positionMint: PublicKey) => {
+    return AccountLoader.AssociatedToken(ctx, positionMint);
+  };
+
 const LOADERS = {
   poolState,
+  poolObservations,
   tokenXVault,
   tokenYVault,
   lpTokenVault,
+  protocolFeeTokenVault,
+  protocolFeeLpVault,
   userXToken,
   userYToken,
   lpTokenMint,
   lpTokenAssociatedAccount,
+  poolFactory,
+  pairRegistry,
+  clPoolState,
+  clTokenVault,
+  clTickArray,
+  clPosition,
+  clPositionTokenAccount,
 };
@@ -102,6 +102,62 @@ export function swapYToXHmm(_: Ctx) {
   };
 }
 
+export function swapXToYHmmExactOut(_: Ctx) {
+  return async (
+    x0: bigint,
+    xScale: number,
+    y0: bigint,
+    yScale: number,
+    c: number,
+    i: bigint,
+    iScale: number,
+    feeNumer: bigint,
+    feedenom: bigint,
+    amountOut: bigint
+  ) => {
+    return hydraMath.swap_x_to_y_hmm_exact_out(
+      x0,
+      xScale,
+      y0,
+      yScale,
+      c,
+      i,
+      iScale,
+      feeNumer,
+      feedenom,
+      amountOut
+    );
+  };
+}
+
+export function swapYToXHmmExactOut(_: Ctx) {
+  return async (
+    x0: bigint,
+    xScale: number,
+    y0: bigint,
+    yScale: number,
+    c: number,
+    i: bigint,
+    iScale: number,
+    feeNumer: bigint,
+    feedenom: bigint,
+    amountOut: bigint
+  ) => {
+    return hydraMath.swap_y_to_x_hmm_exact_out(
+      x0,
+      xScale,
+      y0,
+      yScale,
+      c,
+      i,
+      iScale,
+      feeNumer,
+      feedenom,
+      amountOut
+    );
+  };
+}
+
 export function calculateSwap(_: Ctx) {
   return async (
     tokenXMint: AccountData<TokenMint>,
@@ -131,7 +187,16 @@ export function calculateSwap(_: Ctx) {
           : hydraMath.swap_y_to_x_hmm;
       console.log("swappy: selected direction " + direction);
 
-      const output = await swapper(...args, amount);
+      // owner trade fees are taken from the amount in before it is swapped
+      const { ownerTradeFeeNumerator, ownerTradeFeeDenominator } =
+        poolState.account.data.fees;
+      const ownerTradeFee =
+        BigInt(ownerTradeFeeDenominator.toString()) === 0n
+          ? 0n
+          : (amount * BigInt(ownerTradeFeeNumerator.toString())) /
+            BigInt(ownerTradeFeeDenominator.toString());
+
+      const output = await swapper(...args, amount - ownerTradeFee);
       console.log("swappy: calculateSwap OUT:", output);
 
       return output;
@@ -0,0 +1,363 @@
+import * as anchor from "@project-serum/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import * as SPLToken from "@solana/spl-token";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { toBN } from "../../utils";
+import { inject } from "../../utils/meta-utils";
+import { CL_TICK_ARRAY_SIZE } from "../../config/constants";
+import { PauseFlags } from "../types";
+
+// start tick of the tick array holding tick
+function tickArrayStartTick(tick: bigint, tickSpacing: bigint) {
+  const ticksPerArray = CL_TICK_ARRAY_SIZE * tickSpacing;
+  return (tick / ticksPerArray) * ticksPerArray;
+}
+
+function toAccountMetas(pubkeys: PublicKey[], isWritable: boolean) {
+  return pubkeys.map((pubkey) => ({ pubkey, isSigner: false, isWritable }));
+}
+
+/**
+ * Initialize a concentrated liquidity pool priced at the initializable tick at or below initialTick.
+ * The swap fee rate is feeRateNumerator / feeRateDenominator.
+ */
+export function clInitialize(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    initialTick: bigint,
+    tickSpacing: bigint,
+    compensationParameter: number,
+    feeRateNumerator: bigint,
+    feeRateDenominator: bigint,
+    // [product, price] for pyth, [aggregator] for switchboard or [mockOracle] for a mock oracle
+    ...oracleAccounts: PublicKey[]
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = await inject(accs, ctx).getClAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.methods
+      .clInitialize(
+        toBN(initialTick),
+        toBN(tickSpacing),
+        compensationParameter,
+        toBN(feeRateNumerator),
+        toBN(feeRateDenominator)
+      )
+      .accounts({
+        authority: ctx.provider.wallet.publicKey,
+        payer: ctx.provider.wallet.publicKey,
+        clPoolState: await accounts.clPoolState.key(),
+        tokenXMint,
+        tokenYMint,
+        tokenXVault: await accounts.tokenXVault.key(),
+        tokenYVault: await accounts.tokenYVault.key(),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts(toAccountMetas(oracleAccounts, false))
+      .rpc({ commitment: "confirmed" });
+  };
+}
+
+/**
+ * Create the tick array of a concentrated liquidity pool holding tick, returns its address
+ */
+export function clInitializeTickArray(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    tick: bigint
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const clPoolState = accounts.clPoolState(tokenXMint, tokenYMint);
+    const clPoolStateKey = await clPoolState.key();
+    const { tickSpacing } = await clPoolState.info();
+    const startTick = tickArrayStartTick(tick, BigInt(tickSpacing.toString()));
+    const tickArray = await accounts
+      .clTickArray(clPoolStateKey, startTick)
+      .key();
+
+    await program.methods
+      .clInitializeTickArray(toBN(startTick))
+      .accounts({
+        payer: ctx.provider.wallet.publicKey,
+        clPoolState: clPoolStateKey,
+        tickArray,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    return tickArray;
+  };
+}
+
+/**
+ * Addresses of the tick arrays of a pool holding the ticks, or all tick arrays of the pool when no ticks are given
+ */
+export function clTickArrays(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    ticks?: bigint[]
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const clPoolStateKey = await accounts
+      .clPoolState(tokenXMint, tokenYMint)
+      .key();
+
+    if (!ticks) {
+      const tickArrays = await program.account.clTickArray.all([
+        { memcmp: { offset: 8, bytes: clPoolStateKey.toBase58() } },
+      ]);
+      return tickArrays.map((tickArray) => tickArray.publicKey);
+    }
+
+    const { tickSpacing } = await accounts
+      .clPoolState(tokenXMint, tokenYMint)
+      .info();
+    const startTicks = new Set(
+      ticks.map((tick) =>
+        tickArrayStartTick(tick, BigInt(tickSpacing.toString()))
+      )
+    );
+    return Promise.all(
+      [...startTicks].map((startTick) =>
+        accounts.clTickArray(clPoolStateKey, startTick).key()
+      )
+    );
+  };
+}
+
+/**
+ * Open a position in the range [lowerTick, upperTick) of a concentrated liquidity pool.
+ * The position NFT is minted to the wallet, returns the mint of the position NFT.
+ */
+export function clOpenPosition(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    lowerTick: bigint,
+    upperTick: bigint
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const positionMint = Keypair.generate();
+
+    await program.methods
+      .clOpenPosition(toBN(lowerTick), toBN(upperTick))
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        clPoolState: await accounts.clPoolState(tokenXMint, tokenYMint).key(),
+        positionMint: positionMint.publicKey,
+        position: await accounts.clPosition(positionMint.publicKey).key(),
+        positionTokenAccount: await accounts
+          .clPositionTokenAccount(positionMint.publicKey)
+          .key(),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([positionMint])
+      .rpc();
+
+    return positionMint.publicKey;
+  };
+}
+
+// accounts shared by the instructions managing the position of a position NFT held by the wallet
+async function clPositionAccounts(
+  ctx: Ctx,
+  tokenXMint: PublicKey,
+  tokenYMint: PublicKey,
+  positionMint: PublicKey
+) {
+  const accounts = await inject(accs, ctx).getClAccountLoaders(
+    tokenXMint,
+    tokenYMint
+  );
+  const position = inject(accs, ctx).clPosition(positionMint);
+  const { lowerTick, upperTick } = await position.info();
+  const tickArrays = await clTickArrays(ctx)(tokenXMint, tokenYMint, [
+    BigInt(lowerTick.toString()),
+    BigInt(upperTick.toString()),
+  ]);
+
+  return {
+    accounts: {
+      user: ctx.provider.wallet.publicKey,
+      clPoolState: await accounts.clPoolState.key(),
+      position: await position.key(),
+      positionTokenAccount: await inject(accs, ctx)
+        .clPositionTokenAccount(positionMint)
+        .key(),
+      tokenXMint,
+      tokenYMint,
+      userTokenX: await accounts.userTokenX.key(),
+      userTokenY: await accounts.userTokenY.key(),
+      tokenXVault: await accounts.tokenXVault.key(),
+      tokenYVault: await accounts.tokenYVault.key(),
+      tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+    },
+    tickArrays,
+  };
+}
+
+/**
+ * Deposit up to the max amounts into the position of a position NFT held by the wallet.
+ * Uncollected fees of the position are offset from the amounts debited.
+ */
+export function clDeposit(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    positionMint: PublicKey,
+    tokenXMaxAmount: bigint,
+    tokenYMaxAmount: bigint
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { accounts, tickArrays } = await clPositionAccounts(
+      ctx,
+      tokenXMint,
+      tokenYMint,
+      positionMint
+    );
+
+    await program.methods
+      .clDeposit(toBN(tokenXMaxAmount), toBN(tokenYMaxAmount))
+      .accounts(accounts)
+      .remainingAccounts(toAccountMetas(tickArrays, true))
+      .rpc();
+  };
+}
+
+/**
+ * Withdraw liquidity (at a compute scale of 12) from the position of a position NFT held by the wallet,
+ * along with its uncollected fees
+ */
+export function clWithdraw(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    positionMint: PublicKey,
+    liquidity: bigint,
+    tokenXMinAmount: bigint = 0n,
+    tokenYMinAmount: bigint = 0n
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { accounts, tickArrays } = await clPositionAccounts(
+      ctx,
+      tokenXMint,
+      tokenYMint,
+      positionMint
+    );
+
+    await program.methods
+      .clWithdraw(toBN(liquidity), toBN(tokenXMinAmount), toBN(tokenYMinAmount))
+      .accounts(accounts)
+      .remainingAccounts(toAccountMetas(tickArrays, true))
+      .rpc();
+  };
+}
+
+/**
+ * Collect the uncollected fees of the position of a position NFT held by the wallet,
+ * the liquidity of the position stays in its range
+ */
+export function clCollectFees(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    positionMint: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { accounts, tickArrays } = await clPositionAccounts(
+      ctx,
+      tokenXMint,
+      tokenYMint,
+      positionMint
+    );
+
+    await program.methods
+      .clCollectFees()
+      .accounts(accounts)
+      .remainingAccounts(toAccountMetas(tickArrays, true))
+      .rpc();
+  };
+}
+
+/**
+ * Swap up to amountIn of the token of userFromToken across the ticks of a concentrated liquidity pool.
+ * The swap is partially filled when the pool runs out of liquidity.
+ */
+export function clSwap(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    userFromToken: PublicKey,
+    userToToken: PublicKey,
+    amountIn: bigint,
+    minimumAmountOut: bigint,
+    oraclePrice?: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = await inject(accs, ctx).getClAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+    const tickArrays = await clTickArrays(ctx)(tokenXMint, tokenYMint);
+
+    await program.methods
+      .clSwap(toBN(amountIn), toBN(minimumAmountOut))
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        clPoolState: await accounts.clPoolState.key(),
+        tokenXMint,
+        tokenYMint,
+        userFromToken,
+        userToToken,
+        tokenXVault: await accounts.tokenXVault.key(),
+        tokenYVault: await accounts.tokenYVault.key(),
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        ...toAccountMetas(oraclePrice ? [oraclePrice] : [], false),
+        ...toAccountMetas(tickArrays, true),
+      ])
+      .rpc();
+  };
+}
+
+/**
+ * Pause or resume swaps, deposits and withdrawals of a concentrated liquidity pool,
+ * signed by the pool authority
+ */
+export function clSetPause(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    pause: PauseFlags
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { clPoolState } = await inject(accs, ctx).getClAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.methods
+      .clSetPause(pause)
+      .accounts({
+        authority: ctx.provider.wallet.publicKey,
+        clPoolState: await clPoolState.key(),
+      })
+      .rpc();
+  };
+}
@@ -0,0 +1,102 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { toBN } from "../../utils";
+import { inject } from "../../utils/meta-utils";
+import * as SPLToken from "@solana/spl-token";
+
+/**
+ * Lend amountX and amountY from the pool vaults to the borrower token accounts, then invoke the program of
+ * borrowerInstruction with its accounts and data. It must repay the vaults plus the pool swap fee, rounded up,
+ * before returning or the whole flash loan fails.
+ */
+export function flashLoan(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    borrowerTokenX: PublicKey,
+    borrowerTokenY: PublicKey,
+    amountX: bigint,
+    amountY: bigint,
+    borrowerInstruction: TransactionInstruction
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState, tokenXVault, tokenYVault } = await inject(
+      accs,
+      ctx
+    ).getAccountLoaders(tokenXMint, tokenYMint);
+
+    await program.methods
+      .flashLoan(toBN(amountX), toBN(amountY), borrowerInstruction.data)
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        tokenXMint,
+        tokenYMint,
+        poolState: await poolState.key(),
+        tokenXVault: await tokenXVault.key(),
+        tokenYVault: await tokenYVault.key(),
+        borrowerTokenX,
+        borrowerTokenY,
+        borrowerProgram: borrowerInstruction.programId,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      })
+      // remaining accounts: the accounts of the borrower instruction
+      .remainingAccounts(borrowerInstruction.keys)
+      .rpc();
+  };
+}
+
+
+/**
+ * Send amountOut from the pool vault to borrowerTokenOut, whose mint is the token swapped out, then invoke the
+ * program of borrowerInstruction with its accounts and data. It must repay the amount in of an exact out swap of
+ * amountOut, owner trade fees included, in the other token before returning or the whole flash swap fails.
+ */
+export function flashSwap(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    borrowerTokenOut: PublicKey,
+    amountOut: bigint,
+    maximumAmountIn: bigint,
+    borrowerInstruction: TransactionInstruction,
+    oraclePrice?: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const {
+      poolState,
+      tokenXVault,
+      tokenYVault,
+      protocolFeeXVault,
+      protocolFeeYVault,
+    } = await inject(accs, ctx).getAccountLoaders(tokenXMint, tokenYMint);
+
+    await program.methods
+      .flashSwap(
+        toBN(amountOut),
+        toBN(maximumAmountIn),
+        borrowerInstruction.data
+      )
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        tokenXMint,
+        tokenYMint,
+        poolState: await poolState.key(),
+        tokenXVault: await tokenXVault.key(),
+        tokenYVault: await tokenYVault.key(),
+        protocolFeeXVault: await protocolFeeXVault.key(),
+        protocolFeeYVault: await protocolFeeYVault.key(),
+        borrowerTokenOut,
+        borrowerProgram: borrowerInstruction.programId,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      })
+      // remaining accounts: [oraclePrice?, ...the accounts of the borrower instruction]
+      .remainingAccounts([
+        ...(oraclePrice
+          ? [{ pubkey: oraclePrice, isSigner: false, isWritable: false }]
+          : []),
+        ...borrowerInstruction.keys,
+      ])
+      .rpc();
+  };
+}
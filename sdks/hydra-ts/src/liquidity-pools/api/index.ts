@@ -1,5 +1,22 @@
 export * from "./initialize";
+export * from "./poolFactory";
 export * from "./addLiquidity";
 export * from "./removeLiquidity";
 export * from "./swap";
+export * from "./swapExactOut";
+export * from "./quoteSwap";
+export * from "./flashLoan";
+export * from "./routeSwap";
 export * from "./calculateSwap";
+export * from "./setFees";
+export * from "./setFeeMode";
+export * from "./setCompensationParameter";
+export * from "./setOracle";
+export * from "./setOracleGuards";
+export * from "./setPause";
+export * from "./setCircuitBreaker";
+export * from "./mockOracle";
+export * from "./transferAuthority";
+export * from "./withdrawProtocolFees";
+export * from "./clPool";
+export * from "./observations";
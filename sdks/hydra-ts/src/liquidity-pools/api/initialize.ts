@@ -9,7 +9,7 @@ import { toBN } from "../../utils";
 
 type AnchorPoolFees = { [K in keyof PoolFees]: anchor.BN };
 
-function toAnchorPoolFees(fees: PoolFees): AnchorPoolFees {
+export function toAnchorPoolFees(fees: PoolFees): AnchorPoolFees {
   return {
     swapFeeNumerator: toBN(fees.swapFeeNumerator),
     swapFeeDenominator: toBN(fees.swapFeeDenominator),
@@ -22,6 +22,16 @@ function toAnchorPoolFees(fees: PoolFees): AnchorPoolFees {
   };
 }
 
+/**
+ * Fee tier of a pool, its swap fee in bps
+ */
+function feeTierOf(fees: PoolFees): number {
+  return Number((fees.swapFeeNumerator * 10_000n) / fees.swapFeeDenominator);
+}
+
+/**
+ * Initialize a pool in the fee tier of its swap fee, the tier needs to be offered by the pool factory
+ */
 export function initialize(ctx: Ctx) {
   return async (
     tokenXMint: PublicKey,
@@ -31,13 +41,16 @@ export function initialize(ctx: Ctx) {
     // CPI calls
     poolFees: PoolFees,
     compensationParameter: number = 0,
-    pythProduct?: PublicKey,
-    pythPrice?: PublicKey
+    // [product, price] for pyth, [aggregator] for switchboard or [mockOracle] for a mock oracle
+    ...oracleAccounts: PublicKey[]
   ) => {
     const program = ctx.programs.hydraLiquidityPools;
-    const accounts = await inject(accs, ctx).getAccountLoaders(
+    const feeTierBps = feeTierOf(poolFees);
+    const loaders = inject(accs, ctx);
+    const accounts = await loaders.getAccountLoaders(
       tokenXMint,
-      tokenYMint
+      tokenYMint,
+      feeTierBps
     );
     const tokenXVaultBump = await accounts.tokenXVault.bump();
     const tokenYVaultBump = await accounts.tokenYVault.bump();
@@ -53,7 +66,8 @@ export function initialize(ctx: Ctx) {
         lpTokenVaultBump,
         lpTokenMintBump,
         compensationParameter,
-        toAnchorPoolFees(poolFees)
+        toAnchorPoolFees(poolFees),
+        feeTierBps
       )
       .accounts({
         authority: program.provider.wallet.publicKey,
@@ -62,21 +76,26 @@ export function initialize(ctx: Ctx) {
         tokenXMint,
         tokenYMint,
         lpTokenMint: await accounts.lpTokenMint.key(),
+        factory: await loaders.poolFactory().key(),
+        pairRegistry: await loaders.pairRegistry(tokenXMint, tokenYMint).key(),
         tokenXVault: await accounts.tokenXVault.key(),
         tokenYVault: await accounts.tokenYVault.key(),
         lpTokenVault: await accounts.lpTokenVault.key(),
+        protocolFeeXVault: await accounts.protocolFeeXVault.key(),
+        protocolFeeYVault: await accounts.protocolFeeYVault.key(),
+        protocolFeeLpVault: await accounts.protocolFeeLpVault.key(),
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       });
 
-    const instruction =
-      pythProduct && pythPrice
-        ? initializeBase.remainingAccounts([
-            { pubkey: pythProduct, isSigner: false, isWritable: false },
-            { pubkey: pythPrice, isSigner: false, isWritable: false },
-          ])
-        : initializeBase;
+    const instruction = initializeBase.remainingAccounts(
+      oracleAccounts.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
 
     await instruction.rpc({ commitment: "confirmed" });
   };
@@ -0,0 +1,45 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import { toBN } from "../../utils";
+
+/**
+ * Create a mock price account that can price a pool on a local validator, priced as price * 10^expo
+ */
+export function initializeMockOracle(ctx: Ctx) {
+  return async (
+    mockOracle: Keypair,
+    price: bigint,
+    expo: number,
+    conf: bigint
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+
+    await program.rpc.initializeMockOracle(toBN(price), expo, toBN(conf), {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        payer: ctx.provider.wallet.publicKey,
+        mockOracle: mockOracle.publicKey,
+        systemProgram: SystemProgram.programId,
+      },
+      signers: [mockOracle],
+    });
+
+    return mockOracle.publicKey;
+  };
+}
+
+/**
+ * Publish a new price on a mock price account. A zero price has the oracle report no live price.
+ */
+export function setMockOraclePrice(ctx: Ctx) {
+  return async (mockOracle: PublicKey, price: bigint, conf: bigint) => {
+    const program = ctx.programs.hydraLiquidityPools;
+
+    await program.rpc.setMockOraclePrice(toBN(price), toBN(conf), {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        mockOracle,
+      },
+    });
+  };
+}
@@ -0,0 +1,56 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+
+/**
+ * Create the twap observation ring buffer of a pool, returns its address
+ */
+export function initializeObservations(ctx: Ctx) {
+  return async (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const { poolState } = await accounts.getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+    const poolStateKey = await poolState.key();
+    const observations = await accounts.poolObservations(poolStateKey).key();
+
+    await program.rpc.initializeObservations({
+      accounts: {
+        payer: ctx.provider.wallet.publicKey,
+        poolState: poolStateKey,
+        observations,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    return observations;
+  };
+}
+
+/**
+ * Record the cumulative twap prices of a pool in its observation ring buffer, at most once per slot
+ */
+export function recordObservation(ctx: Ctx) {
+  return async (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const { poolState, tokenXVault, tokenYVault } =
+      await accounts.getAccountLoaders(tokenXMint, tokenYMint);
+    const poolStateKey = await poolState.key();
+
+    await program.rpc.recordObservation({
+      accounts: {
+        poolState: poolStateKey,
+        tokenXMint,
+        tokenYMint,
+        tokenXVault: await tokenXVault.key(),
+        tokenYVault: await tokenYVault.key(),
+        observations: await accounts.poolObservations(poolStateKey).key(),
+      },
+    });
+  };
+}
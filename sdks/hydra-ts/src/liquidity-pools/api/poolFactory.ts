@@ -0,0 +1,72 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+import { RegisteredPool } from "../types";
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+/**
+ * Create the pool factory with the fee tiers (swap fees in bps) and compensation parameters new pools can use,
+ * signed by the upgrade authority of the program
+ */
+export function initializeFactory(ctx: Ctx) {
+  return async (feeTiers: number[], compensationParameters: number[]) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const factory = await inject(accs, ctx).poolFactory().key();
+    const [programData] = await PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+
+    await program.rpc.initializeFactory(feeTiers, compensationParameters, {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        payer: ctx.provider.wallet.publicKey,
+        factory,
+        program: program.programId,
+        programData,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    return factory;
+  };
+}
+
+/**
+ * Replace the fee tiers and compensation parameters offered by the pool factory
+ */
+export function setFactoryConfig(ctx: Ctx) {
+  return async (feeTiers: number[], compensationParameters: number[]) => {
+    const program = ctx.programs.hydraLiquidityPools;
+
+    await program.rpc.setFactoryConfig(feeTiers, compensationParameters, {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        factory: await inject(accs, ctx).poolFactory().key(),
+      },
+    });
+  };
+}
+
+/**
+ * Every pool created for a pair, one per fee tier
+ */
+export function pairPools(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey
+  ): Promise<RegisteredPool[]> => {
+    const registry = inject(accs, ctx).pairRegistry(tokenXMint, tokenYMint);
+    if (!(await registry.isInitialized())) {
+      return [];
+    }
+
+    const { poolCount, pools } = (await registry.info()).data;
+    return pools.slice(0, poolCount);
+  };
+}
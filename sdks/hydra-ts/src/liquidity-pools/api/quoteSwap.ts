@@ -0,0 +1,81 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { toBN } from "../../utils";
+import { inject } from "../../utils/meta-utils";
+import { SwapDirection, SwapMode, SwapQuote } from "../types";
+
+/**
+ * Quote a swap of an exact amount in or out by simulating the quote_swap instruction against the current vaults
+ * and oracle, the result swap and swapExactOut would produce now apart from their slippage checks.
+ * Pass the host fee account the swap will be sent with to quote its host fee.
+ */
+export function quoteSwap(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    direction: SwapDirection,
+    mode: SwapMode,
+    amount: bigint,
+    oraclePrice?: PublicKey,
+    hostFeeAccount?: PublicKey
+  ): Promise<SwapQuote> => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState, tokenXVault, tokenYVault } = await inject(
+      accs,
+      ctx
+    ).getAccountLoaders(tokenXMint, tokenYMint);
+
+    const { raw } = await program.methods
+      .quoteSwap(direction, mode, toBN(amount))
+      .accounts({
+        tokenXMint,
+        tokenYMint,
+        poolState: await poolState.key(),
+        tokenXVault: await tokenXVault.key(),
+        tokenYVault: await tokenYVault.key(),
+      })
+      // remaining accounts: [oraclePrice?, hostFeeAccount?]
+      .remainingAccounts([
+        ...(oraclePrice
+          ? [{ pubkey: oraclePrice, isSigner: false, isWritable: false }]
+          : []),
+        ...(hostFeeAccount
+          ? [{ pubkey: hostFeeAccount, isSigner: false, isWritable: false }]
+          : []),
+      ])
+      .simulate();
+
+    // the runtime logs return data as "Program return: <program id> <base64 data>"
+    const prefix = `Program return: ${program.programId.toBase58()} `;
+    const returnLog = raw.find((log) => log.startsWith(prefix));
+    if (!returnLog) {
+      throw new Error("quote_swap returned no data");
+    }
+
+    const quote = program.coder.types.decode(
+      "SwapQuote",
+      Buffer.from(returnLog.slice(prefix.length), "base64")
+    );
+
+    return {
+      direction: quote.direction,
+      amountIn: BigInt(quote.amountIn.toString()),
+      amountOut: BigInt(quote.amountOut.toString()),
+      fee: BigInt(quote.fee.toString()),
+      ownerTradeFee: BigInt(quote.ownerTradeFee.toString()),
+      swapFeeNumerator: BigInt(quote.swapFeeNumerator.toString()),
+      swapFeeDenominator: BigInt(quote.swapFeeDenominator.toString()),
+      oraclePrice:
+        quote.oraclePrice === null
+          ? null
+          : BigInt(quote.oraclePrice.toString()),
+      xNew: BigInt(quote.xNew.toString()),
+      yNew: BigInt(quote.yNew.toString()),
+      priceImpactBps:
+        quote.priceImpactBps === null
+          ? null
+          : BigInt(quote.priceImpactBps.toString()),
+    };
+  };
+}
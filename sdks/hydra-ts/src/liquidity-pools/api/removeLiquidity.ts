@@ -10,7 +10,9 @@ export function removeLiquidity(ctx: Ctx) {
   return async (
     tokenXMint: PublicKey,
     tokenYMint: PublicKey,
-    lpTokensToBurn: bigint
+    lpTokensToBurn: bigint,
+    tokenXMinAmount: bigint = 0n,
+    tokenYMinAmount: bigint = 0n
   ) => {
     const program = ctx.programs.hydraLiquidityPools;
 
@@ -22,25 +24,32 @@ export function removeLiquidity(ctx: Ctx) {
       lpTokenAssociatedAccount,
       poolState,
       lpTokenMint,
+      protocolFeeLpVault,
     } = await inject(accs, ctx).getAccountLoaders(tokenXMint, tokenYMint);
 
-    await program.rpc.removeLiquidity(toBN(lpTokensToBurn), {
-      accounts: {
-        poolState: await poolState.key(),
-        lpTokenMint: await lpTokenMint.key(),
-        userTokenX: await userTokenX.key(),
-        userTokenY: await userTokenY.key(),
-        user: ctx.provider.wallet.publicKey,
-        tokenXVault: await tokenXVault.key(),
-        tokenYVault: await tokenYVault.key(),
-        tokenXMint,
-        tokenYMint,
-        userRedeemableLpTokens: await lpTokenAssociatedAccount.key(),
-        systemProgram: SystemProgram.programId,
-        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
-        associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
-        rent: web3.SYSVAR_RENT_PUBKEY,
-      },
-    });
+    await program.rpc.removeLiquidity(
+      toBN(lpTokensToBurn),
+      toBN(tokenXMinAmount),
+      toBN(tokenYMinAmount),
+      {
+        accounts: {
+          poolState: await poolState.key(),
+          lpTokenMint: await lpTokenMint.key(),
+          userTokenX: await userTokenX.key(),
+          userTokenY: await userTokenY.key(),
+          user: ctx.provider.wallet.publicKey,
+          tokenXVault: await tokenXVault.key(),
+          tokenYVault: await tokenYVault.key(),
+          tokenXMint,
+          tokenYMint,
+          userRedeemableLpTokens: await lpTokenAssociatedAccount.key(),
+          protocolFeeLpVault: await protocolFeeLpVault.key(),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+        },
+      }
+    );
   };
 }
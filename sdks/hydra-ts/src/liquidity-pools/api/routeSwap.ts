@@ -0,0 +1,74 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { toBN } from "../../utils";
+import { inject } from "../../utils/meta-utils";
+import * as SPLToken from "@solana/spl-token";
+
+export type RoutePool = {
+  tokenXMint: PublicKey;
+  tokenYMint: PublicKey;
+  // required for pools with an oracle
+  oraclePrice?: PublicKey;
+};
+
+/**
+ * Swap through an ordered list of pools, from the token of userFromToken to the token of userToToken.
+ * minimumAmountOut applies to the output of the last pool.
+ */
+export function routeSwap(ctx: Ctx) {
+  return async (
+    userFromToken: PublicKey,
+    userToToken: PublicKey,
+    pools: RoutePool[],
+    amountIn: bigint,
+    minimumAmountOut: bigint
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+
+    const remainingAccounts = [];
+    for (const pool of pools) {
+      const {
+        poolState,
+        tokenXVault,
+        tokenYVault,
+        protocolFeeXVault,
+        protocolFeeYVault,
+      } = await accounts.getAccountLoaders(pool.tokenXMint, pool.tokenYMint);
+
+      // [pool_state, token_x_mint, token_y_mint, token_x_vault, token_y_vault, protocol_fee_x_vault, protocol_fee_y_vault, oracle?]
+      remainingAccounts.push(
+        { pubkey: await poolState.key(), isSigner: false, isWritable: true },
+        { pubkey: pool.tokenXMint, isSigner: false, isWritable: false },
+        { pubkey: pool.tokenYMint, isSigner: false, isWritable: false },
+        { pubkey: await tokenXVault.key(), isSigner: false, isWritable: true },
+        { pubkey: await tokenYVault.key(), isSigner: false, isWritable: true },
+        {
+          pubkey: await protocolFeeXVault.key(),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: await protocolFeeYVault.key(),
+          isSigner: false,
+          isWritable: true,
+        },
+        ...(pool.oraclePrice
+          ? [{ pubkey: pool.oraclePrice, isSigner: false, isWritable: false }]
+          : [])
+      );
+    }
+
+    await program.methods
+      .routeSwap(toBN(amountIn), toBN(minimumAmountOut))
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        userFromToken,
+        userToToken,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+  };
+}
@@ -0,0 +1,30 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+
+export function setCircuitBreaker(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    maxOracleDeviationBps: number,
+    maxSlotPriceMoveBps: number
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setCircuitBreaker(
+      maxOracleDeviationBps,
+      maxSlotPriceMoveBps,
+      {
+        accounts: {
+          authority: ctx.provider.wallet.publicKey,
+          poolState: await poolState.key(),
+        },
+      }
+    );
+  };
+}
@@ -0,0 +1,26 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+
+export function setCompensationParameter(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    compensationParameter: number
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setCompensationParameter(compensationParameter, {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+        factory: await inject(accs, ctx).poolFactory().key(),
+      },
+    });
+  };
+}
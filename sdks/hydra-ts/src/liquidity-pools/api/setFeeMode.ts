@@ -0,0 +1,26 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+import { FeeMode } from "../types";
+
+export function setFeeMode(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    feeMode: FeeMode
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setFeeMode(feeMode, {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+      },
+    });
+  };
+}
@@ -0,0 +1,27 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+import { PoolFees } from "../types";
+import { toAnchorPoolFees } from "./initialize";
+
+export function setFees(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    poolFees: PoolFees
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setFees(toAnchorPoolFees(poolFees), {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+      },
+    });
+  };
+}
@@ -0,0 +1,51 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+
+/**
+ * Attach or replace the oracle of a pool.
+ * oracleAccounts are [product, price] for pyth, [aggregator] for switchboard or [mockOracle] for a mock oracle.
+ */
+export function setOracle(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    oracleAccounts: PublicKey[]
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setOracle({
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+      },
+      remainingAccounts: oracleAccounts.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: false,
+      })),
+    });
+  };
+}
+
+export function unsetOracle(ctx: Ctx) {
+  return async (tokenXMint: PublicKey, tokenYMint: PublicKey) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.unsetOracle({
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+      },
+    });
+  };
+}
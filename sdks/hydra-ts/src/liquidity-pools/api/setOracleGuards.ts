@@ -0,0 +1,34 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+import { toBN } from "../../utils";
+import { OracleGuardPolicy } from "../types";
+
+export function setOracleGuards(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    maxStalenessSlots: bigint,
+    maxConfidenceBps: number,
+    guardPolicy: OracleGuardPolicy
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.setOracleGuards(
+      toBN(maxStalenessSlots),
+      maxConfidenceBps,
+      guardPolicy,
+      {
+        accounts: {
+          authority: ctx.provider.wallet.publicKey,
+          poolState: await poolState.key(),
+        },
+      }
+    );
+  };
+}
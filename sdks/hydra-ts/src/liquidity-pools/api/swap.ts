@@ -16,12 +16,19 @@ export function swap(ctx: Ctx) {
     userToToken: PublicKey,
     amountIn: bigint,
     minimumAmountOut: bigint,
-    pythPrice?: PublicKey
+    oraclePrice?: PublicKey,
+    hostFeeAccount?: PublicKey
   ) => {
     const program = ctx.programs.hydraLiquidityPools;
     const accounts = inject(accs, ctx);
-    const { tokenXVault, tokenYVault, poolState, lpTokenMint } =
-      await accounts.getAccountLoaders(tokenXMint, tokenYMint);
+    const {
+      tokenXVault,
+      tokenYVault,
+      poolState,
+      lpTokenMint,
+      protocolFeeXVault,
+      protocolFeeYVault,
+    } = await accounts.getAccountLoaders(tokenXMint, tokenYMint);
 
     const info = await Token(ctx, userFromToken).info();
     const userToMint =
@@ -42,17 +49,28 @@ export function swap(ctx: Ctx) {
         userToMint,
         tokenXVault: await tokenXVault.key(),
         tokenYVault: await tokenYVault.key(),
+        protocolFeeXVault: await protocolFeeXVault.key(),
+        protocolFeeYVault: await protocolFeeYVault.key(),
         systemProgram: SystemProgram.programId,
         tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
         associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: web3.SYSVAR_RENT_PUBKEY,
       });
 
-    const instruction = pythPrice
-      ? swapBase.remainingAccounts([
-          { pubkey: pythPrice, isSigner: false, isWritable: false },
-        ])
-      : swapBase;
+    // remaining accounts: [oraclePrice?, hostFeeAccount?]
+    const remainingAccounts = [
+      ...(oraclePrice
+        ? [{ pubkey: oraclePrice, isSigner: false, isWritable: false }]
+        : []),
+      ...(hostFeeAccount
+        ? [{ pubkey: hostFeeAccount, isSigner: false, isWritable: true }]
+        : []),
+    ];
+
+    const instruction =
+      remainingAccounts.length > 0
+        ? swapBase.remainingAccounts(remainingAccounts)
+        : swapBase;
 
     await instruction.rpc();
   };
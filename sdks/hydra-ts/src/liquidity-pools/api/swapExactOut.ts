@@ -0,0 +1,77 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { toBN } from "../../utils";
+import { inject } from "../../utils/meta-utils";
+import { Token } from "../../libs/account-loader";
+import { web3 } from "@project-serum/anchor";
+import { SystemProgram } from "@solana/web3.js";
+import * as SPLToken from "@solana/spl-token";
+
+export function swapExactOut(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    userFromToken: PublicKey,
+    userToToken: PublicKey,
+    amountOut: bigint,
+    maximumAmountIn: bigint,
+    oraclePrice?: PublicKey,
+    hostFeeAccount?: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const accounts = inject(accs, ctx);
+    const {
+      tokenXVault,
+      tokenYVault,
+      poolState,
+      lpTokenMint,
+      protocolFeeXVault,
+      protocolFeeYVault,
+    } = await accounts.getAccountLoaders(tokenXMint, tokenYMint);
+
+    const info = await Token(ctx, userFromToken).info();
+    const userToMint =
+      info.data.mint.toString() === tokenXMint.toString()
+        ? tokenYMint
+        : tokenXMint;
+
+    const swapBase = program.methods
+      .swapExactOut(toBN(amountOut), toBN(maximumAmountIn))
+      .accounts({
+        user: ctx.provider.wallet.publicKey,
+        tokenXMint,
+        tokenYMint,
+        poolState: await poolState.key(),
+        lpTokenMint: await lpTokenMint.key(),
+        userFromToken,
+        userToToken,
+        userToMint,
+        tokenXVault: await tokenXVault.key(),
+        tokenYVault: await tokenYVault.key(),
+        protocolFeeXVault: await protocolFeeXVault.key(),
+        protocolFeeYVault: await protocolFeeYVault.key(),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: web3.SYSVAR_RENT_PUBKEY,
+      });
+
+    // remaining accounts: [oraclePrice?, hostFeeAccount?]
+    const remainingAccounts = [
+      ...(oraclePrice
+        ? [{ pubkey: oraclePrice, isSigner: false, isWritable: false }]
+        : []),
+      ...(hostFeeAccount
+        ? [{ pubkey: hostFeeAccount, isSigner: false, isWritable: true }]
+        : []),
+    ];
+
+    const instruction =
+      remainingAccounts.length > 0
+        ? swapBase.remainingAccounts(remainingAccounts)
+        : swapBase;
+
+    await instruction.rpc();
+  };
+}
@@ -0,0 +1,47 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+
+export function transferAuthority(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    newAuthority: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.transferAuthority(newAuthority, {
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+      },
+    });
+  };
+}
+
+export function acceptAuthority(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    pendingAuthority: Keypair
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const { poolState } = await inject(accs, ctx).getAccountLoaders(
+      tokenXMint,
+      tokenYMint
+    );
+
+    await program.rpc.acceptAuthority({
+      accounts: {
+        pendingAuthority: pendingAuthority.publicKey,
+        poolState: await poolState.key(),
+      },
+      signers: [pendingAuthority],
+    });
+  };
+}
@@ -0,0 +1,37 @@
+import { PublicKey } from "@solana/web3.js";
+import { Ctx } from "../../types";
+import * as accs from "../accounts";
+import { inject } from "../../utils/meta-utils";
+import * as SPLToken from "@solana/spl-token";
+
+export function withdrawProtocolFees(ctx: Ctx) {
+  return async (
+    tokenXMint: PublicKey,
+    tokenYMint: PublicKey,
+    tokenXTo: PublicKey,
+    tokenYTo: PublicKey,
+    lpTokenTo: PublicKey
+  ) => {
+    const program = ctx.programs.hydraLiquidityPools;
+    const {
+      poolState,
+      protocolFeeXVault,
+      protocolFeeYVault,
+      protocolFeeLpVault,
+    } = await inject(accs, ctx).getAccountLoaders(tokenXMint, tokenYMint);
+
+    await program.rpc.withdrawProtocolFees({
+      accounts: {
+        authority: ctx.provider.wallet.publicKey,
+        poolState: await poolState.key(),
+        protocolFeeXVault: await protocolFeeXVault.key(),
+        protocolFeeYVault: await protocolFeeYVault.key(),
+        protocolFeeLpVault: await protocolFeeLpVault.key(),
+        tokenXTo,
+        tokenYTo,
+        lpTokenTo,
+        tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      },
+    });
+  };
+}
@@ -13,8 +13,114 @@ export type PoolState = {
   lpTokenVaultBump: number;
   compensationParameter: number; // Range from (0 - 200) / 100 = c. With only 025 increments
   fees: PoolFees;
+  oracle: OracleSettings | null;
+  pendingAuthority: PublicKey;
+  feeMode: FeeMode;
+  volAdjFee: VolAdjFeeState;
+  priceCumulatives: PriceCumulatives;
+  pause: PauseFlags;
+  circuitBreaker: CircuitBreaker;
+  feeTierBps: number; // factory fee tier seeding the lp token mint
 };
 
+export type PoolFactory = {
+  authority: PublicKey;
+  bump: number;
+  feeTierCount: number;
+  feeTiers: number[]; // swap fees in bps, only the first feeTierCount are offered
+  compensationParameterCount: number;
+  compensationParameters: number[];
+  poolCount: bigint;
+};
+
+export type RegisteredPool = {
+  feeTierBps: number;
+  poolState: PublicKey;
+};
+
+export type PairRegistry = {
+  tokenXMint: PublicKey;
+  tokenYMint: PublicKey;
+  poolCount: number;
+  pools: RegisteredPool[];
+};
+
+export type PauseFlags = {
+  swaps: boolean;
+  deposits: boolean;
+  withdrawals: boolean;
+};
+
+// A zero limit disables its check, slotStartPrice is a u128 y per x price at a compute scale of 12
+export type CircuitBreaker = {
+  maxOracleDeviationBps: number;
+  maxSlotPriceMoveBps: number;
+  slot: bigint;
+  slotStartPrice: bigint;
+};
+
+// Prices are u128 values at a compute scale of 12, summed over the slots and seconds they held for
+export type PriceCumulatives = {
+  slot: bigint;
+  timestamp: bigint;
+  yPerXSlots: bigint;
+  xPerYSlots: bigint;
+  yPerXSeconds: bigint;
+  xPerYSeconds: bigint;
+};
+
+export type PoolObservations = {
+  poolState: PublicKey;
+  index: number;
+  count: number;
+  observations: PriceCumulatives[];
+};
+
+export type SwapDirection = { xToY: {} } | { yToX: {} };
+
+export type SwapMode = { exactIn: {} } | { exactOut: {} };
+
+export type SwapQuote = {
+  direction: SwapDirection;
+  amountIn: bigint; // including the swap and owner trade fees
+  amountOut: bigint;
+  fee: bigint; // swap fee paid to liquidity providers
+  ownerTradeFee: bigint;
+  swapFeeNumerator: bigint;
+  swapFeeDenominator: bigint;
+  oraclePrice: bigint | null; // null for a constant product swap
+  xNew: bigint;
+  yNew: bigint;
+  priceImpactBps: bigint | null; // null for an empty pool
+};
+
+export type FeeMode = { percent: {} } | { volatilityAdjusted: {} };
+
+export type VolAdjFeeState = {
+  lastUpdate: bigint;
+  lastPrice: bigint;
+  lastEwma: bigint;
+};
+
+export type OracleProvider =
+  | { pyth: {} }
+  | { switchboard: {} }
+  | { mock: {} };
+
+export type OracleSettings = {
+  provider: OracleProvider;
+  productAccount: PublicKey;
+  priceAccount: PublicKey;
+  lastKnownPrice: bigint;
+  lastKnownPriceSlot: bigint;
+  priceExponent: number;
+  maxStalenessSlots: bigint;
+  maxConfidenceBps: number;
+  guardPolicy: OracleGuardPolicy;
+};
+
+export type OracleGuardPolicy = { fallbackToCpmm: {} } | { rejectSwap: {} };
+
 export type PoolFees = {
   swapFeeNumerator: bigint;
   swapFeeDenominator: bigint;
@@ -25,3 +131,61 @@ export type PoolFees = {
   hostFeeNumerator: bigint;
   hostFeeDenominator: bigint;
 };
+
+// Decimal amounts of concentrated liquidity pools are u128 values at a compute scale of 12
+export type ClFeeGrowth = {
+  feeX: bigint;
+  feeY: bigint;
+  adjX: bigint;
+  adjY: bigint;
+};
+
+export type ClPoolState = {
+  authority: PublicKey;
+  tokenXVault: PublicKey;
+  tokenYVault: PublicKey;
+  tokenXMint: PublicKey;
+  tokenYMint: PublicKey;
+  poolStateBump: number;
+  tokenXVaultBump: number;
+  tokenYVaultBump: number;
+  tickSpacing: bigint;
+  compensationParameter: number;
+  feeRateNumerator: bigint;
+  feeRateDenominator: bigint;
+  oracle: OracleSettings | null;
+  tickArrayCount: number;
+  liq: bigint;
+  rp: bigint;
+  tick: bigint;
+  feeGrowth: ClFeeGrowth;
+  x: bigint;
+  y: bigint;
+  xAdj: bigint;
+  yAdj: bigint;
+  xFee: bigint;
+  yFee: bigint;
+};
+
+export type ClTick = {
+  initialized: boolean;
+  liqNet: bigint;
+  liqGross: bigint;
+  feeGrowthOutside: ClFeeGrowth;
+};
+
+export type ClTickArray = {
+  clPoolState: PublicKey;
+  startTick: bigint;
+  ticks: ClTick[];
+};
+
+export type ClPosition = {
+  clPoolState: PublicKey;
+  positionMint: PublicKey;
+  lowerTick: bigint;
+  upperTick: bigint;
+  liq: bigint;
+  feeGrowthInside: ClFeeGrowth;
+  bump: number;
+};
@@ -24,11 +24,12 @@ const SPL_ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: PublicKey = new PublicKey(
  */
 export async function getPDA(
   programId: PublicKey,
-  seeds: (PublicKey | string)[]
+  seeds: (PublicKey | string | Buffer)[]
 ) {
   const [pubkey, bump] = await anchor.web3.PublicKey.findProgramAddress(
     seeds.map((seed) => {
       if (typeof seed === "string") return anchor.utils.bytes.utf8.encode(seed);
+      if (Buffer.isBuffer(seed)) return seed;
       return seed.toBuffer();
     }),
     programId
@@ -36,6 +37,16 @@ export async function getPDA(
   return [pubkey, bump] as [typeof pubkey, typeof bump];
 }
 
+/** Little endian bytes of a u128, as used in program seeds */
+export function u128ToSeed(amount: bigint): Buffer {
+  return toBN(amount).toArrayLike(Buffer, "le", 16);
+}
+
+/** Little endian bytes of a u16, as used in program seeds */
+export function u16ToSeed(value: number): Buffer {
+  return new BN(value).toArrayLike(Buffer, "le", 2);
+}
+
 export function toBigInt(amount: BN): BigInt {
   return BigInt(amount.toString());
 }
@@ -1,20 +1,344 @@
 import * as anchor from "@project-serum/anchor";
-import { Program } from "@project-serum/anchor";
 import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as SPLToken from "@solana/spl-token";
+import { web3 } from "@project-serum/anchor";
 import * as hydrafarming from "types-ts/codegen/types/hydra_farming";
+import { BTCD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK, AccountLoader } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+import { toBN } from "../../sdks/hydra-ts/src/utils";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
 
 describe("hydra-farming", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.Provider.env());
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
 
   const hydraFarming = new anchor.web3.PublicKey(
     config.localnet.programIds.hydraFarming
   );
   const program = new anchor.Program(hydrafarming.IDL, hydraFarming);
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.rpc.initialize({});
-    console.log("Your transaction signature", tx);
+  let sdk: HydraSDK;
+
+  let btcdMint: PublicKey;
+  let usddMint: PublicKey;
+  let rewardMint: PublicKey;
+  let rewardAccount: PublicKey;
+
+  let poolState: PublicKey;
+  let lpTokenMint: PublicKey;
+  let userLpTokens: PublicKey;
+
+  let farmState: PublicKey;
+  let lpTokenVault: PublicKey;
+  let rewardVault: PublicKey;
+  let userState: PublicKey;
+  let userRewardTokens: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 0n,
+    ownerTradeFeeDenominator: 0n,
+    ownerWithdrawFeeNumerator: 0n,
+    ownerWithdrawFeeDenominator: 0n,
+    hostFeeNumerator: 0n,
+    hostFeeDenominator: 0n,
+  };
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    // Keys will be ordered based on base58 encoding
+    const [btcdMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [btcdMint] = await sdk.common.createMintAndAssociatedVault(
+      btcdMintPair,
+      BTCD_MINT_AMOUNT
+    );
+    [usddMint] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+    [rewardMint, rewardAccount] = await sdk.common.createMintAndAssociatedVault(
+      Keypair.generate(),
+      USDD_MINT_AMOUNT
+    );
+
+    // farm lp tokens from a funded liquidity pool
+    await sdk.liquidityPools.initialize(btcdMint, usddMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      btcdMint,
+      usddMint,
+      6_000_000n,
+      255_575_287_200n,
+      0n
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    poolState = await accounts.poolState.key();
+    lpTokenMint = await accounts.lpTokenMint.key();
+    userLpTokens = await accounts.lpTokenAssociatedAccount.key();
+
+    [farmState] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("farm_state_seed"),
+        lpTokenMint.toBuffer(),
+        rewardMint.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    [lpTokenVault] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("lp_token_vault_seed"),
+        farmState.toBuffer(),
+        lpTokenMint.toBuffer(),
+      ],
+      program.programId
+    );
+    [rewardVault] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("reward_vault_seed"),
+        farmState.toBuffer(),
+        rewardMint.toBuffer(),
+      ],
+      program.programId
+    );
+    [userState] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("user_state_seed"),
+        farmState.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    userRewardTokens = await SPLToken.getAssociatedTokenAddress(
+      rewardMint,
+      provider.wallet.publicKey
+    );
+  });
+
+  function initializeAccounts() {
+    return {
+      authority: provider.wallet.publicKey,
+      payer: provider.wallet.publicKey,
+      farmState,
+      poolState,
+      lpTokenMint,
+      rewardMint,
+      lpTokenVault,
+      rewardVault,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      rent: web3.SYSVAR_RENT_PUBKEY,
+    };
+  }
+
+  function stakingAccounts() {
+    return {
+      user: provider.wallet.publicKey,
+      farmState,
+      userState,
+      userLpTokens,
+      rewardMint,
+      userRewardTokens,
+      lpTokenVault,
+      rewardVault,
+      systemProgram: SystemProgram.programId,
+      associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+      rent: web3.SYSVAR_RENT_PUBKEY,
+    };
+  }
+
+  it("should not initialize a farm without a reward rate", async () => {
+    try {
+      await program.rpc.initialize(toBN(0n), {
+        accounts: initializeAccounts(),
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Reward rate must be greater than zero";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should not initialize a farm at the address of another authority", async () => {
+    const squatter = Keypair.generate();
+    try {
+      await program.rpc.initialize(toBN(1_000n), {
+        accounts: { ...initializeAccounts(), authority: squatter.publicKey },
+        signers: [squatter],
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "A seeds constraint was violated";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should initialize a farm", async () => {
+    await program.rpc.initialize(toBN(1_000n), {
+      accounts: initializeAccounts(),
+    });
+
+    const farmStateAccount: any = await program.account.farmState.fetch(
+      farmState
+    );
+    assert.equal(farmStateAccount.lpTokenMint.toString(), lpTokenMint.toString());
+    assert.equal(farmStateAccount.rewardMint.toString(), rewardMint.toString());
+    assert.equal(farmStateAccount.rewardRate.toString(), "1000");
+    assert.equal(farmStateAccount.totalStaked.toString(), "0");
+
+    // fund the farm with rewards
+    await sdk.common.transfer(rewardAccount, rewardVault, 1_000_000_000n);
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, rewardVault).balance(),
+      1_000_000_000n
+    );
+  });
+
+  it("should only let the authority change the reward rate", async () => {
+    const other = Keypair.generate();
+    try {
+      await program.rpc.setRewardRate(toBN(2_000n), {
+        accounts: { authority: other.publicKey, farmState },
+        signers: [other],
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "A has one constraint was violated";
+      assert(err.toString().includes(errMsg));
+    }
+
+    await program.rpc.setRewardRate(toBN(2_000n), {
+      accounts: { authority: provider.wallet.publicKey, farmState },
+    });
+    const farmStateAccount: any = await program.account.farmState.fetch(
+      farmState
+    );
+    assert.equal(farmStateAccount.rewardRate.toString(), "2000");
+  });
+
+  it("should only let the authority recover rewards", async () => {
+    const other = Keypair.generate();
+    const recoverAccounts = {
+      authority: provider.wallet.publicKey,
+      farmState,
+      rewardVault,
+      authorityRewardTokens: rewardAccount,
+      tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+    };
+    try {
+      await program.rpc.recoverRewards(toBN(1_000n), {
+        accounts: { ...recoverAccounts, authority: other.publicKey },
+        signers: [other],
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "A has one constraint was violated";
+      assert(err.toString().includes(errMsg));
+    }
+
+    await program.rpc.recoverRewards(toBN(1_000n), {
+      accounts: recoverAccounts,
+    });
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, rewardVault).balance(),
+      1_000_000_000n - 1_000n
+    );
+  });
+
+  it("should stake lp tokens", async () => {
+    const lpTokens = await AccountLoader.Token(sdk.ctx, userLpTokens).balance();
+
+    await program.rpc.stake(toBN(1_000_000n), {
+      accounts: stakingAccounts(),
+    });
+
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, userLpTokens).balance(),
+      lpTokens - 1_000_000n
+    );
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, lpTokenVault).balance(),
+      1_000_000n
+    );
+  });
+
+  it("should not unstake more lp tokens than staked", async () => {
+    try {
+      await program.rpc.unstake(toBN(1_000_001n), {
+        accounts: stakingAccounts(),
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Unstake amount exceeds the amount staked";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should not recover rewards owed to stakers", async () => {
+    // let some rewards accrue to the staker
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const rewards = await AccountLoader.Token(sdk.ctx, rewardVault).balance();
+    try {
+      await program.rpc.recoverRewards(toBN(rewards), {
+        accounts: {
+          authority: provider.wallet.publicKey,
+          farmState,
+          rewardVault,
+          authorityRewardTokens: rewardAccount,
+          tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
+        },
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Amount exceeds the rewards in the reward vault not owed to stakers";
+      assert(err.toString().includes(errMsg));
+    }
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, rewardVault).balance(),
+      rewards
+    );
+  });
+
+  it("should unstake lp tokens and claim rewards", async () => {
+    // let some rewards accrue
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    await program.rpc.unstake(toBN(1_000_000n), {
+      accounts: stakingAccounts(),
+    });
+
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, lpTokenVault).balance(),
+      0n
+    );
+    assert(
+      (await AccountLoader.Token(sdk.ctx, userRewardTokens).balance()) > 0n,
+      "no rewards were credited"
+    );
   });
 });
@@ -0,0 +1,407 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import * as SPLToken from "@solana/spl-token";
+import { ETHD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK, AccountLoader } from "hydra-ts";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
+
+describe("hydra-liquidity-pool-cl", () => {
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  let ethdMint: PublicKey;
+  let usddMint: PublicKey;
+  let ethdAccount: PublicKey;
+  let usddAccount: PublicKey;
+  let positionMint: PublicKey;
+
+  // ticks of the prices 2000, 1333 and 3000 rounded down to the tick spacing
+  const tickSpacing = 10n;
+  const initialTick = 76_012n;
+  const lowerTick = 71_950n;
+  const upperTick = 80_060n;
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    const [ethdMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [ethdMint, ethdAccount] = await sdk.common.createMintAndAssociatedVault(
+      ethdMintPair,
+      ETHD_MINT_AMOUNT
+    );
+    [usddMint, usddAccount] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+  });
+
+  it("should initialize a cl pool with hmm and 30bps swap fees", async () => {
+    await sdk.liquidityPools.clInitialize(
+      ethdMint,
+      usddMint,
+      initialTick,
+      tickSpacing,
+      150,
+      3n,
+      1000n
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getClAccountLoaders(
+      ethdMint,
+      usddMint
+    );
+    const clPoolState = await accounts.clPoolState.info();
+    assert.strictEqual(clPoolState.tick.toString(), "76010");
+    assert.strictEqual(clPoolState.liq.toString(), "0");
+    assert.strictEqual(clPoolState.tickArrayCount, 0);
+    assert.strictEqual(await accounts.tokenXVault.balance(), 0n);
+    assert.strictEqual(await accounts.tokenYVault.balance(), 0n);
+  });
+
+  it("should fail to open a position in a range that isn't initializable", async () => {
+    try {
+      await sdk.liquidityPools.clOpenPosition(
+        ethdMint,
+        usddMint,
+        lowerTick + 5n,
+        upperTick
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "Position ticks must be initializable ticks of the pool with the lower tick below the upper tick";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should open a position and mint its position nft to the wallet", async () => {
+    positionMint = await sdk.liquidityPools.clOpenPosition(
+      ethdMint,
+      usddMint,
+      lowerTick,
+      upperTick
+    );
+
+    const position = await sdk.liquidityPools.accounts
+      .clPosition(positionMint)
+      .info();
+    const mint = await SPLToken.getMint(provider.connection, positionMint);
+    assert.strictEqual(
+      position.positionMint.toString(),
+      positionMint.toString()
+    );
+    assert.strictEqual(position.lowerTick.toString(), lowerTick.toString());
+    assert.strictEqual(position.upperTick.toString(), upperTick.toString());
+    assert.strictEqual(position.liq.toString(), "0");
+    assert.strictEqual(mint.supply, 1n);
+    assert.strictEqual(mint.decimals, 0);
+    assert.strictEqual(mint.mintAuthority, null);
+    assert.strictEqual(
+      await sdk.liquidityPools.accounts
+        .clPositionTokenAccount(positionMint)
+        .balance(),
+      1n
+    );
+  });
+
+  it("should fail to deposit without the tick arrays of the position", async () => {
+    try {
+      await sdk.liquidityPools.clDeposit(
+        ethdMint,
+        usddMint,
+        positionMint,
+        2_000_000n,
+        4_000_000_000n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "The program expected this account to be already initialized";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should deposit 2 ethd and 4000 usdd into the range 1333 to 3000", async () => {
+    await sdk.liquidityPools.clInitializeTickArray(
+      ethdMint,
+      usddMint,
+      lowerTick
+    );
+    await sdk.liquidityPools.clInitializeTickArray(
+      ethdMint,
+      usddMint,
+      upperTick
+    );
+
+    const ethdBalance = await AccountLoader.Token(
+      sdk.ctx,
+      ethdAccount
+    ).balance();
+    const usddBalance = await AccountLoader.Token(
+      sdk.ctx,
+      usddAccount
+    ).balance();
+
+    await sdk.liquidityPools.clDeposit(
+      ethdMint,
+      usddMint,
+      positionMint,
+      2_000_000n,
+      4_000_000_000n
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getClAccountLoaders(
+      ethdMint,
+      usddMint
+    );
+    const clPoolState = await accounts.clPoolState.info();
+    const positionState = await sdk.liquidityPools.accounts
+      .clPosition(positionMint)
+      .info();
+    const xDeposited = await accounts.tokenXVault.balance();
+    const yDeposited = await accounts.tokenYVault.balance();
+
+    assert.strictEqual(clPoolState.tickArrayCount, 2);
+    // the price is in range so all of the liquidity of the position is in range
+    assert.strictEqual(
+      positionState.liq.toString(),
+      clPoolState.liq.toString()
+    );
+    assert(xDeposited > 0n && xDeposited <= 2_000_000n);
+    assert(yDeposited > 0n && yDeposited <= 4_000_000_000n);
+    // one of the amounts is fully used
+    assert(xDeposited == 2_000_000n || yDeposited == 4_000_000_000n);
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, ethdAccount).balance(),
+      ethdBalance - xDeposited
+    );
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, usddAccount).balance(),
+      usddBalance - yDeposited
+    );
+  });
+
+  it("should fail a swap due to slippage error", async () => {
+    try {
+      await sdk.liquidityPools.clSwap(
+        ethdMint,
+        usddMint,
+        ethdAccount,
+        usddAccount,
+        1_000_000n,
+        2_000_000_000n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Slippage Amount Exceeded";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fail a swap while swaps of the cl pool are paused", async () => {
+    await sdk.liquidityPools.clSetPause(ethdMint, usddMint, {
+      swaps: true,
+      deposits: false,
+      withdrawals: false,
+    });
+    try {
+      await sdk.liquidityPools.clSwap(
+        ethdMint,
+        usddMint,
+        ethdAccount,
+        usddAccount,
+        1_000_000n,
+        1n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Swaps are paused on the pool";
+      assert(err.toString().includes(errMsg));
+    }
+    await sdk.liquidityPools.clSetPause(ethdMint, usddMint, {
+      swaps: false,
+      deposits: false,
+      withdrawals: false,
+    });
+  });
+
+  it("should swap 1 ethd for usdd down the range", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getClAccountLoaders(
+      ethdMint,
+      usddMint
+    );
+    const usddBalance = await AccountLoader.Token(
+      sdk.ctx,
+      usddAccount
+    ).balance();
+    const yVault = await accounts.tokenYVault.balance();
+
+    await sdk.liquidityPools.clSwap(
+      ethdMint,
+      usddMint,
+      ethdAccount,
+      usddAccount,
+      1_000_000n,
+      1n
+    );
+
+    const clPoolState = await accounts.clPoolState.info();
+    const usddReceived =
+      (await AccountLoader.Token(sdk.ctx, usddAccount).balance()) -
+      usddBalance;
+
+    // sold below the starting price of 2000
+    assert(usddReceived > 1_000_000_000n && usddReceived < 2_000_000_000n);
+    assert.strictEqual(
+      await accounts.tokenYVault.balance(),
+      yVault - usddReceived
+    );
+    assert(BigInt(clPoolState.tick.toString()) < 76_010n);
+    assert(BigInt(clPoolState.feeGrowth.feeX.toString()) > 0n);
+  });
+
+  it("should collect the fees of the position without withdrawing its liquidity", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getClAccountLoaders(
+      ethdMint,
+      usddMint
+    );
+    const position = sdk.liquidityPools.accounts.clPosition(positionMint);
+    const { liq } = await position.info();
+    const { liq: poolLiq } = await accounts.clPoolState.info();
+    const ethdBalance = await AccountLoader.Token(
+      sdk.ctx,
+      ethdAccount
+    ).balance();
+    const xVault = await accounts.tokenXVault.balance();
+
+    await sdk.liquidityPools.clCollectFees(ethdMint, usddMint, positionMint);
+
+    const ethdCollected =
+      (await AccountLoader.Token(sdk.ctx, ethdAccount).balance()) -
+      ethdBalance;
+    const positionState = await position.info();
+    const clPoolState = await accounts.clPoolState.info();
+    // the swap fee on 1 ethd is at most 0.003 ethd
+    assert(ethdCollected > 0n && ethdCollected <= 3_000n);
+    assert.strictEqual(
+      await accounts.tokenXVault.balance(),
+      xVault - ethdCollected
+    );
+    assert.strictEqual(positionState.liq.toString(), liq.toString());
+    assert.strictEqual(clPoolState.liq.toString(), poolLiq.toString());
+    assert.strictEqual(
+      positionState.feeGrowthInside.feeX.toString(),
+      clPoolState.feeGrowth.feeX.toString()
+    );
+
+    // nothing is left to collect until more fees are earned
+    await sdk.liquidityPools.clCollectFees(ethdMint, usddMint, positionMint);
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, ethdAccount).balance(),
+      ethdBalance + ethdCollected
+    );
+  });
+
+  it("should only let the holder of the position nft withdraw", async () => {
+    const treasury = Keypair.generate();
+    const walletPositionAccount = await sdk.liquidityPools.accounts
+      .clPositionTokenAccount(positionMint)
+      .key();
+    const treasuryPositionAccount = await SPLToken.getAssociatedTokenAddress(
+      positionMint,
+      treasury.publicKey
+    );
+
+    await provider.send(
+      new Transaction().add(
+        SPLToken.createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          treasuryPositionAccount,
+          treasury.publicKey,
+          positionMint
+        ),
+        SPLToken.createTransferInstruction(
+          walletPositionAccount,
+          treasuryPositionAccount,
+          provider.wallet.publicKey,
+          1n
+        )
+      )
+    );
+
+    try {
+      await sdk.liquidityPools.clWithdraw(ethdMint, usddMint, positionMint, 1n);
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Token account doesn't hold the position token";
+      assert(err.toString().includes(errMsg));
+    }
+
+    // the treasury moves the position back to the wallet
+    await provider.send(
+      new Transaction().add(
+        SPLToken.createTransferInstruction(
+          treasuryPositionAccount,
+          walletPositionAccount,
+          treasury.publicKey,
+          1n
+        )
+      ),
+      [treasury]
+    );
+    assert.strictEqual(
+      await sdk.liquidityPools.accounts
+        .clPositionTokenAccount(positionMint)
+        .balance(),
+      1n
+    );
+  });
+
+  it("should withdraw the position with its fees", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getClAccountLoaders(
+      ethdMint,
+      usddMint
+    );
+    const position = sdk.liquidityPools.accounts.clPosition(positionMint);
+    const { liq } = await position.info();
+    const ethdBalance = await AccountLoader.Token(
+      sdk.ctx,
+      ethdAccount
+    ).balance();
+
+    await sdk.liquidityPools.clWithdraw(
+      ethdMint,
+      usddMint,
+      positionMint,
+      BigInt(liq.toString())
+    );
+
+    const clPoolState = await accounts.clPoolState.info();
+    const positionState = await position.info();
+    assert.strictEqual(positionState.liq.toString(), "0");
+    assert.strictEqual(clPoolState.liq.toString(), "0");
+    assert(
+      (await AccountLoader.Token(sdk.ctx, ethdAccount).balance()) > ethdBalance
+    );
+    // only rounding is left in the vaults
+    assert((await accounts.tokenXVault.balance()) < 10n);
+  });
+});
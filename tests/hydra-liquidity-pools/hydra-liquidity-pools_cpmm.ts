@@ -131,6 +131,48 @@ describe("hydra-liquidity-pool-cpmm", () => {
     assert.equal(poolStateAccount.tokenYVaultBump, tokenYVaultBump);
   });
 
+  it("should record the pool in the registry of its pair", async () => {
+    const pools = await sdk.liquidityPools.pairPools(btcdMint, usddMint);
+
+    assert.strictEqual(pools.length, 1);
+    assert.strictEqual(pools[0].feeTierBps, 20);
+    assert.strictEqual(pools[0].poolState.toString(), poolState.toString());
+  });
+
+  it("should initialize a pool of the same pair in another fee tier", async () => {
+    await sdk.liquidityPools.initialize(btcdMint, usddMint, {
+      ...poolFees,
+      swapFeeNumerator: 3n,
+      swapFeeDenominator: 1000n,
+    });
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint,
+      30
+    );
+    const poolStateAccount = (await accounts.poolState.info()).data;
+    assert.strictEqual(poolStateAccount.feeTierBps, 30);
+
+    const pools = await sdk.liquidityPools.pairPools(btcdMint, usddMint);
+    assert.deepStrictEqual(pools.map((pool) => pool.feeTierBps), [20, 30]);
+  });
+
+  it("should not initialize a pool in a fee tier the factory doesn't offer", async () => {
+    try {
+      await sdk.liquidityPools.initialize(btcdMint, usddMint, {
+        ...poolFees,
+        swapFeeNumerator: 1n,
+        swapFeeDenominator: 100n,
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg =
+        "Fee tier is not offered by the factory or doesn't match the swap fee";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
   it("should add-first-liquidity to the initialized empty pool", async () => {
     await sdk.liquidityPools.addLiquidity(
       btcdMint,
@@ -224,6 +266,22 @@ describe("hydra-liquidity-pool-cpmm", () => {
     }
   });
 
+  it("should not remove-liquidity due to exceeding slippage", async () => {
+    try {
+      await sdk.liquidityPools.removeLiquidity(
+        btcdMint,
+        usddMint,
+        3302203141188n,
+        16_000_000n, // more than the 15_999_999 credited
+        0n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Slippage Amount Exceeded";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
   it("should remove-liquidity first time", async () => {
     const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
       btcdMint,
@@ -380,6 +438,8 @@ describe("hydra-liquidity-pool-cpmm", () => {
           userToMint: usddMint,
           tokenXVault: await accounts.tokenXVault.key(),
           tokenYVault: await accounts.tokenYVault.key(),
+          protocolFeeXVault: await accounts.protocolFeeXVault.key(),
+          protocolFeeYVault: await accounts.protocolFeeYVault.key(),
           systemProgram: SystemProgram.programId,
           tokenProgram: SPLToken.TOKEN_PROGRAM_ID,
           associatedTokenProgram: SPLToken.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -430,4 +490,121 @@ describe("hydra-liquidity-pool-cpmm", () => {
 
     assert.strictEqual(await accounts.tokenYVault.balance(), 20n);
   });
+
+  it("should not set invalid fees", async () => {
+    try {
+      await sdk.liquidityPools.setFees(btcdMint, usddMint, {
+        ...poolFees,
+        swapFeeNumerator: 500n,
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Invalid Fee input";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should not move the pool out of its fee tier", async () => {
+    try {
+      await sdk.liquidityPools.setFees(btcdMint, usddMint, {
+        ...poolFees,
+        swapFeeNumerator: 3n,
+        swapFeeDenominator: 1000n,
+      });
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Fee tier is not offered by the factory";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should set fees", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+
+    await sdk.liquidityPools.setFees(btcdMint, usddMint, {
+      ...poolFees,
+      swapFeeNumerator: 2n,
+      swapFeeDenominator: 1000n,
+      ownerTradeFeeNumerator: 1n,
+      ownerTradeFeeDenominator: 1000n,
+    });
+
+    const poolStateAccount = (await accounts.poolState.info()).data;
+    assert.equal(poolStateAccount.fees.swapFeeNumerator.toString(), "2");
+    assert.equal(poolStateAccount.fees.swapFeeDenominator.toString(), "1000");
+    assert.equal(poolStateAccount.fees.ownerTradeFeeNumerator.toString(), "1");
+  });
+
+  it("should not set an invalid compensation parameter", async () => {
+    try {
+      await sdk.liquidityPools.setCompensationParameter(btcdMint, usddMint, 42);
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Invalid compensation parameter";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should set the compensation parameter", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+
+    await sdk.liquidityPools.setCompensationParameter(btcdMint, usddMint, 125);
+
+    const poolStateAccount = (await accounts.poolState.info()).data;
+    assert.equal(poolStateAccount.compensationParameter, 125);
+  });
+
+  it("should transfer the pool authority in two steps", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const newAuthority = Keypair.generate();
+
+    await sdk.liquidityPools.transferAuthority(
+      btcdMint,
+      usddMint,
+      newAuthority.publicKey
+    );
+
+    let poolStateAccount = (await accounts.poolState.info()).data;
+    assert.equal(
+      poolStateAccount.authority.toString(),
+      provider.wallet.publicKey.toString()
+    );
+    assert.equal(
+      poolStateAccount.pendingAuthority.toString(),
+      newAuthority.publicKey.toString()
+    );
+
+    try {
+      await sdk.liquidityPools.acceptAuthority(
+        btcdMint,
+        usddMint,
+        Keypair.generate()
+      );
+      assert.ok(false, "No error was thrown");
+    } catch (err: any) {
+      const errMsg = "Signer is not the pending authority of the pool";
+      assert(err.toString().includes(errMsg));
+    }
+
+    await sdk.liquidityPools.acceptAuthority(btcdMint, usddMint, newAuthority);
+
+    poolStateAccount = (await accounts.poolState.info()).data;
+    assert.equal(
+      poolStateAccount.authority.toString(),
+      newAuthority.publicKey.toString()
+    );
+    assert.equal(
+      poolStateAccount.pendingAuthority.toString(),
+      PublicKey.default.toString()
+    );
+  });
 });
@@ -0,0 +1,220 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import * as SPLToken from "@solana/spl-token";
+import { BTCD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
+
+describe("hydra-liquidity-pool-flash-loan", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  let btcdMint: PublicKey;
+  let usddMint: PublicKey;
+  let btcdAccount: PublicKey;
+  let usddAccount: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 0n,
+    ownerTradeFeeDenominator: 0n,
+    ownerWithdrawFeeNumerator: 0n,
+    ownerWithdrawFeeDenominator: 0n,
+    hostFeeNumerator: 0n,
+    hostFeeDenominator: 0n,
+  };
+
+  // the token program is the borrower, its instruction repays the vault from the wallet
+  async function repayX(amount: bigint): Promise<TransactionInstruction> {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    return SPLToken.createTransferInstruction(
+      btcdAccount,
+      await accounts.tokenXVault.key(),
+      provider.wallet.publicKey,
+      amount
+    );
+  }
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    // Keys will be ordered based on base58 encoding
+    const [btcdMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [btcdMint, btcdAccount] = await sdk.common.createMintAndAssociatedVault(
+      btcdMintPair,
+      BTCD_MINT_AMOUNT
+    );
+
+    [usddMint, usddAccount] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+
+    await sdk.liquidityPools.initialize(btcdMint, usddMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      btcdMint,
+      usddMint,
+      6_000_000n,
+      255_575_287_200n,
+      0n
+    );
+  });
+
+  it("should flash loan x repaid with its fee to the liquidity providers", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const btcdBalance = await accounts.userTokenX.balance();
+
+    // 1_000_001 * 1 / 500 = 2_000.002, rounded up
+    await sdk.liquidityPools.flashLoan(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      1_000_001n,
+      0n,
+      await repayX(1_000_001n + 2_001n)
+    );
+
+    assert.strictEqual(await accounts.tokenXVault.balance(), 6_002_001n);
+    assert.strictEqual(await accounts.tokenYVault.balance(), 255_575_287_200n);
+    assert.strictEqual(
+      await accounts.userTokenX.balance(),
+      btcdBalance - 2_001n
+    );
+  });
+
+  it("should fail a flash loan repaid without its fee", async () => {
+    try {
+      await sdk.liquidityPools.flashLoan(
+        btcdMint,
+        usddMint,
+        btcdAccount,
+        usddAccount,
+        1_000_000n,
+        0n,
+        await repayX(1_000_000n + 1_999n)
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Flash loan wasn't repaid with its fee";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fail a flash loan exceeding the pool liquidity", async () => {
+    try {
+      await sdk.liquidityPools.flashLoan(
+        btcdMint,
+        usddMint,
+        btcdAccount,
+        usddAccount,
+        6_002_002n,
+        0n,
+        await repayX(6_002_002n)
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Amount out exceeds the liquidity of the pool";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should flash swap y repaid in x above the exact out swap price", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const xVault = await accounts.tokenXVault.balance();
+    const yVault = await accounts.tokenYVault.balance();
+    const usddBalance = await accounts.userTokenY.balance();
+
+    // 1.000000 usdd costs about 24 units of btcd including the swap fee,
+    // the amount repaid above the amount in stays with the liquidity providers
+    await sdk.liquidityPools.flashSwap(
+      btcdMint,
+      usddMint,
+      usddAccount,
+      1_000_000n,
+      100n,
+      await repayX(100n)
+    );
+
+    assert.strictEqual(await accounts.tokenXVault.balance(), xVault + 100n);
+    assert.strictEqual(
+      await accounts.tokenYVault.balance(),
+      yVault - 1_000_000n
+    );
+    assert.strictEqual(
+      await accounts.userTokenY.balance(),
+      usddBalance + 1_000_000n
+    );
+  });
+
+  it("should fail a flash swap repaid below the swap price", async () => {
+    try {
+      await sdk.liquidityPools.flashSwap(
+        btcdMint,
+        usddMint,
+        usddAccount,
+        1_000_000n,
+        100n,
+        await repayX(1n)
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Flash swap wasn't repaid with the amount in of the swap";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fail a flash loan borrowed by the pool program", async () => {
+    try {
+      await sdk.liquidityPools.flashLoan(
+        btcdMint,
+        usddMint,
+        btcdAccount,
+        usddAccount,
+        1_000_000n,
+        0n,
+        new TransactionInstruction({
+          programId: new PublicKey(
+            config.localnet.programIds.hydraLiquidityPools
+          ),
+          keys: [],
+          data: Buffer.alloc(0),
+        })
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Flash loan borrower program can't be the pool program";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+});
@@ -243,4 +243,181 @@ describe("hydra-liquidity-pool-hmm", () => {
       assert(err.toString().includes(errMsg));
     }
   });
+
+  it("should swap (hmm) with a volatility adjusted fee", async () => {
+    await sdk.liquidityPools.setFeeMode(soldMint, usddMint, {
+      volatilityAdjusted: {},
+    });
+
+    await sdk.liquidityPools.swap(
+      soldMint,
+      usddMint,
+      soldAccount,
+      usddAccount,
+      1_000_000n, // 1 sol
+      0n,
+      pyth_solusd_price
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      soldMint,
+      usddMint
+    );
+    const poolStateAccount = (await accounts.poolState.info()).data;
+
+    assert.deepStrictEqual(poolStateAccount.feeMode, {
+      volatilityAdjusted: {},
+    });
+    assert(poolStateAccount.volAdjFee.lastUpdate > 0n);
+    assert(poolStateAccount.volAdjFee.lastPrice > 0n);
+    // the first swap starts from the default ewma of the fee calculator
+    assert.strictEqual(poolStateAccount.volAdjFee.lastEwma, 178_367_579n);
+  });
+
+  it("should reject a swap (hmm) when the oracle is outside tolerance", async () => {
+    // a zero confidence tolerance and no staleness puts any live price outside tolerance
+    await sdk.liquidityPools.setOracleGuards(soldMint, usddMint, 0n, 0, {
+      rejectSwap: {},
+    });
+
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000n, // 1 sol
+        0n,
+        pyth_solusd_price
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "Oracle price is stale or its confidence interval is too wide";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fall back to a cpmm swap when the oracle is outside tolerance", async () => {
+    await sdk.liquidityPools.setOracleGuards(soldMint, usddMint, 0n, 0, {
+      fallbackToCpmm: {},
+    });
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      soldMint,
+      usddMint
+    );
+    const xBefore = await accounts.tokenXVault.balance();
+
+    await sdk.liquidityPools.swap(
+      soldMint,
+      usddMint,
+      soldAccount,
+      usddAccount,
+      1_000_000n, // 1 sol
+      0n,
+      pyth_solusd_price
+    );
+
+    assert.strictEqual(
+      await accounts.tokenXVault.balance(),
+      xBefore + 1_000_000n
+    );
+  });
+
+  it("should record twap observations of the pool", async () => {
+    const observations = await sdk.liquidityPools.initializeObservations(
+      soldMint,
+      usddMint
+    );
+    await sdk.liquidityPools.recordObservation(soldMint, usddMint);
+
+    await sdk.liquidityPools.swap(
+      soldMint,
+      usddMint,
+      soldAccount,
+      usddAccount,
+      1_000_000n, // 1 sol
+      0n,
+      pyth_solusd_price
+    );
+    await sdk.liquidityPools.recordObservation(soldMint, usddMint);
+
+    const observationsLoader =
+      sdk.liquidityPools.accounts.poolObservations(poolState);
+    assert.strictEqual(
+      (await observationsLoader.key()).toBase58(),
+      observations.toBase58()
+    );
+    const observationsAccount = (await observationsLoader.info()).data;
+    assert.strictEqual(observationsAccount.count, 2);
+
+    const [older, newer] = observationsAccount.observations;
+    assert(newer.slot > older.slot);
+    assert(newer.yPerXSlots > older.yPerXSlots);
+    assert(newer.xPerYSlots > older.xPerYSlots);
+  });
+
+  it("should pause swaps while liquidity can still be removed", async () => {
+    await sdk.liquidityPools.setPause(soldMint, usddMint, {
+      swaps: true,
+      deposits: false,
+      withdrawals: false,
+    });
+
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000n, // 1 sol
+        0n,
+        pyth_solusd_price
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Swaps are paused on the pool";
+      assert(err.toString().includes(errMsg));
+    }
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      soldMint,
+      usddMint
+    );
+    const lpTokensBefore = await accounts.lpTokenAssociatedAccount.balance();
+    await sdk.liquidityPools.removeLiquidity(soldMint, usddMint, 1_000_000n);
+    assert.strictEqual(
+      await accounts.lpTokenAssociatedAccount.balance(),
+      lpTokensBefore - 1_000_000n
+    );
+
+    await sdk.liquidityPools.setPause(soldMint, usddMint, {
+      swaps: false,
+      deposits: false,
+      withdrawals: false,
+    });
+  });
+
+  it("should reject a swap moving the price too far within the slot", async () => {
+    await sdk.liquidityPools.setCircuitBreaker(soldMint, usddMint, 0, 100);
+
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000_000n, // 1000 sol
+        0n,
+        pyth_solusd_price
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Swap moves the pool price too far within the slot";
+      assert(err.toString().includes(errMsg));
+    }
+
+    await sdk.liquidityPools.setCircuitBreaker(soldMint, usddMint, 0, 0);
+  });
 });
@@ -0,0 +1,201 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { SOLD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
+
+describe("hydra-liquidity-pool-mock-oracle", () => {
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  let soldMint: PublicKey;
+  let usddMint: PublicKey;
+  let soldAccount: PublicKey;
+  let usddAccount: PublicKey;
+  let mockOracle: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 0n,
+    ownerTradeFeeDenominator: 0n,
+    ownerWithdrawFeeNumerator: 0n,
+    ownerWithdrawFeeDenominator: 0n,
+    hostFeeNumerator: 0n,
+    hostFeeDenominator: 0n,
+  };
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    // Keys will be ordered based on base58 encoding
+    const [soldMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [soldMint, soldAccount] = await sdk.common.createMintAndAssociatedVault(
+      soldMintPair,
+      SOLD_MINT_AMOUNT
+    );
+
+    [usddMint, usddAccount] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+
+    // $110/sol with a 5 cent confidence interval
+    mockOracle = await sdk.liquidityPools.initializeMockOracle(
+      Keypair.generate(),
+      11_000_000_000n,
+      -8,
+      5_000_000n
+    );
+  });
+
+  it("should initialize a liquidity-pool priced by a mock oracle", async () => {
+    await sdk.liquidityPools.initialize(
+      soldMint,
+      usddMint,
+      poolFees,
+      100,
+      mockOracle
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      soldMint,
+      usddMint
+    );
+    const poolStateAccount = (await accounts.poolState.info()).data;
+
+    assert.deepStrictEqual(poolStateAccount.oracle.provider, { mock: {} });
+    assert.equal(
+      poolStateAccount.oracle.priceAccount.toString(),
+      mockOracle.toString()
+    );
+    assert.equal(poolStateAccount.oracle.priceExponent, 8);
+
+    await sdk.liquidityPools.addLiquidity(
+      soldMint,
+      usddMint,
+      4_000_000_000n, // 4000 sol
+      440_000_000_000n, // 440000 usd
+      0n
+    );
+  });
+
+  it("should swap (hmm) at the mock oracle price", async () => {
+    await sdk.liquidityPools.setMockOraclePrice(
+      mockOracle,
+      10_500_000_000n,
+      5_000_000n
+    );
+
+    await sdk.liquidityPools.swap(
+      soldMint,
+      usddMint,
+      soldAccount,
+      usddAccount,
+      1_000_000n, // 1 sol
+      0n,
+      mockOracle
+    );
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      soldMint,
+      usddMint
+    );
+    const poolStateAccount = (await accounts.poolState.info()).data;
+
+    assert.equal(
+      poolStateAccount.oracle.lastKnownPrice.toString(),
+      "10500000000"
+    );
+  });
+
+  it("should reject a swap without the oracle price account", async () => {
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000n, // 1 sol
+        0n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Oracle accounts are required";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should reject a swap when the live mock price is stale", async () => {
+    await sdk.liquidityPools.setOracleGuards(soldMint, usddMint, 0n, 200, {
+      rejectSwap: {},
+    });
+    await sdk.liquidityPools.setMockOraclePrice(
+      mockOracle,
+      10_500_000_000n,
+      5_000_000n
+    );
+    // let the slot of the live price pass
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000n, // 1 sol
+        0n,
+        mockOracle
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "Oracle price is stale or its confidence interval is too wide";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should reject a swap when the mock oracle has no live price", async () => {
+    await sdk.liquidityPools.setOracleGuards(soldMint, usddMint, 0n, 200, {
+      rejectSwap: {},
+    });
+    await sdk.liquidityPools.setMockOraclePrice(mockOracle, 0n, 0n);
+
+    try {
+      await sdk.liquidityPools.swap(
+        soldMint,
+        usddMint,
+        soldAccount,
+        usddAccount,
+        1_000_000n, // 1 sol
+        0n,
+        mockOracle
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "Oracle price is stale or its confidence interval is too wide";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+});
@@ -0,0 +1,14 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import { HydraSDK } from "hydra-ts";
+
+// root hook, pools of every suite are created through the pool factory
+before(async () => {
+  const sdk = HydraSDK.createFromAnchorProvider(
+    anchor.Provider.env(),
+    config.localnet.programIds
+  );
+
+  // 0.05%, 0.2% and 0.3% swap fees
+  await sdk.liquidityPools.initializeFactory([5, 20, 30], [0, 100, 125, 150]);
+});
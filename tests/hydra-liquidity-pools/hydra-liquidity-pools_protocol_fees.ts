@@ -0,0 +1,180 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BTCD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+import { AccountLoader } from "hydra-ts";
+import { NodeWallet } from "@project-serum/common";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
+
+describe("hydra-liquidity-pool-protocol-fees", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  let btcdMint: PublicKey;
+  let usddMint: PublicKey;
+  let btcdAccount: PublicKey;
+  let usddAccount: PublicKey;
+  let hostFeeAccount: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 1n,
+    ownerTradeFeeDenominator: 1000n,
+    ownerWithdrawFeeNumerator: 1n,
+    ownerWithdrawFeeDenominator: 100n,
+    hostFeeNumerator: 20n,
+    hostFeeDenominator: 100n,
+  };
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    // Keys will be ordered based on base58 encoding
+    const [btcdMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [btcdMint, btcdAccount] = await sdk.common.createMintAndAssociatedVault(
+      btcdMintPair,
+      BTCD_MINT_AMOUNT
+    );
+
+    [usddMint, usddAccount] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+
+    hostFeeAccount = await sdk.common.createAssociatedAccount(
+      btcdMint,
+      Keypair.generate(),
+      (provider.wallet as NodeWallet).payer
+    );
+
+    await sdk.liquidityPools.initialize(btcdMint, usddMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      btcdMint,
+      usddMint,
+      6_000_000n,
+      255_575_287_200n,
+      0n
+    );
+  });
+
+  it("should charge owner trade and host fees on a swap", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const quote = await sdk.liquidityPools.quoteSwap(
+      btcdMint,
+      usddMint,
+      { xToY: {} },
+      { exactIn: {} },
+      1_000_000n,
+      undefined,
+      hostFeeAccount
+    );
+    const usddBalance = await AccountLoader.Token(
+      sdk.ctx,
+      usddAccount
+    ).balance();
+
+    await sdk.liquidityPools.swap(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      1_000_000n,
+      0n,
+      undefined,
+      hostFeeAccount
+    );
+
+    // owner trade fee: 1_000_000 * 1 / 1000 = 1000, of which 20% goes to the host
+    assert.strictEqual(await accounts.protocolFeeXVault.balance(), 800n);
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, hostFeeAccount).balance(),
+      200n
+    );
+    assert.strictEqual(
+      await accounts.tokenXVault.balance(),
+      6_000_000n + 1_000_000n - 1000n
+    );
+
+    // the quote with the host fee account is what the swap charged
+    assert.strictEqual(quote.ownerTradeFee, 1000n);
+    assert.strictEqual(quote.xNew, await accounts.tokenXVault.balance());
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, usddAccount).balance(),
+      usddBalance + quote.amountOut
+    );
+  });
+
+  it("should charge owner withdraw fees on remove-liquidity", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const lpTokens = await accounts.lpTokenAssociatedAccount.balance();
+
+    await sdk.liquidityPools.removeLiquidity(
+      btcdMint,
+      usddMint,
+      1_000_000_000n
+    );
+
+    // owner withdraw fee: 1_000_000_000 * 1 / 100
+    assert.strictEqual(
+      await accounts.protocolFeeLpVault.balance(),
+      10_000_000n
+    );
+    assert.strictEqual(
+      await accounts.lpTokenAssociatedAccount.balance(),
+      lpTokens - 1_000_000_000n
+    );
+  });
+
+  it("should withdraw protocol fees", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const btcdBalance = await accounts.userTokenX.balance();
+    const lpTokens = await accounts.lpTokenAssociatedAccount.balance();
+
+    await sdk.liquidityPools.withdrawProtocolFees(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      await accounts.lpTokenAssociatedAccount.key()
+    );
+
+    assert.strictEqual(await accounts.protocolFeeXVault.balance(), 0n);
+    assert.strictEqual(await accounts.protocolFeeYVault.balance(), 0n);
+    assert.strictEqual(await accounts.protocolFeeLpVault.balance(), 0n);
+    assert.strictEqual(await accounts.userTokenX.balance(), btcdBalance + 800n);
+    assert.strictEqual(
+      await accounts.lpTokenAssociatedAccount.balance(),
+      lpTokens + 10_000_000n
+    );
+  });
+});
@@ -0,0 +1,169 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  BTCD_MINT_AMOUNT,
+  ETHD_MINT_AMOUNT,
+  USDD_MINT_AMOUNT,
+} from "../constants";
+import { HydraSDK, AccountLoader } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+
+function orderKeyPairs(...keyPairs: Keypair[]) {
+  return keyPairs.sort((a, b) =>
+    a.publicKey.toBuffer().compare(b.publicKey.toBuffer())
+  );
+}
+
+describe("hydra-liquidity-pool-route-swap", () => {
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  // mints ordered by key: a < b < c
+  let aMint: PublicKey;
+  let bMint: PublicKey;
+  let cMint: PublicKey;
+  let aAccount: PublicKey;
+  let bAccount: PublicKey;
+  let cAccount: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 0n,
+    ownerTradeFeeDenominator: 0n,
+    ownerWithdrawFeeNumerator: 0n,
+    ownerWithdrawFeeDenominator: 0n,
+    hostFeeNumerator: 0n,
+    hostFeeDenominator: 0n,
+  };
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    const [aMintPair, bMintPair, cMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [aMint, aAccount] = await sdk.common.createMintAndAssociatedVault(
+      aMintPair,
+      BTCD_MINT_AMOUNT
+    );
+    [bMint, bAccount] = await sdk.common.createMintAndAssociatedVault(
+      bMintPair,
+      USDD_MINT_AMOUNT
+    );
+    [cMint, cAccount] = await sdk.common.createMintAndAssociatedVault(
+      cMintPair,
+      ETHD_MINT_AMOUNT
+    );
+
+    await sdk.liquidityPools.initialize(aMint, bMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      aMint,
+      bMint,
+      6_000_000n,
+      255_575_287_200n,
+      0n
+    );
+
+    await sdk.liquidityPools.initialize(bMint, cMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      bMint,
+      cMint,
+      255_575_287_200n,
+      85_000_000_000n,
+      0n
+    );
+  });
+
+  it("should fail a route swap due to slippage error", async () => {
+    try {
+      await sdk.liquidityPools.routeSwap(
+        aAccount,
+        cAccount,
+        [
+          { tokenXMint: aMint, tokenYMint: bMint },
+          { tokenXMint: bMint, tokenYMint: cMint },
+        ],
+        1_000_000n,
+        85_000_000_000n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Slippage Amount Exceeded";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fail a route swap that doesn't end in the token out", async () => {
+    try {
+      await sdk.liquidityPools.routeSwap(
+        aAccount,
+        cAccount,
+        [{ tokenXMint: aMint, tokenYMint: bMint }],
+        1_000_000n,
+        0n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg =
+        "Route pools don't chain the token swapped in to the token swapped out";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should route swap a to c through b", async () => {
+    const abAccounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      aMint,
+      bMint
+    );
+    const bcAccounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      bMint,
+      cMint
+    );
+    const aBalance = await AccountLoader.Token(sdk.ctx, aAccount).balance();
+    const bBalance = await AccountLoader.Token(sdk.ctx, bAccount).balance();
+    const cBalance = await AccountLoader.Token(sdk.ctx, cAccount).balance();
+    const abYVault = await abAccounts.tokenYVault.balance();
+    const bcXVault = await bcAccounts.tokenXVault.balance();
+
+    await sdk.liquidityPools.routeSwap(
+      aAccount,
+      cAccount,
+      [
+        { tokenXMint: aMint, tokenYMint: bMint },
+        { tokenXMint: bMint, tokenYMint: cMint },
+      ],
+      1_000_000n,
+      1n
+    );
+
+    const cReceived =
+      (await AccountLoader.Token(sdk.ctx, cAccount).balance()) - cBalance;
+    const bRouted = abYVault - (await abAccounts.tokenYVault.balance());
+
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, aAccount).balance(),
+      aBalance - 1_000_000n
+    );
+    // the intermediate token moves between the pools, not through the user
+    assert.strictEqual(
+      await AccountLoader.Token(sdk.ctx, bAccount).balance(),
+      bBalance
+    );
+    assert.strictEqual(
+      await bcAccounts.tokenXVault.balance(),
+      bcXVault + bRouted
+    );
+    assert(cReceived > 0n, "no tokens received from the route");
+  });
+});
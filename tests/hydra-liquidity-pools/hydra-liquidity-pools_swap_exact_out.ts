@@ -0,0 +1,278 @@
+import * as anchor from "@project-serum/anchor";
+import config from "config-ts/global-config.json";
+import assert from "assert";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BTCD_MINT_AMOUNT, USDD_MINT_AMOUNT } from "../constants";
+import { HydraSDK } from "hydra-ts";
+import { PoolFees } from "hydra-ts/src/liquidity-pools/types";
+
+function orderKeyPairs(a: Keypair, b: Keypair) {
+  if (a.publicKey.toBuffer().compare(b.publicKey.toBuffer()) > 0) {
+    return [b, a];
+  }
+
+  return [a, b];
+}
+
+describe("hydra-liquidity-pool-swap-exact-out", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.Provider.env();
+  anchor.setProvider(provider);
+
+  let sdk: HydraSDK;
+
+  let btcdMint: PublicKey;
+  let usddMint: PublicKey;
+  let btcdAccount: PublicKey;
+  let usddAccount: PublicKey;
+
+  const poolFees: PoolFees = {
+    swapFeeNumerator: 1n,
+    swapFeeDenominator: 500n,
+    ownerTradeFeeNumerator: 0n,
+    ownerTradeFeeDenominator: 0n,
+    ownerWithdrawFeeNumerator: 0n,
+    ownerWithdrawFeeDenominator: 0n,
+    hostFeeNumerator: 0n,
+    hostFeeDenominator: 0n,
+  };
+
+  before(async () => {
+    sdk = HydraSDK.createFromAnchorProvider(
+      provider,
+      config.localnet.programIds
+    );
+
+    // Keys will be ordered based on base58 encoding
+    const [btcdMintPair, usddMintPair] = orderKeyPairs(
+      Keypair.generate(),
+      Keypair.generate()
+    );
+
+    [btcdMint, btcdAccount] = await sdk.common.createMintAndAssociatedVault(
+      btcdMintPair,
+      BTCD_MINT_AMOUNT
+    );
+
+    [usddMint, usddAccount] = await sdk.common.createMintAndAssociatedVault(
+      usddMintPair,
+      USDD_MINT_AMOUNT
+    );
+
+    await sdk.liquidityPools.initialize(btcdMint, usddMint, poolFees);
+    await sdk.liquidityPools.addLiquidity(
+      btcdMint,
+      usddMint,
+      6_000_000n,
+      255_575_287_200n,
+      0n
+    );
+  });
+
+  it("should fail exact out swap due to slippage error", async () => {
+    try {
+      await sdk.liquidityPools.swapExactOut(
+        btcdMint,
+        usddMint,
+        btcdAccount,
+        usddAccount,
+        36_448_147_560n,
+        999_999n
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Slippage Amount Exceeded";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should fail exact out swap exceeding the pool liquidity", async () => {
+    try {
+      await sdk.liquidityPools.swapExactOut(
+        btcdMint,
+        usddMint,
+        btcdAccount,
+        usddAccount,
+        255_575_287_200n,
+        BTCD_MINT_AMOUNT
+      );
+      assert.ok(false);
+    } catch (err: any) {
+      const errMsg = "Amount out exceeds the liquidity of the pool";
+      assert(err.toString().includes(errMsg));
+    }
+  });
+
+  it("should swap exact out (cpmm) btc to usd (x to y)", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const usddBalance = await accounts.userTokenY.balance();
+
+    await sdk.liquidityPools.swapExactOut(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      36_448_147_560n,
+      1_000_000n
+    );
+
+    assert.strictEqual(
+      await accounts.tokenXVault.balance(),
+      6_000_000n + 1_000_000n // original amount + swap in amount + fee
+    );
+
+    assert.strictEqual(
+      await accounts.tokenYVault.balance(),
+      255_575_287_200n - 36_448_147_560n // original amount - exact swap out amount
+    );
+
+    assert.strictEqual(
+      await accounts.userTokenY.balance(),
+      usddBalance + 36_448_147_560n
+    );
+  });
+
+  it("should swap exact out (cpmm) usd to btc (y to x)", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const btcdBalance = await accounts.userTokenX.balance();
+
+    await sdk.liquidityPools.swapExactOut(
+      btcdMint,
+      usddMint,
+      usddAccount,
+      btcdAccount,
+      1_000_000n,
+      36_594_378_698n
+    );
+
+    assert.strictEqual(
+      await accounts.tokenXVault.balance(),
+      7_000_000n - 1_000_000n // original amount - exact swap out amount
+    );
+
+    assert.strictEqual(
+      await accounts.tokenYVault.balance(),
+      219_127_139_640n + 36_594_378_698n // original amount + swap in amount + fee
+    );
+
+    assert.strictEqual(
+      await accounts.userTokenX.balance(),
+      btcdBalance + 1_000_000n
+    );
+  });
+
+  it("should emit a swapped event", async () => {
+    const program = sdk.ctx.programs.hydraLiquidityPools;
+    let swapped: any;
+    const listener = program.addEventListener("Swapped", (event) => {
+      swapped = event;
+    });
+
+    await sdk.liquidityPools.swapExactOut(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      1_000_000n,
+      1_000n
+    );
+
+    // give the websocket a moment to deliver the logs
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+
+    assert.ok(swapped, "no swapped event was emitted");
+    assert.deepStrictEqual(swapped.direction, { xToY: {} });
+    assert.strictEqual(swapped.amountOut.toString(), "1000000");
+    assert.strictEqual(swapped.oraclePrice, null);
+    assert.strictEqual(
+      swapped.xNew.toString(),
+      (await accounts.tokenXVault.balance()).toString()
+    );
+    assert.strictEqual(
+      swapped.yNew.toString(),
+      (await accounts.tokenYVault.balance()).toString()
+    );
+  });
+
+  it("should quote an exact out swap as it is swapped", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const btcdBalance = await accounts.userTokenX.balance();
+
+    const quote = await sdk.liquidityPools.quoteSwap(
+      btcdMint,
+      usddMint,
+      { xToY: {} },
+      { exactOut: {} },
+      1_000_000n
+    );
+    assert.strictEqual(quote.amountOut, 1_000_000n);
+    assert.strictEqual(quote.ownerTradeFee, 0n);
+    assert.strictEqual(quote.oraclePrice, null);
+    assert.ok(quote.fee > 0n);
+    assert.ok(quote.priceImpactBps !== null);
+
+    await sdk.liquidityPools.swapExactOut(
+      btcdMint,
+      usddMint,
+      btcdAccount,
+      usddAccount,
+      1_000_000n,
+      quote.amountIn
+    );
+
+    assert.strictEqual(
+      await accounts.userTokenX.balance(),
+      btcdBalance - quote.amountIn
+    );
+    assert.strictEqual(await accounts.tokenXVault.balance(), quote.xNew);
+    assert.strictEqual(await accounts.tokenYVault.balance(), quote.yNew);
+  });
+
+  it("should quote an exact in swap as it is swapped", async () => {
+    const accounts = await sdk.liquidityPools.accounts.getAccountLoaders(
+      btcdMint,
+      usddMint
+    );
+    const btcdBalance = await accounts.userTokenX.balance();
+
+    const quote = await sdk.liquidityPools.quoteSwap(
+      btcdMint,
+      usddMint,
+      { yToX: {} },
+      { exactIn: {} },
+      1_000_000_000n
+    );
+    assert.strictEqual(quote.amountIn, 1_000_000_000n);
+
+    await sdk.liquidityPools.swap(
+      btcdMint,
+      usddMint,
+      usddAccount,
+      btcdAccount,
+      1_000_000_000n,
+      quote.amountOut
+    );
+
+    assert.strictEqual(
+      await accounts.userTokenX.balance(),
+      btcdBalance + quote.amountOut
+    );
+    assert.strictEqual(await accounts.tokenXVault.balance(), quote.xNew);
+    assert.strictEqual(await accounts.tokenYVault.balance(), quote.yNew);
+  });
+});